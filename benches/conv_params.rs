@@ -0,0 +1,58 @@
+//! Benchmarks for the PostgreSQL parameter conversion on the hot query path.
+//!
+//! The profiled workload is a four parameter `SELECT` executed a hundred
+//! thousand times. There is no mock connector yet, so the conversion is
+//! measured in isolation: one iteration converts the parameter set a hundred
+//! thousand times, which is the part of the workload that does not leave the
+//! process.
+//!
+//! Run with `cargo bench --features "postgresql expose-drivers" conv_params`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use quaint_forked::{
+    connector::{conv_params, params_to_types},
+    Value,
+};
+
+const QUERIES: usize = 100_000;
+
+fn select_params() -> Vec<Value<'static>> {
+    vec![
+        Value::int64(42),
+        Value::text("Musti"),
+        Value::boolean(true),
+        Value::double(1.5),
+    ]
+}
+
+fn conv_params_hot_select(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conv_params");
+    let params = select_params();
+
+    group.throughput(Throughput::Elements(QUERIES as u64));
+    group.bench_function("4_params_100k_queries", |b| {
+        b.iter(|| {
+            for _ in 0..QUERIES {
+                black_box(conv_params(black_box(&params)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn params_to_types_cache_miss(c: &mut Criterion) {
+    let mut group = c.benchmark_group("params_to_types");
+    let params = select_params();
+
+    // Only paid on a statement cache miss; here as a baseline to compare the
+    // cached path against.
+    group.bench_function("4_params", |b| {
+        b.iter(|| black_box(params_to_types(black_box(&params))))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, conv_params_hot_select, params_to_types_cache_miss);
+criterion_main!(benches);
@@ -0,0 +1,82 @@
+//! Benchmarks for reading large `text`/`bytes` values out of a `ResultSet`.
+//!
+//! The profiled workload is ten thousand rows of megabyte-sized blobs. To keep
+//! a single iteration from allocating gigabytes, the workload is split into a
+//! many-small-rows case and a few-large-rows case with the same total volume.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use quaint_forked::{connector::ResultSet, Value};
+
+const WORKLOADS: [(usize, usize); 2] = [(10_000, 1_024), (100, 1_024 * 1_024)];
+
+fn blob_result_set(rows: usize, blob_size: usize) -> ResultSet {
+    let blob = vec![0xau8; blob_size];
+
+    let rows = (0..rows)
+        .map(|i| vec![Value::int64(i as i64), Value::bytes(blob.clone())])
+        .collect();
+
+    ResultSet::new(vec!["id".into(), "data".into()], rows)
+}
+
+fn copying_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("copying_read");
+
+    for (rows, blob_size) in WORKLOADS {
+        let result_set = blob_result_set(rows, blob_size);
+
+        group.throughput(Throughput::Bytes((rows * blob_size) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{rows}x{blob_size}")),
+            &result_set,
+            |b, result_set| {
+                b.iter(|| {
+                    let mut total = 0;
+
+                    for i in 0..result_set.len() {
+                        let row = result_set.get(i).unwrap();
+                        total += row["data"].to_bytes().unwrap().len();
+                    }
+
+                    total
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn moving_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("moving_read");
+
+    for (rows, blob_size) in WORKLOADS {
+        group.throughput(Throughput::Bytes((rows * blob_size) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{rows}x{blob_size}")),
+            &(rows, blob_size),
+            |b, &(rows, blob_size)| {
+                b.iter_batched(
+                    || blob_result_set(rows, blob_size),
+                    |result_set| {
+                        let mut total = 0;
+
+                        for row in result_set {
+                            let mut values = row.into_iter();
+                            let _id = values.next().unwrap();
+                            total += values.next().unwrap().into_bytes().unwrap().len();
+                        }
+
+                        total
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, copying_read, moving_read);
+criterion_main!(benches);
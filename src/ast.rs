@@ -5,6 +5,8 @@
 //! actual query building is in the [visitor](../visitor/index.html) module.
 //!
 //! For prelude, all important imports are in `quaint::ast::*`.
+mod case;
+mod cast;
 mod column;
 mod compare;
 mod conditions;
@@ -12,16 +14,23 @@ mod conjunctive;
 mod cte;
 mod delete;
 mod expression;
+mod fts;
 mod function;
+#[cfg(feature = "postgis")]
+mod geometry;
 mod grouping;
+mod hints;
 mod index;
 mod insert;
+mod into_outfile;
 mod join;
+mod load_data;
 mod merge;
 mod ops;
 mod ordering;
 mod over;
 mod query;
+mod replace;
 mod row;
 mod select;
 mod table;
@@ -29,27 +38,37 @@ mod union;
 mod update;
 mod values;
 
+pub use case::Case;
+pub use cast::{cast, Cast, CastType};
 pub use column::{Column, DefaultValue, TypeDataLength, TypeFamily};
-pub use compare::{Comparable, Compare, JsonCompare, JsonType};
+pub use compare::{exists, like_escape, like_escape_with, not_exists, Comparable, Compare, JsonCompare, JsonType};
 pub use conditions::ConditionTree;
 pub use conjunctive::Conjunctive;
 pub use cte::{CommonTableExpression, IntoCommonTableExpression};
 pub use delete::Delete;
 pub use expression::*;
+pub use fts::{CreateFts5Table, FtsMatch};
 pub use function::*;
+#[cfg(feature = "postgis")]
+pub use geometry::WkbGeometry;
 pub use grouping::*;
+pub use hints::{Hint, HintDialect};
+pub(crate) use hints::hints_for_dialect;
 pub use index::*;
 pub use insert::*;
+pub use into_outfile::IntoOutfile;
 pub use join::{Join, JoinData, Joinable};
+pub use load_data::LoadDataInfile;
 pub(crate) use merge::*;
 pub use ops::*;
 pub use ordering::{IntoOrderDefinition, Order, OrderDefinition, Orderable, Ordering};
 pub use over::*;
 pub use query::{Query, SelectQuery};
+pub use replace::{MultiRowReplace, ReplaceInto, SingleRowReplace};
 pub use row::Row;
 pub use select::Select;
 pub use table::*;
 pub use union::Union;
 pub use update::*;
 pub(crate) use values::Params;
-pub use values::{IntoRaw, Raw, Value, Values};
+pub use values::{ArrayType, IntoRaw, IpNet, ParamLogLimits, Raw, Value, Values};
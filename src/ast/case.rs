@@ -0,0 +1,108 @@
+use crate::ast::{Expression, ExpressionKind};
+
+/// A `CASE WHEN .. THEN .. ELSE .. END` conditional expression. Usable
+/// anywhere an expression is expected, such as selected columns, `WHERE`
+/// conditions or `ORDER BY`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case<'a> {
+    pub(crate) operand: Option<Box<Expression<'a>>>,
+    pub(crate) branches: Vec<(Expression<'a>, Expression<'a>)>,
+    pub(crate) else_branch: Option<Box<Expression<'a>>>,
+}
+
+impl<'a> Case<'a> {
+    /// Starts a simple `CASE` expression, comparing the given operand against
+    /// the `WHEN` values.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let case = Case::simple(Column::from("role"))
+    ///     .when(Value::text("admin"), Value::integer(1))
+    ///     .otherwise(Value::integer(0));
+    ///
+    /// let query = Select::from_table("users").value(case);
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT CASE `role` WHEN ? THEN ? ELSE ? END FROM `users`", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![Value::text("admin"), Value::integer(1), Value::integer(0)],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn simple<T>(operand: T) -> Self
+    where
+        T: Into<Expression<'a>>,
+    {
+        Self {
+            operand: Some(Box::new(operand.into())),
+            branches: Vec::new(),
+            else_branch: None,
+        }
+    }
+
+    /// Starts a searched `CASE` expression, evaluating the `WHEN` conditions
+    /// in order.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let case = Case::searched()
+    ///     .when(Column::from("age").greater_than_or_equals(18), Value::text("adult"))
+    ///     .otherwise(Value::text("minor"));
+    ///
+    /// let query = Select::from_table("users").value(case);
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT CASE WHEN `age` >= ? THEN ? ELSE ? END FROM `users`",
+    ///     sql
+    /// );
+    ///
+    /// assert_eq!(
+    ///     vec![Value::integer(18), Value::text("adult"), Value::text("minor")],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn searched() -> Self {
+        Self {
+            operand: None,
+            branches: Vec::new(),
+            else_branch: None,
+        }
+    }
+
+    /// Adds a `WHEN .. THEN ..` branch.
+    pub fn when<W, T>(mut self, when: W, then: T) -> Self
+    where
+        W: Into<Expression<'a>>,
+        T: Into<Expression<'a>>,
+    {
+        self.branches.push((when.into(), then.into()));
+        self
+    }
+
+    /// Sets the `ELSE` branch. When not set, a `CASE` with no matching branch
+    /// evaluates to `NULL`.
+    pub fn otherwise<T>(mut self, value: T) -> Self
+    where
+        T: Into<Expression<'a>>,
+    {
+        self.else_branch = Some(Box::new(value.into()));
+        self
+    }
+}
+
+impl<'a> From<Case<'a>> for Expression<'a> {
+    fn from(case: Case<'a>) -> Self {
+        Expression {
+            kind: ExpressionKind::Case(case),
+            alias: None,
+        }
+    }
+}
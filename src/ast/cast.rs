@@ -0,0 +1,65 @@
+use crate::ast::{Expression, ExpressionKind};
+
+/// The target type of a `CAST` expression. The variants are abstract type
+/// names mapped to the dialect's own type in the visitor, e.g. a
+/// [`Text`](Self::Text) cast renders as `CAST(x AS TEXT)` on PostgreSQL and
+/// as `CAST(x AS CHAR)` on MySQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastType {
+    /// A variable length string.
+    Text,
+    /// A 32-bit signed integer.
+    Integer,
+    /// A 64-bit signed integer.
+    BigInt,
+    /// A single precision float.
+    Float,
+    /// A double precision float.
+    Double,
+    /// A boolean, cast to an integer on databases without a boolean type.
+    Boolean,
+    /// A calendar date.
+    Date,
+    /// A date and a time.
+    DateTime,
+    /// A JSON document.
+    Json,
+    /// A UUID. Supported on PostgreSQL and SQL Server, other dialects return
+    /// an `UnsupportedOperation` error when building the query.
+    Uuid,
+    /// A 64-bit unsigned integer. Only supported on MySQL, other dialects
+    /// return an `UnsupportedOperation` error when building the query.
+    Unsigned,
+}
+
+/// A `CAST(expr AS type)` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+    pub(crate) cast_type: CastType,
+}
+
+/// Casts an expression to the given type.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("users").value(cast(Column::from("id"), CastType::Text));
+/// let (sql, _) = Sqlite::build(query)?;
+///
+/// assert_eq!("SELECT CAST(`id` AS TEXT) FROM `users`", sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn cast<'a, E>(expression: E, cast_type: CastType) -> Expression<'a>
+where
+    E: Into<Expression<'a>>,
+{
+    Expression {
+        kind: ExpressionKind::Cast(Cast {
+            expression: Box::new(expression.into()),
+            cast_type,
+        }),
+        alias: None,
+    }
+}
@@ -1,6 +1,6 @@
 use super::Aliasable;
 use crate::{
-    ast::{Expression, ExpressionKind, Table},
+    ast::{Comparable, Compare, Expression, ExpressionKind, Table},
     Value,
 };
 use std::borrow::Cow;
@@ -97,6 +97,71 @@ impl<'a> Column<'a> {
             .map(|d| d == &DefaultValue::Generated)
             .unwrap_or(false)
     }
+
+    /// Tests if the column value starts with the given string: a `LIKE` with
+    /// `%` appended to the pattern. Wildcards in the pattern stay
+    /// meaningful; pass user input through [`like_escape`](super::like_escape)
+    /// first to match it literally.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that(Column::from("foo").begins_with("bar"));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` LIKE ?", sql);
+    /// assert_eq!(vec![Value::from("bar%")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn begins_with<S>(self, pattern: S) -> Compare<'a>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.like(format!("{}%", pattern.into()))
+    }
+
+    /// Tests if the column value ends with the given string: a `LIKE` with
+    /// `%` prepended to the pattern.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that(Column::from("foo").ends_with("bar"));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` LIKE ?", sql);
+    /// assert_eq!(vec![Value::from("%bar")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ends_with<S>(self, pattern: S) -> Compare<'a>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.like(format!("%{}", pattern.into()))
+    }
+
+    /// Tests if the column value contains the given string: a `LIKE` with
+    /// `%` around the pattern.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that(Column::from("foo").contains("bar"));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` LIKE ?", sql);
+    /// assert_eq!(vec![Value::from("%bar%")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn contains<S>(self, pattern: S) -> Compare<'a>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.like(format!("%{}%", pattern.into()))
+    }
 }
 
 impl<'a> From<Column<'a>> for Expression<'a> {
@@ -1,5 +1,5 @@
 use super::ExpressionKind;
-use crate::ast::{Column, ConditionTree, Expression};
+use crate::ast::{Column, ConditionTree, Expression, SelectQuery};
 use std::borrow::Cow;
 
 /// For modeling comparison expressions.
@@ -9,6 +9,12 @@ pub enum Compare<'a> {
     Equals(Box<Expression<'a>>, Box<Expression<'a>>),
     /// `left <> right`
     NotEquals(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left IS DISTINCT FROM right`, a difference check where two `NULL`s
+    /// are not distinct from each other
+    IsDistinctFrom(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left IS NOT DISTINCT FROM right`, an equality check where two
+    /// `NULL`s are the same value
+    IsNotDistinctFrom(Box<Expression<'a>>, Box<Expression<'a>>),
     /// `left < right`
     LessThan(Box<Expression<'a>>, Box<Expression<'a>>),
     /// `left <= right`
@@ -21,10 +27,14 @@ pub enum Compare<'a> {
     In(Box<Expression<'a>>, Box<Expression<'a>>),
     /// `left NOT IN (..)`
     NotIn(Box<Expression<'a>>, Box<Expression<'a>>),
-    /// `left LIKE %..%`
-    Like(Box<Expression<'a>>, Box<Expression<'a>>),
-    /// `left NOT LIKE %..%`
-    NotLike(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left LIKE %..%`, with an optional `ESCAPE` character
+    Like(Box<Expression<'a>>, Box<Expression<'a>>, Option<char>),
+    /// `left NOT LIKE %..%`, with an optional `ESCAPE` character
+    NotLike(Box<Expression<'a>>, Box<Expression<'a>>, Option<char>),
+    /// A case-insensitive `LIKE`, with an optional `ESCAPE` character
+    Ilike(Box<Expression<'a>>, Box<Expression<'a>>, Option<char>),
+    /// A case-insensitive `NOT LIKE`, with an optional `ESCAPE` character
+    NotIlike(Box<Expression<'a>>, Box<Expression<'a>>, Option<char>),
     /// `value IS NULL`
     Null(Box<Expression<'a>>),
     /// `value IS NOT NULL`
@@ -33,23 +43,23 @@ pub enum Compare<'a> {
     Between(Box<Expression<'a>>, Box<Expression<'a>>, Box<Expression<'a>>),
     /// `value` NOT BETWEEN `left` AND `right`
     NotBetween(Box<Expression<'a>>, Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `EXISTS (SELECT ..)`, true when the subquery returns at least one row
+    Exists(Box<SelectQuery<'a>>),
+    /// `NOT EXISTS (SELECT ..)`, true when the subquery returns no rows
+    NotExists(Box<SelectQuery<'a>>),
     /// Raw comparator, allows to use an operator `left <raw> right` as is,
     /// without visitor transformation in between.
     Raw(Box<Expression<'a>>, Cow<'a, str>, Box<Expression<'a>>),
     /// All json related comparators
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     JsonCompare(JsonCompare<'a>),
     /// `left` @@ to_tsquery(`value`)
-    #[cfg(feature = "postgresql")]
     Matches(Box<Expression<'a>>, Cow<'a, str>),
     /// (NOT `left` @@ to_tsquery(`value`))
-    #[cfg(feature = "postgresql")]
     NotMatches(Box<Expression<'a>>, Cow<'a, str>),
     /// ANY (`left`)
-    #[cfg(feature = "postgresql")]
     Any(Box<Expression<'a>>),
     /// ALL (`left`)
-    #[cfg(feature = "postgresql")]
     All(Box<Expression<'a>>),
 }
 
@@ -79,12 +89,48 @@ impl<'a> From<Column<'a>> for JsonType<'a> {
 }
 
 impl<'a> Compare<'a> {
+    /// Sets the `ESCAPE` character of a `LIKE` comparison, making the
+    /// wildcard characters `%` and `_` matchable literally when prefixed
+    /// with it. Has no effect on other comparisons.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("sales").so_that("comment".like("%50!%%").escape('!'));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `sales`.* FROM `sales` WHERE `comment` LIKE ? ESCAPE '!'", sql);
+    /// assert_eq!(vec![Value::from("%50!%%")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn escape(mut self, escape_char: char) -> Self {
+        match &mut self {
+            Self::Like(_, _, escape)
+            | Self::NotLike(_, _, escape)
+            | Self::Ilike(_, _, escape)
+            | Self::NotIlike(_, _, escape) => *escape = Some(escape_char),
+            _ => (),
+        }
+
+        self
+    }
+
+    /// An `IN` with an empty list can never match, rendered as `1=0`.
+    pub(crate) fn is_constant_false(&self) -> bool {
+        matches!(self, Self::In(_, right) if right.is_empty_in_list())
+    }
+
+    /// A `NOT IN` with an empty list always matches, rendered as `1=1`.
+    pub(crate) fn is_constant_true(&self) -> bool {
+        matches!(self, Self::NotIn(_, right) if right.is_empty_in_list())
+    }
+
     /// Finds a possible `(a,y) IN (SELECT x,z FROM B)`, takes the select out and
     /// converts the comparison into `a IN (SELECT x FROM cte_n where z = y)`.
     ///
     /// Left side means a match and the CTE should be handled, right side is a
     /// no-op.
-    #[cfg(feature = "mssql")]
     pub(crate) fn convert_tuple_select_to_cte(
         self,
         level: &mut usize,
@@ -290,6 +336,56 @@ pub trait Comparable<'a> {
     where
         T: Into<Expression<'a>>;
 
+    /// Tests if both sides are not the same value, treating two `NULL`s as
+    /// the same value, unlike `not_equals`. Rendered as `IS DISTINCT FROM`
+    /// where the database supports it, simulated with the NULL-safe equality
+    /// operator elsewhere.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".is_distinct_from(Value::Text(None)));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (`foo` IS ?)", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::Text(None),
+    ///     ],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if both sides are the same value, treating two `NULL`s as the
+    /// same value, unlike `equals`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".is_not_distinct_from(Value::Text(None)));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` IS ?", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::Text(None),
+    ///     ],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
     /// Tests if the left side is smaller than the right side.
     ///
     /// ```rust
@@ -384,6 +480,10 @@ pub trait Comparable<'a> {
 
     /// Tests if the left side is included in the right side collection.
     ///
+    /// An empty list renders as the constant-false expression `1=0` on every
+    /// dialect, and an `AND` holding the comparison short-circuits to `1=0`
+    /// as well, eliding the other conditions and their parameters.
+    ///
     /// ```rust
     /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
     /// # fn main() -> Result<(), quaint::error::Error> {
@@ -404,6 +504,10 @@ pub trait Comparable<'a> {
 
     /// Tests if the left side is not included in the right side collection.
     ///
+    /// An empty list renders as the constant-true expression `1=1` on every
+    /// dialect, and an `OR` holding the comparison short-circuits to `1=1`
+    /// as well, eliding the other conditions and their parameters.
+    ///
     /// ```rust
     /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
     /// # fn main() -> Result<(), quaint::error::Error> {
@@ -469,6 +573,55 @@ pub trait Comparable<'a> {
     where
         T: Into<Expression<'a>>;
 
+    /// Tests if the left side matches the pattern case-insensitively.
+    /// Rendered as `ILIKE` on PostgreSQL and as a plain `LIKE` elsewhere,
+    /// where the default collations already compare case-insensitively.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".ilike("%bar%"));
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"foo\"::text ILIKE $1", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::from("%bar%"),
+    ///     ],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if the left side does not match the pattern
+    /// case-insensitively.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".not_ilike("%bar%"));
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"foo\"::text NOT ILIKE $1", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::from("%bar%"),
+    ///     ],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn not_ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
     /// Tests if the left side is `NULL`.
     ///
     /// ```rust
@@ -558,7 +711,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -578,7 +731,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -608,7 +761,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -638,7 +791,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -666,7 +819,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -694,7 +847,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -713,7 +866,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>;
@@ -732,7 +885,7 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_not_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>;
@@ -756,7 +909,6 @@ pub trait Comparable<'a> {
     /// # Ok(())    
     /// # }
     /// ```
-    #[cfg(feature = "postgresql")]
     fn matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>;
@@ -780,7 +932,6 @@ pub trait Comparable<'a> {
     /// # Ok(())    
     /// # }
     /// ```
-    #[cfg(feature = "postgresql")]
     fn not_matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>;
@@ -796,7 +947,6 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "postgresql")]
     fn any(self) -> Compare<'a>;
 
     /// Matches all elem of a list of values.
@@ -810,7 +960,6 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "postgresql")]
     fn all(self) -> Compare<'a>;
 
     /// Compares two expressions with a custom operator.
@@ -859,6 +1008,24 @@ where
         val.not_equals(comparison)
     }
 
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_distinct_from(comparison)
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_not_distinct_from(comparison)
+    }
+
     fn less_than<T>(self, comparison: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -931,6 +1098,24 @@ where
         val.not_like(pattern)
     }
 
+    fn ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.ilike(pattern)
+    }
+
+    fn not_ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.not_ilike(pattern)
+    }
+
     #[allow(clippy::wrong_self_convention)]
     fn is_null(self) -> Compare<'a> {
         let col: Column<'a> = self.into();
@@ -977,7 +1162,7 @@ where
         left.compare_raw(raw_comparator.into(), right)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -988,7 +1173,7 @@ where
         val.json_array_contains(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -999,7 +1184,7 @@ where
         val.json_array_not_contains(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -1010,7 +1195,7 @@ where
         val.json_array_begins_with(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -1021,7 +1206,7 @@ where
         val.json_array_not_begins_with(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -1032,7 +1217,7 @@ where
         val.json_array_ends_into(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -1043,7 +1228,7 @@ where
         val.json_array_not_ends_into(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -1054,7 +1239,7 @@ where
         val.json_type_equals(json_type)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_not_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -1065,7 +1250,6 @@ where
         val.json_type_not_equals(json_type)
     }
 
-    #[cfg(feature = "postgresql")]
     fn matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -1076,7 +1260,6 @@ where
         val.matches(query)
     }
 
-    #[cfg(feature = "postgresql")]
     fn not_matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -1087,7 +1270,6 @@ where
         val.not_matches(query)
     }
 
-    #[cfg(feature = "postgresql")]
     fn any(self) -> Compare<'a> {
         let col: Column<'a> = self.into();
         let val: Expression<'a> = col.into();
@@ -1095,7 +1277,6 @@ where
         val.any()
     }
 
-    #[cfg(feature = "postgresql")]
     fn all(self) -> Compare<'a> {
         let col: Column<'a> = self.into();
         let val: Expression<'a> = col.into();
@@ -1103,3 +1284,101 @@ where
         val.all()
     }
 }
+
+/// Creates an `EXISTS (SELECT ..)` predicate, true when the subquery returns
+/// at least one row. The subquery can refer to the columns of the outer
+/// query for a correlated check.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let sub_select = Select::from_table("posts")
+///     .value(1)
+///     .so_that(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+///
+/// let query = Select::from_table("users").so_that(exists(sub_select));
+/// let (sql, params) = Sqlite::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT `users`.* FROM `users` WHERE EXISTS \
+///     (SELECT ? FROM `posts` WHERE `posts`.`user_id` = `users`.`id`)",
+///     sql
+/// );
+/// assert_eq!(vec![Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn exists<'a, S>(selection: S) -> Compare<'a>
+where
+    S: Into<SelectQuery<'a>>,
+{
+    Compare::Exists(Box::new(selection.into()))
+}
+
+/// Creates a `NOT EXISTS (SELECT ..)` predicate, true when the subquery
+/// returns no rows.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let sub_select = Select::from_table("posts")
+///     .value(1)
+///     .so_that(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+///
+/// let query = Select::from_table("users").so_that(not_exists(sub_select));
+/// let (sql, _) = Sqlite::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT `users`.* FROM `users` WHERE NOT EXISTS \
+///     (SELECT ? FROM `posts` WHERE `posts`.`user_id` = `users`.`id`)",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn not_exists<'a, S>(selection: S) -> Compare<'a>
+where
+    S: Into<SelectQuery<'a>>,
+{
+    Compare::NotExists(Box::new(selection.into()))
+}
+
+/// Escapes the `LIKE` wildcard characters `%` and `_`, as well as the
+/// backslash escape character itself, in user input meant to be matched
+/// literally. Combine the result with wildcards of your own, and pair the
+/// comparison with [`Compare::escape`] so every dialect uses the same
+/// escape character.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("sales")
+///     .so_that(Column::from("comment").contains(like_escape("50%")).escape('\\'));
+///
+/// let (sql, params) = Sqlite::build(query)?;
+///
+/// assert_eq!("SELECT `sales`.* FROM `sales` WHERE `comment` LIKE ? ESCAPE '\\'", sql);
+/// assert_eq!(vec![Value::from("%50\\%%")], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn like_escape(input: &str) -> String {
+    like_escape_with(input, '\\')
+}
+
+/// Like [`like_escape`], with a custom escape character. Useful when the
+/// pattern is full of backslashes, or with drivers that treat backslashes
+/// in strings specially.
+pub fn like_escape_with(input: &str, escape_char: char) -> String {
+    let mut escaped = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        if c == '%' || c == '_' || c == escape_char {
+            escaped.push(escape_char);
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
@@ -20,7 +20,6 @@ pub enum ConditionTree<'a> {
 impl<'a> ConditionTree<'a> {
     // Finds all possible comparisons between a tuple and a select. If returning
     // a vector of CTEs, they should be handled by the calling party.
-    #[cfg(feature = "mssql")]
     pub(crate) fn convert_tuple_selects_to_ctes(self, level: &mut usize) -> (Self, Vec<CommonTableExpression<'a>>) {
         fn convert_many<'a>(
             exprs: Vec<Expression<'a>>,
@@ -116,6 +115,52 @@ impl<'a> ConditionTree<'a> {
             self
         }
     }
+
+    /// Folds constant-true and constant-false leaves before rendering, e.g.
+    /// an empty `IN` list or a [`NegativeCondition`]. An `AND` containing a
+    /// constant-false short-circuits to constant-false and an `OR` containing
+    /// a constant-true to constant-true, eliding the other conditions and
+    /// their parameters. Important for generated queries with many dynamic
+    /// filters.
+    ///
+    /// [`NegativeCondition`]: #variant.NegativeCondition
+    pub(crate) fn simplify(self) -> ConditionTree<'a> {
+        if self.is_constant_false() {
+            return Self::NegativeCondition;
+        }
+
+        if self.is_constant_true() {
+            return Self::NoCondition;
+        }
+
+        match self {
+            Self::And(exprs) => Self::And(exprs.into_iter().filter(|e| !e.is_constant_true()).collect()),
+            Self::Or(exprs) => Self::Or(exprs.into_iter().filter(|e| !e.is_constant_false()).collect()),
+            tree => tree,
+        }
+    }
+
+    pub(crate) fn is_constant_false(&self) -> bool {
+        match self {
+            Self::NegativeCondition => true,
+            Self::And(exprs) => exprs.iter().any(|e| e.is_constant_false()),
+            Self::Or(exprs) => !exprs.is_empty() && exprs.iter().all(|e| e.is_constant_false()),
+            Self::Not(expr) => expr.is_constant_true(),
+            Self::Single(expr) => expr.is_constant_false(),
+            Self::NoCondition => false,
+        }
+    }
+
+    pub(crate) fn is_constant_true(&self) -> bool {
+        match self {
+            Self::NoCondition => true,
+            Self::And(exprs) => exprs.iter().all(|e| e.is_constant_true()),
+            Self::Or(exprs) => exprs.iter().any(|e| e.is_constant_true()),
+            Self::Not(expr) => expr.is_constant_false(),
+            Self::Single(expr) => expr.is_constant_true(),
+            Self::NegativeCondition => false,
+        }
+    }
 }
 
 impl<'a> Default for ConditionTree<'a> {
@@ -7,6 +7,12 @@ pub struct Delete<'a> {
     pub(crate) table: Table<'a>,
     pub(crate) conditions: Option<ConditionTree<'a>>,
     pub(crate) comment: Option<Cow<'a, str>>,
+    pub(crate) hints: Vec<Hint<'a>>,
+    pub(crate) returning: Option<Vec<Column<'a>>>,
+    pub(crate) using: Vec<Table<'a>>,
+    pub(crate) joins: Vec<Join<'a>>,
+    pub(crate) ordering: Ordering<'a>,
+    pub(crate) limit: Option<Value<'a>>,
 }
 
 impl<'a> From<Delete<'a>> for Query<'a> {
@@ -36,9 +42,153 @@ impl<'a> Delete<'a> {
             table: table.into(),
             conditions: None,
             comment: None,
+            hints: Vec::new(),
+            returning: None,
+            using: Vec::new(),
+            joins: Vec::new(),
+            ordering: Ordering::default(),
+            limit: None,
         }
     }
 
+    /// Adds an ordering to the deleted rows, deciding which ones go first
+    /// when combined with [`limit`]. MySQL renders the `ORDER BY` natively;
+    /// PostgreSQL and SQLite delete through a subquery on the row identity.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Delete::from_table("users").order_by("id").limit(10);
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("DELETE FROM `users` ORDER BY `id` LIMIT ?", sql);
+    /// assert_eq!(vec![Value::from(10_i64)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`limit`]: Self::limit
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+
+    /// Limits the number of deleted rows, useful for purging a large table
+    /// in batches. MySQL renders the `LIMIT` natively, PostgreSQL and SQLite
+    /// delete the rows matching a subquery on the row identity, and SQL
+    /// Server uses `TOP`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Delete::from_table("users").so_that("active".equals(false)).limit(10);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "DELETE FROM \"users\" WHERE \"ctid\" IN \
+    ///     (SELECT \"ctid\" FROM \"users\" WHERE \"active\" = $1 LIMIT $2)",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self
+    }
+
+    /// Adds a table to the `USING` clause, joining it into the delete with
+    /// the given condition. Can be called multiple times for additional
+    /// tables. Only supported on PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let condition = Column::from(("users", "id")).equals(Column::from(("banned", "user_id")));
+    /// let query = Delete::from_table("users").using("banned", condition);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "DELETE FROM \"users\" USING \"banned\" WHERE \"users\".\"id\" = \"banned\".\"user_id\"",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn using<T, C>(mut self, table: T, condition: C) -> Self
+    where
+        T: Into<Table<'a>>,
+        C: Into<ConditionTree<'a>>,
+    {
+        self.using.push(table.into());
+
+        self.conditions = Some(match self.conditions.take() {
+            Some(conditions) => conditions.and(condition.into()),
+            None => condition.into(),
+        });
+
+        self
+    }
+
+    /// Adds an `INNER JOIN` clause to the delete, removing the rows of the
+    /// target table matching the join. Can be called multiple times for
+    /// additional tables. Only supported on MySQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let join = "banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id"))));
+    /// let query = Delete::from_table("users").inner_join(join);
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "DELETE `users` FROM `users` INNER JOIN `banned` ON `banned`.`user_id` = `users`.`id`",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inner_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Inner(join.into()));
+        self
+    }
+
+    /// Adds a `LEFT JOIN` clause to the delete. Only supported on MySQL.
+    pub fn left_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Left(join.into()));
+        self
+    }
+
+    /// Adds a `RIGHT JOIN` clause to the delete. Only supported on MySQL.
+    pub fn right_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Right(join.into()));
+        self
+    }
+
+    /// Returns the values of the deleted rows for the given columns. Only
+    /// rendered on dialects supporting `DELETE .. RETURNING`, currently the
+    /// MariaDB-flavoured MySQL visitor.
+    pub fn returning<K, I>(mut self, columns: I) -> Self
+    where
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>,
+    {
+        self.returning = Some(columns.into_iter().map(|k| k.into()).collect());
+        self
+    }
+
     /// Adds a comment to the delete.
     ///
     /// ```rust
@@ -56,6 +206,14 @@ impl<'a> Delete<'a> {
         self
     }
 
+    /// Adds an optimizer hint to the delete. The visitors render only the
+    /// hints of their own dialect, at the placement the database expects,
+    /// and drop the rest with a trace log.
+    pub fn hint(mut self, hint: Hint<'a>) -> Self {
+        self.hints.push(hint);
+        self
+    }
+
     /// Adds `WHERE` conditions to the query. See
     /// [Comparable](trait.Comparable.html#required-methods) for more examples.
     ///
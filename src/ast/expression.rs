@@ -1,4 +1,4 @@
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 use super::compare::{JsonCompare, JsonType};
 use crate::ast::*;
 use query::SelectQuery;
@@ -23,6 +23,49 @@ impl<'a> Expression<'a> {
         self.alias.as_ref().map(|s| s.as_ref())
     }
 
+    /// A raw SQL fragment with `?` placeholders bound to the given parameters,
+    /// usable wherever an expression is expected. An escape hatch for
+    /// vendor-specific syntax the builder does not cover. Placeholders are
+    /// renumbered into the dialect's parameter style, and building the query
+    /// fails if their count does not match the number of parameters.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let fragment = Expression::raw("\"name\" % ?", vec!["musti"]);
+    /// let query = Select::from_table("users")
+    ///     .so_that("age".less_than(10))
+    ///     .and_where(fragment.equals(true));
+    ///
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"users\".* FROM \"users\" WHERE (\"age\" < $1 AND \"name\" % $2 = $3)",
+    ///     sql
+    /// );
+    ///
+    /// assert_eq!(
+    ///     vec![Value::from(10), Value::from("musti"), Value::from(true)],
+    ///     params
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn raw<S, P, V>(sql: S, params: P) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+        P: IntoIterator<Item = V>,
+        V: Into<Value<'a>>,
+    {
+        Self {
+            kind: ExpressionKind::RawFragment(RawFragment {
+                sql: sql.into(),
+                params: params.into_iter().map(Into::into).collect(),
+            }),
+            alias: None,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn row(row: Row<'a>) -> Self {
         Self {
@@ -132,6 +175,37 @@ impl<'a> Expression<'a> {
         matches!(self.kind, ExpressionKind::Selection(_))
     }
 
+    /// Whether the expression always evaluates to false, e.g. an `IN` with an
+    /// empty list.
+    pub(crate) fn is_constant_false(&self) -> bool {
+        match &self.kind {
+            ExpressionKind::Compare(compare) => compare.is_constant_false(),
+            ExpressionKind::ConditionTree(tree) => tree.is_constant_false(),
+            ExpressionKind::Value(expr) => expr.is_constant_false(),
+            _ => false,
+        }
+    }
+
+    /// Whether the expression always evaluates to true, e.g. a `NOT IN` with
+    /// an empty list.
+    pub(crate) fn is_constant_true(&self) -> bool {
+        match &self.kind {
+            ExpressionKind::Compare(compare) => compare.is_constant_true(),
+            ExpressionKind::ConditionTree(tree) => tree.is_constant_true(),
+            ExpressionKind::Value(expr) => expr.is_constant_true(),
+            _ => false,
+        }
+    }
+
+    /// Whether the expression is an empty list for an `IN` comparison.
+    pub(crate) fn is_empty_in_list(&self) -> bool {
+        match &self.kind {
+            ExpressionKind::Row(row) => row.is_empty(),
+            ExpressionKind::Values(values) => values.row_len() == 0,
+            _ => false,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn is_column(&self) -> bool {
         matches!(self.kind, ExpressionKind::Column(_))
@@ -139,7 +213,6 @@ impl<'a> Expression<'a> {
 
     /// Finds all comparisons between a tuple and a selection. If returning some
     /// CTEs, they should be handled in the calling layer.
-    #[cfg(feature = "mssql")]
     pub(crate) fn convert_tuple_selects_to_ctes(self, level: &mut usize) -> (Self, Vec<CommonTableExpression<'a>>) {
         match self.kind {
             ExpressionKind::Selection(s) => {
@@ -187,6 +260,40 @@ impl<'a> Expression<'a> {
     }
 }
 
+/// A fragment of raw SQL with parameters bound to `?` placeholders. The
+/// placeholders are renumbered into the dialect's parameter style and the
+/// parameters spliced into the right position of the final parameter vector
+/// when the query is built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawFragment<'a> {
+    pub(crate) sql: Cow<'a, str>,
+    pub(crate) params: Vec<Value<'a>>,
+}
+
+/// A qualified asterisk that excludes the given columns. SQL has no native
+/// syntax for exclusion, so the expression expands to an explicit column list
+/// from the known columns when visited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsteriskExcept<'a> {
+    pub table: Table<'a>,
+    pub excluded: Vec<Cow<'a, str>>,
+    pub known_columns: Vec<Cow<'a, str>>,
+}
+
+impl<'a> AsteriskExcept<'a> {
+    /// The remaining columns, qualified with the table.
+    pub(crate) fn expand(self) -> Vec<Expression<'a>> {
+        let table = self.table;
+        let excluded = self.excluded;
+
+        self.known_columns
+            .into_iter()
+            .filter(|name| !excluded.contains(name))
+            .map(|name| Column::new(name).table(table.clone()).into())
+            .collect()
+    }
+}
+
 /// An expression we can compare and use in database queries.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExpressionKind<'a> {
@@ -194,6 +301,8 @@ pub enum ExpressionKind<'a> {
     Parameterized(Value<'a>),
     /// A user-provided value we do not parameterize.
     RawValue(Raw<'a>),
+    /// A raw SQL fragment with parameters bound to `?` placeholders.
+    RawFragment(RawFragment<'a>),
     /// A database column
     Column(Box<Column<'a>>),
     /// Data in a row form, e.g. (1, 2, 3)
@@ -204,6 +313,9 @@ pub enum ExpressionKind<'a> {
     Function(Box<Function<'a>>),
     /// A qualified asterisk to a table
     Asterisk(Option<Box<Table<'a>>>),
+    /// A qualified asterisk minus a set of columns, expanded to an explicit
+    /// column list when visited
+    AsteriskExcept(AsteriskExcept<'a>),
     /// An operation: sum, sub, mul or div.
     Op(Box<SqlOp<'a>>),
     /// A `VALUES` statement
@@ -212,6 +324,12 @@ pub enum ExpressionKind<'a> {
     ConditionTree(ConditionTree<'a>),
     /// A comparison expression
     Compare(Compare<'a>),
+    /// A full-text `MATCH` against an FTS5 virtual table (SQLite)
+    FtsMatch(FtsMatch<'a>),
+    /// A `CASE WHEN .. THEN .. ELSE .. END` conditional expression
+    Case(Case<'a>),
+    /// A `CAST(expr AS type)` expression
+    Cast(Cast<'a>),
     /// A single value, column, row or a nested select
     Value(Box<Expression<'a>>),
     /// DEFAULT keyword, e.g. for `INSERT INTO ... VALUES (..., DEFAULT, ...)`
@@ -337,6 +455,20 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::NotEquals(Box::new(self), Box::new(comparison.into()))
     }
 
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::IsDistinctFrom(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::IsNotDistinctFrom(Box::new(self), Box::new(comparison.into()))
+    }
+
     fn less_than<T>(self, comparison: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -383,14 +515,28 @@ impl<'a> Comparable<'a> for Expression<'a> {
     where
         T: Into<Expression<'a>>,
     {
-        Compare::Like(Box::new(self), Box::new(pattern.into()))
+        Compare::Like(Box::new(self), Box::new(pattern.into()), None)
     }
 
     fn not_like<T>(self, pattern: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
     {
-        Compare::NotLike(Box::new(self), Box::new(pattern.into()))
+        Compare::NotLike(Box::new(self), Box::new(pattern.into()), None)
+    }
+
+    fn ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::Ilike(Box::new(self), Box::new(pattern.into()), None)
+    }
+
+    fn not_ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::NotIlike(Box::new(self), Box::new(pattern.into()), None)
     }
 
     #[allow(clippy::wrong_self_convention)]
@@ -427,7 +573,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::Raw(Box::new(self), raw_comparator.into(), Box::new(right.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -435,7 +581,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::JsonCompare(JsonCompare::ArrayContains(Box::new(self), Box::new(item.into())))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -443,7 +589,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::JsonCompare(JsonCompare::ArrayNotContains(Box::new(self), Box::new(item.into())))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -453,7 +599,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::Equals(Box::new(array_starts_with), Box::new(item.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -463,7 +609,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::NotEquals(Box::new(array_starts_with), Box::new(item.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -473,7 +619,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::Equals(Box::new(array_ends_into), Box::new(item.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -483,7 +629,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::NotEquals(Box::new(array_ends_into), Box::new(item.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -491,7 +637,7 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::JsonCompare(JsonCompare::TypeEquals(Box::new(self), json_type.into()))
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_not_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -499,7 +645,6 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::JsonCompare(JsonCompare::TypeNotEquals(Box::new(self), json_type.into()))
     }
 
-    #[cfg(feature = "postgresql")]
     fn matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -507,7 +652,6 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::Matches(Box::new(self), query.into())
     }
 
-    #[cfg(feature = "postgresql")]
     fn not_matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -515,12 +659,10 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::NotMatches(Box::new(self), query.into())
     }
 
-    #[cfg(feature = "postgresql")]
     fn any(self) -> Compare<'a> {
         Compare::Any(Box::new(self))
     }
 
-    #[cfg(feature = "postgresql")]
     fn all(self) -> Compare<'a> {
         Compare::All(Box::new(self))
     }
@@ -0,0 +1,118 @@
+use crate::ast::{Expression, ExpressionKind, Query, Table, Value};
+use std::borrow::Cow;
+
+/// A `CREATE VIRTUAL TABLE .. USING fts5(..)` statement, creating a SQLite
+/// FTS5 virtual table for full-text search. Only the SQLite visitor can
+/// render the statement, other dialects return an `UnsupportedOperation`
+/// error when building the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateFts5Table<'a> {
+    pub(crate) name: Cow<'a, str>,
+    pub(crate) columns: Vec<Cow<'a, str>>,
+    pub(crate) tokenizer: Option<Cow<'a, str>>,
+}
+
+impl<'a> CreateFts5Table<'a> {
+    /// Creates an FTS5 virtual table with the given name.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let create = CreateFts5Table::new("docs")
+    ///     .columns(["title", "body"])
+    ///     .tokenizer("porter");
+    ///
+    /// let (sql, _) = Sqlite::build(create)?;
+    ///
+    /// assert_eq!(
+    ///     "CREATE VIRTUAL TABLE `docs` USING fts5(`title`, `body`, tokenize = 'porter')",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Self {
+            name: name.into(),
+            columns: Vec::new(),
+            tokenizer: None,
+        }
+    }
+
+    /// The indexed columns of the virtual table.
+    pub fn columns<I, C>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'a, str>>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The tokenizer to use, e.g. `porter` for stemming. Defaults to the
+    /// FTS5 `unicode61` tokenizer when not set.
+    pub fn tokenizer<T>(mut self, tokenizer: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.tokenizer = Some(tokenizer.into());
+        self
+    }
+}
+
+impl<'a> From<CreateFts5Table<'a>> for Query<'a> {
+    fn from(create: CreateFts5Table<'a>) -> Self {
+        Query::CreateFts5Table(Box::new(create))
+    }
+}
+
+/// A full-text `MATCH` condition against an FTS5 virtual table. Only the
+/// SQLite visitor can render the condition, other dialects return an
+/// `UnsupportedOperation` error when building the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FtsMatch<'a> {
+    pub(crate) table: Table<'a>,
+    pub(crate) query: Value<'a>,
+}
+
+impl<'a> FtsMatch<'a> {
+    /// Matches the virtual table against the given FTS5 query string, which
+    /// is always bound as a parameter.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("docs")
+    ///     .value(asterisk())
+    ///     .so_that(ConditionTree::single(FtsMatch::new("docs", "hello world")));
+    ///
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT * FROM `docs` WHERE `docs` MATCH ?", sql);
+    /// assert_eq!(vec![Value::from("hello world")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<T, Q>(table: T, query: Q) -> Self
+    where
+        T: Into<Table<'a>>,
+        Q: Into<Value<'a>>,
+    {
+        Self {
+            table: table.into(),
+            query: query.into(),
+        }
+    }
+}
+
+impl<'a> From<FtsMatch<'a>> for Expression<'a> {
+    fn from(fts_match: FtsMatch<'a>) -> Self {
+        Expression {
+            kind: ExpressionKind::FtsMatch(fts_match),
+            alias: None,
+        }
+    }
+}
@@ -1,51 +1,65 @@
 mod aggregate_to_string;
+mod array_agg;
 mod average;
 mod coalesce;
 mod concat;
 mod count;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+mod greatest;
+#[cfg(feature = "json")]
+mod json_agg;
+#[cfg(feature = "json")]
 mod json_extract;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 mod json_extract_array;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 mod json_unquote;
+mod least;
 mod lower;
 mod maximum;
 mod minimum;
+mod now;
+mod nullif;
+mod percentile;
 mod row_number;
-#[cfg(all(feature = "json", feature = "postgresql"))]
+#[cfg(feature = "json")]
 mod row_to_json;
-#[cfg(any(feature = "postgresql", feature = "mysql"))]
 mod search;
+mod string_agg;
 mod sum;
 mod upper;
 
-#[cfg(feature = "mysql")]
 mod uuid;
 
 pub use aggregate_to_string::*;
+pub use array_agg::*;
 pub use average::*;
 pub use coalesce::*;
 pub use concat::*;
 pub use count::*;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+pub use greatest::*;
+#[cfg(feature = "json")]
+pub use json_agg::*;
+#[cfg(feature = "json")]
 pub use json_extract::*;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 pub(crate) use json_extract_array::*;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 pub use json_unquote::*;
+pub use least::*;
 pub use lower::*;
 pub use maximum::*;
 pub use minimum::*;
+pub use now::*;
+pub use nullif::*;
+pub use percentile::*;
 pub use row_number::*;
-#[cfg(all(feature = "json", feature = "postgresql"))]
+#[cfg(feature = "json")]
 pub use row_to_json::*;
-#[cfg(any(feature = "postgresql", feature = "mysql"))]
 pub use search::*;
+pub use string_agg::*;
 pub use sum::*;
 pub use upper::*;
 
-#[cfg(feature = "mysql")]
 pub use self::uuid::*;
 
 use super::{Aliasable, Expression};
@@ -61,13 +75,15 @@ pub struct Function<'a> {
 impl<'a> Function<'a> {
     pub fn returns_json(&self) -> bool {
         match self.typ_ {
-            #[cfg(all(feature = "json", feature = "postgresql"))]
+            #[cfg(feature = "json")]
             FunctionType::RowToJson(_) => true,
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
+            FunctionType::JsonAgg(_) => true,
+            #[cfg(feature = "json")]
             FunctionType::JsonExtract(_) => true,
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             FunctionType::JsonExtractLastArrayElem(_) => true,
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             FunctionType::JsonExtractFirstArrayElem(_) => true,
             _ => false,
         }
@@ -77,11 +93,17 @@ impl<'a> Function<'a> {
 /// A database function type
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum FunctionType<'a> {
-    #[cfg(all(feature = "json", feature = "postgresql"))]
+    #[cfg(feature = "json")]
     RowToJson(RowToJson<'a>),
     RowNumber(RowNumber<'a>),
     Count(Count<'a>),
     AggregateToString(AggregateToString<'a>),
+    StringAgg(StringAgg<'a>),
+    ArrayAgg(ArrayAgg<'a>),
+    #[cfg(feature = "json")]
+    JsonAgg(JsonAgg<'a>),
+    PercentileCont(PercentileCont<'a>),
+    PercentileDisc(PercentileDisc<'a>),
     Average(Average<'a>),
     Sum(Sum<'a>),
     Lower(Lower<'a>),
@@ -89,25 +111,24 @@ pub(crate) enum FunctionType<'a> {
     Minimum(Minimum<'a>),
     Maximum(Maximum<'a>),
     Coalesce(Coalesce<'a>),
+    NullIf(NullIf<'a>),
+    Greatest(Greatest<'a>),
+    Least(Least<'a>),
     Concat(Concat<'a>),
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     JsonExtract(JsonExtract<'a>),
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     JsonExtractLastArrayElem(JsonExtractLastArrayElem<'a>),
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     JsonExtractFirstArrayElem(JsonExtractFirstArrayElem<'a>),
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     JsonUnquote(JsonUnquote<'a>),
-    #[cfg(any(feature = "postgresql", feature = "mysql"))]
     TextSearch(TextSearch<'a>),
-    #[cfg(any(feature = "postgresql", feature = "mysql"))]
     TextSearchRelevance(TextSearchRelevance<'a>),
-    #[cfg(feature = "mysql")]
     UuidToBin,
-    #[cfg(feature = "mysql")]
     UuidToBinSwapped,
-    #[cfg(feature = "mysql")]
     Uuid,
+    Now,
 }
 
 impl<'a> Aliasable<'a> for Function<'a> {
@@ -122,31 +143,36 @@ impl<'a> Aliasable<'a> for Function<'a> {
     }
 }
 
-#[cfg(all(feature = "json", feature = "postgresql"))]
+#[cfg(feature = "json")]
 function!(RowToJson);
 
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
+function!(JsonAgg);
+
+function!(PercentileCont, PercentileDisc);
+
+#[cfg(feature = "json")]
 function!(JsonExtract);
 
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 function!(JsonExtractLastArrayElem);
 
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 function!(JsonExtractFirstArrayElem);
 
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 function!(JsonUnquote);
 
-#[cfg(any(feature = "postgresql", feature = "mysql"))]
 function!(TextSearch);
 
-#[cfg(any(feature = "postgresql", feature = "mysql"))]
 function!(TextSearchRelevance);
 
 function!(
     RowNumber,
     Count,
     AggregateToString,
+    StringAgg,
+    ArrayAgg,
     Average,
     Sum,
     Lower,
@@ -154,5 +180,8 @@ function!(
     Minimum,
     Maximum,
     Coalesce,
+    NullIf,
+    Greatest,
+    Least,
     Concat
 );
@@ -0,0 +1,68 @@
+use crate::ast::{ConditionTree, Expression, IntoOrderDefinition, Ordering};
+
+/// An aggregate collecting the values of a group into an array. Only
+/// supported on PostgreSQL, where the result decodes into `Value::Array`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayAgg<'a> {
+    pub(crate) value: Box<Expression<'a>>,
+    pub(crate) distinct: bool,
+    pub(crate) ordering: Ordering<'a>,
+    pub(crate) filter: Option<ConditionTree<'a>>,
+}
+
+impl<'a> ArrayAgg<'a> {
+    /// Only aggregate distinct values.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Order the values inside the array.
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+
+    /// Only aggregate the rows matching the given conditions, rendered as a
+    /// `FILTER (WHERE ..)` clause.
+    pub fn filter<T>(mut self, conditions: T) -> Self
+    where
+        T: Into<ConditionTree<'a>>,
+    {
+        self.filter = Some(conditions.into());
+        self
+    }
+}
+
+/// Aggregates the given expression into an array.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("users")
+///     .value(array_agg(Column::from("id")).order_by("id"))
+///     .group_by("team");
+///
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT ARRAY_AGG(\"id\" ORDER BY \"id\") FROM \"users\" GROUP BY \"team\"",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn array_agg<'a, T>(expr: T) -> ArrayAgg<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    ArrayAgg {
+        value: Box::new(expr.into()),
+        distinct: false,
+        ordering: Ordering::default(),
+        filter: None,
+    }
+}
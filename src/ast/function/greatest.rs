@@ -0,0 +1,41 @@
+use super::Function;
+use crate::ast::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Returns the greatest of the argument values
+pub struct Greatest<'a> {
+    pub(crate) exprs: Vec<Expression<'a>>,
+}
+
+/// Returns the greatest of the argument values.
+///
+/// Mind the differing `NULL` semantics between the databases: PostgreSQL
+/// ignores `NULL` arguments and returns `NULL` only when every argument is
+/// `NULL`, while on MySQL and SQL Server a single `NULL` argument makes the
+/// result `NULL`. Wrap the arguments in [`coalesce`](fn.coalesce.html) when
+/// the PostgreSQL behaviour is wanted everywhere.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let exprs: Vec<Expression> = vec![
+///   Column::from("created_at").into(),
+///   Column::from("updated_at").into(),
+/// ];
+/// let query = Select::from_table("users").value(greatest(exprs));
+/// let (sql, _) = Postgres::build(query)?;
+/// assert_eq!("SELECT GREATEST(\"created_at\", \"updated_at\") FROM \"users\"", sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn greatest<'a, T, V>(exprs: V) -> Function<'a>
+where
+    T: Into<Expression<'a>>,
+    V: Into<Vec<T>>,
+{
+    let fun = Greatest {
+        exprs: exprs.into().into_iter().map(|e| e.into()).collect(),
+    };
+
+    fun.into()
+}
@@ -0,0 +1,48 @@
+use crate::ast::{Expression, IntoOrderDefinition, Ordering};
+
+/// An aggregate collecting the values of a group into a JSON array. Only
+/// supported on PostgreSQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonAgg<'a> {
+    pub(crate) value: Box<Expression<'a>>,
+    pub(crate) ordering: Ordering<'a>,
+}
+
+impl<'a> JsonAgg<'a> {
+    /// Order the values inside the JSON array.
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+}
+
+/// Aggregates the given expression into a JSON array.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("posts")
+///     .value(json_agg(Column::from("title")).order_by("title"))
+///     .group_by("author_id");
+///
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT JSON_AGG(\"title\" ORDER BY \"title\") FROM \"posts\" GROUP BY \"author_id\"",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_agg<'a, T>(expr: T) -> JsonAgg<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    JsonAgg {
+        value: Box::new(expr.into()),
+        ordering: Ordering::default(),
+    }
+}
@@ -0,0 +1,39 @@
+use super::Function;
+use crate::ast::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Returns the smallest of the argument values
+pub struct Least<'a> {
+    pub(crate) exprs: Vec<Expression<'a>>,
+}
+
+/// Returns the smallest of the argument values.
+///
+/// The `NULL` semantics follow [`greatest`](fn.greatest.html): PostgreSQL
+/// ignores `NULL` arguments, MySQL and SQL Server return `NULL` when any
+/// argument is `NULL`.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let exprs: Vec<Expression> = vec![
+///   Column::from("price").into(),
+///   Column::from("discounted_price").into(),
+/// ];
+/// let query = Select::from_table("products").value(least(exprs));
+/// let (sql, _) = Postgres::build(query)?;
+/// assert_eq!("SELECT LEAST(\"price\", \"discounted_price\") FROM \"products\"", sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn least<'a, T, V>(exprs: V) -> Function<'a>
+where
+    T: Into<Expression<'a>>,
+    V: Into<Vec<T>>,
+{
+    let fun = Least {
+        exprs: exprs.into().into_iter().map(|e| e.into()).collect(),
+    };
+
+    fun.into()
+}
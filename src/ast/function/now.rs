@@ -0,0 +1,27 @@
+use super::{Function, FunctionType};
+use crate::ast::Expression;
+
+/// The current timestamp of the database server, rendered as
+/// `CURRENT_TIMESTAMP` in every dialect. As an insert or update value it
+/// lets the server produce the timestamp instead of binding one computed
+/// on the client:
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Insert::single_into("users").value("id", 1).value("created_at", now());
+/// let (sql, params) = Sqlite::build(query)?;
+///
+/// assert_eq!("INSERT INTO `users` (`id`,`created_at`) VALUES (?,CURRENT_TIMESTAMP)", sql);
+/// assert_eq!(vec![Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn now() -> Expression<'static> {
+    let func = Function {
+        typ_: FunctionType::Now,
+        alias: None,
+    };
+
+    func.into()
+}
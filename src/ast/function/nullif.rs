@@ -0,0 +1,36 @@
+use super::Function;
+use crate::ast::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Returns `NULL` when both expressions are equal, otherwise the first one
+pub struct NullIf<'a> {
+    pub(crate) left: Box<Expression<'a>>,
+    pub(crate) right: Box<Expression<'a>>,
+}
+
+/// Returns `NULL` if the arguments are equal, otherwise the first argument.
+/// Commonly used to turn sentinel values back into `NULL`, e.g. to guard a
+/// division by zero.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("users").value(nullif(Column::from("company"), Value::text("")));
+/// let (sql, params) = Sqlite::build(query)?;
+/// assert_eq!("SELECT NULLIF(`company`, ?) FROM `users`", sql);
+/// assert_eq!(vec![Value::text("")], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn nullif<'a, L, R>(left: L, right: R) -> Function<'a>
+where
+    L: Into<Expression<'a>>,
+    R: Into<Expression<'a>>,
+{
+    let fun = NullIf {
+        left: Box::new(left.into()),
+        right: Box::new(right.into()),
+    };
+
+    fun.into()
+}
@@ -0,0 +1,77 @@
+use crate::ast::Expression;
+
+/// An ordered-set aggregate returning the value at the given fraction of the
+/// ordered group, interpolating between adjacent values when needed. Only
+/// supported on PostgreSQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentileCont<'a> {
+    pub(crate) fraction: f64,
+    pub(crate) order_by: Box<Expression<'a>>,
+}
+
+/// An ordered-set aggregate returning the first value of the ordered group
+/// whose position equals or exceeds the given fraction. Only supported on
+/// PostgreSQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentileDisc<'a> {
+    pub(crate) fraction: f64,
+    pub(crate) order_by: Box<Expression<'a>>,
+}
+
+/// Calculates the continuous percentile of the ordered values, interpolating
+/// between adjacent values when the fraction does not fall exactly on one.
+///
+/// The fraction must be between `0.0` and `1.0`; values outside the range
+/// error when the query is built.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("response_times").value(percentile_cont(0.5, Column::from("ms")));
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY \"ms\") FROM \"response_times\"",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn percentile_cont<'a, T>(fraction: f64, order_by: T) -> PercentileCont<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    PercentileCont {
+        fraction,
+        order_by: Box::new(order_by.into()),
+    }
+}
+
+/// Calculates the discrete percentile of the ordered values, returning the
+/// first value whose position in the ordering equals or exceeds the fraction.
+///
+/// The fraction must be between `0.0` and `1.0`; values outside the range
+/// error when the query is built.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("response_times").value(percentile_disc(0.9, Column::from("ms")));
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT PERCENTILE_DISC(0.9) WITHIN GROUP (ORDER BY \"ms\") FROM \"response_times\"",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn percentile_disc<'a, T>(fraction: f64, order_by: T) -> PercentileDisc<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    PercentileDisc {
+        fraction,
+        order_by: Box::new(order_by.into()),
+    }
+}
@@ -2,7 +2,7 @@ use super::Function;
 use crate::ast::Table;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg(all(feature = "json", feature = "postgresql"))]
+#[cfg(feature = "json")]
 /// A representation of the `ROW_TO_JSON` function in the database.
 /// Only for `Postgresql`
 pub struct RowToJson<'a> {
@@ -38,7 +38,7 @@ pub struct RowToJson<'a> {
 /// # Ok(())
 /// # }
 /// ```
-#[cfg(all(feature = "json", feature = "postgresql"))]
+#[cfg(feature = "json")]
 pub fn row_to_json<'a, T>(expr: T, pretty_print: bool) -> Function<'a>
 where
     T: Into<Table<'a>>,
@@ -0,0 +1,71 @@
+use crate::ast::{Expression, IntoOrderDefinition, Ordering};
+use std::borrow::Cow;
+
+/// An aggregate concatenating the values of a group into one delimited
+/// string: `STRING_AGG` on PostgreSQL and SQL Server, `GROUP_CONCAT` on
+/// MySQL and SQLite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringAgg<'a> {
+    pub(crate) value: Box<Expression<'a>>,
+    pub(crate) separator: Cow<'a, str>,
+    pub(crate) distinct: bool,
+    pub(crate) ordering: Ordering<'a>,
+}
+
+impl<'a> StringAgg<'a> {
+    /// Change the separator between the values. Defaults to `,`.
+    pub fn separator<S>(mut self, separator: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Only aggregate distinct values. Not supported by SQL Server, and
+    /// SQLite only allows it with the default separator.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Order the values inside the aggregated string. Not supported by
+    /// SQLite.
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+}
+
+/// Aggregates the given expression into a delimited string.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let agg = string_agg(Column::from("name")).separator(", ").order_by("name");
+/// let query = Select::from_table("users").value(agg).group_by("team");
+///
+/// let (sql, params) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT STRING_AGG(\"name\", $1 ORDER BY \"name\") FROM \"users\" GROUP BY \"team\"",
+///     sql
+/// );
+/// assert_eq!(vec![Value::from(", ")], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn string_agg<'a, T>(expr: T) -> StringAgg<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    StringAgg {
+        value: Box::new(expr.into()),
+        separator: Cow::Borrowed(","),
+        distinct: false,
+        ordering: Ordering::default(),
+    }
+}
@@ -0,0 +1,304 @@
+use crate::error::{Error, ErrorKind};
+use std::fmt::Write;
+
+/// A geometry in the Well-Known Binary format, as stored in PostGIS
+/// `geometry` and `geography` columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WkbGeometry(pub Vec<u8>);
+
+impl WkbGeometry {
+    /// Renders the geometry as a human-readable Well-Known Text string, e.g.
+    /// `POINT(1 2)`. Geometries carrying an embedded SRID are prefixed in the
+    /// extended `SRID=4326;POINT(1 2)` form.
+    pub fn to_wkt(&self) -> crate::Result<String> {
+        let mut wkt = String::new();
+        let mut reader = WkbReader::new(&self.0);
+
+        write_geometry(&mut wkt, &mut reader, true)?;
+
+        Ok(wkt)
+    }
+}
+
+impl From<Vec<u8>> for WkbGeometry {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+fn malformed() -> Error {
+    Error::builder(ErrorKind::conversion("Malformed WKB geometry")).build()
+}
+
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> WkbReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            little_endian: true,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> crate::Result<&'a [u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos + len).ok_or_else(malformed)?;
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> crate::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> crate::Result<u32> {
+        let bytes = <[u8; 4]>::try_from(self.take(4)?).unwrap();
+
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_f64(&mut self) -> crate::Result<f64> {
+        let bytes = <[u8; 8]>::try_from(self.take(8)?).unwrap();
+
+        Ok(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+}
+
+struct WkbHeader {
+    base_type: u32,
+    dimensions: usize,
+    srid: Option<u32>,
+}
+
+/// Reads the endianness marker and type word of the next geometry, handling
+/// both the extended (PostGIS) and ISO encodings of the Z/M and SRID flags.
+fn read_header(reader: &mut WkbReader<'_>) -> crate::Result<WkbHeader> {
+    reader.little_endian = match reader.read_u8()? {
+        0 => false,
+        1 => true,
+        _ => return Err(malformed()),
+    };
+
+    let type_word = reader.read_u32()?;
+
+    let mut has_z = type_word & 0x8000_0000 != 0;
+    let mut has_m = type_word & 0x4000_0000 != 0;
+    let has_srid = type_word & 0x2000_0000 != 0;
+
+    let mut base_type = type_word & 0x0fff_ffff;
+
+    if (1000..4000).contains(&base_type) {
+        has_z = has_z || (1000..2000).contains(&base_type) || base_type >= 3000;
+        has_m = has_m || base_type >= 2000;
+        base_type %= 1000;
+    }
+
+    let srid = if has_srid { Some(reader.read_u32()?) } else { None };
+
+    Ok(WkbHeader {
+        base_type,
+        dimensions: 2 + has_z as usize + has_m as usize,
+        srid,
+    })
+}
+
+fn write_geometry(wkt: &mut String, reader: &mut WkbReader<'_>, top_level: bool) -> crate::Result<()> {
+    let header = read_header(reader)?;
+
+    if let (Some(srid), true) = (header.srid, top_level) {
+        write!(wkt, "SRID={srid};").unwrap();
+    }
+
+    let tag = match header.base_type {
+        1 => "POINT",
+        2 => "LINESTRING",
+        3 => "POLYGON",
+        4 => "MULTIPOINT",
+        5 => "MULTILINESTRING",
+        6 => "MULTIPOLYGON",
+        7 => "GEOMETRYCOLLECTION",
+        _ => return Err(malformed()),
+    };
+
+    wkt.push_str(tag);
+    wkt.push('(');
+
+    match header.base_type {
+        1 => write_point(wkt, reader, header.dimensions)?,
+        2 => write_point_sequence(wkt, reader, header.dimensions)?,
+        3 => write_rings(wkt, reader, header.dimensions)?,
+        4 => {
+            for i in 0..reader.read_u32()? {
+                if i > 0 {
+                    wkt.push(',');
+                }
+
+                let point = read_header(reader)?;
+                write_point(wkt, reader, point.dimensions)?;
+            }
+        }
+        5 => {
+            for i in 0..reader.read_u32()? {
+                if i > 0 {
+                    wkt.push(',');
+                }
+
+                let line = read_header(reader)?;
+
+                wkt.push('(');
+                write_point_sequence(wkt, reader, line.dimensions)?;
+                wkt.push(')');
+            }
+        }
+        6 => {
+            for i in 0..reader.read_u32()? {
+                if i > 0 {
+                    wkt.push(',');
+                }
+
+                let polygon = read_header(reader)?;
+
+                wkt.push('(');
+                write_rings(wkt, reader, polygon.dimensions)?;
+                wkt.push(')');
+            }
+        }
+        _ => {
+            for i in 0..reader.read_u32()? {
+                if i > 0 {
+                    wkt.push(',');
+                }
+
+                write_geometry(wkt, reader, false)?;
+            }
+        }
+    }
+
+    wkt.push(')');
+
+    Ok(())
+}
+
+fn write_point(wkt: &mut String, reader: &mut WkbReader<'_>, dimensions: usize) -> crate::Result<()> {
+    for i in 0..dimensions {
+        if i > 0 {
+            wkt.push(' ');
+        }
+
+        write!(wkt, "{}", reader.read_f64()?).unwrap();
+    }
+
+    Ok(())
+}
+
+fn write_point_sequence(wkt: &mut String, reader: &mut WkbReader<'_>, dimensions: usize) -> crate::Result<()> {
+    for i in 0..reader.read_u32()? {
+        if i > 0 {
+            wkt.push(',');
+        }
+
+        write_point(wkt, reader, dimensions)?;
+    }
+
+    Ok(())
+}
+
+fn write_rings(wkt: &mut String, reader: &mut WkbReader<'_>, dimensions: usize) -> crate::Result<()> {
+    for i in 0..reader.read_u32()? {
+        if i > 0 {
+            wkt.push(',');
+        }
+
+        wkt.push('(');
+        write_point_sequence(wkt, reader, dimensions)?;
+        wkt.push(')');
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Vec<u8> {
+        let mut wkb = vec![1u8];
+
+        wkb.extend_from_slice(&1u32.to_le_bytes());
+        wkb.extend_from_slice(&x.to_le_bytes());
+        wkb.extend_from_slice(&y.to_le_bytes());
+
+        wkb
+    }
+
+    #[test]
+    fn point_renders_as_wkt() {
+        let geom = WkbGeometry(point(1.0, 2.5));
+
+        assert_eq!("POINT(1 2.5)", geom.to_wkt().unwrap());
+    }
+
+    #[test]
+    fn srid_renders_in_the_extended_form() {
+        let mut wkb = vec![1u8];
+
+        wkb.extend_from_slice(&(1u32 | 0x2000_0000).to_le_bytes());
+        wkb.extend_from_slice(&4326u32.to_le_bytes());
+        wkb.extend_from_slice(&1.0f64.to_le_bytes());
+        wkb.extend_from_slice(&2.0f64.to_le_bytes());
+
+        let geom = WkbGeometry(wkb);
+
+        assert_eq!("SRID=4326;POINT(1 2)", geom.to_wkt().unwrap());
+    }
+
+    #[test]
+    fn linestring_renders_as_wkt() {
+        let mut wkb = vec![1u8];
+
+        wkb.extend_from_slice(&2u32.to_le_bytes());
+        wkb.extend_from_slice(&2u32.to_le_bytes());
+
+        for coord in [0.0f64, 0.0, 1.0, 1.0] {
+            wkb.extend_from_slice(&coord.to_le_bytes());
+        }
+
+        let geom = WkbGeometry(wkb);
+
+        assert_eq!("LINESTRING(0 0,1 1)", geom.to_wkt().unwrap());
+    }
+
+    #[test]
+    fn multipoint_renders_as_wkt() {
+        let mut wkb = vec![1u8];
+
+        wkb.extend_from_slice(&4u32.to_le_bytes());
+        wkb.extend_from_slice(&2u32.to_le_bytes());
+        wkb.extend_from_slice(&point(0.0, 0.0));
+        wkb.extend_from_slice(&point(1.0, 2.0));
+
+        let geom = WkbGeometry(wkb);
+
+        assert_eq!("MULTIPOINT(0 0,1 2)", geom.to_wkt().unwrap());
+    }
+
+    #[test]
+    fn truncated_input_errors() {
+        let geom = WkbGeometry(vec![1u8, 1, 0, 0, 0]);
+
+        assert!(geom.to_wkt().is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+
+/// The database dialect an optimizer [`Hint`] targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintDialect {
+    Mysql,
+    Postgres,
+    Mssql,
+}
+
+impl HintDialect {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Mysql => "mysql",
+            Self::Postgres => "postgres",
+            Self::Mssql => "mssql",
+        }
+    }
+}
+
+/// An optimizer hint of a single dialect, attached to a statement with
+/// [`Select::hint`], [`Update::hint`] or [`Delete::hint`].
+///
+/// Hints are dialect-specific by nature, so each visitor renders only the
+/// hints of its own dialect, at the placement the database expects: MySQL in
+/// a `/*+ ... */` comment right after the statement keyword, PostgreSQL
+/// (`pg_hint_plan`) in a `/*+ ... */` comment leading the statement, and SQL
+/// Server in an `OPTION (...)` clause ending it. Hints of the other dialects
+/// are dropped with a trace log, so a query targeting several databases
+/// keeps working.
+///
+/// [`Select::hint`]: super::Select::hint
+/// [`Update::hint`]: super::Update::hint
+/// [`Delete::hint`]: super::Delete::hint
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hint<'a> {
+    pub(crate) dialect: HintDialect,
+    pub(crate) text: Cow<'a, str>,
+}
+
+impl<'a> Hint<'a> {
+    /// A MySQL optimizer hint, e.g. `MAX_EXECUTION_TIME(1000)`.
+    pub fn mysql<T>(text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Self::new(HintDialect::Mysql, text.into())
+    }
+
+    /// A `pg_hint_plan` hint for PostgreSQL, e.g. `IndexScan(t idx)`.
+    pub fn postgres<T>(text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Self::new(HintDialect::Postgres, text.into())
+    }
+
+    /// A SQL Server query hint, e.g. `RECOMPILE`.
+    pub fn mssql<T>(text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Self::new(HintDialect::Mssql, text.into())
+    }
+
+    fn new(dialect: HintDialect, text: Cow<'a, str>) -> Self {
+        // A `*/` in the text would terminate the hint comment early and
+        // leak the rest of the text into the statement.
+        let text = if text.contains("*/") {
+            Cow::Owned(text.replace("*/", "* /"))
+        } else {
+            text
+        };
+
+        Self { dialect, text }
+    }
+}
+
+/// Splits out the hint texts of the given dialect, dropping the hints of the
+/// other dialects with a trace log — the same query may target several
+/// databases, so a foreign hint is not an error.
+pub(crate) fn hints_for_dialect<'a>(hints: Vec<Hint<'a>>, dialect: Option<HintDialect>) -> Vec<Cow<'a, str>> {
+    let mut own = Vec::new();
+
+    for hint in hints {
+        if Some(hint.dialect) == dialect {
+            own.push(hint.text);
+        } else {
+            tracing::trace!(
+                message = "Dropping an optimizer hint for another dialect",
+                hint = %hint.text,
+                dialect = hint.dialect.name(),
+            );
+        }
+    }
+
+    own
+}
@@ -10,6 +10,7 @@ pub struct Insert<'a> {
     pub(crate) on_conflict: Option<OnConflict<'a>>,
     pub(crate) returning: Option<Vec<Column<'a>>>,
     pub(crate) comment: Option<Cow<'a, str>>,
+    pub(crate) identity_insert: bool,
 }
 
 /// A builder for an `INSERT` statement for a single row.
@@ -112,6 +113,7 @@ impl<'a> From<SingleRowInsert<'a>> for Insert<'a> {
             on_conflict: None,
             returning: None,
             comment: None,
+            identity_insert: false,
         }
     }
 }
@@ -127,6 +129,7 @@ impl<'a> From<MultiRowInsert<'a>> for Insert<'a> {
             on_conflict: None,
             returning: None,
             comment: None,
+            identity_insert: false,
         }
     }
 }
@@ -167,6 +170,26 @@ impl<'a> Insert<'a> {
         }
     }
 
+    /// Creates an `INSERT` statement inserting a single row consisting
+    /// entirely of default values.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Insert::default_values("users").returning(["id"]);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO \"users\" DEFAULT VALUES RETURNING \"id\"", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_values<T>(table: T) -> Self
+    where
+        T: Into<Table<'a>>,
+    {
+        Insert::from(Insert::single_into(table))
+    }
+
     pub fn single() -> SingleRowInsert<'a> {
         SingleRowInsert {
             table: None,
@@ -201,6 +224,40 @@ impl<'a> Insert<'a> {
         }
     }
 
+    /// Creates an `INSERT INTO ... SELECT` statement, inserting the rows the
+    /// given `SELECT` returns.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let select = Select::from_table("candidates").column("name");
+    /// let query = Insert::from_select("users", ["name"], select);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO \"users\" (\"name\") SELECT \"name\" FROM \"candidates\"", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The column list must match the number of columns the `SELECT` returns,
+    /// raising an error when building the query otherwise.
+    pub fn from_select<T, I, K>(table: T, columns: I, select: Select<'a>) -> Self
+    where
+        T: Into<Table<'a>>,
+        I: IntoIterator<Item = K>,
+        K: Into<Column<'a>>,
+    {
+        Insert {
+            table: Some(table.into()),
+            columns: columns.into_iter().map(|c| c.into()).collect(),
+            values: Expression::from(select),
+            on_conflict: None,
+            returning: None,
+            comment: None,
+            identity_insert: false,
+        }
+    }
+
     pub fn expression_into<T, I, K, E>(table: T, columns: I, expression: E) -> Self
     where
         T: Into<Table<'a>>,
@@ -215,6 +272,7 @@ impl<'a> Insert<'a> {
             on_conflict: None,
             returning: None,
             comment: None,
+            identity_insert: false,
         }
     }
 
@@ -242,6 +300,33 @@ impl<'a> Insert<'a> {
         self
     }
 
+    /// Allows the insert to set an explicit value for an identity column.
+    /// On SQL Server the insert renders wrapped in `SET IDENTITY_INSERT ..
+    /// ON/OFF` statements, restored in the same batch even when the insert
+    /// fails. The other databases accept explicit values for their
+    /// auto-increment columns without a switch, so the flag is a no-op
+    /// there.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mssql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let insert = Insert::single_into("users").value("id", 42);
+    /// let (sql, _) = Mssql::build(Insert::from(insert).with_identity_insert())?;
+    ///
+    /// assert_eq!(
+    ///     "SET IDENTITY_INSERT [users] ON; \
+    ///      INSERT INTO [users] ([id]) VALUES (@P1); \
+    ///      SET IDENTITY_INSERT [users] OFF",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_identity_insert(mut self) -> Self {
+        self.identity_insert = true;
+        self
+    }
+
     /// Sets the returned columns.
     ///
     /// ```rust
@@ -255,7 +340,6 @@ impl<'a> Insert<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(any(feature = "postgresql", feature = "mssql", feature = "sqlite"))]
     pub fn returning<K, I>(mut self, columns: I) -> Self
     where
         K: Into<Column<'a>>,
@@ -327,6 +411,37 @@ impl<'a> MultiRowInsert<'a> {
         self
     }
 
+    /// Adds all rows of the given iterator to be inserted, replacing the
+    /// repeated [`values`](Self::values) pattern when the batch comes from a
+    /// collection.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Insert::multi_into("users", vec!["foo"])
+    ///     .values_many(vec![vec![1], vec![2]]);
+    ///
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO `users` (`foo`) VALUES (?), (?)", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::from(1),
+    ///         Value::from(2),
+    ///     ], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn values_many<I, V>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Row<'a>>,
+    {
+        self.values.extend(rows.into_iter().map(Into::into));
+        self
+    }
+
     /// Convert into a common `Insert` statement.
     pub fn build(self) -> Insert<'a> {
         Insert::from(self)
@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+/// A MySQL `SELECT ... INTO OUTFILE` clause, writing the result set into a
+/// file on the server host. Far more efficient than reading the rows in the
+/// client for large exports. Only the MySQL visitor can render the clause,
+/// other dialects return an `UnsupportedOperation` error when building the
+/// query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntoOutfile<'a> {
+    pub(crate) path: Cow<'a, str>,
+    pub(crate) fields_terminated_by: char,
+    pub(crate) lines_terminated_by: Cow<'a, str>,
+    pub(crate) optionally_enclosed_by: Option<char>,
+}
+
+impl<'a> IntoOutfile<'a> {
+    /// Exports into the given file on the server host. Fields are terminated
+    /// by `,` and lines by `\n` until overridden.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users")
+    ///     .column("name")
+    ///     .into_outfile(IntoOutfile::new("/tmp/users.csv").optionally_enclosed_by('"'));
+    ///
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT `name` FROM `users` INTO OUTFILE '/tmp/users.csv' \
+    ///     FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '\"' LINES TERMINATED BY '\\n'",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+    {
+        Self {
+            path: path.into(),
+            fields_terminated_by: ',',
+            lines_terminated_by: "\n".into(),
+            optionally_enclosed_by: None,
+        }
+    }
+
+    /// The character separating the fields of a row. Defaults to `,`.
+    pub fn fields_terminated_by(mut self, separator: char) -> Self {
+        self.fields_terminated_by = separator;
+        self
+    }
+
+    /// The string separating the rows. Defaults to `\n`.
+    pub fn lines_terminated_by<T>(mut self, separator: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.lines_terminated_by = separator.into();
+        self
+    }
+
+    /// Encloses non-numeric fields with the given character.
+    pub fn optionally_enclosed_by(mut self, enclosing: char) -> Self {
+        self.optionally_enclosed_by = Some(enclosing);
+        self
+    }
+}
+
+impl<'a> From<&'a str> for IntoOutfile<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::new(path)
+    }
+}
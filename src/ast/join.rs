@@ -5,6 +5,7 @@ use crate::ast::{ConditionTree, Table};
 pub struct JoinData<'a> {
     pub(crate) table: Table<'a>,
     pub(crate) conditions: ConditionTree<'a>,
+    pub(crate) lateral: bool,
 }
 
 impl<'a> JoinData<'a> {
@@ -13,8 +14,38 @@ impl<'a> JoinData<'a> {
         Self {
             table: table.into(),
             conditions: ConditionTree::NoCondition,
+            lateral: false,
         }
     }
+
+    /// Make the join source a lateral subquery, allowing it to refer to
+    /// columns of the preceding tables. The source must be an aliased
+    /// sub-select. On SQL Server the join renders as `CROSS APPLY` (inner) or
+    /// `OUTER APPLY` (left), on PostgreSQL and MySQL as `JOIN LATERAL`, and
+    /// SQLite has no support at all.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let latest = Select::from_table("posts")
+    ///     .so_that(("posts", "user_id").equals(Column::from(("users", "id"))))
+    ///     .limit(3);
+    ///
+    /// let join = JoinData::all_from(Table::from(latest).alias("latest")).lateral();
+    /// let query = Select::from_table("users").inner_join(join);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"users\".* FROM \"users\" INNER JOIN LATERAL (SELECT \"posts\".* FROM \"posts\" WHERE \"posts\".\"user_id\" = \"users\".\"id\" LIMIT $1) AS \"latest\" ON 1=1",
+    ///     sql,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lateral(mut self) -> Self {
+        self.lateral = true;
+        self
+    }
 }
 
 impl<'a, T> From<T> for JoinData<'a>
@@ -73,6 +104,7 @@ where
         JoinData {
             table: self.into(),
             conditions: conditions.into(),
+            lateral: false,
         }
     }
 }
@@ -90,6 +122,7 @@ impl<'a> Joinable<'a> for JoinData<'a> {
         JoinData {
             table: self.table,
             conditions,
+            lateral: self.lateral,
         }
     }
 }
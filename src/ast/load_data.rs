@@ -0,0 +1,94 @@
+use crate::ast::{Column, Query, Table};
+use std::borrow::Cow;
+
+/// A MySQL `LOAD DATA LOCAL INFILE` statement, bulk loading rows from a
+/// client-side file or stream. Orders of magnitude faster than batched
+/// `INSERT`s for large imports. Only the MySQL visitor can render the
+/// statement, other dialects return an `UnsupportedOperation` error when
+/// building the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadDataInfile<'a> {
+    pub(crate) table: Table<'a>,
+    pub(crate) path: Option<Cow<'a, str>>,
+    pub(crate) columns: Vec<Column<'a>>,
+    pub(crate) fields_terminated_by: char,
+    pub(crate) lines_terminated_by: Cow<'a, str>,
+}
+
+impl<'a> LoadDataInfile<'a> {
+    /// Bulk load into the given table. Fields are terminated by `,` and lines
+    /// by `\n` until overridden.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let load = LoadDataInfile::into_table("users")
+    ///     .path("/tmp/users.csv")
+    ///     .columns(["id", "name"]);
+    ///
+    /// let (sql, _) = Mysql::build(load)?;
+    ///
+    /// assert_eq!(
+    ///     "LOAD DATA LOCAL INFILE '/tmp/users.csv' INTO TABLE `users` \
+    ///     FIELDS TERMINATED BY ',' LINES TERMINATED BY '\\n' (`id`,`name`)",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_table<T>(table: T) -> Self
+    where
+        T: Into<Table<'a>>,
+    {
+        Self {
+            table: table.into(),
+            path: None,
+            columns: Vec::new(),
+            fields_terminated_by: ',',
+            lines_terminated_by: "\n".into(),
+        }
+    }
+
+    /// The file to read on the client machine. When not set, the statement
+    /// expects the data from a local infile handler installed on the
+    /// connection, streaming in-memory data without touching the disk.
+    pub fn path<P>(mut self, path: P) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+    {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// The columns the fields are loaded into, in the order they appear in
+    /// the data.
+    pub fn columns<I, C>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Column<'a>>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The character separating the fields of a row. Defaults to `,`.
+    pub fn fields_terminated_by(mut self, separator: char) -> Self {
+        self.fields_terminated_by = separator;
+        self
+    }
+
+    /// The string separating the rows. Defaults to `\n`.
+    pub fn lines_terminated_by<T>(mut self, separator: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.lines_terminated_by = separator.into();
+        self
+    }
+}
+
+impl<'a> From<LoadDataInfile<'a>> for Query<'a> {
+    fn from(load: LoadDataInfile<'a>) -> Self {
+        Query::LoadDataInfile(Box::new(load))
+    }
+}
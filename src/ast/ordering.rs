@@ -103,12 +103,24 @@ impl<'a> IntoOrderDefinition<'a> for OrderDefinition<'a> {
     }
 }
 
+impl<'a> IntoOrderDefinition<'a> for Expression<'a> {
+    fn into_order_definition(self) -> OrderDefinition<'a> {
+        (self, None)
+    }
+}
+
 impl<'a> Orderable<'a> for Column<'a> {
     fn order(self, order: Option<Order>) -> OrderDefinition<'a> {
         (self.into(), order)
     }
 }
 
+impl<'a> Orderable<'a> for Expression<'a> {
+    fn order(self, order: Option<Order>) -> OrderDefinition<'a> {
+        (self, order)
+    }
+}
+
 impl<'a> Orderable<'a> for &'a str {
     fn order(self, order: Option<Order>) -> OrderDefinition<'a> {
         let column: Column<'a> = self.into();
@@ -1,5 +1,10 @@
-use crate::ast::{Delete, Insert, Merge, Select, Union, Update};
-use std::borrow::Cow;
+use crate::ast::{CreateFts5Table, Delete, Insert, LoadDataInfile, Merge, ReplaceInto, Select, Union, Update, Value};
+use crate::connector::SqlFamily;
+use std::{
+    borrow::Cow,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
 use super::IntoCommonTableExpression;
 
@@ -12,6 +17,9 @@ pub enum Query<'a> {
     Delete(Box<Delete<'a>>),
     Union(Box<Union<'a>>),
     Merge(Box<Merge<'a>>),
+    ReplaceInto(Box<ReplaceInto<'a>>),
+    LoadDataInfile(Box<LoadDataInfile<'a>>),
+    CreateFts5Table(Box<CreateFts5Table<'a>>),
     Raw(Cow<'a, str>),
 }
 
@@ -44,6 +52,90 @@ impl<'a> Query<'a> {
     pub fn is_union(&self) -> bool {
         matches!(self, Query::Union(_))
     }
+
+    /// Render the query into SQL and parameters for the given database family
+    /// without executing it, e.g. for logging or caching purposes. The family
+    /// of a live connection is available from
+    /// [`ConnectionInfo::sql_family`](crate::connector::ConnectionInfo::sql_family).
+    pub fn to_sql_for(self, family: SqlFamily) -> crate::Result<(String, Vec<Value<'a>>)> {
+        use crate::visitor::Visitor;
+
+        match family {
+            SqlFamily::Postgres => crate::visitor::Postgres::build(self),
+            SqlFamily::Mysql => crate::visitor::Mysql::build(self),
+            SqlFamily::Sqlite => crate::visitor::Sqlite::build(self),
+            SqlFamily::Mssql => crate::visitor::Mssql::build(self),
+        }
+    }
+
+    /// A stable hash of the shape of the query for the given database family,
+    /// usable as a metrics label or cache key. Parameter placeholders are
+    /// normalized and parameter lists collapsed, so queries differing only in
+    /// the number of values in an `IN` list produce the same fingerprint.
+    pub fn fingerprint(&self, family: SqlFamily) -> crate::Result<String> {
+        let (sql, _) = self.clone().to_sql_for(family)?;
+
+        let mut hasher = DefaultHasher::new();
+        normalize_placeholders(&sql).hash(&mut hasher);
+
+        Ok(format!("{:016x}", hasher.finish()))
+    }
+}
+
+/// Rewrites all dialect-specific parameter placeholders to `?` and collapses
+/// lists of placeholders into a single one.
+fn normalize_placeholders(sql: &str) -> String {
+    let mut normalized = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '$' if chars.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) => {
+                while chars.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                    chars.next();
+                }
+
+                normalized.push('?');
+            }
+            '@' if chars.clone().nth(1).map(|c| c.is_ascii_digit()).unwrap_or(false) && chars.peek() == Some(&'P') => {
+                chars.next();
+
+                while chars.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                    chars.next();
+                }
+
+                normalized.push('?');
+            }
+            c => normalized.push(c),
+        }
+    }
+
+    let mut collapsed = String::with_capacity(normalized.len());
+    let mut rest = normalized.as_str();
+
+    while let Some(pos) = rest.find("(?") {
+        collapsed.push_str(&rest[..pos]);
+
+        let mut tail = &rest[pos + 2..];
+        let mut is_list = false;
+
+        while let Some(stripped) = tail.strip_prefix(",?").or_else(|| tail.strip_prefix(", ?")) {
+            tail = stripped;
+            is_list = true;
+        }
+
+        if is_list && tail.starts_with(')') {
+            collapsed.push_str("(?)");
+            tail = &tail[1..];
+        } else {
+            collapsed.push_str("(?");
+        }
+
+        rest = tail;
+    }
+
+    collapsed.push_str(rest);
+    collapsed
 }
 
 /// A database query that only returns data without modifying anything.
@@ -62,7 +154,14 @@ impl<'a> SelectQuery<'a> {
         }
     }
 
-    #[cfg(feature = "mssql")]
+    /// The number of columns the query returns, if statically known.
+    pub(crate) fn column_count(&self) -> Option<usize> {
+        match self {
+            Self::Select(s) => s.column_count(),
+            Self::Union(u) => u.column_count(),
+        }
+    }
+
     pub(crate) fn convert_tuple_selects_to_ctes(
         self,
         level: &mut usize,
@@ -108,3 +207,43 @@ impl<'a> From<SelectQuery<'a>> for Query<'a> {
 }
 
 impl<'a> IntoCommonTableExpression<'a> for SelectQuery<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+    use crate::visitor::{Postgres, Visitor};
+
+    #[test]
+    fn to_sql_for_renders_with_the_requested_dialect() {
+        let query = Query::from(Select::from_table("users").column("id"));
+
+        let (sql, params) = query.clone().to_sql_for(SqlFamily::Postgres).unwrap();
+        let (expected_sql, expected_params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(expected_params, params);
+    }
+
+    #[test]
+    fn fingerprint_collapses_parameter_lists() {
+        let two = Query::from(Select::from_table("users").so_that("id".in_selection(vec![1, 2])));
+        let three = Query::from(Select::from_table("users").so_that("id".in_selection(vec![1, 2, 3])));
+
+        assert_eq!(
+            two.fingerprint(SqlFamily::Postgres).unwrap(),
+            three.fingerprint(SqlFamily::Postgres).unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_distinguishes_different_shapes() {
+        let by_id = Query::from(Select::from_table("users").so_that("id".equals(1)));
+        let by_name = Query::from(Select::from_table("users").so_that("name".equals("Naukio")));
+
+        assert_ne!(
+            by_id.fingerprint(SqlFamily::Postgres).unwrap(),
+            by_name.fingerprint(SqlFamily::Postgres).unwrap()
+        );
+    }
+}
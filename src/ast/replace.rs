@@ -0,0 +1,206 @@
+use crate::ast::*;
+
+/// A builder for a MySQL `REPLACE INTO` statement.
+///
+/// Semantically different from `INSERT ... ON DUPLICATE KEY UPDATE`: an
+/// existing row is deleted and re-inserted instead of updated in place,
+/// firing delete triggers and claiming a new auto-increment value. Only the
+/// MySQL visitor can render the statement, other dialects return an
+/// `UnsupportedOperation` error when building the query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplaceInto<'a> {
+    pub(crate) table: Option<Table<'a>>,
+    pub(crate) columns: Vec<Column<'a>>,
+    pub(crate) values: Expression<'a>,
+}
+
+/// A builder for a `REPLACE INTO` statement for a single row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SingleRowReplace<'a> {
+    pub(crate) table: Option<Table<'a>>,
+    pub(crate) columns: Vec<Column<'a>>,
+    pub(crate) values: Row<'a>,
+}
+
+/// A builder for a `REPLACE INTO` statement for multiple rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiRowReplace<'a> {
+    pub(crate) table: Option<Table<'a>>,
+    pub(crate) columns: Vec<Column<'a>>,
+    pub(crate) values: Vec<Row<'a>>,
+}
+
+impl<'a> From<ReplaceInto<'a>> for Query<'a> {
+    fn from(replace: ReplaceInto<'a>) -> Self {
+        Query::ReplaceInto(Box::new(replace))
+    }
+}
+
+impl<'a> From<SingleRowReplace<'a>> for ReplaceInto<'a> {
+    fn from(replace: SingleRowReplace<'a>) -> Self {
+        let values = if replace.values.is_empty() {
+            Expression::from(Row::new())
+        } else {
+            Expression::from(replace.values)
+        };
+
+        ReplaceInto {
+            table: replace.table,
+            columns: replace.columns,
+            values,
+        }
+    }
+}
+
+impl<'a> From<MultiRowReplace<'a>> for ReplaceInto<'a> {
+    fn from(replace: MultiRowReplace<'a>) -> Self {
+        let values = Expression::from(Values::new(replace.values));
+
+        ReplaceInto {
+            table: replace.table,
+            columns: replace.columns,
+            values,
+        }
+    }
+}
+
+impl<'a> From<SingleRowReplace<'a>> for Query<'a> {
+    fn from(replace: SingleRowReplace<'a>) -> Query<'a> {
+        Query::from(ReplaceInto::from(replace))
+    }
+}
+
+impl<'a> From<MultiRowReplace<'a>> for Query<'a> {
+    fn from(replace: MultiRowReplace<'a>) -> Query<'a> {
+        Query::from(ReplaceInto::from(replace))
+    }
+}
+
+impl<'a> ReplaceInto<'a> {
+    /// Creates a new single row `REPLACE INTO` statement for the given table.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = ReplaceInto::single_into("users").value("foo", 10);
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("REPLACE INTO `users` (`foo`) VALUES (?)", sql);
+    /// assert_eq!(vec![Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn single_into<T>(table: T) -> SingleRowReplace<'a>
+    where
+        T: Into<Table<'a>>,
+    {
+        SingleRowReplace {
+            table: Some(table.into()),
+            columns: Vec::new(),
+            values: Row::new(),
+        }
+    }
+
+    pub fn single() -> SingleRowReplace<'a> {
+        SingleRowReplace {
+            table: None,
+            columns: Vec::new(),
+            values: Row::new(),
+        }
+    }
+
+    /// Creates a new multi row `REPLACE INTO` statement for the given table.
+    pub fn multi_into<T, K, I>(table: T, columns: I) -> MultiRowReplace<'a>
+    where
+        T: Into<Table<'a>>,
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>,
+    {
+        MultiRowReplace {
+            table: Some(table.into()),
+            columns: columns.into_iter().map(|c| c.into()).collect(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn multi<K, I>(columns: I) -> MultiRowReplace<'a>
+    where
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>,
+    {
+        MultiRowReplace {
+            table: None,
+            columns: columns.into_iter().map(|c| c.into()).collect(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn expression_into<T, I, K, E>(table: T, columns: I, expression: E) -> Self
+    where
+        T: Into<Table<'a>>,
+        I: IntoIterator<Item = K>,
+        K: Into<Column<'a>>,
+        E: Into<Expression<'a>>,
+    {
+        ReplaceInto {
+            table: Some(table.into()),
+            columns: columns.into_iter().map(|c| c.into()).collect(),
+            values: expression.into(),
+        }
+    }
+}
+
+impl<'a> SingleRowReplace<'a> {
+    /// Adds a new value to the `REPLACE INTO` statement
+    pub fn value<K, V>(mut self, key: K, val: V) -> SingleRowReplace<'a>
+    where
+        K: Into<Column<'a>>,
+        V: Into<Expression<'a>>,
+    {
+        self.columns.push(key.into());
+        self.values.push(val.into());
+
+        self
+    }
+
+    /// Convert into a common `ReplaceInto` statement.
+    pub fn build(self) -> ReplaceInto<'a> {
+        ReplaceInto::from(self)
+    }
+}
+
+impl<'a> MultiRowReplace<'a> {
+    /// Adds a new row to be replaced.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = ReplaceInto::multi_into("users", vec!["foo"])
+    ///     .values(vec![1])
+    ///     .values(vec![2]);
+    ///
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("REPLACE INTO `users` (`foo`) VALUES (?), (?)", sql);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Value::from(1),
+    ///         Value::from(2),
+    ///     ], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn values<V>(mut self, values: V) -> Self
+    where
+        V: Into<Row<'a>>,
+    {
+        self.values.push(values.into());
+        self
+    }
+
+    /// Convert into a common `ReplaceInto` statement.
+    pub fn build(self) -> ReplaceInto<'a> {
+        ReplaceInto::from(self)
+    }
+}
@@ -1,4 +1,4 @@
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 use super::compare::JsonType;
 use crate::ast::{Comparable, Compare, Expression};
 use std::borrow::Cow;
@@ -39,12 +39,10 @@ impl<'a> Row<'a> {
         self.values.len()
     }
 
-    #[cfg(feature = "mssql")]
     pub(crate) fn is_only_columns(&self) -> bool {
         self.values.iter().all(|v| v.is_column())
     }
 
-    #[cfg(feature = "mssql")]
     pub(crate) fn into_columns(self) -> Vec<crate::ast::Column<'a>> {
         let mut columns = Vec::with_capacity(self.len());
 
@@ -180,6 +178,22 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.not_equals(comparison)
     }
 
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.is_distinct_from(comparison)
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.is_not_distinct_from(comparison)
+    }
+
     fn less_than<T>(self, comparison: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -244,6 +258,22 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.not_like(pattern)
     }
 
+    fn ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.ilike(pattern)
+    }
+
+    fn not_ilike<T>(self, pattern: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.not_ilike(pattern)
+    }
+
     #[allow(clippy::wrong_self_convention)]
     fn is_null(self) -> Compare<'a> {
         let value: Expression<'a> = self.into();
@@ -283,7 +313,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.compare_raw(raw_comparator, right)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -293,7 +323,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_contains(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_contains<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -303,7 +333,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_not_contains(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -313,7 +343,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_begins_with(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_begins_with<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -323,7 +353,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_not_begins_with(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -333,7 +363,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_ends_into(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_array_not_ends_into<T>(self, item: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -343,7 +373,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_array_not_ends_into(item)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -353,7 +383,7 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_type_equals(json_type)
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn json_type_not_equals<T>(self, json_type: T) -> Compare<'a>
     where
         T: Into<JsonType<'a>>,
@@ -363,7 +393,6 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.json_type_not_equals(json_type)
     }
 
-    #[cfg(feature = "postgresql")]
     fn matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -373,7 +402,6 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.matches(query)
     }
 
-    #[cfg(feature = "postgresql")]
     fn not_matches<T>(self, query: T) -> Compare<'a>
     where
         T: Into<Cow<'a, str>>,
@@ -383,14 +411,12 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.not_matches(query)
     }
 
-    #[cfg(feature = "postgresql")]
     fn any(self) -> Compare<'a> {
         let value: Expression<'a> = self.into();
 
         value.any()
     }
 
-    #[cfg(feature = "postgresql")]
     fn all(self) -> Compare<'a> {
         let value: Expression<'a> = self.into();
 
@@ -13,9 +13,14 @@ pub struct Select<'a> {
     pub(crate) having: Option<ConditionTree<'a>>,
     pub(crate) limit: Option<Value<'a>>,
     pub(crate) offset: Option<Value<'a>>,
+    pub(crate) offset_fetch: bool,
+    pub(crate) top: Option<Value<'a>>,
     pub(crate) joins: Vec<Join<'a>>,
     pub(crate) ctes: Vec<CommonTableExpression<'a>>,
     pub(crate) comment: Option<Cow<'a, str>>,
+    pub(crate) hints: Vec<Hint<'a>>,
+    pub(crate) into_outfile: Option<IntoOutfile<'a>>,
+    pub(crate) as_of_system_time: Option<Expression<'a>>,
 }
 
 impl<'a> From<Select<'a>> for Expression<'a> {
@@ -109,6 +114,35 @@ impl<'a> Select<'a> {
         }
     }
 
+    /// Creates a query selecting from a `VALUES` table constructor, aliasing
+    /// the table and its columns. Useful for staging a batch of rows in a
+    /// common table expression, for example for an upsert.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # use quaint::values;
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_values(values!((1, "Musti"), (2, "Naukio")), "vals", ["id", "name"])
+    ///     .column(("vals", "name"));
+    ///
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"vals\".\"name\" FROM (VALUES ($1,$2),($3,$4)) AS \"vals\"(\"id\",\"name\")",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_values<A, I, C>(values: Values<'a>, alias: A, columns: I) -> Self
+    where
+        A: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'a, str>>,
+    {
+        Self::from_table(Table::from(values).alias(alias).alias_columns(columns))
+    }
+
     /// Adds a table to be selected.
     ///
     /// ```rust
@@ -558,6 +592,100 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Sets the row limit using the SQL standard `FETCH FIRST n ROWS ONLY`
+    /// syntax instead of `LIMIT`. Only supported on PostgreSQL and SQL
+    /// Server; on SQL Server [`limit`] renders the same clause.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").fetch_first(10);
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" FETCH FIRST $1 ROWS ONLY", sql);
+    /// assert_eq!(vec![Value::from(10_i64)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`limit`]: Self::limit
+    pub fn fetch_first(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self.offset_fetch = true;
+        self
+    }
+
+    /// Skips the first `offset` rows and takes `fetch`, using the SQL
+    /// standard `OFFSET .. ROWS FETCH FIRST .. ROWS ONLY` syntax instead of
+    /// `LIMIT` and `OFFSET`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").offset_fetch(10, 5);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"users\".* FROM \"users\" OFFSET $1 ROWS FETCH FIRST $2 ROWS ONLY",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn offset_fetch(mut self, offset: usize, fetch: usize) -> Self {
+        self.offset = Some(Value::from(offset));
+        self.limit = Some(Value::from(fetch));
+        self.offset_fetch = true;
+        self
+    }
+
+    /// Takes at most `top` rows, using the `TOP` syntax SQL Server renders
+    /// right after the `SELECT` keyword. Databases without the syntax fold
+    /// the value into the [`limit`] of the query, unless one is set already.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mssql, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").top(10);
+    /// let (sql, _) = Mssql::build(query.clone())?;
+    ///
+    /// assert_eq!("SELECT TOP (@P1) [users].* FROM [users]", sql);
+    ///
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` LIMIT ?", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`limit`]: Self::limit
+    pub fn top(mut self, top: usize) -> Self {
+        self.top = Some(Value::from(top));
+        self
+    }
+
+    /// Reads from a historical snapshot of the tables, as of the given
+    /// timestamp or interval expression. Only supported on CockroachDB,
+    /// through the `Cockroach` flavour of the PostgreSQL visitor.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres, PostgresFlavour}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").as_of_system_time("-10s".raw());
+    /// let (sql, _) = Postgres::build_with_flavour(query, PostgresFlavour::Cockroach)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" AS OF SYSTEM TIME '-10s'", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_of_system_time<E>(mut self, expr: E) -> Self
+    where
+        E: Into<Expression<'a>>,
+    {
+        self.as_of_system_time = Some(expr.into());
+        self
+    }
+
     /// Adds a comment to the select.
     ///
     /// ```rust
@@ -575,6 +703,52 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Adds an optimizer hint to the select. The visitors render only the
+    /// hints of their own dialect, at the placement the database expects,
+    /// and drop the rest with a trace log.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").hint(Hint::mysql("MAX_EXECUTION_TIME(1000)"));
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("SELECT /*+ MAX_EXECUTION_TIME(1000) */ `users`.* FROM `users`", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hint(mut self, hint: Hint<'a>) -> Self {
+        self.hints.push(hint);
+        self
+    }
+
+    /// Exports the result set into a file on the server host instead of
+    /// returning the rows to the client. Only the top-level `SELECT` of a
+    /// MySQL query can be exported; other dialects and subqueries fail when
+    /// building the query.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").into_outfile("/tmp/users.csv");
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT `users`.* FROM `users` INTO OUTFILE '/tmp/users.csv' \
+    ///     FIELDS TERMINATED BY ',' LINES TERMINATED BY '\\n'",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_outfile<O>(mut self, outfile: O) -> Self
+    where
+        O: Into<IntoOutfile<'a>>,
+    {
+        self.into_outfile = Some(outfile.into());
+        self
+    }
+
     /// Adds a common table expression to the select.
     ///
     /// ```rust
@@ -636,7 +810,6 @@ impl<'a> Select<'a> {
     /// - Not comparing a tuple (e.g. `x IN (SELECT ...)`)
     /// - Not using a `IN` or `NOT IN` operation
     /// - Imbalanced number of variables (e.g. `(x, y, z) IN (SELECT a, b ...)`)
-    #[cfg(feature = "mssql")]
     pub(crate) fn convert_tuple_selects_to_ctes(
         mut self,
         top_level: bool,
@@ -690,6 +863,24 @@ impl<'a> Select<'a> {
             })
             .collect()
     }
+
+    /// The number of columns the query returns, if statically known. An
+    /// asterisk or an empty column list expands to the columns of the table
+    /// at execution time, which the builder cannot count.
+    pub(crate) fn column_count(&self) -> Option<usize> {
+        if self.columns.is_empty() {
+            return None;
+        }
+
+        for column in self.columns.iter() {
+            match column.kind {
+                ExpressionKind::Asterisk(_) | ExpressionKind::AsteriskExcept(_) => return None,
+                _ => (),
+            }
+        }
+
+        Some(self.columns.len())
+    }
 }
 
 impl<'a> IntoCommonTableExpression<'a> for Select<'a> {}
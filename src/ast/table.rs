@@ -1,4 +1,7 @@
-use super::{Column, Comparable, ConditionTree, DefaultValue, ExpressionKind, IndexDefinition, Join, JoinData};
+use super::{
+    AsteriskExcept, Column, Comparable, ConditionTree, DefaultValue, ExpressionKind, IndexDefinition, Join, JoinData,
+    RawFragment,
+};
 use crate::{
     ast::{Expression, Row, Select, Values},
     error::{Error, ErrorKind},
@@ -22,6 +25,7 @@ pub enum TableType<'a> {
     JoinedTable(Box<(Cow<'a, str>, Vec<Join<'a>>)>),
     Query(Box<Select<'a>>),
     Values(Values<'a>),
+    Fragment(RawFragment<'a>),
 }
 
 /// A table definition
@@ -30,7 +34,57 @@ pub struct Table<'a> {
     pub typ: TableType<'a>,
     pub alias: Option<Cow<'a, str>>,
     pub database: Option<Cow<'a, str>>,
+    pub(crate) alias_columns: Vec<Cow<'a, str>>,
     pub(crate) index_definitions: Vec<IndexDefinition<'a>>,
+    pub(crate) sample: Option<TableSample>,
+}
+
+/// The sampling method of a `TABLESAMPLE` clause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleMethod {
+    /// Page-level sampling. Fast, but the randomness depends on the physical
+    /// layout of the table.
+    System,
+    /// Row-level sampling. Slower, but uniformly random. Renders as `SYSTEM`
+    /// on SQL Server, which only samples pages.
+    Bernoulli,
+}
+
+/// A `TABLESAMPLE` clause, reading only a random fraction of a table.
+/// Rendered by the PostgreSQL and SQL Server visitors, the other dialects
+/// return an `UnsupportedOperation` error when building the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSample {
+    pub(crate) method: SampleMethod,
+    pub(crate) fraction: f64,
+    pub(crate) seed: Option<i64>,
+}
+
+impl TableSample {
+    /// A page-level sample of the given percentage of the table.
+    pub fn system(fraction: f64) -> Self {
+        Self {
+            method: SampleMethod::System,
+            fraction,
+            seed: None,
+        }
+    }
+
+    /// A row-level sample of the given percentage of the table.
+    pub fn bernoulli(fraction: f64) -> Self {
+        Self {
+            method: SampleMethod::Bernoulli,
+            fraction,
+            seed: None,
+        }
+    }
+
+    /// Seeds the sampling with `REPEATABLE (seed)`, making repeated queries
+    /// return the same rows as long as the table does not change.
+    pub fn repeatable(mut self, seed: i64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 }
 
 impl<'a> PartialEq for Table<'a> {
@@ -40,6 +94,42 @@ impl<'a> PartialEq for Table<'a> {
 }
 
 impl<'a> Table<'a> {
+    /// A table position given as a raw SQL fragment, written into the query
+    /// as-is. An escape hatch for vendor-specific syntax such as table
+    /// sampling or index hints.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let table = Table::raw("\"users\" TABLESAMPLE SYSTEM (1)");
+    /// let query = Select::from_table(table.alias("u")).column(("u", "id"));
+    ///
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"u\".\"id\" FROM \"users\" TABLESAMPLE SYSTEM (1) AS \"u\"",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn raw<S>(sql: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Table {
+            typ: TableType::Fragment(RawFragment {
+                sql: sql.into(),
+                params: Vec::new(),
+            }),
+            alias: None,
+            database: None,
+            alias_columns: Vec::new(),
+            index_definitions: Vec::new(),
+            sample: None,
+        }
+    }
+
     /// Define in which database the table is located
     pub fn database<T>(mut self, database: T) -> Self
     where
@@ -49,6 +139,43 @@ impl<'a> Table<'a> {
         self
     }
 
+    /// Reads only a random fraction of the table with `TABLESAMPLE`. The
+    /// sample applies to this table only, other tables of a join are read in
+    /// full. Only supported on PostgreSQL and SQL Server, and the fraction
+    /// must be a percentage between 0 and 100.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let table = Table::from("events").with_sample(TableSample::system(1.0));
+    /// let query = Select::from_table(table);
+    ///
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"events\".* FROM \"events\" TABLESAMPLE SYSTEM (1)",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_sample(mut self, sample: TableSample) -> Self {
+        self.sample = Some(sample);
+        self
+    }
+
+    /// Names the columns of the table in the alias, rendered as
+    /// `AS "alias"("c1","c2")`. Mainly useful for aliasing the columns of a
+    /// `VALUES` table constructor or a subselect.
+    pub fn alias_columns<I, C>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'a, str>>,
+    {
+        self.alias_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// A qualified asterisk to this table
     pub fn asterisk(self) -> Expression<'a> {
         Expression {
@@ -57,6 +184,39 @@ impl<'a> Table<'a> {
         }
     }
 
+    /// Everything from this table except the given columns. SQL has no native
+    /// syntax for excluding columns, so the full column list of the table must
+    /// be provided and gets expanded to an explicit column list.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let users = Table::from("users");
+    /// let selection = users.clone().asterisk_except(["password_hash"], ["id", "name", "password_hash"]);
+    /// let query = Select::from_table(users).value(selection);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".\"id\", \"users\".\"name\" FROM \"users\"", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn asterisk_except<I, J, C, K>(self, excluded: I, known_columns: J) -> Expression<'a>
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'a, str>>,
+        J: IntoIterator<Item = K>,
+        K: Into<Cow<'a, str>>,
+    {
+        Expression {
+            kind: ExpressionKind::AsteriskExcept(AsteriskExcept {
+                table: self,
+                excluded: excluded.into_iter().map(Into::into).collect(),
+                known_columns: known_columns.into_iter().map(Into::into).collect(),
+            }),
+            alias: None,
+        }
+    }
+
     /// Add unique index definition.
     pub fn add_unique_index(mut self, i: impl Into<IndexDefinition<'a>>) -> Self {
         let definition = i.into();
@@ -170,6 +330,9 @@ impl<'a> Table<'a> {
             TableType::Values(_) => {
                 panic!("You cannot left_join on a table of type Values")
             }
+            TableType::Fragment(_) => {
+                panic!("You cannot left_join on a table of type Fragment")
+            }
         }
 
         self
@@ -217,6 +380,9 @@ impl<'a> Table<'a> {
             TableType::Values(_) => {
                 panic!("You cannot inner_join on a table of type Values")
             }
+            TableType::Fragment(_) => {
+                panic!("You cannot inner_join on a table of type Fragment")
+            }
         }
 
         self
@@ -264,6 +430,9 @@ impl<'a> Table<'a> {
             TableType::Values(_) => {
                 panic!("You cannot right_join on a table of type Values")
             }
+            TableType::Fragment(_) => {
+                panic!("You cannot right_join on a table of type Fragment")
+            }
         }
 
         self
@@ -311,6 +480,9 @@ impl<'a> Table<'a> {
             TableType::Values(_) => {
                 panic!("You cannot full_join on a table of type Values")
             }
+            TableType::Fragment(_) => {
+                panic!("You cannot full_join on a table of type Fragment")
+            }
         }
 
         self
@@ -323,7 +495,9 @@ impl<'a> From<&'a str> for Table<'a> {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            alias_columns: Vec::new(),
             index_definitions: Vec::new(),
+            sample: None,
         }
     }
 }
@@ -334,7 +508,9 @@ impl<'a> From<&'a String> for Table<'a> {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            alias_columns: Vec::new(),
             index_definitions: Vec::new(),
+            sample: None,
         }
     }
 }
@@ -373,7 +549,9 @@ impl<'a> From<String> for Table<'a> {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            alias_columns: Vec::new(),
             index_definitions: Vec::new(),
+            sample: None,
         }
     }
 }
@@ -390,7 +568,9 @@ impl<'a> From<Values<'a>> for Table<'a> {
             typ: TableType::Values(values),
             alias: None,
             database: None,
+            alias_columns: Vec::new(),
             index_definitions: Vec::new(),
+            sample: None,
         }
     }
 }
@@ -408,7 +588,9 @@ impl<'a> From<Select<'a>> for Table<'a> {
             typ: TableType::Query(Box::new(select)),
             alias: None,
             database: None,
+            alias_columns: Vec::new(),
             index_definitions: Vec::new(),
+            sample: None,
         }
     }
 }
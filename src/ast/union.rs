@@ -114,10 +114,16 @@ impl<'a> Union<'a> {
             .collect()
     }
 
+    /// The number of columns the union returns, if statically known. All the
+    /// queries of a union return the same number of columns, so counting the
+    /// first one is enough.
+    pub(crate) fn column_count(&self) -> Option<usize> {
+        self.selects.first().and_then(|select| select.column_count())
+    }
+
     /// Finds all comparisons between tuples and selects in the queries and
     /// converts them to common table expressions for making the query
     /// compatible with databases not supporting tuples.
-    #[cfg(feature = "mssql")]
     pub(crate) fn convert_tuple_selects_into_ctes(
         mut self,
         top_level: bool,
@@ -9,6 +9,10 @@ pub struct Update<'a> {
     pub(crate) values: Vec<Expression<'a>>,
     pub(crate) conditions: Option<ConditionTree<'a>>,
     pub(crate) comment: Option<Cow<'a, str>>,
+    pub(crate) hints: Vec<Hint<'a>>,
+    pub(crate) from_tables: Vec<Table<'a>>,
+    pub(crate) joins: Vec<Join<'a>>,
+    pub(crate) limit: Option<Value<'a>>,
 }
 
 impl<'a> From<Update<'a>> for Query<'a> {
@@ -29,9 +33,115 @@ impl<'a> Update<'a> {
             values: Vec::new(),
             conditions: None,
             comment: None,
+            hints: Vec::new(),
+            from_tables: Vec::new(),
+            joins: Vec::new(),
+            limit: None,
         }
     }
 
+    /// Limits the number of updated rows, useful for touching a large table
+    /// in batches. Only supported on MySQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("users").set("checked", true).limit(10);
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("UPDATE `users` SET `checked` = ? LIMIT ?", sql);
+    /// assert_eq!(vec![Value::from(true), Value::from(10_i64)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self
+    }
+
+    /// Adds a table to the `FROM` clause, joining it into the update with
+    /// the given condition. Can be called multiple times for additional
+    /// tables. Only supported on PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let condition = Column::from(("users", "id")).equals(Column::from(("profiles", "user_id")));
+    /// let query = Update::table("users")
+    ///     .set("name", Column::from(("profiles", "name")))
+    ///     .and_from("profiles", condition);
+    ///
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "UPDATE \"users\" SET \"name\" = \"profiles\".\"name\" FROM \"profiles\" WHERE \"users\".\"id\" = \"profiles\".\"user_id\"",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_from<T, C>(mut self, table: T, condition: C) -> Self
+    where
+        T: Into<Table<'a>>,
+        C: Into<ConditionTree<'a>>,
+    {
+        self.from_tables.push(table.into());
+
+        self.conditions = Some(match self.conditions.take() {
+            Some(conditions) => conditions.and(condition.into()),
+            None => condition.into(),
+        });
+
+        self
+    }
+
+    /// Adds an `INNER JOIN` clause to the update, allowing the assignments
+    /// to refer to the columns of the joined table. Can be called multiple
+    /// times for additional tables. Only supported on MySQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let join = "profiles".on(Column::from(("profiles", "user_id")).equals(Column::from(("users", "id"))));
+    /// let query = Update::table("users")
+    ///     .inner_join(join)
+    ///     .set(("users", "name"), Column::from(("profiles", "name")));
+    ///
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "UPDATE `users` INNER JOIN `profiles` ON `profiles`.`user_id` = `users`.`id` SET `users`.`name` = `profiles`.`name`",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inner_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Inner(join.into()));
+        self
+    }
+
+    /// Adds a `LEFT JOIN` clause to the update. Only supported on MySQL.
+    pub fn left_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Left(join.into()));
+        self
+    }
+
+    /// Adds a `RIGHT JOIN` clause to the update. Only supported on MySQL.
+    pub fn right_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(Join::Right(join.into()));
+        self
+    }
+
     /// Add another column value assignment to the query
     ///
     /// ```rust
@@ -80,6 +190,14 @@ impl<'a> Update<'a> {
         self
     }
 
+    /// Adds an optimizer hint to the update. The visitors render only the
+    /// hints of their own dialect, at the placement the database expects,
+    /// and drop the rest with a trace log.
+    pub fn hint(mut self, hint: Hint<'a>) -> Self {
+        self.hints.push(hint);
+        self
+    }
+
     /// Adds `WHERE` conditions to the query. See
     /// [Comparable](trait.Comparable.html#required-methods) for more examples.
     ///
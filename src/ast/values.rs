@@ -2,9 +2,9 @@ use crate::ast::*;
 use crate::error::{Error, ErrorKind};
 
 #[cfg(feature = "bigdecimal")]
-use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use bigdecimal::{BigDecimal, FromPrimitive};
 #[cfg(feature = "chrono")]
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 #[cfg(feature = "json")]
 use serde_json::{Number, Value as JsonValue};
 use std::{
@@ -36,6 +36,55 @@ where
     }
 }
 
+/// An IP address or network, as stored in PostgreSQL `inet` and `cidr`
+/// columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+    /// The address part.
+    pub address: std::net::IpAddr,
+    /// The network prefix length, when the value carries one (`cidr`, or
+    /// `inet` with a netmask).
+    pub prefix: Option<u8>,
+}
+
+impl fmt::Display for IpNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.prefix {
+            Some(prefix) => write!(f, "{}/{}", self.address, prefix),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+impl From<std::net::IpAddr> for IpNet {
+    fn from(address: std::net::IpAddr) -> Self {
+        Self { address, prefix: None }
+    }
+}
+
+impl FromStr for IpNet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix) = match s.split_once('/') {
+            Some((address, prefix)) => {
+                let prefix = prefix
+                    .parse()
+                    .map_err(|_| Error::builder(ErrorKind::conversion("Invalid network prefix length")).build())?;
+
+                (address, Some(prefix))
+            }
+            None => (s, None),
+        };
+
+        let address = address
+            .parse()
+            .map_err(|_| Error::builder(ErrorKind::conversion("Invalid IP address")).build())?;
+
+        Ok(Self { address, prefix })
+    }
+}
+
 /// A value we must parameterize for the prepared statement. Null values should be
 /// defined by their corresponding type variants with a `None` value for best
 /// compatibility.
@@ -45,6 +94,9 @@ pub enum Value<'a> {
     Int32(Option<i32>),
     /// 64-bit signed integer.
     Int64(Option<i64>),
+    /// 128-bit signed integer, covering values outside of the 64-bit range,
+    /// such as MySQL `BIGINT UNSIGNED` columns.
+    Int128(Option<i128>),
     /// 32-bit floating point.
     Float(Option<f32>),
     /// 64-bit floating point.
@@ -61,6 +113,10 @@ pub enum Value<'a> {
     Char(Option<char>),
     /// An array value (PostgreSQL).
     Array(Option<Vec<Value<'a>>>),
+    /// An array value with an explicit element type (PostgreSQL). The type
+    /// makes the value bindable as a true array parameter even when the
+    /// elements alone cannot determine it.
+    TypedArray(Option<Vec<Value<'a>>>, ArrayType),
     /// A numeric value.
     #[cfg(feature = "bigdecimal")]
         Numeric(Option<BigDecimal>),
@@ -73,25 +129,109 @@ pub enum Value<'a> {
         /// An UUID value.
     Uuid(Option<Uuid>),
     #[cfg(feature = "chrono")]
-        /// A datetime value.
-    DateTime(Option<DateTime<Utc>>),
+        /// A datetime value without a timezone.
+    DateTime(Option<NaiveDateTime>),
+    #[cfg(feature = "chrono")]
+        /// A datetime value in UTC.
+    DateTimeUtc(Option<DateTime<Utc>>),
     #[cfg(feature = "chrono")]
         /// A date value.
     Date(Option<NaiveDate>),
     #[cfg(feature = "chrono")]
         /// A time value.
     Time(Option<NaiveTime>),
+    #[cfg(feature = "postgis")]
+        /// A PostGIS geometry or geography value in the Well-Known Binary
+        /// format.
+    Geometry(Option<WkbGeometry>),
+    /// An IP address or network (PostgreSQL `inet` and `cidr`).
+    IpAddr(Option<IpNet>),
+    /// A bit-string (PostgreSQL `bit(n)` and `varbit`, MySQL `bit(n)`),
+    /// stored as a string of `0` and `1` characters with the most
+    /// significant bit first.
+    BitString(Option<Cow<'a, str>>),
+}
+
+/// The element type of a [`Value::TypedArray`]. A binding hint for arrays
+/// whose elements cannot determine the type on their own: empty arrays,
+/// arrays of NULLs and arrays of enum or JSON values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayType {
+    /// 32-bit signed integers.
+    Int32,
+    /// 64-bit signed integers.
+    Int64,
+    /// 32-bit floating point numbers.
+    Float,
+    /// 64-bit floating point numbers.
+    Double,
+    /// Strings.
+    Text,
+    /// Byte blobs.
+    Bytes,
+    /// Booleans.
+    Boolean,
+    /// Numeric values with arbitrary precision.
+    #[cfg(feature = "bigdecimal")]
+    Numeric,
+    /// JSON documents, bound as `jsonb`.
+    #[cfg(feature = "json")]
+    Json,
+    /// UUIDs.
+    #[cfg(feature = "uuid")]
+    Uuid,
+    /// Datetime values in UTC, bound as `timestamptz`.
+    #[cfg(feature = "chrono")]
+    DateTime,
 }
 
-pub(crate) struct Params<'a>(pub(crate) &'a [Value<'a>]);
+/// Limits applied when rendering parameter values into logs, preventing huge
+/// blobs, documents or arrays from ending up in the logs verbatim.
+#[derive(Debug, Clone, Copy)]
+pub struct ParamLogLimits {
+    /// The maximum number of characters rendered for text-like values (text,
+    /// enums, XML and JSON). Longer values are cut with an ellipsis, keeping
+    /// the original length visible.
+    pub max_text_length: usize,
+    /// The maximum number of array elements rendered. The element count is
+    /// always included.
+    pub max_array_elements: usize,
+    /// The number of leading bytes rendered as a hex prefix for byte values.
+    pub bytes_hex_prefix_length: usize,
+}
+
+impl Default for ParamLogLimits {
+    fn default() -> Self {
+        Self {
+            max_text_length: 128,
+            max_array_elements: 8,
+            bytes_hex_prefix_length: 8,
+        }
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
+pub(crate) struct Params<'a> {
+    values: &'a [Value<'a>],
+    limits: ParamLogLimits,
+}
+
+impl<'a> Params<'a> {
+    pub(crate) fn new(values: &'a [Value<'a>], limits: ParamLogLimits) -> Self {
+        Self { values, limits }
+    }
+}
 
 impl<'a> fmt::Display for Params<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let len = self.0.len();
+        let len = self.values.len();
 
         write!(f, "[")?;
-        for (i, val) in self.0.iter().enumerate() {
-            write!(f, "{val}")?;
+        for (i, val) in self.values.iter().enumerate() {
+            fmt_bounded(val, f, &self.limits)?;
 
             if i < (len - 1) {
                 write!(f, ",")?;
@@ -101,11 +241,77 @@ impl<'a> fmt::Display for Params<'a> {
     }
 }
 
+/// Renders a value for logging, truncating it according to the given limits.
+/// Values that cannot grow are rendered with their normal `Display`
+/// implementation.
+fn fmt_bounded(value: &Value<'_>, f: &mut fmt::Formatter<'_>, limits: &ParamLogLimits) -> fmt::Result {
+    fn fmt_text(f: &mut fmt::Formatter<'_>, text: &str, limits: &ParamLogLimits) -> fmt::Result {
+        if text.chars().count() > limits.max_text_length {
+            let prefix: String = text.chars().take(limits.max_text_length).collect();
+            write!(f, "\"{}…\" (len: {})", prefix, text.len())
+        } else {
+            write!(f, "\"{text}\"")
+        }
+    }
+
+    match value {
+        Value::Text(Some(text)) => fmt_text(f, text, limits),
+        Value::Enum(Some(text)) => fmt_text(f, text, limits),
+        Value::Xml(Some(text)) => fmt_text(f, text, limits),
+        #[cfg(feature = "json")]
+        Value::Json(Some(json)) => fmt_text(f, &json.to_string(), limits),
+        Value::Bytes(Some(bytes)) => {
+            let prefix_len = limits.bytes_hex_prefix_length.min(bytes.len());
+
+            write!(f, "<bytes: {}, 0x{}", bytes.len(), hex::encode(&bytes[..prefix_len]))?;
+
+            if bytes.len() > prefix_len {
+                write!(f, "…")?;
+            }
+
+            write!(f, ">")
+        }
+        #[cfg(feature = "postgis")]
+        Value::Geometry(Some(geom)) => {
+            let prefix_len = limits.bytes_hex_prefix_length.min(geom.0.len());
+
+            write!(f, "<geometry: {}, 0x{}", geom.0.len(), hex::encode(&geom.0[..prefix_len]))?;
+
+            if geom.0.len() > prefix_len {
+                write!(f, "…")?;
+            }
+
+            write!(f, ">")
+        }
+        Value::Array(Some(vals)) | Value::TypedArray(Some(vals), _) => {
+            write!(f, "<array: {}> [", vals.len())?;
+
+            let shown = limits.max_array_elements.min(vals.len());
+
+            for (i, val) in vals.iter().take(shown).enumerate() {
+                fmt_bounded(val, f, limits)?;
+
+                if i < (shown - 1) {
+                    write!(f, ",")?;
+                }
+            }
+
+            if vals.len() > shown {
+                write!(f, ",…")?;
+            }
+
+            write!(f, "]")
+        }
+        other => fmt::Display::fmt(other, f),
+    }
+}
+
 impl<'a> fmt::Display for Value<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let res = match self {
             Value::Int32(val) => val.map(|v| write!(f, "{v}")),
             Value::Int64(val) => val.map(|v| write!(f, "{v}")),
+            Value::Int128(val) => val.map(|v| write!(f, "{v}")),
             Value::Float(val) => val.map(|v| write!(f, "{v}")),
             Value::Double(val) => val.map(|v| write!(f, "{v}")),
             Value::Text(val) => val.as_ref().map(|v| write!(f, "\"{v}\"")),
@@ -113,7 +319,7 @@ impl<'a> fmt::Display for Value<'a> {
             Value::Enum(val) => val.as_ref().map(|v| write!(f, "\"{v}\"")),
             Value::Boolean(val) => val.map(|v| write!(f, "{v}")),
             Value::Char(val) => val.map(|v| write!(f, "'{v}'")),
-            Value::Array(vals) => vals.as_ref().map(|vals| {
+            Value::Array(vals) | Value::TypedArray(vals, _) => vals.as_ref().map(|vals| {
                 let len = vals.len();
 
                 write!(f, "[")?;
@@ -136,9 +342,15 @@ impl<'a> fmt::Display for Value<'a> {
             #[cfg(feature = "chrono")]
             Value::DateTime(val) => val.map(|v| write!(f, "\"{v}\"")),
             #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(val) => val.map(|v| write!(f, "\"{v}\"")),
+            #[cfg(feature = "chrono")]
             Value::Date(val) => val.map(|v| write!(f, "\"{v}\"")),
             #[cfg(feature = "chrono")]
             Value::Time(val) => val.map(|v| write!(f, "\"{v}\"")),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(val) => val.as_ref().map(|v| write!(f, "<{} bytes geometry>", v.0.len())),
+            Value::IpAddr(val) => val.map(|v| write!(f, "\"{v}\"")),
+            Value::BitString(val) => val.as_ref().map(|v| write!(f, "b\"{v}\"")),
         };
 
         match res {
@@ -154,6 +366,10 @@ impl<'a> From<Value<'a>> for serde_json::Value {
         let res = match pv {
             Value::Int32(i) => i.map(|i| serde_json::Value::Number(Number::from(i))),
             Value::Int64(i) => i.map(|i| serde_json::Value::Number(Number::from(i))),
+            Value::Int128(i) => i.map(|i| match i64::try_from(i) {
+                Ok(i) => serde_json::Value::Number(Number::from(i)),
+                Err(_) => serde_json::Value::String(i.to_string()),
+            }),
             Value::Float(f) => f.map(|f| match Number::from_f64(f as f64) {
                 Some(number) => serde_json::Value::Number(number),
                 None => serde_json::Value::Null,
@@ -174,21 +390,29 @@ impl<'a> From<Value<'a>> for serde_json::Value {
                 serde_json::Value::String(s)
             }),
             Value::Xml(cow) => cow.map(|cow| serde_json::Value::String(cow.into_owned())),
-            Value::Array(v) => {
+            Value::Array(v) | Value::TypedArray(v, _) => {
                 v.map(|v| serde_json::Value::Array(v.into_iter().map(serde_json::Value::from).collect()))
             }
             #[cfg(feature = "bigdecimal")]
-            Value::Numeric(d) => d.map(|d| serde_json::to_value(d.to_f64().unwrap()).unwrap()),
+            // A string, not a number: `f64` cannot represent every decimal
+            // exactly.
+            Value::Numeric(d) => d.map(|d| serde_json::Value::String(d.to_string())),
             #[cfg(feature = "json")]
             Value::Json(v) => v,
             #[cfg(feature = "uuid")]
             Value::Uuid(u) => u.map(|u| serde_json::Value::String(u.hyphenated().to_string())),
             #[cfg(feature = "chrono")]
-            Value::DateTime(dt) => dt.map(|dt| serde_json::Value::String(dt.to_rfc3339())),
+            Value::DateTime(dt) => dt.map(|dt| serde_json::Value::String(format!("{dt}"))),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.map(|dt| serde_json::Value::String(dt.to_rfc3339())),
             #[cfg(feature = "chrono")]
             Value::Date(date) => date.map(|date| serde_json::Value::String(format!("{date}"))),
             #[cfg(feature = "chrono")]
             Value::Time(time) => time.map(|time| serde_json::Value::String(format!("{time}"))),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(geom) => geom.map(|geom| serde_json::Value::String(hex::encode(geom.0))),
+            Value::IpAddr(ip) => ip.map(|ip| serde_json::Value::String(ip.to_string())),
+            Value::BitString(bits) => bits.map(|bits| serde_json::Value::String(bits.into_owned())),
         };
 
         match res {
@@ -215,6 +439,14 @@ impl<'a> Value<'a> {
         Value::Int64(Some(value.into()))
     }
 
+    /// Creates a new 128-bit signed integer.
+    pub fn int128<I>(value: I) -> Self
+    where
+        I: Into<i128>,
+    {
+        Value::Int128(Some(value.into()))
+    }
+
     /// Creates a new 32-bit signed integer.
     pub fn integer<I>(value: I) -> Self
     where
@@ -288,18 +520,35 @@ impl<'a> Value<'a> {
         Value::Array(Some(value.into_iter().map(|v| v.into()).collect()))
     }
 
+    /// Creates a new array value with an explicit element type. In contrast
+    /// to [`array`](Self::array), the value binds as a true PostgreSQL array
+    /// parameter even when it is empty or contains NULL elements.
+    pub fn typed_array<I, V>(value: I, r#type: ArrayType) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value<'a>>,
+    {
+        Value::TypedArray(Some(value.into_iter().map(|v| v.into()).collect()), r#type)
+    }
+
     /// Creates a new uuid value.
     #[cfg(feature = "uuid")]
         pub const fn uuid(value: Uuid) -> Self {
         Value::Uuid(Some(value))
     }
 
-    /// Creates a new datetime value.
+    /// Creates a new datetime value without a timezone.
     #[cfg(feature = "chrono")]
-        pub const fn datetime(value: DateTime<Utc>) -> Self {
+        pub const fn datetime(value: NaiveDateTime) -> Self {
         Value::DateTime(Some(value))
     }
 
+    /// Creates a new datetime value in UTC.
+    #[cfg(feature = "chrono")]
+        pub const fn datetime_utc(value: DateTime<Utc>) -> Self {
+        Value::DateTimeUtc(Some(value))
+    }
+
     /// Creates a new date value.
     #[cfg(feature = "chrono")]
         pub const fn date(value: NaiveDate) -> Self {
@@ -326,11 +575,78 @@ impl<'a> Value<'a> {
         Value::Xml(Some(value.into()))
     }
 
+    /// Creates a new geometry value from Well-Known Binary bytes.
+    #[cfg(feature = "postgis")]
+        pub fn geometry<G>(value: G) -> Self
+    where
+        G: Into<WkbGeometry>,
+    {
+        Value::Geometry(Some(value.into()))
+    }
+
+    /// Creates a new IP address value.
+    pub fn ip_addr<I>(value: I) -> Self
+    where
+        I: Into<IpNet>,
+    {
+        Value::IpAddr(Some(value.into()))
+    }
+
+    /// Creates a new bit-string value from a string of `0` and `1`
+    /// characters, most significant bit first.
+    pub fn bit_string<T>(value: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Value::BitString(Some(value.into()))
+    }
+
+    /// Takes ownership of the borrowed contents, detaching the value from
+    /// the lifetime of its source.
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::Int32(val) => Value::Int32(val),
+            Value::Int64(val) => Value::Int64(val),
+            Value::Int128(val) => Value::Int128(val),
+            Value::Float(val) => Value::Float(val),
+            Value::Double(val) => Value::Double(val),
+            Value::Text(val) => Value::Text(val.map(|val| Cow::Owned(val.into_owned()))),
+            Value::Enum(val) => Value::Enum(val.map(|val| Cow::Owned(val.into_owned()))),
+            Value::Bytes(val) => Value::Bytes(val.map(|val| Cow::Owned(val.into_owned()))),
+            Value::Boolean(val) => Value::Boolean(val),
+            Value::Char(val) => Value::Char(val),
+            Value::Array(vals) => Value::Array(vals.map(|vals| vals.into_iter().map(Value::into_owned).collect())),
+            Value::TypedArray(vals, typ) => {
+                Value::TypedArray(vals.map(|vals| vals.into_iter().map(Value::into_owned).collect()), typ)
+            }
+            #[cfg(feature = "bigdecimal")]
+            Value::Numeric(val) => Value::Numeric(val),
+            #[cfg(feature = "json")]
+            Value::Json(val) => Value::Json(val),
+            Value::Xml(val) => Value::Xml(val.map(|val| Cow::Owned(val.into_owned()))),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(val) => Value::Uuid(val),
+            #[cfg(feature = "chrono")]
+            Value::DateTime(val) => Value::DateTime(val),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(val) => Value::DateTimeUtc(val),
+            #[cfg(feature = "chrono")]
+            Value::Date(val) => Value::Date(val),
+            #[cfg(feature = "chrono")]
+            Value::Time(val) => Value::Time(val),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(val) => Value::Geometry(val),
+            Value::IpAddr(val) => Value::IpAddr(val),
+            Value::BitString(val) => Value::BitString(val.map(|val| Cow::Owned(val.into_owned()))),
+        }
+    }
+
     /// `true` if the `Value` is null.
     pub const fn is_null(&self) -> bool {
         match self {
             Value::Int32(i) => i.is_none(),
             Value::Int64(i) => i.is_none(),
+            Value::Int128(i) => i.is_none(),
             Value::Float(i) => i.is_none(),
             Value::Double(i) => i.is_none(),
             Value::Text(t) => t.is_none(),
@@ -339,6 +655,7 @@ impl<'a> Value<'a> {
             Value::Boolean(b) => b.is_none(),
             Value::Char(c) => c.is_none(),
             Value::Array(v) => v.is_none(),
+            Value::TypedArray(v, _) => v.is_none(),
             Value::Xml(s) => s.is_none(),
             #[cfg(feature = "bigdecimal")]
             Value::Numeric(r) => r.is_none(),
@@ -347,11 +664,17 @@ impl<'a> Value<'a> {
             #[cfg(feature = "chrono")]
             Value::DateTime(dt) => dt.is_none(),
             #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.is_none(),
+            #[cfg(feature = "chrono")]
             Value::Date(d) => d.is_none(),
             #[cfg(feature = "chrono")]
             Value::Time(t) => t.is_none(),
             #[cfg(feature = "json")]
             Value::Json(json) => json.is_none(),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(g) => g.is_none(),
+            Value::IpAddr(ip) => ip.is_none(),
+            Value::BitString(b) => b.is_none(),
         }
     }
 
@@ -419,6 +742,58 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Transforms the `Value` to a `Vec<u8>` if it's text or a byte slice,
+    /// otherwise `None`. In contrast to [`to_bytes`](Self::to_bytes), an owned
+    /// buffer is moved out without copying.
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Value::Text(Some(cow)) => Some(cow.into_owned().into_bytes()),
+            Value::Bytes(Some(cow)) => Some(cow.into_owned()),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this value is the `Geometry` variant.
+    #[cfg(feature = "postgis")]
+        pub const fn is_geometry(&self) -> bool {
+        matches!(self, Value::Geometry(_))
+    }
+
+    /// Returns the Well-Known Binary geometry if the value is a geometry, otherwise `None`.
+    #[cfg(feature = "postgis")]
+        pub const fn as_geometry(&self) -> Option<&WkbGeometry> {
+        match self {
+            Value::Geometry(Some(geom)) => Some(geom),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this value is the `IpAddr` variant.
+    pub const fn is_ip_addr(&self) -> bool {
+        matches!(self, Value::IpAddr(_))
+    }
+
+    /// Returns the IP address or network if the value is one, otherwise `None`.
+    pub const fn as_ip_addr(&self) -> Option<IpNet> {
+        match self {
+            Value::IpAddr(Some(ip)) => Some(*ip),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this value is the `BitString` variant.
+    pub const fn is_bit_string(&self) -> bool {
+        matches!(self, Value::BitString(_))
+    }
+
+    /// Returns the bit-string if the value is one, otherwise `None`.
+    pub fn as_bit_string(&self) -> Option<&str> {
+        match self {
+            Value::BitString(Some(bits)) => Some(bits.borrow()),
+            _ => None,
+        }
+    }
+
     /// `true` if the `Value` is a 32-bit signed integer.
     pub const fn is_i32(&self) -> bool {
         matches!(self, Value::Int32(_))
@@ -431,7 +806,7 @@ impl<'a> Value<'a> {
 
     /// `true` if the `Value` is a signed integer.
     pub const fn is_integer(&self) -> bool {
-        matches!(self, Value::Int32(_) | Value::Int64(_))
+        matches!(self, Value::Int32(_) | Value::Int64(_) | Value::Int128(_))
     }
 
     /// Returns an `i64` if the value is a 64-bit signed integer, otherwise `None`.
@@ -450,11 +825,21 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Returns an `i128` if the value is a 128-bit signed integer, otherwise
+    /// `None`.
+    pub const fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Int128(i) => *i,
+            _ => None,
+        }
+    }
+
     /// Returns an `i64` if the value is a signed integer, otherwise `None`.
     pub fn as_integer(&self) -> Option<i64> {
         match self {
             Value::Int32(i) => i.map(|i| i as i64),
             Value::Int64(i) => *i,
+            Value::Int128(i) => i.and_then(|i| i64::try_from(i).ok()),
             _ => None,
         }
     }
@@ -527,12 +912,12 @@ impl<'a> Value<'a> {
 
     /// `true` if the `Value` is an Array.
     pub const fn is_array(&self) -> bool {
-        matches!(self, Value::Array(_))
+        matches!(self, Value::Array(_) | Value::TypedArray(..))
     }
 
     pub const fn as_array(&self) -> Option<&Vec<Value>> {
         match self {
-            Value::Array(a) => a.as_ref(),
+            Value::Array(a) | Value::TypedArray(a, _) => a.as_ref(),
             _ => None,
         }
     }
@@ -552,21 +937,46 @@ impl<'a> Value<'a> {
         }
     }
 
-    /// `true` if the `Value` is a DateTime.
+    /// `true` if the `Value` is a DateTime, with or without a timezone.
     #[cfg(feature = "chrono")]
     pub const fn is_datetime(&self) -> bool {
-        matches!(self, Value::DateTime(_))
+        matches!(self, Value::DateTime(_) | Value::DateTimeUtc(_))
     }
 
-    /// Returns a `DateTime` if the value is a `DateTime`, otherwise `None`.
+    /// Returns a `NaiveDateTime` if the value is a timezone-less `DateTime`,
+    /// otherwise `None`.
     #[cfg(feature = "chrono")]
-        pub const fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        pub const fn as_datetime(&self) -> Option<NaiveDateTime> {
         match self {
             Value::DateTime(dt) => *dt,
             _ => None,
         }
     }
 
+    /// Returns a `DateTime` in UTC if the value is a `DateTimeUtc`, otherwise
+    /// `None`.
+    #[cfg(feature = "chrono")]
+        pub const fn as_datetime_utc(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Value::DateTimeUtc(dt) => *dt,
+            _ => None,
+        }
+    }
+
+    /// Returns any datetime value as a `DateTime` in UTC, assuming UTC for
+    /// timezone-less values.
+    #[cfg(feature = "chrono")]
+    #[deprecated(
+        note = "assuming UTC for timezone-less datetimes is lossy, match `Value::DateTime` and `Value::DateTimeUtc` separately"
+    )]
+        pub fn as_datetime_assume_utc(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Value::DateTime(dt) => dt.map(|dt| DateTime::from_utc(dt, Utc)),
+            Value::DateTimeUtc(dt) => *dt,
+            _ => None,
+        }
+    }
+
     /// `true` if the `Value` is a Date.
     #[cfg(feature = "chrono")]
         pub const fn is_date(&self) -> bool {
@@ -628,7 +1038,7 @@ impl<'a> Value<'a> {
         T: TryFrom<Value<'a>>,
     {
         match self {
-            Value::Array(Some(vec)) => {
+            Value::Array(Some(vec)) | Value::TypedArray(Some(vec), _) => {
                 let rslt: Result<Vec<_>, _> = vec.into_iter().map(T::try_from).collect();
                 match rslt {
                     Err(_) => None,
@@ -651,7 +1061,9 @@ value!(val: f64, Double, val);
 value!(val: f32, Float, val);
 
 #[cfg(feature = "chrono")]
-value!(val: DateTime<Utc>, DateTime, val);
+value!(val: DateTime<Utc>, DateTimeUtc, val);
+#[cfg(feature = "chrono")]
+value!(val: NaiveDateTime, DateTime, val);
 #[cfg(feature = "chrono")]
 value!(val: chrono::NaiveTime, Time, val);
 #[cfg(feature = "chrono")]
@@ -663,13 +1075,31 @@ value!(val: JsonValue, Json, val);
 #[cfg(feature = "uuid")]
 value!(val: Uuid, Uuid, val);
 
+/// A conversion error naming the source variant and the target type.
+fn conversion_error(value: &Value<'_>, target: &str) -> Error {
+    let kind = ErrorKind::conversion(format!("Couldn't convert value of type `{value:?}` to {target}."));
+
+    Error::builder(kind).build()
+}
+
 impl<'a> TryFrom<Value<'a>> for i64 {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<i64, Self::Error> {
-        value
-            .as_i64()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not an i64")).build())
+        i64::try_from(&value)
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for i64 {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<i64, Self::Error> {
+        match value {
+            Value::Int32(Some(i)) => Ok(i64::from(*i)),
+            Value::Int64(Some(i)) => Ok(*i),
+            Value::Int128(Some(i)) => i64::try_from(*i).map_err(|_| conversion_error(value, "i64")),
+            _ => Err(conversion_error(value, "i64")),
+        }
     }
 }
 
@@ -677,9 +1107,22 @@ impl<'a> TryFrom<Value<'a>> for i32 {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<i32, Self::Error> {
-        value
-            .as_i32()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not an i32")).build())
+        i32::try_from(&value)
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for i32 {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<i32, Self::Error> {
+        match value {
+            Value::Int32(Some(i)) => Ok(*i),
+            // A wider integer converts when the value fits, and errors
+            // instead of truncating when it does not.
+            Value::Int64(Some(i)) => i32::try_from(*i).map_err(|_| conversion_error(value, "i32")),
+            Value::Int128(Some(i)) => i32::try_from(*i).map_err(|_| conversion_error(value, "i32")),
+            _ => Err(conversion_error(value, "i32")),
+        }
     }
 }
 
@@ -698,9 +1141,19 @@ impl<'a> TryFrom<Value<'a>> for f64 {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<f64, Self::Error> {
-        value
-            .as_f64()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not a f64")).build())
+        f64::try_from(&value)
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for f64 {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<f64, Self::Error> {
+        match value {
+            Value::Float(Some(f)) => Ok(f64::from(*f)),
+            Value::Double(Some(f)) => Ok(*f),
+            _ => Err(conversion_error(value, "f64")),
+        }
     }
 }
 
@@ -708,9 +1161,40 @@ impl<'a> TryFrom<Value<'a>> for String {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<String, Self::Error> {
+        match value.into_string() {
+            Some(string) => Ok(string),
+            None => Err(Error::builder(ErrorKind::conversion("Not a string")).build()),
+        }
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for String {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<String, Self::Error> {
         value
-            .into_string()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not a string")).build())
+            .as_str()
+            .map(|s| s.to_owned())
+            .ok_or_else(|| conversion_error(value, "String"))
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Vec<u8>, Self::Error> {
+        match value.into_bytes() {
+            Some(bytes) => Ok(bytes),
+            None => Err(Error::builder(ErrorKind::conversion("Not bytes")).build()),
+        }
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<Vec<u8>, Self::Error> {
+        value.to_bytes().ok_or_else(|| conversion_error(value, "Vec<u8>"))
     }
 }
 
@@ -718,9 +1202,51 @@ impl<'a> TryFrom<Value<'a>> for bool {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<bool, Self::Error> {
-        value
-            .as_bool()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not a bool")).build())
+        bool::try_from(&value)
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for bool {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<bool, Self::Error> {
+        value.as_bool().ok_or_else(|| conversion_error(value, "bool"))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<'a> TryFrom<Value<'a>> for Uuid {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Uuid, Self::Error> {
+        Uuid::try_from(&value)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<'a> TryFrom<&Value<'a>> for Uuid {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<Uuid, Self::Error> {
+        match Option::<Uuid>::try_from(value)? {
+            Some(uuid) => Ok(uuid),
+            None => Err(conversion_error(value, "uuid::Uuid")),
+        }
+    }
+}
+
+// An infallible `From<Value>` for an owned `serde_json::Value` exists
+// already, so only the reference conversion is fallible and strict about
+// the variant.
+#[cfg(feature = "json")]
+impl<'a> TryFrom<&Value<'a>> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<serde_json::Value, Self::Error> {
+        match value {
+            Value::Json(Some(json)) => Ok(json.clone()),
+            value => Err(conversion_error(value, "serde_json::Value")),
+        }
     }
 }
 
@@ -729,9 +1255,55 @@ impl<'a> TryFrom<Value<'a>> for DateTime<Utc> {
     type Error = Error;
 
     fn try_from(value: Value<'a>) -> Result<DateTime<Utc>, Self::Error> {
+        DateTime::<Utc>::try_from(&value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<&Value<'a>> for DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<DateTime<Utc>, Self::Error> {
+        #[allow(deprecated)]
         value
-            .as_datetime()
-            .ok_or_else(|| Error::builder(ErrorKind::conversion("Not a datetime")).build())
+            .as_datetime_assume_utc()
+            .ok_or_else(|| conversion_error(value, "DateTime<Utc>"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<Value<'a>> for NaiveDate {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<NaiveDate, Self::Error> {
+        NaiveDate::try_from(&value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<&Value<'a>> for NaiveDate {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<NaiveDate, Self::Error> {
+        value.as_date().ok_or_else(|| conversion_error(value, "NaiveDate"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<Value<'a>> for NaiveTime {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<NaiveTime, Self::Error> {
+        NaiveTime::try_from(&value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<&Value<'a>> for NaiveTime {
+    type Error = Error;
+
+    fn try_from(value: &Value<'a>) -> Result<NaiveTime, Self::Error> {
+        value.as_time().ok_or_else(|| conversion_error(value, "NaiveTime"))
     }
 }
 
@@ -888,7 +1460,6 @@ impl<'a> IntoIterator for Values<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[cfg(feature = "chrono")]
     use std::str::FromStr;
 
     #[test]
@@ -935,6 +1506,30 @@ mod tests {
         assert_eq!(values, vec![datetime]);
     }
 
+    #[test]
+    fn an_i64_in_the_i32_range_converts_into_an_i32() {
+        let converted = i32::try_from(&Value::int64(420)).unwrap();
+        assert_eq!(420, converted);
+    }
+
+    #[test]
+    fn an_i64_outside_the_i32_range_errors_instead_of_truncating() {
+        let err = i32::try_from(&Value::int64(i64::MAX)).unwrap_err();
+        assert!(matches!(err.kind(), crate::error::ErrorKind::ConversionError(_)));
+    }
+
+    #[test]
+    fn a_double_does_not_convert_into_an_integer() {
+        let err = i64::try_from(&Value::double(420.5)).unwrap_err();
+        assert!(matches!(err.kind(), crate::error::ErrorKind::ConversionError(_)));
+    }
+
+    #[test]
+    fn an_i32_widens_into_an_i64() {
+        let converted = i64::try_from(&Value::int32(420)).unwrap();
+        assert_eq!(420, converted);
+    }
+
     #[test]
     fn a_parameterized_value_of_an_array_cant_be_converted_into_a_vec_of_the_wrong_type() {
         let pv = Value::array(vec![1]);
@@ -946,9 +1541,13 @@ mod tests {
     #[cfg(feature = "chrono")]
     fn display_format_for_datetime() {
         let dt: DateTime<Utc> = DateTime::from_str("2019-07-27T05:30:30Z").expect("failed while parsing date");
-        let pv = Value::datetime(dt);
+        let pv = Value::datetime_utc(dt);
 
         assert_eq!(format!("{pv}"), "\"2019-07-27 05:30:30 UTC\"");
+
+        let pv = Value::datetime(dt.naive_utc());
+
+        assert_eq!(format!("{pv}"), "\"2019-07-27 05:30:30\"");
     }
 
     #[test]
@@ -969,6 +1568,23 @@ mod tests {
         assert_eq!(format!("{pv}"), "\"16:17:00\"");
     }
 
+    #[test]
+    fn ip_net_parses_and_displays_with_optional_prefix() {
+        let ip: IpNet = "127.0.0.1".parse().unwrap();
+
+        assert_eq!(ip.address, std::net::IpAddr::from_str("127.0.0.1").unwrap());
+        assert_eq!(ip.prefix, None);
+        assert_eq!(format!("{ip}"), "127.0.0.1");
+
+        let net: IpNet = "2001:db8::1/64".parse().unwrap();
+
+        assert_eq!(net.address, std::net::IpAddr::from_str("2001:db8::1").unwrap());
+        assert_eq!(net.prefix, Some(64));
+        assert_eq!(format!("{net}"), "2001:db8::1/64");
+
+        assert!("not-an-address".parse::<IpNet>().is_err());
+    }
+
     #[test]
     #[cfg(feature = "uuid")]
     fn display_format_for_uuid() {
@@ -977,4 +1593,63 @@ mod tests {
 
         assert_eq!(format!("{pv}"), "\"67e55044-10b1-426f-9247-bb680e5fe0c8\"");
     }
+
+    fn log_limits() -> ParamLogLimits {
+        ParamLogLimits {
+            max_text_length: 4,
+            max_array_elements: 2,
+            bytes_hex_prefix_length: 4,
+        }
+    }
+
+    fn render(values: &[Value<'_>]) -> String {
+        format!("{}", Params::new(values, log_limits()))
+    }
+
+    #[test]
+    fn bounded_params_short_text_renders_verbatim() {
+        assert_eq!("[\"meow\"]", render(&[Value::text("meow")]));
+    }
+
+    #[test]
+    fn bounded_params_long_text_truncates_with_length() {
+        assert_eq!("[\"meow…\" (len: 8)]", render(&[Value::text("meowmeow")]));
+    }
+
+    #[test]
+    fn bounded_params_bytes_render_length_and_hex_prefix() {
+        assert_eq!("[<bytes: 4, 0xdeadbeef>]", render(&[Value::bytes(vec![0xde, 0xad, 0xbe, 0xef])]));
+
+        assert_eq!(
+            "[<bytes: 6, 0xdeadbeef…>]",
+            render(&[Value::bytes(vec![0xde, 0xad, 0xbe, 0xef, 0x00, 0x00])])
+        );
+    }
+
+    #[test]
+    fn bounded_params_arrays_render_count_and_first_elements() {
+        assert_eq!("[<array: 2> [1,2]]", render(&[Value::array(vec![1, 2])]));
+        assert_eq!("[<array: 4> [1,2,…]]", render(&[Value::array(vec![1, 2, 3, 4])]));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn bounded_params_json_truncates_rendered_document() {
+        let json = serde_json::json!({ "key": "a very long value" });
+
+        assert_eq!("[\"{\"ke…\" (len: 27)]", render(&[Value::json(json)]));
+    }
+
+    #[test]
+    fn bounded_params_scalars_render_unchanged() {
+        assert_eq!(
+            "[123456789,1.5,true,null]",
+            render(&[
+                Value::int64(123456789),
+                Value::double(1.5),
+                Value::boolean(true),
+                Value::Int32(None),
+            ])
+        );
+    }
 }
@@ -10,13 +10,40 @@
 //! querying interface.
 
 mod connection_info;
+#[cfg(any(feature = "mssql", feature = "postgresql", feature = "mysql"))]
+pub mod credentials;
+mod dry_run;
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
+pub(crate) mod events;
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
 pub(crate) mod metrics;
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
 mod queryable;
 mod result_set;
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
+mod tag;
 #[cfg(any(feature = "mssql", feature = "postgresql", feature = "mysql"))]
 mod timeout;
+#[cfg(any(feature = "mssql", feature = "postgresql", feature = "mysql"))]
+mod tls;
 mod transaction;
 pub mod owned_transaction;
+#[cfg_attr(
+    not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+    allow(dead_code)
+)]
 mod type_identifier;
 
 #[cfg(feature = "mssql")]
@@ -33,12 +60,18 @@ pub use self::mysql::*;
 #[cfg(feature = "postgresql")]
 pub use self::postgres::*;
 pub use self::result_set::*;
+#[cfg(any(feature = "mssql", feature = "postgresql", feature = "mysql"))]
+pub use self::tls::*;
 pub use connection_info::*;
+pub use dry_run::DryRunConnector;
+pub use events::{set_connection_event_callbacks, ConnectionEvent, ConnectionEvents};
+pub use metrics::{set_slow_query_callback, SlowQuery};
 #[cfg(feature = "mssql")]
 pub use mssql::*;
 pub use queryable::*;
 #[cfg(feature = "sqlite")]
 pub use sqlite::*;
+pub use tag::TaggedQueryable;
 pub use transaction::*;
 pub use owned_transaction::*;
 #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
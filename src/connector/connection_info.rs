@@ -1,5 +1,9 @@
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
 use crate::error::{Error, ErrorKind};
-use std::{borrow::Cow, fmt};
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
 use url::Url;
 
 #[cfg(feature = "mssql")]
@@ -14,6 +18,7 @@ use crate::connector::SqliteParams;
 use std::convert::TryFrom;
 
 /// General information about a SQL connection.
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
 #[derive(Debug, Clone)]
 pub enum ConnectionInfo {
     /// A PostgreSQL connection URL.
@@ -37,6 +42,7 @@ pub enum ConnectionInfo {
         InMemorySqlite { db_name: String },
 }
 
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
 impl ConnectionInfo {
     /// Parse `ConnectionInfo` out from an SQL connection string.
     ///
@@ -89,7 +95,13 @@ impl ConnectionInfo {
             #[cfg(feature = "postgresql")]
             SqlFamily::Postgres => Ok(ConnectionInfo::Postgres(PostgresUrl::new(url)?)),
             #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            _ => {
+                let kind = ErrorKind::DatabaseUrlIsInvalid(format!(
+                    "The connector for {sql_family} is not enabled in this build."
+                ));
+
+                Err(Error::builder(kind).build())
+            }
         }
     }
 
@@ -230,27 +242,19 @@ impl ConnectionInfo {
 /// One of the supported SQL variants.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SqlFamily {
-    #[cfg(feature = "postgresql")]
-        Postgres,
-    #[cfg(feature = "mysql")]
-        Mysql,
-    #[cfg(feature = "sqlite")]
-        Sqlite,
-    #[cfg(feature = "mssql")]
-        Mssql,
+    Postgres,
+    Mysql,
+    Sqlite,
+    Mssql,
 }
 
 impl SqlFamily {
     /// Get a string representation of the family.
     pub fn as_str(self) -> &'static str {
         match self {
-            #[cfg(feature = "postgresql")]
             SqlFamily::Postgres => "postgresql",
-            #[cfg(feature = "mysql")]
             SqlFamily::Mysql => "mysql",
-            #[cfg(feature = "sqlite")]
             SqlFamily::Sqlite => "sqlite",
-            #[cfg(feature = "mssql")]
             SqlFamily::Mssql => "mssql",
         }
     }
@@ -258,11 +262,8 @@ impl SqlFamily {
     /// Convert url scheme to an SqlFamily.
     pub fn from_scheme(url_scheme: &str) -> Option<Self> {
         match url_scheme {
-            #[cfg(feature = "sqlite")]
             "file" | "sqlite" => Some(SqlFamily::Sqlite),
-            #[cfg(feature = "postgresql")]
             "postgres" | "postgresql" => Some(SqlFamily::Postgres),
-            #[cfg(feature = "mysql")]
             "mysql" => Some(SqlFamily::Mysql),
             _ => None,
         }
@@ -274,52 +275,24 @@ impl SqlFamily {
     }
 
     /// True, if family is PostgreSQL.
-    #[cfg(feature = "postgresql")]
     pub fn is_postgres(&self) -> bool {
         matches!(self, SqlFamily::Postgres)
     }
 
-    /// True, if family is PostgreSQL.
-    #[cfg(not(feature = "postgresql"))]
-    pub fn is_postgres(&self) -> bool {
-        false
-    }
-
     /// True, if family is MySQL.
-    #[cfg(feature = "mysql")]
     pub fn is_mysql(&self) -> bool {
         matches!(self, SqlFamily::Mysql)
     }
 
-    /// True, if family is MySQL.
-    #[cfg(not(feature = "mysql"))]
-    pub fn is_mysql(&self) -> bool {
-        false
-    }
-
     /// True, if family is SQLite.
-    #[cfg(feature = "sqlite")]
     pub fn is_sqlite(&self) -> bool {
         matches!(self, SqlFamily::Sqlite)
     }
 
-    /// True, if family is SQLite.
-    #[cfg(not(feature = "sqlite"))]
-    pub fn is_sqlite(&self) -> bool {
-        false
-    }
-
     /// True, if family is SQL Server.
-    #[cfg(feature = "mssql")]
     pub fn is_mssql(&self) -> bool {
         matches!(self, SqlFamily::Mssql)
     }
-
-    /// True, if family is SQL Server.
-    #[cfg(not(feature = "mssql"))]
-    pub fn is_mssql(&self) -> bool {
-        false
-    }
 }
 
 impl fmt::Display for SqlFamily {
@@ -0,0 +1,62 @@
+//! Shared percent-decoding and encoding of connection string components,
+//! so credentials with special characters behave the same on every
+//! connector.
+
+use percent_encoding::{percent_decode, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::borrow::Cow;
+
+/// Every character with a meaning in an URL or a JDBC connection string,
+/// escaped when encoding a component and expected to be escaped when
+/// decoding one.
+const COMPONENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'!')
+    .add(b'"')
+    .add(b'#')
+    .add(b'$')
+    .add(b'%')
+    .add(b'&')
+    .add(b'\'')
+    .add(b'(')
+    .add(b')')
+    .add(b'*')
+    .add(b'+')
+    .add(b',')
+    .add(b'/')
+    .add(b':')
+    .add(b';')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'?')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Percent-decodes a single component of a connection string. Bytes not
+/// decoding to valid UTF-8 fall back to the raw value, so a component that
+/// was never encoded keeps working.
+pub fn percent_decode_component<'a>(value: &'a str, component: &str) -> Cow<'a, str> {
+    match percent_decode(value.as_bytes()).decode_utf8() {
+        Ok(decoded) => decoded,
+        Err(_) => {
+            tracing::warn!("Couldn't decode {component} to UTF-8, using the non-decoded version.");
+
+            value.into()
+        }
+    }
+}
+
+/// Percent-encodes a single component for embedding into a connection
+/// string, escaping every character with a meaning in an URL. The inverse
+/// of [`percent_decode_component`], for building connection strings from
+/// raw credentials.
+pub fn percent_encode_component(value: &str) -> Cow<'_, str> {
+    utf8_percent_encode(value, COMPONENT).into()
+}
@@ -0,0 +1,187 @@
+//! A dry-run mode for auditing the SQL a workflow would run.
+//!
+//! Testing and auditing workflows need to see the exact statements a piece
+//! of code produces without touching the database. [`DryRunConnector`]
+//! implements [`Queryable`] by recording every statement together with its
+//! parameters instead of executing it, answering each call with an empty
+//! success result.
+
+use super::{BatchResult, IsolationLevel, Queryable, ResultSet, TransactionCapable};
+use crate::ast::{Query, Value};
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// Wraps a [`Queryable`], recording every statement instead of executing
+/// it. Created with [`Queryable::with_dry_run`]:
+///
+/// ```rust,no_run
+/// # use quaint::{prelude::*, single::Quaint};
+/// # async fn function() -> Result<(), quaint::error::Error> {
+/// # let conn = Quaint::new("file:///tmp/example.db").await?;
+/// let dry_run = conn.with_dry_run();
+///
+/// dry_run.query(Select::from_table("users").into()).await?;
+///
+/// let queries = dry_run.dry_run_queries();
+/// assert_eq!("SELECT `users`.* FROM `users`", queries[0].0);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The wrapped connection renders the queries in its dialect and answers
+/// the capability questions of a transaction, but no statement reaches it.
+pub struct DryRunConnector<'a> {
+    inner: &'a dyn Queryable,
+    queries: Mutex<Vec<(String, Vec<Value<'static>>)>>,
+}
+
+impl<'a> DryRunConnector<'a> {
+    /// Wraps the given connection, recording every statement instead of
+    /// executing it.
+    pub fn new(inner: &'a dyn Queryable) -> Self {
+        Self {
+            inner,
+            queries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The recorded statements in execution order, together with their
+    /// parameters.
+    pub fn dry_run_queries(&self) -> Vec<(String, Vec<Value<'static>>)> {
+        self.queries.lock().unwrap().clone()
+    }
+
+    fn record(&self, sql: &str, params: &[Value<'_>]) {
+        let params = params.iter().map(|param| param.clone().into_owned()).collect();
+
+        self.queries.lock().unwrap().push((sql.to_string(), params));
+    }
+}
+
+#[async_trait]
+impl<'a> Queryable for DryRunConnector<'a> {
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let (sql, params) = self.render(q)?;
+        self.query_raw(&sql, &params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let (sql, params) = self.render(q)?;
+        self.execute_raw(&sql, &params).await
+    }
+
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        self.inner.render(q)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.record(sql, params);
+
+        Ok(ResultSet::new(Vec::new(), Vec::new()))
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.query_raw(sql, params).await
+    }
+
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        self.record(sql, params);
+
+        Ok(Vec::new())
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.record(sql, params);
+
+        Ok(0)
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.execute_raw(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.record(cmd, &[]);
+
+        Ok(())
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    fn is_healthy(&self) -> bool {
+        true
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.inner.in_transaction()
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
+        self.record(&format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"), &[]);
+
+        Ok(())
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        self.inner.requires_isolation_first()
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+}
+
+impl<'a> TransactionCapable for DryRunConnector<'a> {}
+
+#[cfg(test)]
+#[cfg(feature = "sqlite")]
+mod tests {
+    use crate::{ast::*, connector::Sqlite, prelude::*};
+
+    #[tokio::test]
+    async fn dry_run_records_instead_of_executing() {
+        let conn = Sqlite::new_in_memory().unwrap();
+        let dry_run = conn.with_dry_run();
+
+        let result = dry_run.query(Select::from_table("users").into()).await.unwrap();
+        assert!(result.is_empty());
+
+        dry_run
+            .execute_raw("DELETE FROM users WHERE id = ?", &[Value::int64(1)])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                ("SELECT `users`.* FROM `users`".to_string(), Vec::new()),
+                ("DELETE FROM users WHERE id = ?".to_string(), vec![Value::int64(1)]),
+            ],
+            dry_run.dry_run_queries()
+        );
+
+        // The table never existed, proving no statement reached the
+        // database.
+        assert!(conn.query_raw("SELECT * FROM users", &[]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dry_run_records_a_transaction_sequence() {
+        let conn = Sqlite::new_in_memory().unwrap();
+        let dry_run = conn.with_dry_run();
+
+        let tx = dry_run.start_transaction(None).await.unwrap();
+        tx.raw_cmd("DELETE FROM users").await.unwrap();
+        tx.commit().await.unwrap();
+
+        let queries = dry_run.dry_run_queries();
+        let statements: Vec<_> = queries.iter().map(|(sql, _)| sql.as_str()).collect();
+
+        assert_eq!(vec!["BEGIN", "DELETE FROM users", "COMMIT"], statements);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error::Error;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    OnceLock,
+};
+
+/// Identifies the physical connection a connection event belongs to.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionEvent {
+    /// The name of the connector that owns the connection, e.g. `postgres`.
+    pub connector: &'static str,
+    /// A process-wide serial number, assigned when the connection is opened.
+    pub connection_id: u64,
+}
+
+type OpenedCallback = Box<dyn Fn(ConnectionEvent) + Send + Sync>;
+type ClosedCallback = Box<dyn Fn(ConnectionEvent, &str) + Send + Sync>;
+type ErrorCallback = Box<dyn Fn(ConnectionEvent, &Error) + Send + Sync>;
+
+/// Callbacks triggered in the lifecycle of a physical database connection.
+/// The callbacks run on the connection's executor, so they should hand the
+/// event over fast, e.g. by bumping a counter or pushing into a channel.
+#[derive(Default)]
+pub struct ConnectionEvents {
+    pub(crate) on_opened: Option<OpenedCallback>,
+    pub(crate) on_closed: Option<ClosedCallback>,
+    pub(crate) on_error: Option<ErrorCallback>,
+}
+
+impl ConnectionEvents {
+    /// Triggered when a physical connection to the database is established.
+    pub fn on_opened<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.on_opened = Some(Box::new(callback));
+        self
+    }
+
+    /// Triggered when a physical connection is closed, with a short reason
+    /// such as `closed` or `error`.
+    pub fn on_closed<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ConnectionEvent, &str) + Send + Sync + 'static,
+    {
+        self.on_closed = Some(Box::new(callback));
+        self
+    }
+
+    /// Triggered when a physical connection errors out, replacing the default
+    /// `tracing::error!` log line.
+    pub fn on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ConnectionEvent, &Error) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+}
+
+static CONNECTION_EVENTS: OnceLock<ConnectionEvents> = OnceLock::new();
+
+/// The serial number given to the next opened connection. Starts from one so
+/// zero can be read as "not assigned".
+static CONNECTION_SERIAL: AtomicU64 = AtomicU64::new(1);
+
+/// Registers the connection event callbacks. The callbacks are process-wide
+/// and can only be registered once; subsequent registrations are ignored.
+pub fn set_connection_event_callbacks(events: ConnectionEvents) {
+    let _ = CONNECTION_EVENTS.set(events);
+}
+
+/// Assigns a serial number to a freshly opened connection and triggers the
+/// `on_opened` callback.
+pub(crate) fn connection_opened(connector: &'static str) -> u64 {
+    let connection_id = CONNECTION_SERIAL.fetch_add(1, Ordering::Relaxed);
+
+    if let Some(callback) = CONNECTION_EVENTS.get().and_then(|events| events.on_opened.as_ref()) {
+        callback(ConnectionEvent {
+            connector,
+            connection_id,
+        });
+    }
+
+    connection_id
+}
+
+/// Triggers the `on_closed` callback for a connection.
+pub(crate) fn connection_closed(connector: &'static str, connection_id: u64, reason: &str) {
+    if let Some(callback) = CONNECTION_EVENTS.get().and_then(|events| events.on_closed.as_ref()) {
+        callback(
+            ConnectionEvent {
+                connector,
+                connection_id,
+            },
+            reason,
+        );
+    }
+}
+
+/// Triggers the `on_error` callback for a connection, falling back to a
+/// `tracing::error!` log line when no callback is registered.
+pub(crate) fn connection_error(connector: &'static str, connection_id: u64, error: &Error) {
+    match CONNECTION_EVENTS.get().and_then(|events| events.on_error.as_ref()) {
+        Some(callback) => callback(
+            ConnectionEvent {
+                connector,
+                connection_id,
+            },
+            error,
+        ),
+        None => tracing::error!(
+            message = "Error in connection",
+            connector,
+            connection_id,
+            error = ?error,
+        ),
+    }
+}
@@ -1,10 +1,132 @@
 use tracing::{info_span, Instrument};
 
-use crate::ast::{Params, Value};
-use std::{future::Future, time::Instant};
+use crate::ast::{ParamLogLimits, Params, Value};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// The prefix used in the metric names. Not set means the backwards
+/// compatible defaults: `prisma` for the metrics Prisma consumes, `quaint`
+/// for the quaint-specific ones.
+static METRIC_PREFIX: OnceLock<String> = OnceLock::new();
+
+pub(crate) fn set_metric_prefix(prefix: String) {
+    let _ = METRIC_PREFIX.set(prefix);
+}
+
+/// The full name of a metric: the configured prefix when one is set,
+/// otherwise the given default prefix.
+pub(crate) fn metric_name(default_prefix: &str, suffix: &str) -> String {
+    let prefix = METRIC_PREFIX.get().map(|prefix| prefix.as_str()).unwrap_or(default_prefix);
+
+    format!("{prefix}_{suffix}")
+}
+
+/// Connection metadata recorded on every query span. The field names follow
+/// the OpenTelemetry semantic conventions for database client calls.
+#[derive(Debug, Clone)]
+pub(crate) struct SpanInfo {
+    /// The database vendor, e.g. `postgres`. Rendered as `db.system`.
+    pub(crate) system: &'static str,
+    /// The name of the database queries run against. Rendered as `db.name`.
+    pub(crate) db_name: String,
+    /// The hostname or socket path of the server. `None` for in-process
+    /// databases such as SQLite. Rendered as `net.peer.name`.
+    pub(crate) peer_name: Option<String>,
+    /// The port of the server. Rendered as `net.peer.port`.
+    pub(crate) peer_port: Option<u16>,
+}
+
+/// Normalizes a SQL string into a stable fingerprint for log aggregation.
+/// String and numeric literals are replaced with `?`, so every run of the
+/// same statement shape produces the same fingerprint no matter which values
+/// were inlined. Placeholders and quoted identifiers pass through untouched.
+pub(crate) fn fingerprint(query: &str) -> String {
+    let mut out = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut prev = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            // A string literal, with quotes escaped by doubling them.
+            '\'' => {
+                out.push('?');
+
+                while let Some(c) = chars.next() {
+                    if c == '\'' {
+                        if chars.peek() == Some(&'\'') {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+            // A quoted identifier. Copied verbatim so digits inside it are
+            // not mistaken for literals.
+            '"' | '`' | '[' => {
+                let closing = match c {
+                    '[' => ']',
+                    c => c,
+                };
+
+                out.push(c);
+
+                for c in chars.by_ref() {
+                    out.push(c);
+
+                    if c == closing {
+                        break;
+                    }
+                }
+            }
+            // A numeric literal, unless the digit continues an identifier or
+            // a placeholder such as `$1` or `@P1`.
+            c if c.is_ascii_digit() => {
+                let in_word = prev.is_some_and(|prev: char| prev.is_alphanumeric() || matches!(prev, '_' | '$' | '?'));
+
+                if in_word {
+                    out.push(c);
+                } else {
+                    out.push('?');
+
+                    while let Some(c) = chars.peek() {
+                        if c.is_ascii_digit() || *c == '.' {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+
+        prev = Some(c);
+    }
+
+    out
+}
+
+/// The SQL operation of a statement, read from its first keyword and
+/// recorded in the `db.operation` span attribute. `None` for anything other
+/// than the four data manipulation statements.
+pub(crate) fn operation(query: &str) -> Option<&'static str> {
+    let keyword = query.split_whitespace().next()?;
+
+    ["SELECT", "INSERT", "UPDATE", "DELETE"]
+        .into_iter()
+        .find(|operation| keyword.eq_ignore_ascii_case(operation))
+}
 
 pub(crate) async fn query<'a, F, T, U>(
     tag: &'static str,
+    info: &'a SpanInfo,
     query: &'a str,
     params: &'a [Value<'_>],
     f: F,
@@ -13,8 +135,47 @@ where
     F: FnOnce() -> U + 'a,
     U: Future<Output = crate::Result<T>>,
 {
-    let span = info_span!("quaint:query", "db.statement" = %query);
-    do_query(tag, query, params, f).instrument(span).await
+    let span = info_span!(
+        "quaint:query",
+        "db.statement" = %fingerprint(query),
+        "db.operation" = operation(query),
+        "db.system" = info.system,
+        "db.name" = %info.db_name,
+        "net.peer.name" = info.peer_name.as_deref(),
+        "net.peer.port" = info.peer_port.map(u64::from),
+        "otel.kind" = "client",
+        "otel.status_code" = tracing::field::Empty,
+        "duration_ms" = tracing::field::Empty,
+    );
+
+    // When the query runs in a task spawned outside of the subscriber
+    // context, e.g. through `tokio::task::spawn`, the span attaches to the
+    // propagated OpenTelemetry context instead of starting a new trace.
+    #[cfg(feature = "opentelemetry")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = opentelemetry::Context::current();
+
+        if context.has_active_span() {
+            span.set_parent(context);
+        }
+    }
+
+    let start = Instant::now();
+    let res = do_query(tag, query, params, f).instrument(span.clone()).await;
+
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    span.record(
+        "otel.status_code",
+        match res {
+            Ok(_) => "OK",
+            Err(_) => "ERROR",
+        },
+    );
+
+    res
 }
 
 async fn do_query<'a, F, T, U>(tag: &'static str, query: &'a str, params: &'a [Value<'_>], f: F) -> crate::Result<T>
@@ -50,13 +211,117 @@ where
         trace_query(query, params, result, start);
     }
 
+    let connector = tag.split('.').next().unwrap_or(tag);
+
     histogram!(format!("{tag}.query.time"), start.elapsed());
-    histogram!("prisma_datasource_queries_duration_histogram_ms", start.elapsed());
-    increment_counter!("prisma_datasource_queries_total");
+    histogram!(
+        metric_name("prisma", "datasource_queries_duration_histogram_ms"),
+        start.elapsed(),
+        "db.system" => connector
+    );
+    increment_counter!(metric_name("prisma", "datasource_queries_total"), "db.system" => connector);
+
+    report_slow_query(tag, query, params, start.elapsed());
 
     res
 }
 
+/// A statement that took longer than the configured slow query threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct SlowQuery<'a> {
+    /// The name of the connector that ran the statement, e.g. `postgres`.
+    pub connector: &'a str,
+    /// The SQL of the statement, truncated to `QUAINT_SLOW_QUERY_MAX_LENGTH`
+    /// characters if the variable is set.
+    pub query: &'a str,
+    /// The number of parameters bound to the statement.
+    pub param_count: usize,
+    /// How long the statement took, including the network roundtrips.
+    pub duration: Duration,
+    /// The threshold the statement exceeded.
+    pub threshold: Duration,
+}
+
+/// The slow query threshold in milliseconds. Zero means slow query reporting
+/// is disabled.
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(0);
+
+type SlowQueryCallback = Box<dyn Fn(SlowQuery<'_>) + Send + Sync>;
+
+static SLOW_QUERY_CALLBACK: OnceLock<SlowQueryCallback> = OnceLock::new();
+
+pub(crate) fn set_slow_query_threshold(threshold: Option<Duration>) {
+    let millis = threshold.map(|threshold| threshold.as_millis() as u64).unwrap_or(0);
+
+    SLOW_QUERY_THRESHOLD_MS.store(millis, Ordering::Relaxed);
+}
+
+/// Registers a callback to be triggered for every statement exceeding the
+/// slow query threshold, replacing the default `tracing::warn!` log line. The
+/// callback runs on the connection's executor, so it should hand the data
+/// over fast, e.g. by pushing into a ring buffer.
+///
+/// The callback is process-wide and can only be registered once; subsequent
+/// registrations are ignored.
+pub fn set_slow_query_callback<F>(callback: F)
+where
+    F: Fn(SlowQuery<'_>) + Send + Sync + 'static,
+{
+    let _ = SLOW_QUERY_CALLBACK.set(Box::new(callback));
+}
+
+fn report_slow_query(tag: &'static str, query: &str, params: &[Value<'_>], duration: Duration) {
+    let threshold_ms = SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed);
+
+    if threshold_ms == 0 || (duration.as_millis() as u64) < threshold_ms {
+        return;
+    }
+
+    let connector = tag.split('.').next().unwrap_or(tag);
+
+    let query = match slow_query_max_length() {
+        Some(max_length) if query.len() > max_length => {
+            let mut end = max_length;
+
+            while !query.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            &query[..end]
+        }
+        _ => query,
+    };
+
+    let slow_query = SlowQuery {
+        connector,
+        query,
+        param_count: params.len(),
+        duration,
+        threshold: Duration::from_millis(threshold_ms),
+    };
+
+    match SLOW_QUERY_CALLBACK.get() {
+        Some(callback) => callback(slow_query),
+        None => tracing::warn!(
+            message = "Slow query",
+            connector,
+            query = %slow_query.query,
+            param_count = slow_query.param_count,
+            duration_ms = duration.as_millis() as u64,
+            threshold_ms,
+        ),
+    }
+}
+
+/// The maximum number of bytes of SQL included in a slow query report.
+/// Overridable with the `QUAINT_SLOW_QUERY_MAX_LENGTH` environment variable,
+/// by default the statement is not truncated.
+fn slow_query_max_length() -> Option<usize> {
+    static MAX_LENGTH: OnceLock<Option<usize>> = OnceLock::new();
+
+    *MAX_LENGTH.get_or_init(|| env_usize("QUAINT_SLOW_QUERY_MAX_LENGTH"))
+}
+
 #[cfg(feature = "pooled")]
 pub(crate) async fn check_out<F, T>(f: F) -> std::result::Result<T, mobc_forked::Error<crate::error::Error>>
 where
@@ -79,17 +344,131 @@ where
     );
 
     histogram!("pool.check_out", start.elapsed());
+    histogram!(
+        metric_name("quaint", "pool_checkout_duration_ms"),
+        start.elapsed().as_millis() as f64
+    );
 
     res
 }
 
+/// The limits used for rendering parameter values into the query logs.
+/// Overridable with the `QUAINT_LOG_MAX_PARAM_LENGTH`,
+/// `QUAINT_LOG_MAX_ARRAY_ELEMENTS` and `QUAINT_LOG_BYTES_HEX_PREFIX`
+/// environment variables.
+fn param_log_limits() -> ParamLogLimits {
+    static LIMITS: OnceLock<ParamLogLimits> = OnceLock::new();
+
+    *LIMITS.get_or_init(|| {
+        let mut limits = ParamLogLimits::default();
+
+        if let Some(max_text_length) = env_usize("QUAINT_LOG_MAX_PARAM_LENGTH") {
+            limits.max_text_length = max_text_length;
+        }
+
+        if let Some(max_array_elements) = env_usize("QUAINT_LOG_MAX_ARRAY_ELEMENTS") {
+            limits.max_array_elements = max_array_elements;
+        }
+
+        if let Some(bytes_hex_prefix_length) = env_usize("QUAINT_LOG_BYTES_HEX_PREFIX") {
+            limits.bytes_hex_prefix_length = bytes_hex_prefix_length;
+        }
+
+        limits
+    })
+}
+
+fn env_usize(key: &str) -> Option<usize> {
+    std::env::var(key).ok().and_then(|value| value.parse().ok())
+}
+
+/// Logs the full SQL together with the parameter values. The raw statement
+/// only surfaces at `TRACE` level; the span carries the fingerprinted
+/// version for everything above it.
 fn trace_query<'a>(query: &'a str, params: &'a [Value<'_>], result: &str, start: Instant) {
-    tracing::debug!(
+    tracing::trace!(
         query = %query,
-        params = %Params(params),
+        params = %Params::new(params, param_log_limits()),
         result,
         item_type = "query",
         is_query = true,
         duration_ms = start.elapsed().as_millis() as u64,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint, operation};
+
+    #[test]
+    fn operation_reads_the_first_keyword() {
+        assert_eq!(Some("SELECT"), operation("SELECT * FROM `users`"));
+        assert_eq!(Some("INSERT"), operation("insert into `users` (`id`) values (?)"));
+        assert_eq!(Some("UPDATE"), operation("Update `users` SET `name` = ?"));
+        assert_eq!(Some("DELETE"), operation(" DELETE FROM `users`"));
+    }
+
+    #[test]
+    fn operation_is_none_for_other_statements() {
+        assert_eq!(None, operation("CREATE TABLE `users` (id int)"));
+        assert_eq!(None, operation(""));
+    }
+
+    #[test]
+    fn fingerprint_replaces_string_literals() {
+        assert_eq!(
+            "SELECT * FROM `users` WHERE `name` = ?",
+            fingerprint("SELECT * FROM `users` WHERE `name` = 'Musti'")
+        );
+    }
+
+    #[test]
+    fn fingerprint_handles_escaped_quotes() {
+        assert_eq!(
+            "SELECT ? AS `quote`",
+            fingerprint("SELECT 'it''s a cat' AS `quote`")
+        );
+    }
+
+    #[test]
+    fn fingerprint_replaces_numeric_literals() {
+        assert_eq!(
+            "SELECT * FROM \"users\" WHERE \"age\" > ? AND \"score\" < ?",
+            fingerprint("SELECT * FROM \"users\" WHERE \"age\" > 18 AND \"score\" < 4.5")
+        );
+    }
+
+    #[test]
+    fn fingerprint_keeps_placeholders() {
+        assert_eq!(
+            "SELECT * FROM \"users\" WHERE \"id\" = $1 OR \"id\" = $2",
+            fingerprint("SELECT * FROM \"users\" WHERE \"id\" = $1 OR \"id\" = $2")
+        );
+
+        assert_eq!(
+            "SELECT * FROM [users] WHERE [id] = @P1",
+            fingerprint("SELECT * FROM [users] WHERE [id] = @P1")
+        );
+
+        assert_eq!(
+            "SELECT * FROM `users` WHERE `id` = ?",
+            fingerprint("SELECT * FROM `users` WHERE `id` = ?")
+        );
+    }
+
+    #[test]
+    fn fingerprint_keeps_digits_in_identifiers() {
+        assert_eq!(
+            "SELECT col1, \"col2\" FROM t1 WHERE col1 = ?",
+            fingerprint("SELECT col1, \"col2\" FROM t1 WHERE col1 = 42")
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_stable_across_values() {
+        let a = fingerprint("INSERT INTO `users` (`name`, `age`) VALUES ('Musti', 9)");
+        let b = fingerprint("INSERT INTO `users` (`name`, `age`) VALUES ('Naukio', 10)");
+
+        assert_eq!(a, b);
+    }
+}
@@ -4,14 +4,14 @@ mod error;
 use std::sync::Arc;
 use super::{IsolationLevel, TransactionOptions};
 use crate::{
-    ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet, Transaction},
+    ast::{Insert, Query, Value},
+    connector::{metrics, queryable::*, BatchResult, ResultSet, SslAcceptMode, TlsConfig, Transaction},
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
 use async_trait::async_trait;
 use connection_string::JdbcString;
-use futures::lock::Mutex;
+use futures::{lock::Mutex, TryStreamExt};
 use std::{
     convert::TryFrom,
     fmt,
@@ -35,6 +35,7 @@ use crate::connector::{OwnedTransaction};
 pub struct MssqlUrl {
     connection_string: String,
     query_params: MssqlQueryParams,
+    tls_config: Option<TlsConfig>,
 }
 
 /// TLS mode when connecting to SQL Server.
@@ -90,6 +91,8 @@ pub(crate) struct MssqlQueryParams {
     transaction_isolation_level: Option<IsolationLevel>,
     max_connection_lifetime: Option<Duration>,
     max_idle_connection_lifetime: Option<Duration>,
+    slow_query_threshold: Option<Duration>,
+    query_comment: Option<String>,
 }
 
 static SQL_SERVER_DEFAULT_ISOLATION: IsolationLevel = IsolationLevel::ReadCommitted;
@@ -157,6 +160,12 @@ impl MssqlUrl {
         self.query_params.schema()
     }
 
+    /// The comment prepended to every statement, tagging the statements of
+    /// this connection in the logs.
+    pub fn query_comment(&self) -> Option<&str> {
+        self.query_params.query_comment.as_deref()
+    }
+
     /// Database hostname.
     pub fn host(&self) -> &str {
         self.query_params.host()
@@ -208,6 +217,11 @@ impl MssqlUrl {
     pub fn max_idle_connection_lifetime(&self) -> Option<Duration> {
         self.query_params.max_idle_connection_lifetime()
     }
+
+    /// The threshold after which a statement is reported as a slow query.
+    pub fn slow_query_threshold(&self) -> Option<Duration> {
+        self.query_params.slow_query_threshold()
+    }
 }
 
 impl MssqlQueryParams {
@@ -270,6 +284,10 @@ impl MssqlQueryParams {
     fn max_idle_connection_lifetime(&self) -> Option<Duration> {
         self.max_idle_connection_lifetime
     }
+
+    fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_threshold
+    }
 }
 
 /// A connector interface for the SQL Server database.
@@ -279,12 +297,27 @@ pub struct Mssql {
     url: MssqlUrl,
     socket_timeout: Option<Duration>,
     is_healthy: AtomicBool,
+    span_info: metrics::SpanInfo,
 }
 
 impl Mssql {
     /// Creates a new connection to SQL Server.
     pub async fn new(url: MssqlUrl) -> crate::Result<Self> {
-        let config = Config::from_jdbc_string(&url.connection_string)?;
+        if let Some(threshold) = url.slow_query_threshold() {
+            super::metrics::set_slow_query_threshold(Some(threshold));
+        }
+
+        if url.has_in_memory_tls_material() {
+            let kind = ErrorKind::TlsError {
+                message: "The SQL Server driver only accepts certificates as file paths, not in-memory.".into(),
+            };
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut config = Config::from_jdbc_string(&url.connection_string)?;
+        url.apply_tls(&mut config);
+
         let tcp = TcpStream::connect_named(&config).await?;
         let socket_timeout = url.socket_timeout();
 
@@ -293,6 +326,7 @@ impl Mssql {
                 Ok(client) => Ok(client),
                 Err(tiberius::error::Error::Routing { host, port }) => {
                     let mut config = Config::from_jdbc_string(&url.connection_string)?;
+                    url.apply_tls(&mut config);
                     config.host(host);
                     config.port(port);
 
@@ -305,8 +339,16 @@ impl Mssql {
 
         let client = super::timeout::connect(url.connect_timeout(), connecting).await?;
 
+        super::events::connection_opened("mssql");
+
         let this = Self {
             client: Mutex::new(client),
+            span_info: metrics::SpanInfo {
+                system: "mssql",
+                db_name: url.dbname().to_string(),
+                peer_name: Some(url.host().to_string()),
+                peer_port: Some(url.port()),
+            },
             url,
             socket_timeout,
             is_healthy: AtomicBool::new(true),
@@ -344,12 +386,19 @@ impl Mssql {
 #[async_trait]
 impl Queryable for Mssql {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
-        let (sql, params) = visitor::Mssql::build(q)?;
+        let (sql, params) = self.render(q)?;
         self.query_raw(&sql, &params[..]).await
     }
 
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        visitor::Mssql::build(q)
+    }
+
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
-        metrics::query("mssql.query_raw", sql, params, move || async move {
+        let sql = super::tag::with_default_comment(sql, self.url.query_comment());
+        let sql = sql.as_ref();
+
+        metrics::query("mssql.query_raw", &self.span_info, sql, params, move || async move {
             let mut client = self.client.lock().await;
 
             let mut query = tiberius::Query::new(sql);
@@ -393,13 +442,60 @@ impl Queryable for Mssql {
         self.query_raw(sql, params).await
     }
 
+    /// The TDS stream only reports statements returning rows, so statements
+    /// without a result set inside the batch produce no item here.
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        let sql = super::tag::with_default_comment(sql, self.url.query_comment());
+        let sql = sql.as_ref();
+
+        metrics::query("mssql.query_multi_raw", &self.span_info, sql, params, move || async move {
+            let mut client = self.client.lock().await;
+
+            let mut query = tiberius::Query::new(sql);
+
+            for param in params {
+                query.bind(param);
+            }
+
+            let mut stream = self.perform_io(query.query(&mut client)).await?;
+            let mut results = Vec::new();
+
+            while let Some(item) = stream.try_next().await? {
+                match item {
+                    QueryItem::Metadata(meta) => {
+                        let columns = meta.columns().iter().map(|c| c.name().to_string()).collect();
+
+                        results.push(BatchResult::ResultSet(ResultSet::new(columns, Vec::new())));
+                    }
+                    QueryItem::Row(row) => {
+                        let mut values: Vec<Value<'_>> = Vec::with_capacity(row.len());
+
+                        for val in row.into_iter() {
+                            values.push(Value::try_from(val)?);
+                        }
+
+                        if let Some(BatchResult::ResultSet(result_set)) = results.last_mut() {
+                            result_set.rows.push(values);
+                        }
+                    }
+                }
+            }
+
+            Ok(results)
+        })
+        .await
+    }
+
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
-        let (sql, params) = visitor::Mssql::build(q)?;
+        let (sql, params) = self.render(q)?;
         self.execute_raw(&sql, &params[..]).await
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
-        metrics::query("mssql.execute_raw", sql, params, move || async move {
+        let sql = super::tag::with_default_comment(sql, self.url.query_comment());
+        let sql = sql.as_ref();
+
+        metrics::query("mssql.execute_raw", &self.span_info, sql, params, move || async move {
             let mut query = tiberius::Query::new(sql);
 
             for param in params {
@@ -419,7 +515,10 @@ impl Queryable for Mssql {
     }
 
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
-        metrics::query("mssql.raw_cmd", cmd, &[], move || async move {
+        let cmd = super::tag::with_default_comment(cmd, self.url.query_comment());
+        let cmd = cmd.as_ref();
+
+        metrics::query("mssql.raw_cmd", &self.span_info, cmd, &[], move || async move {
             let mut client = self.client.lock().await;
             self.perform_io(client.simple_query(cmd)).await?.into_results().await?;
             Ok(())
@@ -438,6 +537,81 @@ impl Queryable for Mssql {
         Ok(version_string)
     }
 
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        Ok(crate::visitor::Mssql::CAPABILITIES)
+    }
+
+    // There is no `RETURNING` clause, but the visitor renders the returning
+    // columns with an `OUTPUT`, so the key comes back with the insert like
+    // on the other databases.
+    async fn insert_returning(&self, insert: Insert<'_>, pk_column: &str) -> crate::Result<Value<'static>> {
+        super::queryable::ensure_single_row_insert(&insert)?;
+
+        let result = self.insert(insert.returning(vec![pk_column])).await?;
+
+        super::queryable::take_pk_column(result, pk_column)
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        let query = "SELECT 1 FROM information_schema.tables WHERE table_name = @P1 AND table_schema = COALESCE(@P2, SCHEMA_NAME())";
+        let schema = schema.map(Value::text).unwrap_or(Value::Text(None));
+
+        let result = self.query_raw(query, &[Value::text(table), schema]).await?;
+
+        Ok(!result.is_empty())
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        let query = "SELECT column_name, data_type, is_nullable, column_default FROM information_schema.columns WHERE table_name = @P1 AND table_schema = SCHEMA_NAME() ORDER BY ordinal_position";
+
+        let result = self.query_raw(query, &[Value::text(table)]).await?;
+        let mut columns = Vec::with_capacity(result.len());
+
+        for row in result {
+            columns.push(ColumnInfo {
+                name: row[0].to_string().unwrap_or_default(),
+                data_type: row[1].to_string().unwrap_or_default(),
+                is_nullable: matches!(row[2].as_str(), Some("YES")),
+                default: row[3].to_string(),
+            });
+        }
+
+        Ok(columns)
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<ForeignKeyInfo>> {
+        // `information_schema` on SQL Server cannot align referencing and
+        // referenced columns, so this goes through the `sys` catalog views.
+        // The action descriptions are reported with underscores and are
+        // rewritten to the SQL spelling used by the other connectors.
+        let query = "SELECT fk.name, cp.name, OBJECT_NAME(fk.referenced_object_id), cr.name, \
+               REPLACE(fk.delete_referential_action_desc, '_', ' '), \
+               REPLACE(fk.update_referential_action_desc, '_', ' ') \
+             FROM sys.foreign_keys fk \
+             JOIN sys.foreign_key_columns fkc ON fkc.constraint_object_id = fk.object_id \
+             JOIN sys.columns cp ON cp.object_id = fkc.parent_object_id AND cp.column_id = fkc.parent_column_id \
+             JOIN sys.columns cr ON cr.object_id = fkc.referenced_object_id AND cr.column_id = fkc.referenced_column_id \
+             WHERE OBJECT_NAME(fk.parent_object_id) = @P1 AND SCHEMA_NAME(fk.schema_id) = SCHEMA_NAME() \
+             ORDER BY fk.name, fkc.constraint_column_id";
+
+        let result = self.query_raw(query, &[Value::text(table)]).await?;
+
+        Ok(super::queryable::foreign_keys_from_rows(result))
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<IndexInfo>> {
+        let query = "SELECT i.name, c.name, i.is_unique, i.type_desc, i.filter_definition \
+             FROM sys.indexes i \
+             JOIN sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id \
+             JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id \
+             WHERE i.object_id = OBJECT_ID(@P1) AND i.name IS NOT NULL \
+             ORDER BY i.name, ic.key_ordinal";
+
+        let result = self.query_raw(query, &[Value::text(table)]).await?;
+
+        Ok(super::queryable::indexes_from_rows(result))
+    }
+
     fn is_healthy(&self) -> bool {
         self.is_healthy.load(Ordering::SeqCst)
     }
@@ -466,9 +640,38 @@ impl MssqlUrl {
         Ok(Self {
             connection_string,
             query_params,
+            tls_config: None,
         })
     }
 
+    /// Use the given programmatic TLS configuration instead of the settings
+    /// from the connection string. The SQL Server driver only accepts
+    /// certificates as file paths, so a configuration carrying in-memory
+    /// certificate material errors when the connection is opened.
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls_config = Some(tls);
+        self
+    }
+
+    /// True when the programmatic TLS configuration carries certificate
+    /// material in memory. The driver only takes certificate paths, so such
+    /// a configuration cannot reach it.
+    fn has_in_memory_tls_material(&self) -> bool {
+        self.tls_config
+            .as_ref()
+            .map(|tls| tls.root_cert_pem.0.is_some() || tls.identity_pkcs12.0.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Applies the programmatic TLS configuration to the driver config.
+    fn apply_tls(&self, config: &mut Config) {
+        let Some(ref tls) = self.tls_config else { return };
+
+        if let Some(SslAcceptMode::AcceptInvalidCerts) = tls.accept_mode {
+            config.trust_cert();
+        }
+    }
+
     fn with_jdbc_prefix(input: &str) -> String {
         if input.starts_with("jdbc:sqlserver") {
             input.into()
@@ -487,9 +690,16 @@ impl MssqlUrl {
 
         let port = conn.port();
         let props = conn.properties_mut();
-        let user = props.remove("user");
-        let password = props.remove("password");
-        let database = props.remove("database").unwrap_or_else(|| String::from("master"));
+        let user = props
+            .remove("user")
+            .map(|user| super::credentials::percent_decode_component(&user, "username").into_owned());
+        let password = props
+            .remove("password")
+            .map(|password| super::credentials::percent_decode_component(&password, "password").into_owned());
+        let database = props
+            .remove("database")
+            .map(|database| super::credentials::percent_decode_component(&database, "database name").into_owned())
+            .unwrap_or_else(|| String::from("master"));
         let schema = props.remove("schema").unwrap_or_else(|| String::from("dbo"));
 
         let connection_limit = props
@@ -581,6 +791,21 @@ impl MssqlUrl {
             _ => (),
         }
 
+        let query_comment = props
+            .remove("querycomment")
+            .or_else(|| props.remove("query_comment"));
+
+        let mut slow_query_threshold = props
+            .remove("slowthreshold")
+            .or_else(|| props.remove("slow_threshold"))
+            .map(|param| param.parse().map(Duration::from_millis))
+            .transpose()?;
+
+        match slow_query_threshold {
+            Some(dur) if dur.as_millis() == 0 => slow_query_threshold = None,
+            _ => (),
+        }
+
         Ok(MssqlQueryParams {
             encrypt,
             port,
@@ -598,6 +823,8 @@ impl MssqlUrl {
             transaction_isolation_level,
             max_connection_lifetime,
             max_idle_connection_lifetime,
+            slow_query_threshold,
+            query_comment,
         })
     }
 }
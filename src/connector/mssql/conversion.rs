@@ -6,13 +6,20 @@ use bigdecimal::BigDecimal;
 use std::{borrow::Cow, convert::TryFrom};
 #[cfg(feature = "bigdecimal")]
 use tiberius::ToSql;
-use tiberius::{ColumnData, FromSql, IntoSql};
+use tiberius::{numeric::Numeric, ColumnData, FromSql, IntoSql};
 
 impl<'a> IntoSql<'a> for &'a Value<'a> {
     fn into_sql(self) -> ColumnData<'a> {
         match self {
             Value::Int32(val) => val.into_sql(),
             Value::Int64(val) => val.into_sql(),
+            Value::Int128(val) => match val {
+                Some(i) => match i64::try_from(*i) {
+                    Ok(i) => ColumnData::I64(Some(i)),
+                    Err(_) => ColumnData::Numeric(Some(Numeric::new_with_scale(*i, 0))),
+                },
+                None => ColumnData::I64(None),
+            },
             Value::Float(val) => val.into_sql(),
             Value::Double(val) => val.into_sql(),
             Value::Text(val) => val.as_deref().into_sql(),
@@ -21,7 +28,13 @@ impl<'a> IntoSql<'a> for &'a Value<'a> {
             Value::Boolean(val) => val.into_sql(),
             Value::Char(val) => val.as_ref().map(|val| format!("{val}")).into_sql(),
             Value::Xml(val) => val.as_deref().into_sql(),
-            Value::Array(_) => panic!("Arrays are not supported on SQL Server."),
+            Value::IpAddr(val) => val.map(|val| val.to_string()).into_sql(),
+            // SQL Server has no bit-string type; the textual form is the
+            // best a string-typed parameter can carry.
+            Value::BitString(val) => val.as_deref().into_sql(),
+            Value::Array(_) | Value::TypedArray(..) => panic!("Arrays are not supported on SQL Server."),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(_) => panic!("Geometry values are only supported on PostgreSQL."),
             #[cfg(feature = "bigdecimal")]
             Value::Numeric(val) => (*val).to_sql(),
             #[cfg(feature = "json")]
@@ -31,6 +44,8 @@ impl<'a> IntoSql<'a> for &'a Value<'a> {
             #[cfg(feature = "chrono")]
             Value::DateTime(val) => val.into_sql(),
             #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(val) => val.into_sql(),
+            #[cfg(feature = "chrono")]
             Value::Date(val) => val.into_sql(),
             #[cfg(feature = "chrono")]
             Value::Time(val) => val.into_sql(),
@@ -62,17 +77,15 @@ impl TryFrom<ColumnData<'static>> for Value<'static> {
             }
             #[cfg(feature = "chrono")]
             dt @ ColumnData::DateTime(_) => {
-                use tiberius::time::chrono::{DateTime, NaiveDateTime, Utc};
+                use tiberius::time::chrono::NaiveDateTime;
 
-                let dt = NaiveDateTime::from_sql(&dt)?.map(|dt| DateTime::<Utc>::from_utc(dt, Utc));
-                Value::DateTime(dt)
+                Value::DateTime(NaiveDateTime::from_sql(&dt)?)
             }
             #[cfg(feature = "chrono")]
             dt @ ColumnData::SmallDateTime(_) => {
-                use tiberius::time::chrono::{DateTime, NaiveDateTime, Utc};
+                use tiberius::time::chrono::NaiveDateTime;
 
-                let dt = NaiveDateTime::from_sql(&dt)?.map(|dt| DateTime::<Utc>::from_utc(dt, Utc));
-                Value::DateTime(dt)
+                Value::DateTime(NaiveDateTime::from_sql(&dt)?)
             }
             #[cfg(feature = "chrono")]
             dt @ ColumnData::Time(_) => {
@@ -87,17 +100,15 @@ impl TryFrom<ColumnData<'static>> for Value<'static> {
             }
             #[cfg(feature = "chrono")]
             dt @ ColumnData::DateTime2(_) => {
-                use tiberius::time::chrono::{DateTime, NaiveDateTime, Utc};
-
-                let dt = NaiveDateTime::from_sql(&dt)?.map(|dt| DateTime::<Utc>::from_utc(dt, Utc));
+                use tiberius::time::chrono::NaiveDateTime;
 
-                Value::DateTime(dt)
+                Value::DateTime(NaiveDateTime::from_sql(&dt)?)
             }
             #[cfg(feature = "chrono")]
             dt @ ColumnData::DateTimeOffset(_) => {
                 use tiberius::time::chrono::{DateTime, Utc};
 
-                Value::DateTime(DateTime::<Utc>::from_sql(&dt)?)
+                Value::DateTimeUtc(DateTime::<Utc>::from_sql(&dt)?)
             }
             ColumnData::Xml(cow) => Value::Xml(cow.map(|xml_data| Cow::Owned(xml_data.into_owned().into_string()))),
         };
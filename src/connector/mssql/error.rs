@@ -17,228 +17,324 @@ impl From<tiberius::error::Error> for Error {
 
                 Error::builder(ErrorKind::TlsError { message }).build()
             }
-            tiberius::error::Error::Server(e) if [3902u32, 3903u32, 3971u32].iter().any(|code| e.code() == *code) => {
-                let kind = ErrorKind::TransactionAlreadyClosed(e.message().to_string());
+            tiberius::error::Error::Server(e) => from_server_error(e.code(), e.message()),
+            e => Error::builder(ErrorKind::QueryError(e.into())).build(),
+        }
+    }
+}
 
-                let mut builder = Error::builder(kind);
+/// Maps a SQL Server error into an [`Error`], classified by the error number.
+/// The messages are locale-dependent, so they are only used for extracting
+/// names of constraints and such, never for classification. The original code
+/// and message are always preserved on the resulting error.
+fn from_server_error(code: u32, message: &str) -> Error {
+    match code {
+        3902 | 3903 | 3971 => {
+            let kind = ErrorKind::TransactionAlreadyClosed(message.to_string());
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 8169 => {
-                let kind = ErrorKind::conversion(e.message().to_string());
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                let mut builder = Error::builder(kind);
+            builder.build()
+        }
+        8169 => {
+            let kind = ErrorKind::conversion(message.to_string());
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 18456 => {
-                let user = e.message().split('\'').nth(1).into();
-                let kind = ErrorKind::AuthenticationFailed { user };
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                let mut builder = Error::builder(kind);
+            builder.build()
+        }
+        18456 => {
+            let user = message.split('\'').nth(1).into();
+            let kind = ErrorKind::AuthenticationFailed { user };
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 4060 => {
-                let db_name = e.message().split('"').nth(1).into();
-                let kind = ErrorKind::DatabaseDoesNotExist { db_name };
-                let mut builder = Error::builder(kind);
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.build()
+        }
+        4060 => {
+            let db_name = message.split('"').nth(1).into();
+            let kind = ErrorKind::DatabaseDoesNotExist { db_name };
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 515 => {
-                let constraint = e
-                    .message()
-                    .split_whitespace()
-                    .nth(7)
-                    .and_then(|s| s.split('\'').nth(1))
-                    .map(|s| DatabaseConstraint::fields(Some(s)))
-                    .unwrap_or(DatabaseConstraint::CannotParse);
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                let kind = ErrorKind::NullConstraintViolation { constraint };
-                let mut builder = Error::builder(kind);
+            builder.build()
+        }
+        515 => {
+            let constraint = message
+                .split_whitespace()
+                .nth(7)
+                .and_then(|s| s.split('\'').nth(1))
+                .map(|s| DatabaseConstraint::fields(Some(s)))
+                .unwrap_or(DatabaseConstraint::CannotParse);
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let kind = ErrorKind::NullConstraintViolation { constraint };
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 1801 => {
-                let db_name = e.message().split('\'').nth(1).into();
-                let kind = ErrorKind::DatabaseAlreadyExists { db_name };
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
+
+            builder.build()
+        }
+        1801 => {
+            let db_name = message.split('\'').nth(1).into();
+            let kind = ErrorKind::DatabaseAlreadyExists { db_name };
 
-                let mut builder = Error::builder(kind);
+            let mut builder = Error::builder(kind);
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 2627 => {
-                let constraint = e
-                    .message()
-                    .split(". ")
-                    .nth(1)
-                    .and_then(|s| s.split(' ').last())
-                    .and_then(|s| s.split('\'').nth(1))
-                    .map(ToString::to_string)
-                    .map(DatabaseConstraint::Index)
-                    .unwrap_or(DatabaseConstraint::CannotParse);
-
-                let kind = ErrorKind::UniqueConstraintViolation { constraint };
-                let mut builder = Error::builder(kind);
-
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.build()
+        }
+        2627 => {
+            // "Violation of UNIQUE KEY constraint '<name>'. ..." — the first
+            // quoted token is the constraint name.
+            let constraint = message
+                .split('\'')
+                .nth(1)
+                .map(ToString::to_string)
+                .map(DatabaseConstraint::Index)
+                .unwrap_or(DatabaseConstraint::CannotParse);
+
+            let kind = ErrorKind::UniqueConstraintViolation { constraint };
+            let mut builder = Error::builder(kind);
+
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
+
+            builder.build()
+        }
+        547 => {
+            let constraint = message
+                .split('.')
+                .next()
+                .and_then(|s| s.split_whitespace().last())
+                .and_then(|s| s.split('\"').nth(1))
+                .map(ToString::to_string)
+                .map(DatabaseConstraint::Index)
+                .unwrap_or(DatabaseConstraint::CannotParse);
+
+            let kind = ErrorKind::ForeignKeyConstraintViolation { constraint };
+            let mut builder = Error::builder(kind);
+
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
+
+            builder.build()
+        }
+        1505 => {
+            let constraint = message
+                .split('\'')
+                .nth(3)
+                .map(ToString::to_string)
+                .map(DatabaseConstraint::Index)
+                .unwrap_or(DatabaseConstraint::CannotParse);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 547 => {
-                let constraint = e
-                    .message()
-                    .split('.')
-                    .next()
-                    .and_then(|s| s.split_whitespace().last())
-                    .and_then(|s| s.split('\"').nth(1))
-                    .map(ToString::to_string)
-                    .map(DatabaseConstraint::Index)
-                    .unwrap_or(DatabaseConstraint::CannotParse);
-
-                let kind = ErrorKind::ForeignKeyConstraintViolation { constraint };
-                let mut builder = Error::builder(kind);
-
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let kind = ErrorKind::UniqueConstraintViolation { constraint };
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 1505 => {
-                let constraint = e
-                    .message()
-                    .split('\'')
-                    .nth(3)
-                    .map(ToString::to_string)
-                    .map(DatabaseConstraint::Index)
-                    .unwrap_or(DatabaseConstraint::CannotParse);
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                let kind = ErrorKind::UniqueConstraintViolation { constraint };
-                let mut builder = Error::builder(kind);
+            builder.build()
+        }
+        2601 => {
+            let constraint = message
+                .split_whitespace()
+                .nth(11)
+                .and_then(|s| s.split('\'').nth(1))
+                .map(ToString::to_string)
+                .map(DatabaseConstraint::Index)
+                .unwrap_or(DatabaseConstraint::CannotParse);
+
+            let kind = ErrorKind::UniqueConstraintViolation { constraint };
+            let mut builder = Error::builder(kind);
+
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
+
+            builder.build()
+        }
+        2628 => {
+            let column = message.split('\'').nth(3).into();
+            let kind = ErrorKind::LengthMismatch { column };
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let mut builder = Error::builder(kind);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 2601 => {
-                let constraint = e
-                    .message()
-                    .split_whitespace()
-                    .nth(11)
-                    .and_then(|s| s.split('\'').nth(1))
-                    .map(ToString::to_string)
-                    .map(DatabaseConstraint::Index)
-                    .unwrap_or(DatabaseConstraint::CannotParse);
-
-                let kind = ErrorKind::UniqueConstraintViolation { constraint };
-                let mut builder = Error::builder(kind);
-
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 1801 => {
-                let db_name = e.message().split('\'').nth(1).into();
-                let kind = ErrorKind::DatabaseAlreadyExists { db_name };
+            builder.build()
+        }
+        208 => {
+            let table = message
+                .split_whitespace()
+                .nth(3)
+                .and_then(|s| s.split('\'').nth(1))
+                .into();
 
-                let mut builder = Error::builder(kind);
+            let kind = ErrorKind::TableDoesNotExist { table };
+            let mut builder = Error::builder(kind);
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 2628 => {
-                let column = e.message().split('\'').nth(3).into();
-                let kind = ErrorKind::LengthMismatch { column };
+            builder.build()
+        }
+        207 => {
+            let column = message
+                .split_whitespace()
+                .nth(3)
+                .and_then(|s| s.split('\'').nth(1))
+                .into();
 
-                let mut builder = Error::builder(kind);
+            let kind = ErrorKind::ColumnNotFound { column };
+            let mut builder = Error::builder(kind);
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 208 => {
-                let table = e
-                    .message()
-                    .split_whitespace()
-                    .nth(3)
-                    .and_then(|s| s.split('\'').nth(1))
-                    .into();
+            builder.build()
+        }
+        // Deadlock victim. Retriable after the competing transaction ends.
+        1205 => {
+            let mut builder = Error::builder(ErrorKind::TransactionWriteConflict);
 
-                let kind = ErrorKind::TableDoesNotExist { table };
-                let mut builder = Error::builder(kind);
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            builder.build()
+        }
+        // Lock request timeout (raised when `SET LOCK_TIMEOUT` is exceeded).
+        1222 => {
+            let mut builder = Error::builder(ErrorKind::SocketTimeout);
 
-                builder.build()
-            }
-            tiberius::error::Error::Server(e) if e.code() == 207 => {
-                let column = e
-                    .message()
-                    .split_whitespace()
-                    .nth(3)
-                    .and_then(|s| s.split('\'').nth(1))
-                    .into();
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                let kind = ErrorKind::ColumnNotFound { column };
-                let mut builder = Error::builder(kind);
+            builder.build()
+        }
+        _ => {
+            let kind = ErrorKind::QueryError(message.to_string().into());
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+            let mut builder = Error::builder(kind);
+            builder.set_original_code(format!("{code}"));
+            builder.set_original_message(message.to_string());
 
-                builder.build()
+            builder.build()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_server_error;
+    use crate::error::{DatabaseConstraint, ErrorKind};
+
+    #[test]
+    fn deadlock_victim_is_a_write_conflict() {
+        let message = "Transaction (Process ID 52) was deadlocked on lock resources with another process and has been chosen as the deadlock victim. Rerun the transaction.";
+        let error = from_server_error(1205, message);
+
+        assert!(matches!(error.kind(), ErrorKind::TransactionWriteConflict));
+        assert_eq!(Some("1205"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
+
+    #[test]
+    fn lock_request_timeout_is_a_timeout() {
+        let message = "Lock request time out period exceeded.";
+        let error = from_server_error(1222, message);
+
+        assert!(matches!(error.kind(), ErrorKind::SocketTimeout));
+        assert_eq!(Some("1222"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
+
+    #[test]
+    fn duplicate_key_in_constraint_names_the_constraint() {
+        let message = "Violation of UNIQUE KEY constraint 'UQ_cat_name'. Cannot insert duplicate key in object 'dbo.cats'. The duplicate key value is (musti).";
+        let error = from_server_error(2627, message);
+
+        match error.kind() {
+            ErrorKind::UniqueConstraintViolation { constraint } => {
+                assert_eq!(&DatabaseConstraint::Index(String::from("UQ_cat_name")), constraint);
             }
-            tiberius::error::Error::Server(e) if e.code() == 1205 => {
-                let mut builder = Error::builder(ErrorKind::TransactionWriteConflict);
+            kind => panic!("Expected UniqueConstraintViolation, got {kind:?}"),
+        }
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+        assert_eq!(Some("2627"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
 
-                builder.build()
+    #[test]
+    fn duplicate_key_in_unique_index_names_the_index() {
+        let message = "Cannot insert duplicate key row in object 'dbo.cats' with unique index 'cats_name_idx'. The duplicate key value is (musti).";
+        let error = from_server_error(2601, message);
+
+        match error.kind() {
+            ErrorKind::UniqueConstraintViolation { constraint } => {
+                assert_eq!(&DatabaseConstraint::Index(String::from("cats_name_idx")), constraint);
             }
-            tiberius::error::Error::Server(e) if e.code() == 3903 => {
-                let mut builder = Error::builder(ErrorKind::RollbackWithoutBegin);
+            kind => panic!("Expected UniqueConstraintViolation, got {kind:?}"),
+        }
 
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+        assert_eq!(Some("2601"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
 
-                builder.build()
+    #[test]
+    fn foreign_key_violation_names_the_constraint() {
+        let message = "The INSERT statement conflicted with the FOREIGN KEY constraint \"FK_cat_human\". The conflict occurred in database \"test\", table \"dbo.humans\", column 'id'.";
+        let error = from_server_error(547, message);
+
+        match error.kind() {
+            ErrorKind::ForeignKeyConstraintViolation { constraint } => {
+                assert_eq!(&DatabaseConstraint::Index(String::from("FK_cat_human")), constraint);
             }
-            tiberius::error::Error::Server(e) => {
-                let kind = ErrorKind::QueryError(e.clone().into());
+            kind => panic!("Expected ForeignKeyConstraintViolation, got {kind:?}"),
+        }
 
-                let mut builder = Error::builder(kind);
-                builder.set_original_code(format!("{}", e.code()));
-                builder.set_original_message(e.message().to_string());
+        assert_eq!(Some("547"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
 
-                builder.build()
+    #[test]
+    fn unique_violation_in_another_locale_still_classifies() {
+        // Error numbers are stable across locales, messages are not. The
+        // constraint name parse fails gracefully, the classification holds.
+        let message = "Yksilöllisen rajoitteen rikkomus.";
+        let error = from_server_error(2601, message);
+
+        match error.kind() {
+            ErrorKind::UniqueConstraintViolation { constraint } => {
+                assert_eq!(&DatabaseConstraint::CannotParse, constraint);
             }
-            e => Error::builder(ErrorKind::QueryError(e.into())).build(),
+            kind => panic!("Expected UniqueConstraintViolation, got {kind:?}"),
         }
+
+        assert_eq!(Some("2601"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
+    }
+
+    #[test]
+    fn unclassified_errors_fall_back_to_query_error() {
+        let message = "Something user-defined went wrong.";
+        let error = from_server_error(50000, message);
+
+        assert!(matches!(error.kind(), ErrorKind::QueryError(_)));
+        assert_eq!(Some("50000"), error.original_code());
+        assert_eq!(Some(message), error.original_message());
     }
 }
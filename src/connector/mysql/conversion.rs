@@ -4,7 +4,7 @@ use crate::{
     error::{Error, ErrorKind},
 };
 #[cfg(feature = "chrono")]
-use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use mysql_async::{
     self as my,
     consts::{ColumnFlags, ColumnType},
@@ -23,15 +23,34 @@ pub fn conv_params(params: &[Value<'_>]) -> crate::Result<my::Params> {
             let res = match pv {
                 Value::Int32(i) => i.map(|i| my::Value::Int(i as i64)),
                 Value::Int64(i) => i.map(my::Value::Int),
+                Value::Int128(i) => i
+                    .map(|i| match u64::try_from(i) {
+                        Ok(i) => Ok(my::Value::UInt(i)),
+                        Err(_) => match i64::try_from(i) {
+                            Ok(i) => Ok(my::Value::Int(i)),
+                            Err(_) => {
+                                let msg = format!("Value {i} does not fit in a MySQL integer column");
+                                let kind = ErrorKind::value_out_of_range(msg);
+
+                                Err(Error::builder(kind).build())
+                            }
+                        },
+                    })
+                    .transpose()?,
                 Value::Float(f) => f.map(my::Value::Float),
                 Value::Double(f) => f.map(my::Value::Double),
-                Value::Text(s) => s.clone().map(|s| my::Value::Bytes((*s).as_bytes().to_vec())),
-                Value::Bytes(bytes) => bytes.clone().map(|bytes| my::Value::Bytes(bytes.into_owned())),
-                Value::Enum(s) => s.clone().map(|s| my::Value::Bytes((*s).as_bytes().to_vec())),
+                Value::Text(s) => s.as_ref().map(|s| my::Value::Bytes(s.as_bytes().to_vec())),
+                Value::Bytes(bytes) => bytes.as_ref().map(|bytes| my::Value::Bytes(bytes.to_vec())),
+                Value::Enum(s) => s.as_ref().map(|s| my::Value::Bytes(s.as_bytes().to_vec())),
                 Value::Boolean(b) => b.map(|b| my::Value::Int(b as i64)),
                 Value::Char(c) => c.map(|c| my::Value::Bytes(vec![c as u8])),
                 Value::Xml(s) => s.as_ref().map(|s| my::Value::Bytes((s).as_bytes().to_vec())),
-                Value::Array(_) => {
+                Value::IpAddr(ip) => ip.map(|ip| my::Value::Bytes(ip.to_string().into_bytes())),
+                Value::BitString(bits) => bits
+                    .as_ref()
+                    .map(|bits| bits_to_bytes(bits).map(my::Value::Bytes))
+                    .transpose()?,
+                Value::Array(_) | Value::TypedArray(..) => {
                     let msg = "Arrays are not supported in MySQL.";
                     let kind = ErrorKind::conversion(msg);
 
@@ -40,6 +59,16 @@ pub fn conv_params(params: &[Value<'_>]) -> crate::Result<my::Params> {
 
                     return Err(builder.build());
                 }
+                #[cfg(feature = "postgis")]
+                Value::Geometry(_) => {
+                    let msg = "Geometry values are only supported on PostgreSQL.";
+                    let kind = ErrorKind::conversion(msg);
+
+                    let mut builder = Error::builder(kind);
+                    builder.set_original_message(msg);
+
+                    return Err(builder.build());
+                }
                 #[cfg(feature = "bigdecimal")]
                 Value::Numeric(f) => f.as_ref().map(|f| my::Value::Bytes(f.to_string().as_bytes().to_vec())),
                 #[cfg(feature = "json")]
@@ -74,6 +103,18 @@ pub fn conv_params(params: &[Value<'_>]) -> crate::Result<my::Params> {
                         dt.timestamp_subsec_micros(),
                     )
                 }),
+                #[cfg(feature = "chrono")]
+                Value::DateTimeUtc(dt) => dt.map(|dt| {
+                    my::Value::Date(
+                        dt.year() as u16,
+                        dt.month() as u8,
+                        dt.day() as u8,
+                        dt.hour() as u8,
+                        dt.minute() as u8,
+                        dt.second() as u8,
+                        dt.timestamp_subsec_micros(),
+                    )
+                }),
             };
 
             match res {
@@ -181,20 +222,20 @@ impl TypeIdentifier for my::Column {
     fn is_bytes(&self) -> bool {
         use ColumnType::*;
 
-        let is_a_blob = matches!(
+        matches!(
             self.column_type(),
             MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB | MYSQL_TYPE_BLOB
-        ) && self.character_set() == 63;
-
-        let is_bits = self.column_type() == MYSQL_TYPE_BIT && self.column_length() > 1;
-
-        is_a_blob || is_bits
+        ) && self.character_set() == 63
     }
 
     fn is_bool(&self) -> bool {
         self.column_type() == ColumnType::MYSQL_TYPE_BIT && self.column_length() == 1
     }
 
+    fn is_bits(&self) -> bool {
+        self.column_type() == ColumnType::MYSQL_TYPE_BIT && self.column_length() > 1
+    }
+
     fn is_json(&self) -> bool {
         self.column_type() == ColumnType::MYSQL_TYPE_JSON
     }
@@ -208,156 +249,229 @@ impl TypeIdentifier for my::Column {
     }
 }
 
-impl TakeRow for my::Row {
-    fn take_result_row(&mut self) -> crate::Result<Vec<Value<'static>>> {
-        fn convert(row: &mut my::Row, i: usize) -> crate::Result<Value<'static>> {
-            let value = row.take(i).ok_or_else(|| {
-                let msg = "Index out of bounds";
+/// `tinyint(1)` is how MySQL spells `boolean`, but the type information only
+/// survives in the display width of the column.
+fn is_tinyint1(column: &my::Column) -> bool {
+    column.column_type() == ColumnType::MYSQL_TYPE_TINY && column.column_length() == 1
+}
+
+/// Packs a bit-string of `0` and `1` characters into the big-endian byte
+/// representation MySQL expects for a `bit(n)` parameter.
+fn bits_to_bytes(bits: &str) -> crate::Result<Vec<u8>> {
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    let padding = bytes.len() * 8 - bits.len();
+
+    for (i, c) in bits.chars().enumerate() {
+        match c {
+            '0' => (),
+            '1' => bytes[(padding + i) / 8] |= 0x80 >> ((padding + i) % 8),
+            _ => {
+                let msg = format!("Unexpected character for bits input. Expected only 0 and 1. Got: {c}");
                 let kind = ErrorKind::conversion(msg);
 
-                Error::builder(kind).build()
-            })?;
+                return Err(Error::builder(kind).build());
+            }
+        }
+    }
 
-            let column = row.columns_ref().get(i).ok_or_else(|| {
-                let msg = "Index out of bounds";
-                let kind = ErrorKind::conversion(msg);
+    Ok(bytes)
+}
 
-                Error::builder(kind).build()
-            })?;
+/// Unpacks the big-endian byte representation of a MySQL `bit(n)` value into
+/// a string of `0` and `1` characters. The driver pads the value to full
+/// bytes, so the padding bits in front of the declared length are dropped.
+fn bytes_to_bits(bytes: &[u8], length: usize) -> String {
+    let total = bytes.len() * 8;
+    let mut bits = String::with_capacity(length);
 
-            let res = match value {
-                // JSON is returned as bytes.
-                #[cfg(feature = "json")]
-                my::Value::Bytes(b) if column.is_json() => {
-                    serde_json::from_slice(&b).map(Value::json).map_err(|_| {
-                        let msg = "Unable to convert bytes to JSON";
-                        let kind = ErrorKind::conversion(msg);
+    for _ in total..length {
+        bits.push('0');
+    }
 
-                        Error::builder(kind).build()
-                    })?
-                }
-                my::Value::Bytes(b) if column.is_enum() => {
-                    let s = String::from_utf8(b)?;
-                    Value::enum_variant(s)
-                }
-                // NEWDECIMAL returned as bytes. See https://mariadb.com/kb/en/resultset-row/#decimal-binary-encoding
-                #[cfg(feature = "bigdecimal")]
-                my::Value::Bytes(b) if column.is_real() => {
-                    let s = String::from_utf8(b).map_err(|_| {
-                        let msg = "Could not convert NEWDECIMAL from bytes to String.";
-                        let kind = ErrorKind::conversion(msg);
+    for i in total.saturating_sub(length)..total {
+        match bytes[i / 8] >> (7 - i % 8) & 1 {
+            0 => bits.push('0'),
+            _ => bits.push('1'),
+        }
+    }
 
-                        Error::builder(kind).build()
-                    })?;
+    bits
+}
 
-                    let dec = s.parse().map_err(|_| {
-                        let msg = "Could not convert NEWDECIMAL string to a BigDecimal.";
-                        let kind = ErrorKind::conversion(msg);
+impl TakeRow for my::Row {
+    fn take_result_row(&mut self) -> crate::Result<Vec<Value<'static>>> {
+        take_result_row(self, false)
+    }
+}
 
-                        Error::builder(kind).build()
-                    })?;
+/// Converts a driver row into quaint values. With `tinyint1_is_bool`,
+/// `tinyint(1)` columns decode to `Value::Boolean` like on the other
+/// connectors, falling back to the integer value with a warning when a row
+/// holds something else than 0, 1 or NULL.
+pub(crate) fn take_result_row(row: &mut my::Row, tinyint1_is_bool: bool) -> crate::Result<Vec<Value<'static>>> {
+    fn convert(row: &mut my::Row, i: usize, tinyint1_is_bool: bool) -> crate::Result<Value<'static>> {
+        let value = row.take(i).ok_or_else(|| {
+            let msg = "Index out of bounds";
+            let kind = ErrorKind::conversion(msg);
+
+            Error::builder(kind).build()
+        })?;
+
+        let column = row.columns_ref().get(i).ok_or_else(|| {
+            let msg = "Index out of bounds";
+            let kind = ErrorKind::conversion(msg);
+
+            Error::builder(kind).build()
+        })?;
+
+        let res = match value {
+            // JSON is returned as bytes.
+            #[cfg(feature = "json")]
+            my::Value::Bytes(b) if column.is_json() => {
+                serde_json::from_slice(&b).map(Value::json).map_err(|_| {
+                    let msg = "Unable to convert bytes to JSON";
+                    let kind = ErrorKind::conversion(msg);
 
-                    Value::numeric(dec)
-                }
-                my::Value::Bytes(b) if column.is_bool() => match b.as_slice() {
-                    [0] => Value::boolean(false),
-                    _ => Value::boolean(true),
-                },
-                // https://dev.mysql.com/doc/internals/en/character-set.html
-                my::Value::Bytes(b) if column.character_set() == 63 => Value::bytes(b),
-                my::Value::Bytes(s) => Value::text(String::from_utf8(s)?),
-                my::Value::Int(i) if column.is_int64() => Value::int64(i),
-                my::Value::Int(i) => Value::int32(i as i32),
-                my::Value::UInt(i) => Value::int64(i64::try_from(i).map_err(|_| {
-                    let msg = "Unsigned integers larger than 9_223_372_036_854_775_807 are currently not handled.";
-                    let kind = ErrorKind::value_out_of_range(msg);
+                    Error::builder(kind).build()
+                })?
+            }
+            my::Value::Bytes(b) if column.is_enum() => {
+                let s = String::from_utf8(b)?;
+                Value::enum_variant(s)
+            }
+            // NEWDECIMAL returned as bytes. See https://mariadb.com/kb/en/resultset-row/#decimal-binary-encoding
+            #[cfg(feature = "bigdecimal")]
+            my::Value::Bytes(b) if column.is_real() => {
+                let s = String::from_utf8(b).map_err(|_| {
+                    let msg = "Could not convert NEWDECIMAL from bytes to String.";
+                    let kind = ErrorKind::conversion(msg);
 
                     Error::builder(kind).build()
-                })?),
-                my::Value::Float(f) => Value::from(f),
-                my::Value::Double(f) => Value::from(f),
-                #[cfg(feature = "chrono")]
-                my::Value::Date(year, month, day, hour, min, sec, micro) => {
-                    if day == 0 || month == 0 {
-                        let msg = format!(
-                            "The column `{}` contained an invalid datetime value with either day or month set to zero.",
-                            column.name_str()
-                        );
-                        let kind = ErrorKind::value_out_of_range(msg);
-                        return Err(Error::builder(kind).build());
-                    }
+                })?;
+
+                let dec = s.parse().map_err(|_| {
+                    let msg = "Could not convert NEWDECIMAL string to a BigDecimal.";
+                    let kind = ErrorKind::conversion(msg);
 
-                    let time = NaiveTime::from_hms_micro_opt(hour.into(), min.into(), sec.into(), micro).unwrap();
+                    Error::builder(kind).build()
+                })?;
 
-                    let date = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into()).unwrap();
-                    let dt = NaiveDateTime::new(date, time);
+                Value::numeric(dec)
+            }
+            my::Value::Bytes(b) if column.is_bool() => match b.as_slice() {
+                [0] => Value::boolean(false),
+                _ => Value::boolean(true),
+            },
+            my::Value::Bytes(b) if column.is_bits() => {
+                Value::bit_string(bytes_to_bits(&b, column.column_length() as usize))
+            }
+            // https://dev.mysql.com/doc/internals/en/character-set.html
+            my::Value::Bytes(b) if column.character_set() == 63 => Value::bytes(b),
+            my::Value::Bytes(s) => Value::text(String::from_utf8(s)?),
+            my::Value::Int(i) if tinyint1_is_bool && is_tinyint1(column) => match i {
+                0 => Value::boolean(false),
+                1 => Value::boolean(true),
+                i => {
+                    tracing::warn!(
+                        "Column `{}` is a tinyint(1), but contains the value {}. Decoding it as an integer.",
+                        column.name_str(),
+                        i
+                    );
 
-                    Value::datetime(DateTime::<Utc>::from_utc(dt, Utc))
+                    Value::int32(i as i32)
+                }
+            },
+            my::Value::Int(i) if column.is_int64() => Value::int64(i),
+            my::Value::Int(i) => Value::int32(i as i32),
+            my::Value::UInt(i) => match i64::try_from(i) {
+                Ok(i) => Value::int64(i),
+                Err(_) => Value::int128(i),
+            },
+            my::Value::Float(f) => Value::from(f),
+            my::Value::Double(f) => Value::from(f),
+            #[cfg(feature = "chrono")]
+            my::Value::Date(year, month, day, hour, min, sec, micro) => {
+                if day == 0 || month == 0 {
+                    let msg = format!(
+                        "The column `{}` contained an invalid datetime value with either day or month set to zero.",
+                        column.name_str()
+                    );
+                    let kind = ErrorKind::value_out_of_range(msg);
+                    return Err(Error::builder(kind).build());
                 }
-                #[cfg(feature = "chrono")]
-                my::Value::Time(is_neg, days, hours, minutes, seconds, micros) => {
-                    if is_neg {
-                        let kind = ErrorKind::conversion("Failed to convert a negative time");
-                        return Err(Error::builder(kind).build());
-                    }
 
-                    if days != 0 {
-                        let kind = ErrorKind::conversion("Failed to read a MySQL `time` as duration");
-                        return Err(Error::builder(kind).build());
-                    }
+                let time = NaiveTime::from_hms_micro_opt(hour.into(), min.into(), sec.into(), micro).unwrap();
 
-                    let time =
-                        NaiveTime::from_hms_micro_opt(hours.into(), minutes.into(), seconds.into(), micros).unwrap();
-                    Value::time(time)
+                let date = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into()).unwrap();
+                let dt = NaiveDateTime::new(date, time);
+
+                Value::datetime(dt)
+            }
+            #[cfg(feature = "chrono")]
+            my::Value::Time(is_neg, days, hours, minutes, seconds, micros) => {
+                if is_neg {
+                    let kind = ErrorKind::conversion("Failed to convert a negative time");
+                    return Err(Error::builder(kind).build());
                 }
-                my::Value::NULL => match column {
-                    t if t.is_bool() => Value::Boolean(None),
-                    t if t.is_enum() => Value::Enum(None),
-                    t if t.is_null() => Value::Int32(None),
-                    t if t.is_int64() => Value::Int64(None),
-                    t if t.is_int32() => Value::Int32(None),
-                    t if t.is_float() => Value::Float(None),
-                    t if t.is_double() => Value::Double(None),
-                    t if t.is_text() => Value::Text(None),
-                    t if t.is_bytes() => Value::Bytes(None),
-                    #[cfg(feature = "bigdecimal")]
-                    t if t.is_real() => Value::Numeric(None),
-                    #[cfg(feature = "chrono")]
-                    t if t.is_datetime() => Value::DateTime(None),
-                    #[cfg(feature = "chrono")]
-                    t if t.is_time() => Value::Time(None),
-                    #[cfg(feature = "chrono")]
-                    t if t.is_date() => Value::Date(None),
-                    #[cfg(feature = "json")]
-                    t if t.is_json() => Value::Json(None),
-                    typ => {
-                        let msg = format!("Value of type {typ:?} is not supported with the current configuration");
-
-                        let kind = ErrorKind::conversion(msg);
-                        return Err(Error::builder(kind).build());
-                    }
-                },
-                #[cfg(not(feature = "chrono"))]
+
+                if days != 0 {
+                    let kind = ErrorKind::conversion("Failed to read a MySQL `time` as duration");
+                    return Err(Error::builder(kind).build());
+                }
+
+                let time =
+                    NaiveTime::from_hms_micro_opt(hours.into(), minutes.into(), seconds.into(), micros).unwrap();
+                Value::time(time)
+            }
+            my::Value::NULL => match column {
+                t if tinyint1_is_bool && is_tinyint1(t) => Value::Boolean(None),
+                t if t.is_bool() => Value::Boolean(None),
+                t if t.is_bits() => Value::BitString(None),
+                t if t.is_enum() => Value::Enum(None),
+                t if t.is_null() => Value::Int32(None),
+                t if t.is_int64() => Value::Int64(None),
+                t if t.is_int32() => Value::Int32(None),
+                t if t.is_float() => Value::Float(None),
+                t if t.is_double() => Value::Double(None),
+                t if t.is_text() => Value::Text(None),
+                t if t.is_bytes() => Value::Bytes(None),
+                #[cfg(feature = "bigdecimal")]
+                t if t.is_real() => Value::Numeric(None),
+                #[cfg(feature = "chrono")]
+                t if t.is_datetime() => Value::DateTime(None),
+                #[cfg(feature = "chrono")]
+                t if t.is_time() => Value::Time(None),
+                #[cfg(feature = "chrono")]
+                t if t.is_date() => Value::Date(None),
+                #[cfg(feature = "json")]
+                t if t.is_json() => Value::Json(None),
                 typ => {
-                    let msg = format!(
-                        "Value of type {:?} is not supported with the current configuration",
-                        typ
-                    );
+                    let msg = format!("Value of type {typ:?} is not supported with the current configuration");
 
                     let kind = ErrorKind::conversion(msg);
-                    Err(Error::builder(kind).build())?
+                    return Err(Error::builder(kind).build());
                 }
-            };
+            },
+            #[cfg(not(feature = "chrono"))]
+            typ => {
+                let msg = format!(
+                    "Value of type {:?} is not supported with the current configuration",
+                    typ
+                );
 
-            Ok(res)
-        }
+                let kind = ErrorKind::conversion(msg);
+                Err(Error::builder(kind).build())?
+            }
+        };
 
-        let mut row = Vec::with_capacity(self.len());
+        Ok(res)
+    }
 
-        for i in 0..self.len() {
-            row.push(convert(self, i)?);
-        }
+    let mut values = Vec::with_capacity(row.len());
 
-        Ok(row)
+    for i in 0..row.len() {
+        values.push(convert(row, i, tinyint1_is_bool)?);
     }
+
+    Ok(values)
 }
@@ -3,11 +3,21 @@ use crate::{
     ast::*,
 };
 use async_trait::async_trait;
-use metrics::{decrement_gauge, increment_gauge};
-use std::sync::Arc;
+use metrics::{decrement_gauge, increment_counter, increment_gauge};
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Arc,
+};
 
 extern crate metrics as metrics;
 
+/// The transaction is open and has not been closed yet.
+const STATE_PENDING: u8 = 0;
+/// `commit` was called on the transaction.
+const STATE_COMMITTED: u8 = 1;
+/// `rollback` was called on the transaction.
+const STATE_ROLLED_BACK: u8 = 2;
+
 /// A representation of an SQL database transaction. If not commited, a
 /// transaction will be rolled back by default when dropped.
 ///
@@ -15,6 +25,10 @@ extern crate metrics as metrics;
 /// transaction object will panic.
 pub struct OwnedTransaction {
     pub(crate) inner: Arc<dyn Queryable>,
+    /// Whether the transaction is still pending, committed or rolled back.
+    /// The state transitions away from pending only once, keeping the
+    /// active gauge from being decremented twice.
+    state: AtomicU8,
 }
 
 impl OwnedTransaction {
@@ -23,7 +37,10 @@ impl OwnedTransaction {
         begin_stmt: &str,
         tx_opts: TransactionOptions,
     ) -> crate::Result<OwnedTransaction> {
-        let this = Self { inner: inner.clone() };
+        let this = Self {
+            inner: inner.clone(),
+            state: AtomicU8::new(STATE_PENDING),
+        };
 
         if tx_opts.isolation_first {
             if let Some(isolation) = tx_opts.isolation_level {
@@ -41,54 +58,168 @@ impl OwnedTransaction {
 
         inner.server_reset_query_owned(&this).await?;
 
-        increment_gauge!("prisma_client_queries_active", 1.0);
+        increment_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
         Ok(this)
     }
 
     /// Commit the changes to the database and consume the transaction.
     pub async fn commit(&self) -> crate::Result<()> {
-        decrement_gauge!("prisma_client_queries_active", 1.0);
+        self.close(STATE_COMMITTED)?;
+        decrement_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
+
         self.inner.raw_cmd("COMMIT").await?;
+        increment_counter!(super::metrics::metric_name("quaint", "tx_committed_total"));
 
         Ok(())
     }
 
     /// Rolls back the changes to the database.
     pub async fn rollback(&self) -> crate::Result<()> {
-        decrement_gauge!("prisma_client_queries_active", 1.0);
+        self.close(STATE_ROLLED_BACK)?;
+        decrement_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
+
         self.inner.raw_cmd("ROLLBACK").await?;
+        increment_counter!(super::metrics::metric_name("quaint", "tx_rolled_back_total"));
 
         Ok(())
     }
+
+    /// `true` while neither [`commit`] nor [`rollback`] has been called.
+    ///
+    /// [`commit`]: Self::commit
+    /// [`rollback`]: Self::rollback
+    pub fn is_pending(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == STATE_PENDING
+    }
+
+    /// `true` once [`commit`](Self::commit) has been called.
+    pub fn is_committed(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == STATE_COMMITTED
+    }
+
+    /// `true` once [`rollback`](Self::rollback) has been called.
+    pub fn is_rolled_back(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == STATE_ROLLED_BACK
+    }
+
+    /// Transitions the transaction away from pending, erroring when another
+    /// call won the race or the transaction was closed before.
+    fn close(&self, state: u8) -> crate::Result<()> {
+        self.state
+            .compare_exchange(STATE_PENDING, state, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|_| ())
+            .map_err(|_| Self::already_closed())
+    }
+
+    /// Refuses to use the transaction once it is committed or rolled back,
+    /// instead of sending the statement to the connection and getting a
+    /// cryptic driver error back.
+    fn ensure_open(&self) -> crate::Result<()> {
+        if self.is_pending() {
+            Ok(())
+        } else {
+            Err(Self::already_closed())
+        }
+    }
+
+    fn already_closed() -> crate::error::Error {
+        let kind = crate::error::ErrorKind::TransactionAlreadyClosed(
+            "The transaction was already committed or rolled back.".into(),
+        );
+
+        crate::error::Error::builder(kind).build()
+    }
+
+    /// `VACUUM` cannot run inside a transaction block, so calling it on a
+    /// transaction always errors with `InvalidOperation`. Run it on the
+    /// connection directly instead.
+    pub async fn vacuum(&self, _table: Option<&str>, _full: bool, _analyze: bool) -> crate::Result<()> {
+        let kind = crate::error::ErrorKind::InvalidOperation("VACUUM cannot run inside a transaction.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// See [`vacuum`](Self::vacuum); maintenance commands are only sent
+    /// outside of transaction blocks.
+    pub async fn analyze(&self, _table: Option<&str>) -> crate::Result<()> {
+        let kind = crate::error::ErrorKind::InvalidOperation("ANALYZE cannot run inside a transaction.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// Takes a Postgres advisory lock scoped to this transaction through
+    /// `pg_advisory_xact_lock`, released automatically on commit or
+    /// rollback. With `try_only` the call never blocks and returns `false`
+    /// if another session already holds the lock, otherwise it waits for
+    /// the lock and always returns `true`. Only available on PostgreSQL.
+    #[cfg(feature = "postgresql")]
+    pub async fn advisory_xact_lock<K>(&self, key: K, try_only: bool) -> crate::Result<bool>
+    where
+        K: Into<crate::connector::AdvisoryLockKey>,
+    {
+        let key = key.into();
+
+        if try_only {
+            let res = self
+                .inner
+                .query_raw(&key.statement("pg_try_advisory_xact_lock"), &key.params())
+                .await?;
+
+            Ok(res.into_single()?[0].as_bool().unwrap_or(false))
+        } else {
+            self.inner
+                .query_raw(&key.statement("pg_advisory_xact_lock"), &key.params())
+                .await?;
+
+            Ok(true)
+        }
+    }
 }
 
 #[async_trait]
 impl Queryable for OwnedTransaction {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        self.ensure_open()?;
         self.inner.query(q).await
     }
 
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        self.ensure_open()?;
+        self.inner.render(q)
+    }
+
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        self.ensure_open()?;
         self.inner.execute(q).await
     }
 
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.ensure_open()?;
         self.inner.query_raw(sql, params).await
     }
 
     async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.ensure_open()?;
         self.inner.query_raw_typed(sql, params).await
     }
 
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        self.ensure_open()?;
+        self.inner.query_multi_raw(sql, params).await
+    }
+
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.ensure_open()?;
         self.inner.execute_raw(sql, params).await
     }
 
     async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.ensure_open()?;
         self.inner.execute_raw_typed(sql, params).await
     }
 
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.ensure_open()?;
         self.inner.raw_cmd(cmd).await
     }
 
@@ -96,10 +227,43 @@ impl Queryable for OwnedTransaction {
         self.inner.version().await
     }
 
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    async fn insert_returning(&self, insert: Insert<'_>, pk_column: &str) -> crate::Result<Value<'static>> {
+        self.ensure_open()?;
+        self.inner.insert_returning(insert, pk_column).await
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        self.inner.table_exists(table, schema).await
+    }
+
+    async fn column_names(&self, table: &str) -> crate::Result<Vec<String>> {
+        self.inner.column_names(table).await
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        self.inner.column_info(table).await
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<ForeignKeyInfo>> {
+        self.inner.foreign_keys(table).await
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<IndexInfo>> {
+        self.inner.indexes(table).await
+    }
+
     fn is_healthy(&self) -> bool {
         self.inner.is_healthy()
     }
 
+    fn in_transaction(&self) -> bool {
+        true
+    }
+
     async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
         self.inner.set_tx_isolation_level(isolation_level).await
     }
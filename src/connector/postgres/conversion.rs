@@ -2,7 +2,7 @@
 mod decimal;
 
 use crate::{
-    ast::Value,
+    ast::{ArrayType, IpNet, Value},
     connector::queryable::{GetRow, ToColumnNames},
     error::{Error, ErrorKind},
 };
@@ -15,6 +15,7 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 #[cfg(feature = "bigdecimal")]
 pub(crate) use decimal::DecimalWrapper;
 use postgres_types::{FromSql, ToSql, WrongType};
+use smallvec::SmallVec;
 use std::{convert::TryFrom, error::Error as StdError};
 use tokio_postgres::{
     types::{self, IsNull, Kind, Type as PostgresType},
@@ -24,15 +25,53 @@ use tokio_postgres::{
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
-pub(crate) fn conv_params<'a>(params: &'a [Value<'a>]) -> Vec<&'a (dyn types::ToSql + Sync)> {
-    params.iter().map(|x| x as &(dyn ToSql + Sync)).collect::<Vec<_>>()
+/// Borrows the parameter values as trait objects for the driver. The result
+/// lives on the stack for up to sixteen parameters, so the common short
+/// query does not allocate for the conversion.
+pub fn conv_params<'a>(params: &'a [Value<'a>]) -> SmallVec<[&'a (dyn types::ToSql + Sync); 16]> {
+    params.iter().map(|x| x as &(dyn ToSql + Sync)).collect()
+}
+
+/// Maps a typed array element hint to the matching Postgres array type.
+fn hinted_array_type(r#type: ArrayType) -> PostgresType {
+    match r#type {
+        ArrayType::Int32 => PostgresType::INT4_ARRAY,
+        ArrayType::Int64 => PostgresType::INT8_ARRAY,
+        ArrayType::Float => PostgresType::FLOAT4_ARRAY,
+        ArrayType::Double => PostgresType::FLOAT8_ARRAY,
+        ArrayType::Text => PostgresType::TEXT_ARRAY,
+        ArrayType::Bytes => PostgresType::BYTEA_ARRAY,
+        ArrayType::Boolean => PostgresType::BOOL_ARRAY,
+        #[cfg(feature = "bigdecimal")]
+        ArrayType::Numeric => PostgresType::NUMERIC_ARRAY,
+        #[cfg(feature = "json")]
+        ArrayType::Json => PostgresType::JSONB_ARRAY,
+        #[cfg(feature = "uuid")]
+        ArrayType::Uuid => PostgresType::UUID_ARRAY,
+        #[cfg(feature = "chrono")]
+        ArrayType::DateTime => PostgresType::TIMESTAMPTZ_ARRAY,
+    }
+}
+
+/// The number of dimensions of an array value; zero for a scalar.
+fn array_dimensions(value: &Value<'_>) -> usize {
+    match value.as_array() {
+        Some(values) => 1 + values.iter().map(array_dimensions).max().unwrap_or(0),
+        None => 0,
+    }
 }
 
 /// Maps a list of query parameter values to a list of Postgres type.
-pub(crate) fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
+pub fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
     params
         .iter()
         .map(|p| -> PostgresType {
+            // A typed array carries its element type, so nothing needs to be
+            // inferred — even when the array is empty or NULL.
+            if let Value::TypedArray(_, r#type) = p {
+                return hinted_array_type(*r#type);
+            }
+
             // While we can infer the underlying type of a null, Prisma can't.
             // Therefore, we let PG infer the underlying type.
             if p.is_null() {
@@ -42,12 +81,19 @@ pub(crate) fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
             match p {
                 Value::Int32(_) => PostgresType::INT4,
                 Value::Int64(_) => PostgresType::INT8,
+                Value::Int128(_) => PostgresType::NUMERIC,
                 Value::Float(_) => PostgresType::FLOAT4,
                 Value::Double(_) => PostgresType::FLOAT8,
                 Value::Text(_) => PostgresType::TEXT,
                 // Enums are special types, we can't statically infer them, so we let PG infer it
                 Value::Enum(_) => PostgresType::UNKNOWN,
                 Value::Bytes(_) => PostgresType::BYTEA,
+                // The geometry OID is assigned dynamically when PostGIS is
+                // installed, so we let PG infer the type.
+                #[cfg(feature = "postgis")]
+                Value::Geometry(_) => PostgresType::UNKNOWN,
+                Value::IpAddr(_) => PostgresType::INET,
+                Value::BitString(_) => PostgresType::VARBIT,
                 Value::Boolean(_) => PostgresType::BOOL,
                 Value::Char(_) => PostgresType::CHAR,
                 #[cfg(feature = "bigdecimal")]
@@ -58,7 +104,9 @@ pub(crate) fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
                 #[cfg(feature = "uuid")]
                 Value::Uuid(_) => PostgresType::UUID,
                 #[cfg(feature = "chrono")]
-                Value::DateTime(_) => PostgresType::TIMESTAMPTZ,
+                Value::DateTime(_) => PostgresType::TIMESTAMP,
+                #[cfg(feature = "chrono")]
+                Value::DateTimeUtc(_) => PostgresType::TIMESTAMPTZ,
                 #[cfg(feature = "chrono")]
                 Value::Date(_) => PostgresType::TIMESTAMP,
                 #[cfg(feature = "chrono")]
@@ -84,12 +132,17 @@ pub(crate) fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
                     match first {
                         Value::Int32(_) => PostgresType::INT4_ARRAY,
                         Value::Int64(_) => PostgresType::INT8_ARRAY,
+                        Value::Int128(_) => PostgresType::NUMERIC_ARRAY,
                         Value::Float(_) => PostgresType::FLOAT4_ARRAY,
                         Value::Double(_) => PostgresType::FLOAT8_ARRAY,
                         Value::Text(_) => PostgresType::TEXT_ARRAY,
                         // Enums are special types, we can't statically infer them, so we let PG infer it
                         Value::Enum(_) => PostgresType::UNKNOWN,
                         Value::Bytes(_) => PostgresType::BYTEA_ARRAY,
+                        #[cfg(feature = "postgis")]
+                        Value::Geometry(_) => PostgresType::UNKNOWN,
+                        Value::IpAddr(_) => PostgresType::INET_ARRAY,
+                        Value::BitString(_) => PostgresType::VARBIT_ARRAY,
                         Value::Boolean(_) => PostgresType::BOOL_ARRAY,
                         Value::Char(_) => PostgresType::CHAR_ARRAY,
                         #[cfg(feature = "bigdecimal")]
@@ -100,15 +153,18 @@ pub(crate) fn params_to_types(params: &[Value<'_>]) -> Vec<PostgresType> {
                         #[cfg(feature = "uuid")]
                         Value::Uuid(_) => PostgresType::UUID_ARRAY,
                         #[cfg(feature = "chrono")]
-                        Value::DateTime(_) => PostgresType::TIMESTAMPTZ_ARRAY,
+                        Value::DateTime(_) => PostgresType::TIMESTAMP_ARRAY,
+                        #[cfg(feature = "chrono")]
+                        Value::DateTimeUtc(_) => PostgresType::TIMESTAMPTZ_ARRAY,
                         #[cfg(feature = "chrono")]
                         Value::Date(_) => PostgresType::TIMESTAMP_ARRAY,
                         #[cfg(feature = "chrono")]
                         Value::Time(_) => PostgresType::TIME_ARRAY,
                         // In the case of nested arrays, we let PG infer the type
-                        Value::Array(_) => PostgresType::UNKNOWN,
+                        Value::Array(_) | Value::TypedArray(..) => PostgresType::UNKNOWN,
                     }
                 }
+                Value::TypedArray(_, r#type) => hinted_array_type(*r#type),
             }
         })
         .collect()
@@ -126,6 +182,62 @@ impl<'a> FromSql<'a> for XmlString {
     }
 }
 
+#[cfg(feature = "postgis")]
+struct WkbBytes(Vec<u8>);
+
+#[cfg(feature = "postgis")]
+impl<'a> FromSql<'a> for WkbBytes {
+    fn from_sql(_ty: &PostgresType, raw: &'a [u8]) -> Result<WkbBytes, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(WkbBytes(raw.to_owned()))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+}
+
+struct PgIpNet(IpNet);
+
+impl<'a> FromSql<'a> for PgIpNet {
+    fn from_sql(_ty: &PostgresType, raw: &'a [u8]) -> Result<PgIpNet, Box<dyn std::error::Error + Sync + Send>> {
+        if raw.len() < 4 {
+            return Err("malformed inet value".into());
+        }
+
+        let bits = raw[1];
+        let is_cidr = raw[2] != 0;
+
+        // PGSQL_AF_INET is 2, IPv6 is one above it.
+        let address: std::net::IpAddr = match raw[0] {
+            2 => <[u8; 4]>::try_from(&raw[4..])?.into(),
+            _ => <[u8; 16]>::try_from(&raw[4..])?.into(),
+        };
+
+        let max_bits = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = if is_cidr || bits != max_bits { Some(bits) } else { None };
+
+        Ok(PgIpNet(IpNet { address, prefix }))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        matches!(ty, &PostgresType::INET | &PostgresType::CIDR)
+    }
+}
+
+struct MacAddrString(String);
+
+impl<'a> FromSql<'a> for MacAddrString {
+    fn from_sql(_ty: &PostgresType, raw: &'a [u8]) -> Result<MacAddrString, Box<dyn std::error::Error + Sync + Send>> {
+        let formatted = raw.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(":");
+
+        Ok(MacAddrString(formatted))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        matches!(ty, &PostgresType::MACADDR | &PostgresType::MACADDR8)
+    }
+}
+
 struct EnumString {
     value: String,
 }
@@ -252,8 +364,7 @@ impl GetRow for PostgresRow {
                 PostgresType::TIMESTAMP => match row.try_get(i)? {
                     Some(val) => {
                         let ts: NaiveDateTime = val;
-                        let dt = DateTime::<Utc>::from_utc(ts, Utc);
-                        Value::datetime(dt)
+                        Value::datetime(ts)
                     }
                     None => Value::DateTime(None),
                 },
@@ -261,9 +372,9 @@ impl GetRow for PostgresRow {
                 PostgresType::TIMESTAMPTZ => match row.try_get(i)? {
                     Some(val) => {
                         let ts: DateTime<Utc> = val;
-                        Value::datetime(ts)
+                        Value::datetime_utc(ts)
                     }
-                    None => Value::DateTime(None),
+                    None => Value::DateTimeUtc(None),
                 },
                 #[cfg(feature = "chrono")]
                 PostgresType::DATE => match row.try_get(i)? {
@@ -362,9 +473,7 @@ impl GetRow for PostgresRow {
                     Some(val) => {
                         let val: Vec<Option<NaiveDateTime>> = val;
 
-                        let dates = val
-                            .into_iter()
-                            .map(|dt| Value::DateTime(dt.map(|dt| DateTime::<Utc>::from_utc(dt, Utc))));
+                        let dates = val.into_iter().map(Value::DateTime);
 
                         Value::array(dates)
                     }
@@ -416,7 +525,7 @@ impl GetRow for PostgresRow {
                 PostgresType::TIMESTAMPTZ_ARRAY => match row.try_get(i)? {
                     Some(val) => {
                         let val: Vec<Option<DateTime<Utc>>> = val;
-                        let dates = val.into_iter().map(Value::DateTime);
+                        let dates = val.into_iter().map(Value::DateTimeUtc);
 
                         Value::array(dates)
                     }
@@ -488,28 +597,33 @@ impl GetRow for PostgresRow {
                 },
                 PostgresType::INET | PostgresType::CIDR => match row.try_get(i)? {
                     Some(val) => {
-                        let val: std::net::IpAddr = val;
-                        Value::text(val.to_string())
+                        let val: PgIpNet = val;
+                        Value::ip_addr(val.0)
                     }
-                    None => Value::Text(None),
+                    None => Value::IpAddr(None),
                 },
                 PostgresType::INET_ARRAY | PostgresType::CIDR_ARRAY => match row.try_get(i)? {
                     Some(val) => {
-                        let val: Vec<Option<std::net::IpAddr>> = val;
-                        let addrs = val
-                            .into_iter()
-                            .map(|ip| Value::Text(ip.map(|ip| ip.to_string().into())));
+                        let val: Vec<Option<PgIpNet>> = val;
+                        let addrs = val.into_iter().map(|ip| Value::IpAddr(ip.map(|ip| ip.0)));
 
                         Value::array(addrs)
                     }
                     None => Value::Array(None),
                 },
+                PostgresType::MACADDR | PostgresType::MACADDR8 => match row.try_get(i)? {
+                    Some(val) => {
+                        let val: MacAddrString = val;
+                        Value::text(val.0)
+                    }
+                    None => Value::Text(None),
+                },
                 PostgresType::BIT | PostgresType::VARBIT => match row.try_get(i)? {
                     Some(val) => {
                         let val: BitVec = val;
-                        Value::text(bits_to_string(&val)?)
+                        Value::bit_string(bits_to_string(&val)?)
                     }
-                    None => Value::Text(None),
+                    None => Value::BitString(None),
                 },
                 PostgresType::BIT_ARRAY | PostgresType::VARBIT_ARRAY => match row.try_get(i)? {
                     Some(val) => {
@@ -517,8 +631,8 @@ impl GetRow for PostgresRow {
                         let stringified = val
                             .into_iter()
                             .map(|bits| match bits {
-                                Some(bits) => bits_to_string(&bits).map(Value::text),
-                                None => Ok(Value::Text(None)),
+                                Some(bits) => bits_to_string(&bits).map(Value::bit_string),
+                                None => Ok(Value::BitString(None)),
                             })
                             .collect::<crate::Result<Vec<_>>>()?;
 
@@ -543,6 +657,17 @@ impl GetRow for PostgresRow {
                     None => Value::Array(None),
                 },
                 ref x => match x.kind() {
+                    // PostGIS registers its types dynamically, so they can
+                    // only be recognized by name.
+                    #[cfg(feature = "postgis")]
+                    _ if matches!(x.name(), "geometry" | "geography") => match row.try_get(i)? {
+                        Some(val) => {
+                            let val: WkbBytes = val;
+
+                            Value::geometry(val.0)
+                        }
+                        None => Value::Geometry(None),
+                    },
                     Kind::Enum(_) => match row.try_get(i)? {
                         Some(val) => {
                             let val: EnumString = val;
@@ -686,8 +811,14 @@ impl<'a> ToSql for Value<'a> {
                 .map(|integer| BigDecimal::from_i64(integer).unwrap())
                 .map(DecimalWrapper)
                 .map(|dw| dw.to_sql(ty, out)),
+            #[cfg(feature = "bigdecimal")]
+            (Value::Int128(integer), &PostgresType::NUMERIC) => integer
+                .map(|integer| BigDecimal::from_i128(integer).unwrap())
+                .map(DecimalWrapper)
+                .map(|dw| dw.to_sql(ty, out)),
             (Value::Int32(integer), &PostgresType::TEXT) => integer.map(|integer| format!("{integer}").to_sql(ty, out)),
             (Value::Int64(integer), &PostgresType::TEXT) => integer.map(|integer| format!("{integer}").to_sql(ty, out)),
+            (Value::Int128(integer), &PostgresType::TEXT) => integer.map(|integer| format!("{integer}").to_sql(ty, out)),
             (Value::Int32(integer), &PostgresType::OID) => match integer {
                 Some(i) => {
                     let integer = u32::try_from(*i).map_err(|_| {
@@ -718,6 +849,20 @@ impl<'a> ToSql for Value<'a> {
             },
             (Value::Int32(integer), _) => integer.map(|integer| integer.to_sql(ty, out)),
             (Value::Int64(integer), _) => integer.map(|integer| integer.to_sql(ty, out)),
+            (Value::Int128(integer), _) => match integer {
+                Some(i) => {
+                    let integer = i64::try_from(*i).map_err(|_| {
+                        let kind = ErrorKind::conversion(format!(
+                            "Unable to fit integer value '{i}' into an INT8 (64-bit signed integer)."
+                        ));
+
+                        Error::builder(kind).build()
+                    })?;
+
+                    Some(integer.to_sql(ty, out))
+                }
+                _ => None,
+            },
             (Value::Float(float), &PostgresType::FLOAT8) => float.map(|float| (float as f64).to_sql(ty, out)),
             #[cfg(feature = "bigdecimal")]
             (Value::Float(float), &PostgresType::NUMERIC) => float
@@ -743,7 +888,7 @@ impl<'a> ToSql for Value<'a> {
                 f.to_sql(ty, out)
             }),
             #[cfg(feature = "bigdecimal")]
-            (Value::Array(values), &PostgresType::FLOAT4_ARRAY) => values.as_ref().map(|values| {
+            (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::FLOAT4_ARRAY) => values.as_ref().map(|values| {
                 let mut floats = Vec::with_capacity(values.len());
 
                 for value in values.iter() {
@@ -768,7 +913,7 @@ impl<'a> ToSql for Value<'a> {
                 floats.to_sql(ty, out)
             }),
             #[cfg(feature = "bigdecimal")]
-            (Value::Array(values), &PostgresType::FLOAT8_ARRAY) => values.as_ref().map(|values| {
+            (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::FLOAT8_ARRAY) => values.as_ref().map(|values| {
                 let mut floats = Vec::with_capacity(values.len());
 
                 for value in values.iter() {
@@ -817,7 +962,7 @@ impl<'a> ToSql for Value<'a> {
                 parsed_uuid.to_sql(ty, out)
             }),
             #[cfg(feature = "uuid")]
-            (Value::Array(values), &PostgresType::UUID_ARRAY) => values.as_ref().map(|values| {
+            (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::UUID_ARRAY) => values.as_ref().map(|values| {
                 let parsed_uuid: Vec<Option<Uuid>> = values
                     .iter()
                     .map(<Option<Uuid>>::try_from)
@@ -831,7 +976,13 @@ impl<'a> ToSql for Value<'a> {
                     parsed_ip_addr.to_sql(ty, out)
                 })
             }
-            (Value::Array(values), &PostgresType::INET_ARRAY) | (Value::Array(values), &PostgresType::CIDR_ARRAY) => {
+            (Value::IpAddr(ip), &PostgresType::TEXT) => ip.map(|ip| ip.to_string().to_sql(ty, out)),
+            (Value::IpAddr(ip), _) => ip.map(|ip| {
+                write_inet(out, ip, ty == &PostgresType::CIDR);
+                Ok(IsNull::No)
+            }),
+            (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::INET_ARRAY)
+            | (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::CIDR_ARRAY) => {
                 values.as_ref().map(|values| {
                     let parsed_ip_addr: Vec<Option<std::net::IpAddr>> = values
                         .iter()
@@ -853,7 +1004,13 @@ impl<'a> ToSql for Value<'a> {
                 })
             }
             (Value::Text(string), _) => string.as_ref().map(|ref string| string.to_sql(ty, out)),
-            (Value::Array(values), &PostgresType::BIT_ARRAY) | (Value::Array(values), &PostgresType::VARBIT_ARRAY) => {
+            (Value::BitString(string), _) => string.as_ref().map(|string| {
+                let bits: BitVec = string_to_bits(string)?;
+
+                bits.to_sql(ty, out)
+            }),
+            (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::BIT_ARRAY)
+            | (Value::Array(values) | Value::TypedArray(values, _), &PostgresType::VARBIT_ARRAY) => {
                 values.as_ref().map(|values| {
                     let bitvecs: Vec<Option<BitVec>> = values
                         .iter()
@@ -864,16 +1021,30 @@ impl<'a> ToSql for Value<'a> {
                 })
             }
             (Value::Bytes(bytes), _) => bytes.as_ref().map(|bytes| bytes.as_ref().to_sql(ty, out)),
+            #[cfg(feature = "postgis")]
+            (Value::Geometry(geom), _) => geom.as_ref().map(|geom| geom.0.as_slice().to_sql(ty, out)),
             (Value::Enum(string), _) => string.as_ref().map(|string| {
                 out.extend_from_slice(string.as_bytes());
                 Ok(IsNull::No)
             }),
             (Value::Boolean(boo), _) => boo.map(|boo| boo.to_sql(ty, out)),
             (Value::Char(c), _) => c.map(|c| (c as i8).to_sql(ty, out)),
-            (Value::Array(vec), typ) if matches!(typ.kind(), Kind::Array(_)) => {
-                vec.as_ref().map(|vec| vec.to_sql(ty, out))
+            (Value::Array(vec) | Value::TypedArray(vec, _), typ) if matches!(typ.kind(), Kind::Array(_)) => {
+                vec.as_ref().map(|vec| {
+                    if vec.iter().any(|value| value.is_array()) {
+                        let dimensions = 1 + vec.iter().map(array_dimensions).max().unwrap_or(0);
+
+                        let kind = ErrorKind::conversion(format!(
+                            "Cannot bind a {dimensions}-dimensional array: only one-dimensional arrays are supported as parameters."
+                        ));
+
+                        return Err(Error::builder(kind).build().into());
+                    }
+
+                    vec.to_sql(ty, out)
+                })
             }
-            (Value::Array(vec), typ) => {
+            (Value::Array(vec) | Value::TypedArray(vec, _), typ) => {
                 let kind = ErrorKind::conversion(format!(
                     "Couldn't serialize value `{vec:?}` into a `{typ}`. Value is a list but `{typ}` is not."
                 ));
@@ -886,7 +1057,11 @@ impl<'a> ToSql for Value<'a> {
             #[cfg(feature = "uuid")]
             (Value::Uuid(value), _) => value.map(|value| value.to_sql(ty, out)),
             #[cfg(feature = "chrono")]
-            (Value::DateTime(value), &PostgresType::DATE) => value.map(|value| value.date_naive().to_sql(ty, out)),
+            (Value::DateTime(value), &PostgresType::DATE) => value.map(|value| value.date().to_sql(ty, out)),
+            #[cfg(feature = "chrono")]
+            (Value::DateTimeUtc(value), &PostgresType::DATE) => {
+                value.map(|value| value.date_naive().to_sql(ty, out))
+            }
             #[cfg(feature = "chrono")]
             (Value::Date(value), _) => value.map(|value| value.to_sql(ty, out)),
             #[cfg(feature = "chrono")]
@@ -894,6 +1069,8 @@ impl<'a> ToSql for Value<'a> {
             #[cfg(feature = "chrono")]
             (Value::DateTime(value), &PostgresType::TIME) => value.map(|value| value.time().to_sql(ty, out)),
             #[cfg(feature = "chrono")]
+            (Value::DateTimeUtc(value), &PostgresType::TIME) => value.map(|value| value.time().to_sql(ty, out)),
+            #[cfg(feature = "chrono")]
             (Value::DateTime(value), &PostgresType::TIMETZ) => value.map(|value| {
                 let result = value.time().to_sql(ty, out)?;
                 // We assume UTC. see https://www.postgresql.org/docs/9.5/datatype-datetime.html
@@ -901,7 +1078,20 @@ impl<'a> ToSql for Value<'a> {
                 Ok(result)
             }),
             #[cfg(feature = "chrono")]
-            (Value::DateTime(value), _) => value.map(|value| value.naive_utc().to_sql(ty, out)),
+            (Value::DateTimeUtc(value), &PostgresType::TIMETZ) => value.map(|value| {
+                let result = value.time().to_sql(ty, out)?;
+                // We assume UTC. see https://www.postgresql.org/docs/9.5/datatype-datetime.html
+                out.extend_from_slice(&[0; 4]);
+                Ok(result)
+            }),
+            #[cfg(feature = "chrono")]
+            (Value::DateTime(value), &PostgresType::TIMESTAMPTZ) => {
+                value.map(|value| DateTime::<Utc>::from_utc(value, Utc).to_sql(ty, out))
+            }
+            #[cfg(feature = "chrono")]
+            (Value::DateTime(value), _) => value.map(|value| value.to_sql(ty, out)),
+            #[cfg(feature = "chrono")]
+            (Value::DateTimeUtc(value), _) => value.map(|value| value.naive_utc().to_sql(ty, out)),
         };
 
         match res {
@@ -917,6 +1107,22 @@ impl<'a> ToSql for Value<'a> {
     tokio_postgres::types::to_sql_checked!();
 }
 
+/// Encodes an IP address or network in the binary `inet`/`cidr` wire format:
+/// address family, prefix bits, the cidr flag, the address length and the
+/// address bytes.
+fn write_inet(out: &mut BytesMut, ip: IpNet, is_cidr: bool) {
+    match ip.address {
+        std::net::IpAddr::V4(addr) => {
+            out.extend_from_slice(&[2, ip.prefix.unwrap_or(32), is_cidr as u8, 4]);
+            out.extend_from_slice(&addr.octets());
+        }
+        std::net::IpAddr::V6(addr) => {
+            out.extend_from_slice(&[3, ip.prefix.unwrap_or(128), is_cidr as u8, 16]);
+            out.extend_from_slice(&addr.octets());
+        }
+    }
+}
+
 fn string_to_bits(s: &str) -> crate::Result<BitVec> {
     use bit_vec::*;
 
@@ -957,6 +1163,7 @@ impl<'a> TryFrom<&Value<'a>> for Option<BitVec> {
 
     fn try_from(value: &Value<'a>) -> Result<Option<BitVec>, Self::Error> {
         match value {
+            Value::BitString(Some(bits)) => string_to_bits(bits).map(Option::Some),
             val @ Value::Text(Some(_)) => {
                 let text = val.as_str().unwrap();
 
@@ -0,0 +1,477 @@
+use crate::{
+    ast::Value,
+    connector::ResultRow,
+    error::{Error, ErrorKind},
+};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use std::{borrow::Cow, pin::Pin, sync::Arc, time::Duration};
+use tokio_postgres::{types::FromSql, types::Type as PostgresType, CopyOutStream};
+
+#[cfg(feature = "bigdecimal")]
+use super::conversion::DecimalWrapper;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+/// The data format of a `COPY` transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// The default tab-separated text format.
+    Text,
+    /// The PostgreSQL binary format.
+    Binary,
+}
+
+/// Renders the `COPY ... TO STDOUT` statement for the given source. A source
+/// starting with `SELECT` is treated as a query and wrapped in parentheses,
+/// anything else is passed through as a table reference.
+pub(crate) fn copy_out_statement(source: &str, format: CopyFormat) -> String {
+    let source = source.trim();
+
+    let target = if is_query(source) {
+        Cow::from(format!("({source})"))
+    } else {
+        Cow::from(source)
+    };
+
+    match format {
+        CopyFormat::Text => format!("COPY {target} TO STDOUT"),
+        CopyFormat::Binary => format!("COPY {target} TO STDOUT BINARY"),
+    }
+}
+
+/// The statement used for sniffing the column names and types of a copy
+/// source before the transfer starts.
+pub(crate) fn probe_statement(source: &str) -> Cow<'_, str> {
+    let source = source.trim();
+
+    if is_query(source) {
+        Cow::from(source)
+    } else {
+        Cow::from(format!("SELECT * FROM {source}"))
+    }
+}
+
+fn is_query(source: &str) -> bool {
+    source
+        .get(..6)
+        .map(|s| s.eq_ignore_ascii_case("select"))
+        .unwrap_or(false)
+}
+
+/// Wraps a raw copy stream, applying the given socket timeout to every chunk
+/// instead of the transfer as a whole.
+pub(crate) fn chunks_with_timeout(
+    stream: CopyOutStream,
+    socket_timeout: Option<Duration>,
+) -> impl Stream<Item = crate::Result<Bytes>> {
+    let state = (Box::pin(stream), false);
+
+    futures::stream::unfold(state, move |(mut stream, errored)| async move {
+        if errored {
+            return None;
+        }
+
+        let chunk = crate::connector::timeout::socket(socket_timeout, async {
+            stream.next().await.transpose()
+        })
+        .await;
+
+        match chunk {
+            Ok(Some(bytes)) => Some((Ok(bytes), (stream, false))),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), (stream, true))),
+        }
+    })
+}
+
+/// Decodes a binary format copy stream into rows, applying the given socket
+/// timeout to every chunk.
+pub(crate) fn decode_binary_rows(
+    stream: CopyOutStream,
+    socket_timeout: Option<Duration>,
+    columns: Arc<Vec<String>>,
+    types: Vec<PostgresType>,
+) -> impl Stream<Item = crate::Result<ResultRow>> {
+    struct State {
+        stream: Pin<Box<CopyOutStream>>,
+        decoder: BinaryCopyDecoder,
+        columns: Arc<Vec<String>>,
+        types: Vec<PostgresType>,
+        errored: bool,
+    }
+
+    let state = State {
+        stream: Box::pin(stream),
+        decoder: BinaryCopyDecoder::new(),
+        columns,
+        types,
+        errored: false,
+    };
+
+    futures::stream::unfold(state, move |mut state| async move {
+        if state.errored {
+            return None;
+        }
+
+        loop {
+            match state.decoder.next_row(&state.types) {
+                Ok(Some(values)) => {
+                    let row = ResultRow {
+                        columns: state.columns.clone(),
+                        values,
+                    };
+
+                    return Some((Ok(row), state));
+                }
+                Ok(None) if state.decoder.is_complete() => return None,
+                Ok(None) => {
+                    let chunk = crate::connector::timeout::socket(socket_timeout, async {
+                        state.stream.next().await.transpose()
+                    })
+                    .await;
+
+                    match chunk {
+                        Ok(Some(bytes)) => state.decoder.push(&bytes),
+                        Ok(None) if state.decoder.is_complete() => return None,
+                        Ok(None) => {
+                            let kind = ErrorKind::conversion("Binary COPY data ended before the file trailer.");
+
+                            state.errored = true;
+                            return Some((Err(Error::builder(kind).build()), state));
+                        }
+                        Err(e) => {
+                            state.errored = true;
+                            return Some((Err(e), state));
+                        }
+                    }
+                }
+                Err(e) => {
+                    state.errored = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+/// An incremental decoder for the PostgreSQL binary `COPY` file format. Data
+/// chunks are buffered as they arrive and complete rows are taken out as soon
+/// as they are available, so chunk boundaries can fall anywhere.
+pub(crate) struct BinaryCopyDecoder {
+    buf: Vec<u8>,
+    header_parsed: bool,
+    done: bool,
+}
+
+impl BinaryCopyDecoder {
+    const SIGNATURE: &'static [u8] = b"PGCOPY\n\xff\r\n\0";
+
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            header_parsed: false,
+            done: false,
+        }
+    }
+
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// Whether the file trailer was seen, marking a complete transfer.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.done
+    }
+
+    /// Takes the next complete row out of the buffer. Returns `Ok(None)` when
+    /// more data is needed, or when the file trailer was reached (see
+    /// [`is_complete`](Self::is_complete)).
+    pub(crate) fn next_row(&mut self, types: &[PostgresType]) -> crate::Result<Option<Vec<Value<'static>>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if !self.header_parsed && !self.parse_header()? {
+            return Ok(None);
+        }
+
+        let mut pos = 0;
+
+        let field_count = match read_i16(&self.buf, &mut pos) {
+            Some(count) => count,
+            None => return Ok(None),
+        };
+
+        if field_count == -1 {
+            self.done = true;
+            return Ok(None);
+        }
+
+        if field_count as usize != types.len() {
+            let kind = ErrorKind::conversion(format!(
+                "Binary COPY row has {field_count} fields, expected {}.",
+                types.len()
+            ));
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut values = Vec::with_capacity(types.len());
+
+        for ty in types {
+            let field_length = match read_i32(&self.buf, &mut pos) {
+                Some(length) => length,
+                None => return Ok(None),
+            };
+
+            if field_length == -1 {
+                values.push(decode_field(ty, None)?);
+            } else {
+                let field_length = field_length as usize;
+
+                if self.buf.len() < pos + field_length {
+                    return Ok(None);
+                }
+
+                values.push(decode_field(ty, Some(&self.buf[pos..pos + field_length]))?);
+                pos += field_length;
+            }
+        }
+
+        self.buf.drain(..pos);
+
+        Ok(Some(values))
+    }
+
+    /// Validates and consumes the file header, returning false when the
+    /// buffer doesn't hold it completely yet.
+    fn parse_header(&mut self) -> crate::Result<bool> {
+        if self.buf.len() < Self::SIGNATURE.len() + 8 {
+            return Ok(false);
+        }
+
+        if &self.buf[..Self::SIGNATURE.len()] != Self::SIGNATURE {
+            let kind = ErrorKind::conversion("Invalid binary COPY signature.");
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut pos = Self::SIGNATURE.len();
+
+        let flags = read_i32(&self.buf, &mut pos).unwrap();
+
+        if flags & (1 << 16) != 0 {
+            let kind = ErrorKind::conversion("Binary COPY data with OIDs is not supported.");
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let extension_length = read_i32(&self.buf, &mut pos).unwrap() as usize;
+
+        if self.buf.len() < pos + extension_length {
+            return Ok(false);
+        }
+
+        self.buf.drain(..pos + extension_length);
+        self.header_parsed = true;
+
+        Ok(true)
+    }
+}
+
+fn read_i16(buf: &[u8], pos: &mut usize) -> Option<i16> {
+    let bytes = buf.get(*pos..*pos + 2)?;
+    *pos += 2;
+
+    Some(i16::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i32(buf: &[u8], pos: &mut usize) -> Option<i32> {
+    let bytes = buf.get(*pos..*pos + 4)?;
+    *pos += 4;
+
+    Some(i32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+/// Decodes a single field of a binary format row. The binary `COPY` format
+/// doesn't carry any type information of its own, so the types are sniffed
+/// from the source with a prepared statement beforehand.
+fn decode_field(ty: &PostgresType, bytes: Option<&[u8]>) -> crate::Result<Value<'static>> {
+    let value = match *ty {
+        PostgresType::BOOL => Value::Boolean(decode_with::<bool>(ty, bytes)?),
+        PostgresType::INT2 => Value::Int32(decode_with::<i16>(ty, bytes)?.map(i32::from)),
+        PostgresType::INT4 => Value::Int32(decode_with::<i32>(ty, bytes)?),
+        PostgresType::INT8 => Value::Int64(decode_with::<i64>(ty, bytes)?),
+        PostgresType::TEXT | PostgresType::VARCHAR | PostgresType::BPCHAR | PostgresType::NAME => {
+            Value::Text(decode_with::<String>(ty, bytes)?.map(Cow::from))
+        }
+        PostgresType::BYTEA => Value::Bytes(decode_with::<Vec<u8>>(ty, bytes)?.map(Cow::from)),
+        #[cfg(feature = "chrono")]
+        PostgresType::TIMESTAMP => {
+            let ts = decode_with::<chrono::NaiveDateTime>(ty, bytes)?;
+
+            Value::DateTime(ts)
+        }
+        #[cfg(feature = "chrono")]
+        PostgresType::TIMESTAMPTZ => Value::DateTimeUtc(decode_with::<DateTime<Utc>>(ty, bytes)?),
+        #[cfg(feature = "uuid")]
+        PostgresType::UUID => Value::Uuid(decode_with::<uuid::Uuid>(ty, bytes)?),
+        #[cfg(feature = "bigdecimal")]
+        PostgresType::NUMERIC => Value::Numeric(decode_with::<DecimalWrapper>(ty, bytes)?.map(|dw| dw.0)),
+        ref ty => {
+            let kind = ErrorKind::UnsupportedColumnType {
+                column_type: format!("{ty} (OID {})", ty.oid()),
+            };
+
+            return Err(Error::builder(kind).build());
+        }
+    };
+
+    Ok(value)
+}
+
+fn decode_with<'a, T: FromSql<'a>>(ty: &PostgresType, bytes: Option<&'a [u8]>) -> crate::Result<Option<T>> {
+    match bytes {
+        Some(bytes) => match T::from_sql(ty, bytes) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                let kind = ErrorKind::conversion(format!("Couldn't decode a binary COPY field of type {ty}: {e}"));
+
+                Err(Error::builder(kind).build())
+            }
+        },
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_file(rows: &[&[Option<&[u8]>]]) -> Vec<u8> {
+        let mut buf = BinaryCopyDecoder::SIGNATURE.to_vec();
+
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        buf.extend_from_slice(&0i32.to_be_bytes());
+
+        for row in rows {
+            buf.extend_from_slice(&(row.len() as i16).to_be_bytes());
+
+            for field in *row {
+                match field {
+                    Some(bytes) => {
+                        buf.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                        buf.extend_from_slice(bytes);
+                    }
+                    None => buf.extend_from_slice(&(-1i32).to_be_bytes()),
+                }
+            }
+        }
+
+        buf.extend_from_slice(&(-1i16).to_be_bytes());
+        buf
+    }
+
+    #[test]
+    fn copy_out_statement_wraps_queries() {
+        assert_eq!(
+            "COPY (SELECT id FROM cats) TO STDOUT BINARY",
+            copy_out_statement("SELECT id FROM cats", CopyFormat::Binary),
+        );
+
+        assert_eq!("COPY cats TO STDOUT", copy_out_statement("cats", CopyFormat::Text));
+    }
+
+    #[test]
+    fn decodes_supported_types() {
+        let file = encoded_file(&[
+            &[Some(&2i32.to_be_bytes()), Some(b"musti"), Some(&[1u8])],
+            &[Some(&3i32.to_be_bytes()), None, Some(&[0u8])],
+        ]);
+
+        let types = vec![PostgresType::INT4, PostgresType::TEXT, PostgresType::BOOL];
+        let mut decoder = BinaryCopyDecoder::new();
+
+        decoder.push(&file);
+
+        let row = decoder.next_row(&types).unwrap().unwrap();
+
+        assert_eq!(
+            vec![Value::int32(2), Value::text("musti"), Value::boolean(true)],
+            row
+        );
+
+        let row = decoder.next_row(&types).unwrap().unwrap();
+
+        assert_eq!(
+            vec![Value::int32(3), Value::Text(None), Value::boolean(false)],
+            row
+        );
+
+        assert_eq!(None, decoder.next_row(&types).unwrap());
+        assert!(decoder.is_complete());
+    }
+
+    #[test]
+    fn decodes_across_chunk_boundaries() {
+        let file = encoded_file(&[&[Some(&42i64.to_be_bytes())]]);
+        let types = vec![PostgresType::INT8];
+        let mut decoder = BinaryCopyDecoder::new();
+
+        for byte in file {
+            decoder.push(&[byte]);
+        }
+
+        let row = decoder.next_row(&types).unwrap().unwrap();
+
+        assert_eq!(vec![Value::int64(42)], row);
+        assert_eq!(None, decoder.next_row(&types).unwrap());
+        assert!(decoder.is_complete());
+    }
+
+    #[test]
+    fn returns_none_until_a_row_is_complete() {
+        let file = encoded_file(&[&[Some(b"meow")]]);
+        let types = vec![PostgresType::TEXT];
+        let mut decoder = BinaryCopyDecoder::new();
+
+        decoder.push(&file[..file.len() - 4]);
+
+        assert_eq!(None, decoder.next_row(&types).unwrap());
+        assert!(!decoder.is_complete());
+
+        decoder.push(&file[file.len() - 4..]);
+
+        let row = decoder.next_row(&types).unwrap().unwrap();
+        assert_eq!(vec![Value::text("meow")], row);
+    }
+
+    #[test]
+    fn unsupported_type_names_the_oid() {
+        let file = encoded_file(&[&[Some(&[0u8; 16])]]);
+        let types = vec![PostgresType::POINT];
+        let mut decoder = BinaryCopyDecoder::new();
+
+        decoder.push(&file);
+
+        let err = decoder.next_row(&types).unwrap_err();
+
+        match err.kind() {
+            ErrorKind::UnsupportedColumnType { column_type } => {
+                assert_eq!(&format!("point (OID {})", PostgresType::POINT.oid()), column_type);
+            }
+            kind => panic!("Expected UnsupportedColumnType, got {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_signature_is_an_error() {
+        let types = vec![PostgresType::INT4];
+        let mut decoder = BinaryCopyDecoder::new();
+
+        decoder.push(b"NOT A COPY HEADER AT ALL");
+
+        assert!(decoder.next_row(&types).is_err());
+    }
+}
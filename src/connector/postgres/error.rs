@@ -194,6 +194,27 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.build()
             }
+            // `ERRCODE_OBJECT_NOT_IN_PREREQUISITE_STATE`, e.g. two-phase
+            // commit on a server with `max_prepared_transactions` disabled.
+            Some(code) if code == "55000" => {
+                let code = code.to_string();
+                let db_error = e.into_source().and_then(|e| e.downcast::<DbError>().ok());
+                let message = db_error.as_ref().map(|e| e.message());
+
+                let kind = ErrorKind::InvalidOperation(message.map(ToString::to_string).unwrap_or_else(|| {
+                    "The object is not in the state the operation requires.".into()
+                }));
+
+                let mut builder = Error::builder(kind);
+
+                builder.set_original_code(code);
+
+                if let Some(message) = message {
+                    builder.set_original_message(message);
+                }
+
+                builder.build()
+            }
             Some(code) if code == "42P01" => {
                 let code = code.to_string();
                 let db_error = e.into_source().and_then(|e| e.downcast::<DbError>().ok());
@@ -273,6 +294,13 @@ impl From<tokio_postgres::error::Error> for Error {
                     return tls_error;
                 }
 
+                // A connection refused because `target_session_attrs` was
+                // not fulfilled surfaces as an IO error, so it has to be
+                // checked before the generic IO conversion.
+                if let Some(session_error) = try_extracting_session_attrs_error(&e) {
+                    return session_error;
+                }
+
                 // Same for IO errors.
                 if let Some(io_error) = try_extracting_io_error(&e) {
                     return io_error;
@@ -309,6 +337,19 @@ impl From<tokio_postgres::error::Error> for Error {
                         builder.set_original_message(reason);
                         builder.build()
                     } // double sigh
+                    // CockroachDB asks the client to retry a conflicting
+                    // transaction with a "restart transaction" error, not
+                    // always carrying the 40001 SQLSTATE.
+                    r if r.contains("restart transaction") => {
+                        let mut builder = Error::builder(ErrorKind::TransactionWriteConflict);
+
+                        if let Some(code) = code {
+                            builder.set_original_code(code);
+                        };
+
+                        builder.set_original_message(reason);
+                        builder.build()
+                    }
                     _ => {
                         let code = code.map(|c| c.to_string());
                         let mut builder = Error::builder(ErrorKind::QueryError(e.into()));
@@ -344,27 +385,37 @@ fn try_extracting_tls_error(err: &tokio_postgres::error::Error) -> Option<Error>
         .map(|err| err.into())
 }
 
-fn try_extracting_io_error(err: &tokio_postgres::error::Error) -> Option<Error> {
+/// `tokio_postgres` reports a server not fulfilling `target_session_attrs`
+/// as a `PermissionDenied` IO error with a fixed message.
+fn try_extracting_session_attrs_error(err: &tokio_postgres::error::Error) -> Option<Error> {
     use std::error::Error as _;
 
     err.source()
         .and_then(|err| err.downcast_ref::<std::io::Error>())
-        .map(|err| ErrorKind::ConnectionError(Box::new(std::io::Error::new(err.kind(), format!("{err}")))))
-        .map(|kind| Error::builder(kind).build())
+        .filter(|err| {
+            err.kind() == std::io::ErrorKind::PermissionDenied
+                && matches!(
+                    err.to_string().as_str(),
+                    "database does not allow writes" | "database is not read only"
+                )
+        })
+        .map(|err| {
+            let message = format!("{err}");
+
+            let mut builder = Error::builder(ErrorKind::SessionAttributesMismatch {
+                message: message.clone(),
+            });
+
+            builder.set_original_message(message);
+            builder.build()
+        })
 }
 
-impl From<native_tls::Error> for Error {
-    fn from(e: native_tls::Error) -> Error {
-        Error::from(&e)
-    }
-}
-
-impl From<&native_tls::Error> for Error {
-    fn from(e: &native_tls::Error) -> Error {
-        let kind = ErrorKind::TlsError {
-            message: format!("{e}"),
-        };
+fn try_extracting_io_error(err: &tokio_postgres::error::Error) -> Option<Error> {
+    use std::error::Error as _;
 
-        Error::builder(kind).build()
-    }
+    err.source()
+        .and_then(|err| err.downcast_ref::<std::io::Error>())
+        .map(|err| ErrorKind::ConnectionError(Box::new(std::io::Error::new(err.kind(), format!("{err}")))))
+        .map(|kind| Error::builder(kind).build())
 }
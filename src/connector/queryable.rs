@@ -1,6 +1,7 @@
 use std::sync::Arc;
-use super::{IsolationLevel, ResultSet, Transaction, TransactionOptions};
+use super::{BatchResult, DryRunConnector, IsolationLevel, ResultRow, ResultSet, TaggedQueryable, Transaction, TransactionOptions};
 use crate::ast::*;
+use crate::visitor::Capabilities;
 use async_trait::async_trait;
 use crate::connector::{OwnedTransaction};
 
@@ -16,12 +17,93 @@ pub trait ToColumnNames {
     fn to_column_names(&self) -> Vec<String>;
 }
 
+/// Applies the `assume_utc` connector option to a decoded row,
+/// reinterpreting every timezone-less datetime as UTC. Values decoded from
+/// a `timestamptz`-style column are in UTC already and pass through
+/// untouched.
+pub(crate) fn assume_utc_values(values: &mut [Value<'static>]) {
+    for value in values.iter_mut() {
+        assume_utc_value(value);
+    }
+}
+
+fn assume_utc_value(value: &mut Value<'static>) {
+    match value {
+        #[cfg(feature = "chrono")]
+        Value::DateTime(dt) => {
+            *value = Value::DateTimeUtc(dt.map(|dt| chrono::DateTime::from_utc(dt, chrono::Utc)));
+        }
+        Value::Array(Some(values)) => {
+            for value in values.iter_mut() {
+                assume_utc_value(value);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Schema information for a single column of a table, as reported by
+/// [`Queryable::column_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    /// The name of the column.
+    pub name: String,
+    /// The data type of the column, spelled the way the database reports it.
+    pub data_type: String,
+    /// Whether the column accepts `NULL` values.
+    pub is_nullable: bool,
+    /// The default value expression of the column, if one is set. The
+    /// expression is reported verbatim and not evaluated.
+    pub default: Option<String>,
+}
+
+/// A foreign key constraint of a table, as reported by
+/// [`Queryable::foreign_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyInfo {
+    /// The name of the constraint. SQLite does not name foreign keys, so
+    /// there the numeric id from `foreign_key_list` is reported instead.
+    pub constraint_name: String,
+    /// The referencing columns, in constraint order.
+    pub columns: Vec<String>,
+    /// The table the constraint points to.
+    pub referenced_table: String,
+    /// The referenced columns, matching `columns` pairwise.
+    pub referenced_columns: Vec<String>,
+    /// The `ON DELETE` action, e.g. `NO ACTION` or `CASCADE`.
+    pub on_delete: String,
+    /// The `ON UPDATE` action, e.g. `NO ACTION` or `CASCADE`.
+    pub on_update: String,
+}
+
+/// An index of a table, as reported by [`Queryable::indexes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+    /// The name of the index.
+    pub name: String,
+    /// The indexed columns, in index order.
+    pub columns: Vec<String>,
+    /// Whether the index enforces uniqueness.
+    pub is_unique: bool,
+    /// The access method of the index, e.g. `btree` or `hash`, spelled the
+    /// way the database reports it.
+    pub index_type: String,
+    /// The predicate of a partial index, reported verbatim. `None` means the
+    /// index covers all rows.
+    pub where_clause: Option<String>,
+}
+
 /// Represents a connection or a transaction that can be queried.
 #[async_trait]
 pub trait Queryable: Send + Sync {
     /// Execute the given query.
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet>;
 
+    /// Render the given query to the SQL dialect of the connected database,
+    /// returning the statement and its parameters without executing
+    /// anything.
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)>;
+
     /// Execute a query given as SQL, interpolating the given parameters.
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet>;
 
@@ -49,6 +131,24 @@ pub trait Queryable: Send + Sync {
     /// NOTE: This method will eventually be removed & merged into Queryable::query_raw().
     async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64>;
 
+    /// Execute a multi-statement batch or a stored procedure call given as
+    /// SQL, returning one [`BatchResult`] per statement: the rows of a
+    /// statement returning data, or the affected row count of one that does
+    /// not.
+    ///
+    /// The coverage differs per connector. PostgreSQL uses the simple query
+    /// protocol, which returns every value as text and does not support bind
+    /// parameters. SQL Server reports only the statements returning rows.
+    /// MySQL appends the final `OK` of a stored procedure call as a count
+    /// item.
+    async fn query_multi_raw(&self, _sql: &str, _params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        let kind = crate::error::ErrorKind::UnsupportedOperation(
+            "query_multi_raw is not supported on this connector.".into(),
+        );
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
     /// Run a command in the database, for queries that can't be run using
     /// prepared statements.
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()>;
@@ -62,6 +162,26 @@ pub trait Queryable: Send + Sync {
     /// Returns false, if connection is considered to not be in a working state.
     fn is_healthy(&self) -> bool;
 
+    /// Whether the connection is currently inside a transaction, letting
+    /// code running both inside and outside of one adapt, e.g. skip a
+    /// redundant `SAVEPOINT`. Plain connections return `false`;
+    /// [`Transaction`] and [`OwnedTransaction`](super::OwnedTransaction)
+    /// return `true`.
+    fn in_transaction(&self) -> bool {
+        false
+    }
+
+    /// The features the connected database supports: the static
+    /// [`Capabilities`] of the dialect, refined with the server version where
+    /// the dialect gained features over time, e.g. `RETURNING` on SQLite 3.35
+    /// or newer.
+    async fn capabilities(&self) -> crate::Result<Capabilities> {
+        let kind =
+            crate::error::ErrorKind::UnsupportedOperation("capabilities is not supported on this connector.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
     /// Execute a `SELECT` query.
     async fn select(&self, q: Select<'_>) -> crate::Result<ResultSet> {
         self.query(q.into()).await
@@ -83,6 +203,170 @@ pub trait Queryable: Send + Sync {
         Ok(())
     }
 
+    /// Create an FTS5 full-text search virtual table. Errors with
+    /// `UnsupportedOperation` on connectors other than SQLite.
+    async fn create_fts_table(&self, q: CreateFts5Table<'_>) -> crate::Result<()> {
+        self.query(q.into()).await?;
+        Ok(())
+    }
+
+    /// Check whether the given `SELECT` matches any rows, without
+    /// materializing them. The query is wrapped in `SELECT 1 FROM (...)
+    /// LIMIT 1`, so at most a single constant value crosses the wire.
+    async fn exists(&self, select: Select<'_>) -> crate::Result<bool> {
+        let query = Select::from_table(Table::from(strip_ordering(select)).alias("q"))
+            .value(1)
+            .limit(1);
+
+        let result = self.query(query.into()).await?;
+
+        Ok(!result.is_empty())
+    }
+
+    /// Count the rows the given `SELECT` matches, without materializing them.
+    /// The query is wrapped in `SELECT COUNT(*) FROM (...)`, leaving any
+    /// grouping or distinct of the inner query intact.
+    async fn count(&self, select: Select<'_>) -> crate::Result<u64> {
+        let query = Select::from_table(Table::from(strip_ordering(select)).alias("q")).value(count(asterisk()));
+
+        let result = self.query(query.into()).await?;
+
+        let count = result
+            .into_single()?
+            .into_iter()
+            .next()
+            .and_then(|value| value.as_integer())
+            .unwrap_or(0);
+
+        Ok(count as u64)
+    }
+
+    /// Check whether a table with the given name exists, optionally
+    /// restricted to the given schema. Without a schema, the default schema
+    /// of the connection is searched. A schema that does not exist reports
+    /// `false` instead of an error.
+    async fn table_exists(&self, _table: &str, _schema: Option<&str>) -> crate::Result<bool> {
+        let kind =
+            crate::error::ErrorKind::UnsupportedOperation("table_exists is not supported on this connector.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// The names of the columns of the given table in ordinal order, taken
+    /// from the live schema of the default schema of the connection. An
+    /// unknown table reports an empty list.
+    async fn column_names(&self, table: &str) -> crate::Result<Vec<String>> {
+        Ok(self.column_info(table).await?.into_iter().map(|info| info.name).collect())
+    }
+
+    /// Name, data type, nullability and default value for every column of
+    /// the given table in ordinal order, taken from the live schema of the
+    /// default schema of the connection. An unknown table reports an empty
+    /// list.
+    async fn column_info(&self, _table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        let kind =
+            crate::error::ErrorKind::UnsupportedOperation("column_info is not supported on this connector.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// The foreign key constraints of the given table in the default schema
+    /// of the connection, with the columns of each constraint in constraint
+    /// order. An unknown table reports an empty list.
+    async fn foreign_keys(&self, _table: &str) -> crate::Result<Vec<ForeignKeyInfo>> {
+        let kind =
+            crate::error::ErrorKind::UnsupportedOperation("foreign_keys is not supported on this connector.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// The indexes of the given table in the default schema of the
+    /// connection, with the columns of each index in index order. An unknown
+    /// table reports an empty list.
+    async fn indexes(&self, _table: &str) -> crate::Result<Vec<IndexInfo>> {
+        let kind = crate::error::ErrorKind::UnsupportedOperation("indexes is not supported on this connector.".into());
+
+        Err(crate::error::Error::builder(kind).build())
+    }
+
+    /// Insert a single row and return the value of the given primary key
+    /// column, picking the mechanism of the connected database to get the
+    /// key of a fresh row.
+    ///
+    /// Databases with a `RETURNING` clause — and SQL Server with its
+    /// `OUTPUT` — send the column back with the insert, keeping the type of
+    /// the key intact. Without one, MySQL and older SQLite report the
+    /// generated integer key of the statement, so there the column must be
+    /// an auto-increment integer. Multi-row inserts error instead of
+    /// guessing which key to report.
+    async fn insert_returning(&self, insert: Insert<'_>, pk_column: &str) -> crate::Result<Value<'static>> {
+        ensure_single_row_insert(&insert)?;
+
+        if self.capabilities().await?.returning {
+            let result = self.insert(insert.returning(vec![pk_column])).await?;
+
+            return take_pk_column(result, pk_column);
+        }
+
+        let result = self.insert(insert).await?;
+
+        match result.last_insert_id() {
+            Some(id) => Ok(Value::int64(id as i64)),
+            None => {
+                let kind = crate::error::ErrorKind::UnsupportedOperation(
+                    "The connector did not report the key of the inserted row.".into(),
+                );
+
+                Err(crate::error::Error::builder(kind).build())
+            }
+        }
+    }
+
+    /// Fetch the row matching the given unique columns, inserting it first
+    /// when missing. Returns the winning row together with a flag telling
+    /// whether this call created it.
+    ///
+    /// Safe under concurrency: the insert ignores unique constraint
+    /// conflicts, so when two callers race for the same key, both get the
+    /// row the winner created and only the winner sees the created flag set.
+    async fn get_or_create(
+        &self,
+        table: Table<'_>,
+        unique_values: &[(Column<'_>, Value<'_>)],
+        insert_values: &[(Column<'_>, Value<'_>)],
+    ) -> crate::Result<(ResultRow, bool)> {
+        let find = {
+            let mut select = Select::from_table(table.clone());
+
+            for (column, value) in unique_values {
+                select = select.and_where(column.clone().equals(value.clone()));
+            }
+
+            select.limit(1)
+        };
+
+        if let Some(row) = self.query(find.clone().into()).await?.into_iter().next() {
+            return Ok((row, false));
+        }
+
+        // The unique index metadata is needed for databases handling the
+        // conflict with a `MERGE` statement.
+        let unique_columns: Vec<Column<'_>> = unique_values.iter().map(|(column, _)| column.clone()).collect();
+        let mut insert = Insert::single_into(table.add_unique_index(unique_columns));
+
+        for (column, value) in unique_values.iter().chain(insert_values.iter()) {
+            insert = insert.value(column.clone(), value.clone());
+        }
+
+        // An ignored conflict affects no rows, meaning another writer won
+        // the race and their row is the one to return.
+        let created = self.execute(insert.build().on_conflict(OnConflict::DoNothing).into()).await? > 0;
+
+        let row = self.query(find.into()).await?.into_single()?;
+
+        Ok((row, created))
+    }
+
     /// Execute an arbitrary function in the beginning of each transaction.
     async fn server_reset_query(&self, _: &Transaction<'_>) -> crate::Result<()> {
         Ok(())
@@ -98,6 +382,27 @@ pub trait Queryable: Send + Sync {
         "BEGIN"
     }
 
+    /// Wrap the connection so every statement running through the wrapper
+    /// carries the given tag as a leading `/* ... */` comment, making the
+    /// statements of a scope — e.g. a single request handler — easy to find
+    /// in `pg_stat_statements` or a slow query log.
+    fn with_tag<'a>(&'a self, tag: &str) -> TaggedQueryable<'a>
+    where
+        Self: Sized,
+    {
+        TaggedQueryable::new(self, tag)
+    }
+
+    /// Wrap the connection so every statement is recorded instead of
+    /// executed, for asserting on the exact SQL a workflow would run
+    /// without side effects. See [`DryRunConnector`].
+    fn with_dry_run(&self) -> DryRunConnector<'_>
+    where
+        Self: Sized,
+    {
+        DryRunConnector::new(self)
+    }
+
     /// Sets the transaction isolation level to given value.
     /// Implementers have to make sure that the passed isolation level is valid for the underlying database.
     async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()>;
@@ -117,6 +422,155 @@ where
         let opts = TransactionOptions::new(isolation, self.requires_isolation_first());
         Transaction::new(self, self.begin_statement(), opts).await
     }
+
+    /// Starts a new transaction with the given `BEGIN` statement instead of
+    /// the default of the connector, for syntax a plain `BEGIN` cannot
+    /// express, e.g. `BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY
+    /// DEFERRABLE` on PostgreSQL or `BEGIN IMMEDIATE` on SQLite.
+    ///
+    /// The statement runs verbatim, so it must never contain
+    /// user-controlled input. When a standard isolation level is all the
+    /// transaction needs, prefer [`start_transaction`] with an
+    /// [`IsolationLevel`] instead.
+    ///
+    /// [`start_transaction`]: Self::start_transaction
+    async fn begin_transaction_with_stmt(&self, stmt: &str) -> crate::Result<Transaction<'_>> {
+        let opts = TransactionOptions::new(None, self.requires_isolation_first());
+        Transaction::new(self, stmt, opts).await
+    }
+}
+
+/// Refuses an insert of more than one row, where reporting a single primary
+/// key would have to guess which row the caller meant.
+pub(crate) fn ensure_single_row_insert(insert: &Insert<'_>) -> crate::Result<()> {
+    if let ExpressionKind::Values(values) = &insert.values.kind {
+        if values.rows.len() > 1 {
+            let kind = crate::error::ErrorKind::UnsupportedOperation(
+                "insert_returning supports only single-row inserts.".into(),
+            );
+
+            return Err(crate::error::Error::builder(kind).build());
+        }
+    }
+
+    Ok(())
+}
+
+/// Takes the value of the primary key column out of the single row an insert
+/// with a returning clause sends back.
+pub(crate) fn take_pk_column(result: ResultSet, pk_column: &str) -> crate::Result<Value<'static>> {
+    let last_insert_id = result.last_insert_id();
+    let row = result.into_single()?;
+
+    if let Some(value) = row.get(pk_column) {
+        return Ok(value.clone());
+    }
+
+    // The insert requested only the key column, so a value the driver did
+    // not name is still the key. SQLite reports no column metadata at all
+    // for a `RETURNING` clause on a temporary table — there the row comes
+    // back empty and the generated key of the statement is reported
+    // instead.
+    if let Some(value) = row.into_iter().next() {
+        return Ok(value);
+    }
+
+    if let Some(id) = last_insert_id {
+        return Ok(Value::int64(id as i64));
+    }
+
+    let kind = crate::error::ErrorKind::ColumnNotFound {
+        column: crate::error::Name::available(pk_column),
+    };
+
+    Err(crate::error::Error::builder(kind).build())
+}
+
+/// Drops the ordering of a query used as a derived table in an existence or
+/// count check. The order changes nothing in the result there, and SQL Server
+/// rejects `ORDER BY` in a derived table outright — unless a limit or offset
+/// depends on it, in which case it has to stay.
+fn strip_ordering(mut select: Select<'_>) -> Select<'_> {
+    if select.limit.is_none() && select.offset.is_none() {
+        select.ordering = Ordering::default();
+    }
+
+    select
+}
+
+/// Compares the leading `major.minor.patch` of a server version string
+/// against the wanted minimum. Non-digit labels such as `-MariaDB` are
+/// skipped, missing segments count as zero.
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+pub(crate) fn version_at_least(version: &str, major: u64, minor: u64, patch: u64) -> bool {
+    let mut segments = version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.parse::<u64>().unwrap_or(0));
+
+    let found = (
+        segments.next().unwrap_or(0),
+        segments.next().unwrap_or(0),
+        segments.next().unwrap_or(0),
+    );
+
+    found >= (major, minor, patch)
+}
+
+/// Groups rows of the shape `(constraint name, column, referenced table,
+/// referenced column, on delete, on update)` into one `ForeignKeyInfo` per
+/// constraint. The rows have to be ordered by constraint name and column
+/// position, which the introspection queries of the connectors guarantee.
+pub(crate) fn foreign_keys_from_rows(result: ResultSet) -> Vec<ForeignKeyInfo> {
+    let mut keys: Vec<ForeignKeyInfo> = Vec::new();
+
+    for row in result {
+        let constraint_name = row[0].to_string().unwrap_or_default();
+
+        match keys.last_mut() {
+            Some(last) if last.constraint_name == constraint_name => {
+                last.columns.push(row[1].to_string().unwrap_or_default());
+                last.referenced_columns.push(row[3].to_string().unwrap_or_default());
+            }
+            _ => keys.push(ForeignKeyInfo {
+                constraint_name,
+                columns: vec![row[1].to_string().unwrap_or_default()],
+                referenced_table: row[2].to_string().unwrap_or_default(),
+                referenced_columns: vec![row[3].to_string().unwrap_or_default()],
+                on_delete: row[4].to_string().unwrap_or_default(),
+                on_update: row[5].to_string().unwrap_or_default(),
+            }),
+        }
+    }
+
+    keys
+}
+
+/// Groups rows of the shape `(index name, column, unique flag, index type,
+/// partial predicate)` into one `IndexInfo` per index. The rows have to be
+/// ordered by index name and column position, which the introspection
+/// queries of the connectors guarantee.
+pub(crate) fn indexes_from_rows(result: ResultSet) -> Vec<IndexInfo> {
+    let mut indexes: Vec<IndexInfo> = Vec::new();
+
+    for row in result {
+        let name = row[0].to_string().unwrap_or_default();
+
+        match indexes.last_mut() {
+            Some(last) if last.name == name => {
+                last.columns.push(row[1].to_string().unwrap_or_default());
+            }
+            _ => indexes.push(IndexInfo {
+                name,
+                columns: vec![row[1].to_string().unwrap_or_default()],
+                is_unique: row[2].as_bool().unwrap_or(false),
+                index_type: row[3].to_string().unwrap_or_default(),
+                where_clause: row[4].to_string(),
+            }),
+        }
+    }
+
+    indexes
 }
 
 pub async fn start_owned_transaction(queryable: Arc<dyn Queryable>, isolation: Option<IsolationLevel>) -> crate::Result<OwnedTransaction> {
@@ -124,3 +578,19 @@ pub async fn start_owned_transaction(queryable: Arc<dyn Queryable>, isolation: O
     OwnedTransaction::new(queryable.clone(), queryable.begin_statement(), opts).await
 }
 
+
+#[cfg(test)]
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+mod tests {
+    use super::version_at_least;
+
+    #[test]
+    fn version_at_least_compares_the_leading_segments() {
+        assert!(version_at_least("3.39.2", 3, 35, 0));
+        assert!(version_at_least("8.0.14-log", 8, 0, 14));
+        assert!(!version_at_least("8.0.13", 8, 0, 14));
+        assert!(!version_at_least("5.5.5-10.6.12-MariaDB", 8, 0, 0));
+        assert!(version_at_least("3.35", 3, 35, 0));
+        assert!(!version_at_least("", 3, 35, 0));
+    }
+}
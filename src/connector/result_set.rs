@@ -10,6 +10,35 @@ use std::sync::Arc;
 #[cfg(feature = "json")]
 use serde_json::Map;
 
+/// The result of a single statement in a multi-statement batch or a stored
+/// procedure call, as returned by
+/// [`query_multi_raw`](super::Queryable::query_multi_raw).
+#[derive(Debug)]
+pub enum BatchResult {
+    /// The rows and column names of a statement returning data.
+    ResultSet(ResultSet),
+    /// The number of rows affected by a statement returning no data.
+    Count(u64),
+}
+
+impl BatchResult {
+    /// The rows of the statement, if it returned data.
+    pub fn as_result_set(&self) -> Option<&ResultSet> {
+        match self {
+            Self::ResultSet(result_set) => Some(result_set),
+            Self::Count(_) => None,
+        }
+    }
+
+    /// The affected row count of the statement, if it returned no data.
+    pub fn as_count(&self) -> Option<u64> {
+        match self {
+            Self::ResultSet(_) => None,
+            Self::Count(count) => Some(*count),
+        }
+    }
+}
+
 /// Encapsulates a set of results and their respective column names.
 #[derive(Debug, Default)]
 pub struct ResultSet {
@@ -74,6 +103,26 @@ impl ResultSet {
             None => Err(Error::builder(ErrorKind::NotFound).build()),
         }
     }
+
+    /// Converts the result set into a JSON array with one object per row,
+    /// keyed by the column names. Bytes are encoded as base64 strings,
+    /// timestamps as RFC 3339 strings and decimals as strings to keep their
+    /// precision.
+    ///
+    /// ```
+    /// # use quaint::{ast::Value, connector::*};
+    /// # use serde_json::json;
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![vec![Value::int32(1), "Musti".into()]];
+    ///
+    /// let result_set = ResultSet::new(names, rows);
+    ///
+    /// assert_eq!(json!([{"id": 1, "name": "Musti"}]), result_set.to_json());
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(self) -> serde_json::Value {
+        serde_json::Value::from(self)
+    }
 }
 
 impl IntoIterator for ResultSet {
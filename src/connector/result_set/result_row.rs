@@ -1,8 +1,8 @@
 use crate::{
     ast::Value,
-    error::{Error, ErrorKind},
+    error::{Error, ErrorKind, Name},
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// An owned version of a `Row` in a `ResultSet`. See
 /// [ResultRowRef](struct.ResultRowRef.html) for documentation on data access.
@@ -73,6 +73,92 @@ impl ResultRow {
             None => Err(Error::builder(ErrorKind::NotFound).build()),
         }
     }
+
+    /// Takes the value with the given column name from the row, converted
+    /// into the requested type. A missing column, a `NULL` value or a value
+    /// of the wrong type errors with the reason; use [`try_get_opt`] for a
+    /// nullable column.
+    ///
+    /// ```
+    /// # use quaint::connector::*;
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![vec![1234.into(), "Musti".into()]];
+    ///
+    /// let result_set = ResultSet::new(names, rows);
+    /// let row = result_set.into_single().unwrap();
+    ///
+    /// assert_eq!(1234, row.try_get::<i64>("id").unwrap());
+    /// assert_eq!("Musti", row.try_get::<String>("name").unwrap());
+    /// assert!(row.try_get::<i64>("name").is_err());
+    /// ```
+    ///
+    /// [`try_get_opt`]: Self::try_get_opt
+    pub fn try_get<T>(&self, name: &str) -> crate::Result<T>
+    where
+        T: for<'b> TryFrom<&'b Value<'static>, Error = Error>,
+    {
+        match self.get(name) {
+            Some(value) => T::try_from(value),
+            None => {
+                let kind = ErrorKind::ColumnNotFound {
+                    column: Name::available(name),
+                };
+
+                Err(Error::builder(kind).build())
+            }
+        }
+    }
+
+    /// Takes the value with the given column name from the row like
+    /// [`try_get`], reporting a `NULL` value as `None` instead of an error.
+    ///
+    /// ```
+    /// # use quaint::{ast::Value, connector::*};
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![vec![1234.into(), Value::Text(None)]];
+    ///
+    /// let result_set = ResultSet::new(names, rows);
+    /// let row = result_set.into_single().unwrap();
+    ///
+    /// assert_eq!(Some(1234), row.try_get_opt::<i64>("id").unwrap());
+    /// assert_eq!(None, row.try_get_opt::<String>("name").unwrap());
+    /// ```
+    ///
+    /// [`try_get`]: Self::try_get
+    pub fn try_get_opt<T>(&self, name: &str) -> crate::Result<Option<T>>
+    where
+        T: for<'b> TryFrom<&'b Value<'static>, Error = Error>,
+    {
+        match self.get(name) {
+            Some(value) if value.is_null() => Ok(None),
+            Some(value) => Ok(Some(T::try_from(value)?)),
+            None => {
+                let kind = ErrorKind::ColumnNotFound {
+                    column: Name::available(name),
+                };
+
+                Err(Error::builder(kind).build())
+            }
+        }
+    }
+
+    /// Converts the row into a map from column name to value. When the query
+    /// returns the same column name more than once, the last value wins;
+    /// access the row by position to get the earlier ones.
+    ///
+    /// ```
+    /// # use quaint::{ast::Value, connector::*};
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![vec!["1234".into(), "Musti".into()]];
+    ///
+    /// let result_set = ResultSet::new(names, rows);
+    /// let row = result_set.into_single().unwrap();
+    ///
+    /// assert_eq!(Some(&Value::from("Musti")), row.into_map().get("name"));
+    /// ```
+    pub fn into_map(self) -> HashMap<String, Value<'static>> {
+        self.columns.iter().cloned().zip(self.values).collect()
+    }
 }
 
 impl<'a> ResultRowRef<'a> {
@@ -6,12 +6,12 @@ pub use rusqlite::{params_from_iter, version as sqlite_version};
 use super::IsolationLevel;
 use crate::{
     ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet},
+    connector::{metrics, queryable::*, BatchResult, ResultSet},
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
 use async_trait::async_trait;
-use std::{convert::TryFrom, path::Path, time::Duration};
+use std::{convert::TryFrom, path::Path, str::FromStr, time::Duration};
 use tokio::sync::Mutex;
 
 pub(crate) const DEFAULT_SQLITE_SCHEMA_NAME: &str = "main";
@@ -23,6 +23,16 @@ pub use rusqlite;
 /// A connector interface for the SQLite database
 pub struct Sqlite {
     pub(crate) client: Mutex<rusqlite::Connection>,
+    /// Whether timezone-less datetime values decode as UTC instead of as
+    /// naive datetimes.
+    assume_utc: bool,
+    /// The comment prepended to every statement, tagging the statements of
+    /// this connection in the logs.
+    query_comment: Option<String>,
+    /// How `BEGIN` acquires locks; `None` leaves the SQLite default of
+    /// deferred locking.
+    transaction_mode: Option<TransactionMode>,
+    span_info: metrics::SpanInfo,
 }
 
 /// Wraps a connection url and exposes the parsing logic used by Quaint,
@@ -37,6 +47,95 @@ pub struct SqliteParams {
     pub socket_timeout: Option<Duration>,
     pub max_connection_lifetime: Option<Duration>,
     pub max_idle_connection_lifetime: Option<Duration>,
+    pub slow_query_threshold: Option<Duration>,
+    pub journal_mode: Option<JournalMode>,
+    pub transaction_mode: Option<TransactionMode>,
+    pub assume_utc: bool,
+    pub query_comment: Option<String>,
+}
+
+/// The journal mode of a SQLite database, set with `PRAGMA journal_mode`
+/// during connection setup. `Wal` gives dramatically better concurrent read
+/// performance than the default rollback journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalMode {
+    Delete,
+    Wal,
+    Memory,
+    Off,
+}
+
+impl JournalMode {
+    /// The value as used in the `PRAGMA journal_mode` statement.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delete => "DELETE",
+            Self::Wal => "WAL",
+            Self::Memory => "MEMORY",
+            Self::Off => "OFF",
+        }
+    }
+}
+
+impl FromStr for JournalMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "delete" => Ok(Self::Delete),
+            "wal" => Ok(Self::Wal),
+            "memory" => Ok(Self::Memory),
+            "off" => Ok(Self::Off),
+            _ => Err(Error::builder(ErrorKind::InvalidConnectionArguments).build()),
+        }
+    }
+}
+
+/// How `BEGIN` acquires locks, set with the `transaction_mode` connection
+/// string parameter.
+///
+/// The SQLite default, `Deferred`, takes no lock until the first statement
+/// needing one. A transaction reading first and writing later then upgrades
+/// its lock mid-flight, which fails with `SQLITE_BUSY` under write
+/// contention - after work was already done. `Immediate` takes the write
+/// lock at `BEGIN` instead, moving the failure to the start of the
+/// transaction where nothing has to be unwound, and `Exclusive` additionally
+/// blocks new readers.
+///
+/// A busy `BEGIN IMMEDIATE` is retried by SQLite itself for up to the
+/// configured busy timeout (the `socket_timeout` parameter) and then
+/// surfaces as an error classified as transient by
+/// [`Error::is_transient`](crate::error::Error::is_transient), signalling
+/// the caller to retry the whole transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionMode {
+    Deferred,
+    Immediate,
+    Exclusive,
+}
+
+impl TransactionMode {
+    /// The `BEGIN` statement starting a transaction in this mode.
+    pub fn begin_statement(&self) -> &'static str {
+        match self {
+            Self::Deferred => "BEGIN DEFERRED",
+            Self::Immediate => "BEGIN IMMEDIATE",
+            Self::Exclusive => "BEGIN EXCLUSIVE",
+        }
+    }
+}
+
+impl FromStr for TransactionMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "deferred" => Ok(Self::Deferred),
+            "immediate" => Ok(Self::Immediate),
+            "exclusive" => Ok(Self::Exclusive),
+            _ => Err(Error::builder(ErrorKind::InvalidConnectionArguments).build()),
+        }
+    }
 }
 
 impl TryFrom<&str> for SqliteParams {
@@ -60,12 +159,16 @@ impl TryFrom<&str> for SqliteParams {
             let mut socket_timeout = None;
             let mut max_connection_lifetime = None;
             let mut max_idle_connection_lifetime = None;
+            let mut slow_query_threshold = None;
+            let mut journal_mode = None;
+            let mut transaction_mode = None;
+            let mut assume_utc = false;
+            let mut query_comment = None;
 
             if path_parts.len() > 1 {
-                let params = path_parts.last().unwrap().split('&').map(|kv| {
-                    let splitted: Vec<&str> = kv.split('=').collect();
-                    (splitted[0], splitted[1])
-                });
+                // A malformed pair without a `=` carries no value we could
+                // use, so it is skipped instead of crashing the parser.
+                let params = path_parts.last().unwrap().split('&').filter_map(|kv| kv.split_once('='));
 
                 for (k, v) in params {
                     match k {
@@ -105,6 +208,31 @@ impl TryFrom<&str> for SqliteParams {
                                 max_idle_connection_lifetime = Some(Duration::from_secs(as_int));
                             }
                         }
+                        "slow_threshold" => {
+                            let as_int: u64 = v
+                                .parse()
+                                .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                            if as_int == 0 {
+                                slow_query_threshold = None;
+                            } else {
+                                slow_query_threshold = Some(Duration::from_millis(as_int));
+                            }
+                        }
+                        "journal_mode" => {
+                            journal_mode = Some(JournalMode::from_str(v)?);
+                        }
+                        "transaction_mode" => {
+                            transaction_mode = Some(TransactionMode::from_str(v)?);
+                        }
+                        "query_comment" => {
+                            query_comment = Some(v.to_string());
+                        }
+                        "assume_utc" => {
+                            assume_utc = v
+                                .parse::<bool>()
+                                .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+                        }
                         _ => {
                             tracing::trace!(message = "Discarding connection string param", param = k);
                         }
@@ -119,6 +247,11 @@ impl TryFrom<&str> for SqliteParams {
                 socket_timeout,
                 max_connection_lifetime,
                 max_idle_connection_lifetime,
+                slow_query_threshold,
+                journal_mode,
+                transaction_mode,
+                assume_utc,
+                query_comment,
             })
         }
     }
@@ -134,6 +267,11 @@ impl TryFrom<&str> for Sqlite {
         }
 
         let params = SqliteParams::try_from(path)?;
+
+        if let Some(threshold) = params.slow_query_threshold {
+            super::metrics::set_slow_query_threshold(Some(threshold));
+        }
+
         let file_path = params.file_path;
 
         let conn = rusqlite::Connection::open(file_path.as_str())?;
@@ -142,9 +280,39 @@ impl TryFrom<&str> for Sqlite {
             conn.busy_timeout(timeout)?;
         };
 
+        if let Some(mode) = params.journal_mode {
+            // The pragma returns the mode now in use, which we can discard
+            // here, but `pragma_update` would error on the returned row.
+            conn.pragma_update_and_check(None, "journal_mode", &mode.as_str(), |_| Ok(()))?;
+        }
+
         let client = Mutex::new(conn);
 
-        Ok(Sqlite { client })
+        super::events::connection_opened("sqlite");
+
+        Ok(Sqlite {
+            client,
+            assume_utc: params.assume_utc,
+            query_comment: params.query_comment,
+            transaction_mode: params.transaction_mode,
+            span_info: metrics::SpanInfo {
+                system: "sqlite",
+                db_name: file_path,
+                peer_name: None,
+                peer_port: None,
+            },
+        })
+    }
+}
+
+impl Drop for Sqlite {
+    fn drop(&mut self) {
+        // `PRAGMA optimize` is recommended by SQLite before closing a
+        // connection, analyzing the tables the connection queried. A failure
+        // only loses the query planner statistics, so the result is ignored.
+        if let Ok(client) = self.client.try_lock() {
+            let _ = client.execute_batch("PRAGMA optimize;");
+        }
     }
 }
 
@@ -157,11 +325,47 @@ impl Sqlite {
     pub fn new_in_memory() -> crate::Result<Sqlite> {
         let client = rusqlite::Connection::open_in_memory()?;
 
+        super::events::connection_opened("sqlite");
+
         Ok(Sqlite {
             client: Mutex::new(client),
+            assume_utc: false,
+            query_comment: None,
+            transaction_mode: None,
+            span_info: metrics::SpanInfo {
+                system: "sqlite",
+                db_name: ":memory:".into(),
+                peer_name: None,
+                peer_port: None,
+            },
         })
     }
 
+    /// Attaches another database file to the connection under the given
+    /// alias. Tables in the attached database are referenced with a
+    /// database-qualified table, e.g. `Table::from(("alias", "users"))`,
+    /// allowing queries to span multiple files.
+    pub async fn attach_database(&self, path: &Path, alias: &str) -> crate::Result<()> {
+        let path = path.to_str().ok_or_else(|| {
+            Error::builder(ErrorKind::conversion("The path of an attached database must be UTF-8")).build()
+        })?;
+
+        let client = self.client.lock().await;
+        client.execute("ATTACH DATABASE ? AS ?", [path, alias])?;
+
+        Ok(())
+    }
+
+    /// Detaches a database previously attached with [`attach_database`].
+    ///
+    /// [`attach_database`]: #method.attach_database
+    pub async fn detach_database(&self, alias: &str) -> crate::Result<()> {
+        let client = self.client.lock().await;
+        client.execute("DETACH DATABASE ?", [alias])?;
+
+        Ok(())
+    }
+
     /// The underlying rusqlite::Connection. Only available with the `expose-drivers` Cargo
     /// feature. This is a lower level API when you need to get into database specific features.
     #[cfg(feature = "expose-drivers")]
@@ -175,12 +379,19 @@ impl TransactionCapable for Sqlite {}
 #[async_trait]
 impl Queryable for Sqlite {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
-        let (sql, params) = visitor::Sqlite::build(q)?;
+        let (sql, params) = self.render(q)?;
         self.query_raw(&sql, &params).await
     }
 
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        visitor::Sqlite::build(q)
+    }
+
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
-        metrics::query("sqlite.query_raw", sql, params, move || async move {
+        let sql = super::tag::with_default_comment(sql, self.query_comment.as_deref());
+        let sql = sql.as_ref();
+
+        metrics::query("sqlite.query_raw", &self.span_info, sql, params, move || async move {
             let client = self.client.lock().await;
 
             let mut stmt = client.prepare_cached(sql)?;
@@ -189,7 +400,13 @@ impl Queryable for Sqlite {
             let mut result = ResultSet::new(rows.to_column_names(), Vec::new());
 
             while let Some(row) = rows.next()? {
-                result.rows.push(row.get_result_row()?);
+                let mut values = row.get_result_row()?;
+
+                if self.assume_utc {
+                    super::queryable::assume_utc_values(&mut values);
+                }
+
+                result.rows.push(values);
             }
 
             result.set_last_insert_id(u64::try_from(client.last_insert_rowid()).unwrap_or(0));
@@ -203,13 +420,58 @@ impl Queryable for Sqlite {
         self.query_raw(sql, params).await
     }
 
+    /// SQLite has no native multi-statement protocol, so the statements run
+    /// sequentially. Each statement binds the leading parameters it declares
+    /// from the given list.
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        let sql = super::tag::with_default_comment(sql, self.query_comment.as_deref());
+        let sql = sql.as_ref();
+
+        metrics::query("sqlite.query_multi_raw", &self.span_info, sql, params, move || async move {
+            let client = self.client.lock().await;
+            let mut results = Vec::new();
+
+            for statement in split_statements(sql) {
+                let mut stmt = client.prepare_cached(statement)?;
+                let wanted = stmt.parameter_count();
+
+                if stmt.column_count() > 0 {
+                    let mut rows = stmt.query(params_from_iter(params.iter().take(wanted)))?;
+                    let mut result = ResultSet::new(rows.to_column_names(), Vec::new());
+
+                    while let Some(row) = rows.next()? {
+                        let mut values = row.get_result_row()?;
+
+                        if self.assume_utc {
+                            super::queryable::assume_utc_values(&mut values);
+                        }
+
+                        result.rows.push(values);
+                    }
+
+                    results.push(BatchResult::ResultSet(result));
+                } else {
+                    let changes = u64::try_from(stmt.execute(params_from_iter(params.iter().take(wanted)))?)?;
+
+                    results.push(BatchResult::Count(changes));
+                }
+            }
+
+            Ok(results)
+        })
+        .await
+    }
+
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
-        let (sql, params) = visitor::Sqlite::build(q)?;
+        let (sql, params) = self.render(q)?;
         self.execute_raw(&sql, &params).await
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
-        metrics::query("sqlite.query_raw", sql, params, move || async move {
+        let sql = super::tag::with_default_comment(sql, self.query_comment.as_deref());
+        let sql = sql.as_ref();
+
+        metrics::query("sqlite.query_raw", &self.span_info, sql, params, move || async move {
             let client = self.client.lock().await;
             let mut stmt = client.prepare_cached(sql)?;
             let res = u64::try_from(stmt.execute(params_from_iter(params.iter()))?)?;
@@ -224,7 +486,10 @@ impl Queryable for Sqlite {
     }
 
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
-        metrics::query("sqlite.raw_cmd", cmd, &[], move || async move {
+        let cmd = super::tag::with_default_comment(cmd, self.query_comment.as_deref());
+        let cmd = cmd.as_ref();
+
+        metrics::query("sqlite.raw_cmd", &self.span_info, cmd, &[], move || async move {
             let client = self.client.lock().await;
             client.execute_batch(cmd)?;
             Ok(())
@@ -236,6 +501,125 @@ impl Queryable for Sqlite {
         Ok(Some(rusqlite::version().into()))
     }
 
+    fn begin_statement(&self) -> &'static str {
+        match self.transaction_mode {
+            Some(mode) => mode.begin_statement(),
+            None => "BEGIN",
+        }
+    }
+
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        Ok(crate::visitor::Capabilities {
+            // `INSERT .. RETURNING` landed in SQLite 3.35.
+            returning: super::queryable::version_at_least(rusqlite::version(), 3, 35, 0),
+            ..crate::visitor::Sqlite::CAPABILITIES
+        })
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        let schema = schema.unwrap_or("main");
+
+        // An unknown schema name would make the `sqlite_master` query fail,
+        // so a missing attached database reports `false` up front.
+        let attached = self
+            .query_raw("SELECT 1 FROM pragma_database_list WHERE name = ?", &[Value::text(schema)])
+            .await?;
+
+        if attached.is_empty() {
+            return Ok(false);
+        }
+
+        let query = format!(
+            "SELECT 1 FROM \"{}\".sqlite_master WHERE type = 'table' AND name = ?",
+            schema.replace('"', "\"\"")
+        );
+
+        let result = self.query_raw(&query, &[Value::text(table)]).await?;
+
+        Ok(!result.is_empty())
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        let query = "SELECT name, type, \"notnull\", dflt_value FROM pragma_table_info(?)";
+
+        let result = self.query_raw(query, &[Value::text(table)]).await?;
+        let mut columns = Vec::with_capacity(result.len());
+
+        for row in result {
+            columns.push(ColumnInfo {
+                name: row[0].to_string().unwrap_or_default(),
+                data_type: row[1].to_string().unwrap_or_default(),
+                is_nullable: row[2].as_integer().map(|notnull| notnull == 0).unwrap_or(true),
+                default: row[3].to_string(),
+            });
+        }
+
+        Ok(columns)
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<ForeignKeyInfo>> {
+        // SQLite has no names for foreign keys, so the numeric id of the
+        // pragma stands in for the constraint name.
+        let query = "SELECT CAST(id AS TEXT), \"from\", \"table\", \"to\", on_delete, on_update FROM pragma_foreign_key_list(?) ORDER BY id, seq";
+
+        let result = self.query_raw(query, &[Value::text(table)]).await?;
+
+        Ok(crate::connector::queryable::foreign_keys_from_rows(result))
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<IndexInfo>> {
+        let list = self
+            .query_raw(
+                "SELECT name, \"unique\", partial FROM pragma_index_list(?) ORDER BY seq",
+                &[Value::text(table)],
+            )
+            .await?;
+
+        let mut indexes = Vec::with_capacity(list.len());
+
+        for row in list {
+            let name = row[0].to_string().unwrap_or_default();
+
+            let columns = self
+                .query_raw("SELECT name FROM pragma_index_info(?) ORDER BY seqno", &[Value::text(&name)])
+                .await?
+                .into_iter()
+                .filter_map(|row| row[0].to_string())
+                .collect();
+
+            // The pragmas only flag an index as partial; the predicate text
+            // has to come out of the `CREATE INDEX` statement itself.
+            let where_clause = if row[2].as_bool().unwrap_or(false) {
+                self.query_raw(
+                    "SELECT sql FROM sqlite_master WHERE type = 'index' AND name = ?",
+                    &[Value::text(&name)],
+                )
+                .await?
+                .into_iter()
+                .next()
+                .and_then(|row| row[0].to_string())
+                .and_then(|sql| {
+                    sql.to_uppercase()
+                        .rfind(" WHERE ")
+                        .map(|pos| sql[pos + " WHERE ".len()..].trim().to_string())
+                })
+            } else {
+                None
+            };
+
+            indexes.push(IndexInfo {
+                name,
+                columns,
+                is_unique: row[1].as_bool().unwrap_or(false),
+                // SQLite only has one index access method.
+                index_type: "btree".into(),
+                where_clause,
+            });
+        }
+
+        Ok(indexes)
+    }
+
     fn is_healthy(&self) -> bool {
         true
     }
@@ -257,6 +641,31 @@ impl Queryable for Sqlite {
     }
 }
 
+/// Splits a multi-statement string on `;`, skipping separators inside string
+/// literals and quoted identifiers.
+pub(crate) fn split_statements(sql: &str) -> impl Iterator<Item = &str> {
+    let mut statements = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in sql.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+            None if c == ';' => {
+                statements.push(&sql[start..i]);
+                start = i + 1;
+            }
+            None => (),
+        }
+    }
+
+    statements.push(&sql[start..]);
+
+    statements.into_iter().map(str::trim).filter(|s| !s.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +696,359 @@ mod tests {
         assert_eq!(params.file_path, "dev.db");
     }
 
+    #[test]
+    fn sqlite_params_from_str_should_parse_journal_mode() {
+        let path = "file:dev.db?journal_mode=wal";
+        let params = SqliteParams::try_from(path).unwrap();
+        assert_eq!(params.journal_mode, Some(JournalMode::Wal));
+
+        let path = "file:dev.db?journal_mode=nope";
+        assert!(SqliteParams::try_from(path).is_err());
+    }
+
+    #[test]
+    fn sqlite_params_from_str_should_parse_assume_utc() {
+        let path = "file:dev.db?assume_utc=true";
+        let params = SqliteParams::try_from(path).unwrap();
+        assert!(params.assume_utc);
+
+        let path = "file:dev.db";
+        let params = SqliteParams::try_from(path).unwrap();
+        assert!(!params.assume_utc);
+
+        let path = "file:dev.db?assume_utc=nope";
+        assert!(SqliteParams::try_from(path).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn assume_utc_decodes_datetime_columns_as_utc() {
+        let file_path = std::env::temp_dir().join("quaint_assume_utc_test.db");
+        let file_path = file_path.to_str().unwrap();
+        let _ = std::fs::remove_file(file_path);
+
+        let conn = Sqlite::new(file_path).unwrap();
+
+        conn.raw_cmd("CREATE TABLE assume_utc_test (id int, at datetime)")
+            .await
+            .unwrap();
+
+        // 02:30 does not exist in Central Europe on this date; stored
+        // datetimes are plain strings to SQLite, so the value round-trips
+        // regardless.
+        conn.raw_cmd("INSERT INTO assume_utc_test VALUES (1, '2023-03-26 02:30:00')")
+            .await
+            .unwrap();
+
+        let naive = chrono::NaiveDate::from_ymd_opt(2023, 3, 26)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+
+        let row = conn
+            .query_raw("SELECT at FROM assume_utc_test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(Value::datetime(naive), row[0]);
+
+        let conn = Sqlite::new(&format!("{file_path}?assume_utc=true")).unwrap();
+
+        let row = conn
+            .query_raw("SELECT at FROM assume_utc_test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(
+            Value::datetime_utc(chrono::DateTime::from_utc(naive, chrono::Utc)),
+            row[0]
+        );
+
+        let _ = std::fs::remove_file(file_path);
+    }
+
+    #[test]
+    fn connection_opened_callback_is_triggered() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static OPENED: AtomicUsize = AtomicUsize::new(0);
+
+        crate::connector::set_connection_event_callbacks(crate::connector::ConnectionEvents::default().on_opened(
+            |event| {
+                assert!(event.connection_id > 0);
+
+                if event.connector == "sqlite" {
+                    OPENED.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        ));
+
+        let before = OPENED.load(Ordering::SeqCst);
+        let _conn = Sqlite::new_in_memory().unwrap();
+
+        assert!(OPENED.load(Ordering::SeqCst) > before);
+    }
+
+    #[test]
+    fn split_statements_respects_quoting() {
+        let sql = "SELECT 'a;b'; UPDATE t SET name = \"x;y\" WHERE id = 1;";
+        let statements: Vec<&str> = split_statements(sql).collect();
+
+        assert_eq!(vec!["SELECT 'a;b'", "UPDATE t SET name = \"x;y\" WHERE id = 1"], statements);
+    }
+
+    #[tokio::test]
+    async fn query_multi_raw_returns_rows_and_counts() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE multi_test (id INTEGER, name TEXT)")
+            .await
+            .unwrap();
+
+        let sql = "INSERT INTO multi_test (id, name) VALUES (1, 'Musti'), (2, 'Naukio'); SELECT id, name FROM multi_test ORDER BY id; DELETE FROM multi_test";
+        let results = conn.query_multi_raw(sql, &[]).await.unwrap();
+
+        assert_eq!(3, results.len());
+        assert_eq!(Some(2), results[0].as_count());
+
+        let rows = results[1].as_result_set().unwrap();
+
+        assert_eq!(&vec!["id".to_string(), "name".to_string()], rows.columns());
+        assert_eq!(2, rows.len());
+
+        assert_eq!(Some(2), results[2].as_count());
+    }
+
+    #[tokio::test]
+    async fn capabilities_refine_returning_with_the_version() {
+        let conn = Sqlite::new_in_memory().unwrap();
+        let caps = conn.capabilities().await.unwrap();
+
+        assert_eq!(
+            crate::connector::queryable::version_at_least(rusqlite::version(), 3, 35, 0),
+            caps.returning
+        );
+
+        assert!(caps.row_values);
+        assert!(caps.upsert_where);
+        assert!(!caps.lateral);
+        assert!(!caps.distinct_on);
+    }
+
+    #[tokio::test]
+    async fn owned_transaction_reports_its_state() {
+        use crate::connector::start_owned_transaction;
+        use std::sync::Arc;
+
+        let conn = Arc::new(Sqlite::new_in_memory().unwrap());
+
+        let tx = start_owned_transaction(conn.clone(), None).await.unwrap();
+
+        assert!(tx.is_pending());
+        assert!(!tx.is_committed());
+        assert!(!tx.is_rolled_back());
+
+        tx.commit().await.unwrap();
+
+        assert!(!tx.is_pending());
+        assert!(tx.is_committed());
+
+        let err = tx.raw_cmd("SELECT 1").await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionAlreadyClosed(_)));
+
+        let err = tx.commit().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionAlreadyClosed(_)));
+
+        let tx = start_owned_transaction(conn, None).await.unwrap();
+        tx.rollback().await.unwrap();
+
+        assert!(tx.is_rolled_back());
+        assert!(!tx.is_committed());
+    }
+
+    #[tokio::test]
+    async fn table_exists_checks_sqlite_master() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE exists_test (id INTEGER)").await.unwrap();
+
+        assert!(conn.table_exists("exists_test", None).await.unwrap());
+        assert!(conn.table_exists("exists_test", Some("main")).await.unwrap());
+        assert!(!conn.table_exists("missing", None).await.unwrap());
+        assert!(!conn.table_exists("exists_test", Some("missing_schema")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn column_info_reads_the_table_schema() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE column_info_test (id INTEGER NOT NULL, name TEXT, score REAL DEFAULT 1.5)")
+            .await
+            .unwrap();
+
+        assert_eq!(vec!["id", "name", "score"], conn.column_names("column_info_test").await.unwrap());
+
+        let columns = conn.column_info("column_info_test").await.unwrap();
+
+        assert_eq!("INTEGER", &columns[0].data_type);
+        assert!(!columns[0].is_nullable);
+        assert_eq!(None, columns[0].default);
+
+        assert_eq!("TEXT", &columns[1].data_type);
+        assert!(columns[1].is_nullable);
+
+        assert_eq!(Some("1.5".to_string()), columns[2].default);
+
+        assert!(conn.column_info("missing").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn foreign_keys_reads_the_foreign_key_list() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE fk_parent (id INTEGER PRIMARY KEY)").await.unwrap();
+        conn.raw_cmd(
+            "CREATE TABLE fk_child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES fk_parent (id) ON DELETE CASCADE)",
+        )
+        .await
+        .unwrap();
+
+        let keys = conn.foreign_keys("fk_child").await.unwrap();
+
+        assert_eq!(1, keys.len());
+        assert_eq!(vec!["parent_id".to_string()], keys[0].columns);
+        assert_eq!("fk_parent", &keys[0].referenced_table);
+        assert_eq!(vec!["id".to_string()], keys[0].referenced_columns);
+        assert_eq!("CASCADE", &keys[0].on_delete);
+        assert_eq!("NO ACTION", &keys[0].on_update);
+
+        assert!(conn.foreign_keys("fk_parent").await.unwrap().is_empty());
+        assert!(conn.foreign_keys("missing").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn indexes_reads_the_index_list() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE index_test (id INTEGER, email TEXT, age INTEGER)")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE UNIQUE INDEX index_test_email ON index_test (email)")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE INDEX index_test_adults ON index_test (age, id) WHERE age >= 18")
+            .await
+            .unwrap();
+
+        let mut indexes = conn.indexes("index_test").await.unwrap();
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(2, indexes.len());
+
+        assert_eq!("index_test_adults", &indexes[0].name);
+        assert_eq!(vec!["age".to_string(), "id".to_string()], indexes[0].columns);
+        assert!(!indexes[0].is_unique);
+        assert_eq!(Some("age >= 18".to_string()), indexes[0].where_clause);
+
+        assert_eq!("index_test_email", &indexes[1].name);
+        assert_eq!(vec!["email".to_string()], indexes[1].columns);
+        assert!(indexes[1].is_unique);
+        assert_eq!(None, indexes[1].where_clause);
+
+        assert!(conn.indexes("missing").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn wal_mode_should_be_active_after_connecting_with_the_parameter() {
+        let conn = Sqlite::try_from("file:db/wal_test.db?journal_mode=wal").unwrap();
+
+        let result = conn.query_raw("PRAGMA journal_mode", &[]).await.unwrap();
+        let result = result.into_single().unwrap();
+
+        assert_eq!(result[0].as_str(), Some("wal"));
+    }
+
+    #[tokio::test]
+    async fn transaction_mode_changes_the_begin_statement() {
+        let file_path = std::env::temp_dir().join("quaint_transaction_mode_url_test.db");
+        let url = format!("file:{}", file_path.display());
+
+        let conn = Sqlite::try_from(format!("{url}?transaction_mode=immediate").as_str()).unwrap();
+        assert_eq!("BEGIN IMMEDIATE", conn.begin_statement());
+
+        let conn = Sqlite::try_from(format!("{url}?transaction_mode=exclusive").as_str()).unwrap();
+        assert_eq!("BEGIN EXCLUSIVE", conn.begin_statement());
+
+        let conn = Sqlite::try_from(url.as_str()).unwrap();
+        assert_eq!("BEGIN", conn.begin_statement());
+
+        assert!(Sqlite::try_from(format!("{url}?transaction_mode=pessimistic").as_str()).is_err());
+    }
+
+    #[tokio::test]
+    async fn immediate_transactions_move_write_contention_to_begin() {
+        let file_path = std::env::temp_dir().join("quaint_transaction_mode_test.db");
+        let file_path = file_path.to_str().unwrap();
+        let _ = std::fs::remove_file(file_path);
+
+        let setup = Sqlite::new(file_path).unwrap();
+        setup.raw_cmd("CREATE TABLE tx_mode_test (id int)").await.unwrap();
+
+        // In the default deferred mode both transactions begin, and the
+        // loser fails only when it tries to write, mid-transaction.
+        let conn_a = Sqlite::new(file_path).unwrap();
+        let conn_b = Sqlite::new(file_path).unwrap();
+
+        let tx_a = conn_a.start_transaction(None).await.unwrap();
+        tx_a.raw_cmd("INSERT INTO tx_mode_test VALUES (1)").await.unwrap();
+
+        let tx_b = conn_b.start_transaction(None).await.unwrap();
+        tx_b.query_raw("SELECT id FROM tx_mode_test", &[]).await.unwrap();
+
+        let err = tx_b.raw_cmd("INSERT INTO tx_mode_test VALUES (2)").await.unwrap_err();
+        assert!(err.is_transient());
+
+        tx_b.rollback().await.unwrap();
+        tx_a.rollback().await.unwrap();
+
+        // In immediate mode the loser fails already at `BEGIN` with a
+        // transient error, before any work was done.
+        let url = format!("{file_path}?transaction_mode=immediate");
+        let conn_c = Sqlite::new(&url).unwrap();
+        let conn_d = Sqlite::new(&url).unwrap();
+
+        let tx_c = conn_c.start_transaction(None).await.unwrap();
+
+        let err = match conn_d.start_transaction(None).await {
+            Err(err) => err,
+            Ok(_) => panic!("BEGIN IMMEDIATE should have failed under contention"),
+        };
+        assert!(err.is_transient());
+
+        tx_c.commit().await.unwrap();
+
+        // Once the winner commits, the loser can retry successfully.
+        let tx_d = conn_d.start_transaction(None).await.unwrap();
+        tx_d.raw_cmd("INSERT INTO tx_mode_test VALUES (2)").await.unwrap();
+        tx_d.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn in_transaction_reports_transaction_scope() {
+        let conn = Sqlite::new_in_memory().unwrap();
+        assert!(!conn.in_transaction());
+
+        let tx = conn.start_transaction(None).await.unwrap();
+        assert!(tx.in_transaction());
+        tx.commit().await.unwrap();
+
+        assert!(!conn.in_transaction());
+    }
+
     #[tokio::test]
     async fn unknown_table_should_give_a_good_error() {
         let conn = Sqlite::try_from("file:db/test.db").unwrap();
@@ -327,6 +1089,102 @@ mod tests {
         assert!(matches!(err.kind(), ErrorKind::TableDoesNotExist { .. }));
     }
 
+    #[tokio::test]
+    async fn attach_database_allows_queries_across_files() {
+        let path = Path::new("db/attach_test.db");
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.attach_database(path, "other").await.unwrap();
+
+        conn.raw_cmd("CREATE TABLE IF NOT EXISTS other.users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);")
+            .await
+            .unwrap();
+
+        conn.raw_cmd("DELETE FROM other.users").await.unwrap();
+
+        let table = Table::from(("other", "users"));
+
+        let insert = Insert::single_into(table.clone()).value("name", "musti");
+        conn.insert(insert.into()).await.unwrap();
+
+        let select = Select::from_table(table).value(asterisk());
+        let result = conn.select(select.clone()).await.unwrap();
+        let result = result.into_single().unwrap();
+
+        assert_eq!(result.get("name").unwrap(), &Value::text("musti"));
+
+        conn.detach_database("other").await.unwrap();
+
+        let err = conn.select(select).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TableDoesNotExist { .. }));
+    }
+
+    #[tokio::test]
+    async fn attaching_the_same_alias_twice_errors() {
+        let path = Path::new("db/attach_test.db");
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.attach_database(path, "twice").await.unwrap();
+        assert!(conn.attach_database(path, "twice").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn attaching_a_file_in_a_missing_directory_errors() {
+        let conn = Sqlite::new_in_memory().unwrap();
+        let res = conn.attach_database(Path::new("db/no/such/dir.db"), "missing").await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn transactions_span_attached_databases() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.attach_database(Path::new("db/attach_tx_test.db"), "other")
+            .await
+            .unwrap();
+
+        conn.raw_cmd("CREATE TABLE cats (id INTEGER PRIMARY KEY);").await.unwrap();
+
+        conn.raw_cmd("CREATE TABLE IF NOT EXISTS other.dogs (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        conn.raw_cmd("DELETE FROM other.dogs").await.unwrap();
+
+        // A rollback has to revert the writes on both sides of the
+        // attachment.
+        let tx = conn.start_transaction(None).await.unwrap();
+        tx.insert(Insert::single_into("cats").value("id", 1).into()).await.unwrap();
+
+        tx.insert(Insert::single_into(("other", "dogs")).value("id", 1).into())
+            .await
+            .unwrap();
+
+        tx.rollback().await.unwrap();
+
+        for table in [Table::from("cats"), Table::from(("other", "dogs"))] {
+            let select = Select::from_table(table).value(count(asterisk()));
+            let result = conn.select(select).await.unwrap().into_single().unwrap();
+
+            assert_eq!(result[0].as_i64(), Some(0));
+        }
+    }
+
+    #[cfg(feature = "pooled")]
+    #[tokio::test]
+    async fn pooled_connections_reattach_databases() {
+        let mut builder = crate::pooled::Quaint::builder("file:db/attach_pool_main.db").unwrap();
+        builder.attach_database("other".to_string(), "db/attach_pool_other.db".to_string());
+
+        let pool = builder.build();
+        let conn = pool.check_out().await.unwrap();
+
+        conn.raw_cmd("CREATE TABLE IF NOT EXISTS other.users (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn quoting_in_returning_in_sqlite_works() {
         let conn = Sqlite::new_in_memory().unwrap();
@@ -353,4 +1211,36 @@ mod tests {
 
         assert_eq!(result.get("txt space").unwrap(), &Value::text("henlo"));
     }
+
+    #[tokio::test]
+    async fn fts5_phrase_match_finds_indexed_documents() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        let create = CreateFts5Table::new("docs").columns(["title", "body"]);
+        conn.create_fts_table(create).await.unwrap();
+
+        let documents = [
+            ("cats", "musti and naukio sit on the mat"),
+            ("dogs", "a good dog sits on the porch"),
+            ("both", "on the mat, a cat and a dog"),
+        ];
+
+        for (title, body) in documents {
+            let insert = Insert::single_into("docs").value("title", title).value("body", body);
+            conn.insert(insert.into()).await.unwrap();
+        }
+
+        let select = Select::from_table("docs")
+            .value(asterisk())
+            .so_that(ConditionTree::single(FtsMatch::new("docs", "\"on the mat\"")));
+
+        let result = conn.select(select).await.unwrap();
+
+        let titles: Vec<_> = result
+            .into_iter()
+            .map(|row| row.get("title").unwrap().as_str().unwrap().to_string())
+            .collect();
+
+        assert_eq!(vec!["cats", "both"], titles);
+    }
 }
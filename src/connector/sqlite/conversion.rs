@@ -179,7 +179,7 @@ impl<'a> GetRow for SqliteRow<'a> {
                         #[cfg(feature = "chrono")]
                         c if c.is_datetime() => {
                             let dt = chrono::Utc.timestamp_millis_opt(i).unwrap();
-                            Value::datetime(dt)
+                            Value::datetime_utc(dt)
                         }
                         c if c.is_int32() => {
                             if let Ok(converted) = i32::try_from(i) {
@@ -213,14 +213,15 @@ impl<'a> GetRow for SqliteRow<'a> {
 
                     parse_res.and_then(|s| {
                         chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
-                            .map(|nd| chrono::DateTime::<chrono::Utc>::from_utc(nd, chrono::Utc))
+                            .map(Value::datetime)
                             .or_else(|_| {
-                                chrono::DateTime::parse_from_rfc3339(s).map(|dt| dt.with_timezone(&chrono::Utc))
+                                chrono::DateTime::parse_from_rfc3339(s)
+                                    .map(|dt| Value::datetime_utc(dt.with_timezone(&chrono::Utc)))
                             })
                             .or_else(|_| {
-                                chrono::DateTime::parse_from_rfc2822(s).map(|dt| dt.with_timezone(&chrono::Utc))
+                                chrono::DateTime::parse_from_rfc2822(s)
+                                    .map(|dt| Value::datetime_utc(dt.with_timezone(&chrono::Utc)))
                             })
-                            .map(Value::datetime)
                             .map_err(|chrono_error| {
                                 let builder =
                                     Error::builder(ErrorKind::ConversionError(chrono_error.to_string().into()));
@@ -253,6 +254,19 @@ impl<'a> ToSql for Value<'a> {
         let value = match self {
             Value::Int32(integer) => integer.map(ToSqlOutput::from),
             Value::Int64(integer) => integer.map(ToSqlOutput::from),
+            Value::Int128(integer) => match integer {
+                Some(i) => {
+                    let integer = i64::try_from(*i).map_err(|_| {
+                        let kind =
+                            ErrorKind::conversion(format!("Unable to fit integer value '{i}' into an SQLite INTEGER."));
+
+                        RusqlError::ToSqlConversionFailure(Box::new(Error::builder(kind).build()))
+                    })?;
+
+                    Some(ToSqlOutput::from(integer))
+                }
+                None => None,
+            },
             Value::Float(float) => float.map(|f| f as f64).map(ToSqlOutput::from),
             Value::Double(double) => double.map(ToSqlOutput::from),
             Value::Text(cow) => cow.as_ref().map(|cow| ToSqlOutput::from(cow.as_ref())),
@@ -260,7 +274,7 @@ impl<'a> ToSql for Value<'a> {
             Value::Boolean(boo) => boo.map(ToSqlOutput::from),
             Value::Char(c) => c.map(|c| ToSqlOutput::from(c as u8)),
             Value::Bytes(bytes) => bytes.as_ref().map(|bytes| ToSqlOutput::from(bytes.as_ref())),
-            Value::Array(_) => {
+            Value::Array(_) | Value::TypedArray(..) => {
                 let msg = "Arrays are not supported in SQLite.";
                 let kind = ErrorKind::conversion(msg);
 
@@ -269,6 +283,16 @@ impl<'a> ToSql for Value<'a> {
 
                 return Err(RusqlError::ToSqlConversionFailure(Box::new(builder.build())));
             }
+            #[cfg(feature = "postgis")]
+            Value::Geometry(_) => {
+                let msg = "Geometry values are only supported on PostgreSQL.";
+                let kind = ErrorKind::conversion(msg);
+
+                let mut builder = Error::builder(kind);
+                builder.set_original_message(msg);
+
+                return Err(RusqlError::ToSqlConversionFailure(Box::new(builder.build())));
+            }
             #[cfg(feature = "bigdecimal")]
             Value::Numeric(d) => d
                 .as_ref()
@@ -282,11 +306,17 @@ impl<'a> ToSql for Value<'a> {
                 ToSqlOutput::from(stringified)
             }),
             Value::Xml(cow) => cow.as_ref().map(|cow| ToSqlOutput::from(cow.as_ref())),
+            Value::IpAddr(ip) => ip.map(|ip| ToSqlOutput::from(ip.to_string())),
+            // SQLite has no bit type; the textual form keeps the value
+            // readable and comparable.
+            Value::BitString(bits) => bits.as_ref().map(|bits| ToSqlOutput::from(bits.as_ref())),
             #[cfg(feature = "uuid")]
             Value::Uuid(value) => value.map(|value| ToSqlOutput::from(value.hyphenated().to_string())),
             #[cfg(feature = "chrono")]
             Value::DateTime(value) => value.map(|value| ToSqlOutput::from(value.timestamp_millis())),
             #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(value) => value.map(|value| ToSqlOutput::from(value.timestamp_millis())),
+            #[cfg(feature = "chrono")]
             Value::Date(date) => date
                 .and_then(|date| date.and_hms_opt(0, 0, 0))
                 .map(|dt| ToSqlOutput::from(dt.timestamp_millis())),
@@ -0,0 +1,274 @@
+//! Query tagging for observability.
+//!
+//! Prepending a `/* app=myapp,request_id=abc123 */` comment to a statement
+//! makes it easy to correlate the entries of `pg_stat_statements` or a slow
+//! query log with application requests. The comment can come from two
+//! places: the `query_comment` connection string option tags every statement
+//! of a connection, and [`Queryable::with_tag`] tags the statements running
+//! through the returned wrapper. The `/* ... */` syntax is understood by all
+//! supported databases.
+
+use super::{BatchResult, IsolationLevel, Queryable, ResultSet};
+use crate::ast::{Query, Value};
+use async_trait::async_trait;
+use std::borrow::Cow;
+
+/// Wraps a [`Queryable`], prepending a `/* ... */` comment to every
+/// statement running through it. Created with [`Queryable::with_tag`], and
+/// useful for scoping a tag to a part of the program, e.g. a single request
+/// handler:
+///
+/// ```rust,no_run
+/// # use quaint::{prelude::*, single::Quaint};
+/// # async fn function() -> Result<(), quaint::error::Error> {
+/// # let conn = Quaint::new("file:///tmp/example.db").await?;
+/// let tagged = conn.with_tag("request_id=abc123");
+///
+/// // Runs `/* request_id=abc123 */ SELECT 1`.
+/// tagged.query_raw("SELECT 1", &[]).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TaggedQueryable<'a> {
+    inner: &'a dyn Queryable,
+    comment: String,
+}
+
+impl<'a> TaggedQueryable<'a> {
+    /// Wraps the given connection, tagging every statement with the given
+    /// comment text.
+    pub fn new(inner: &'a dyn Queryable, tag: &str) -> Self {
+        Self {
+            inner,
+            comment: tag.to_string(),
+        }
+    }
+
+    fn tag_sql(&self, sql: &str) -> String {
+        inject_comment(sql, &self.comment)
+    }
+}
+
+#[async_trait]
+impl<'a> Queryable for TaggedQueryable<'a> {
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let (sql, params) = self.render(q)?;
+        self.query_raw(&sql, &params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let (sql, params) = self.render(q)?;
+        self.execute_raw(&sql, &params).await
+    }
+
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        self.inner.render(q)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.inner.query_raw(&self.tag_sql(sql), params).await
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.inner.query_raw_typed(&self.tag_sql(sql), params).await
+    }
+
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        self.inner.query_multi_raw(&self.tag_sql(sql), params).await
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.inner.execute_raw(&self.tag_sql(sql), params).await
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.inner.execute_raw_typed(&self.tag_sql(sql), params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.inner.raw_cmd(&self.tag_sql(cmd)).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.inner.is_healthy()
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.inner.in_transaction()
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
+        self.inner.set_tx_isolation_level(isolation_level).await
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        self.inner.requires_isolation_first()
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+}
+
+/// Prepends the comment to the statement as `/* comment */`, stripping any
+/// comments already leading the statement so repeated tagging does not pile
+/// up. A `*/` inside the comment text is defused to keep the text from
+/// terminating the comment early.
+pub(crate) fn inject_comment(sql: &str, comment: &str) -> String {
+    format!("/* {} */ {}", sanitize_comment(comment), strip_leading_comments(sql))
+}
+
+/// Prepends the configured default comment to the statement. A statement
+/// already starting with a comment — its own or the tag of a
+/// [`Queryable::with_tag`] wrapper — is left alone, so the more specific
+/// tag wins over the connection-wide default.
+pub(crate) fn with_default_comment<'a>(sql: &'a str, comment: Option<&str>) -> Cow<'a, str> {
+    match comment {
+        Some(comment) if !has_leading_comment(sql) => Cow::Owned(inject_comment(sql, comment)),
+        _ => Cow::Borrowed(sql),
+    }
+}
+
+fn has_leading_comment(sql: &str) -> bool {
+    sql.trim_start().starts_with("/*")
+}
+
+/// The statement without its leading `/* ... */` comments and surrounding
+/// whitespace. Nested block comments count as one comment, matching how
+/// PostgreSQL parses them.
+fn strip_leading_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+
+    while let Some(comment) = rest.strip_prefix("/*") {
+        let mut depth = 1;
+        let mut chars = comment.char_indices().peekable();
+        let mut end = None;
+
+        while let Some((i, c)) = chars.next() {
+            match (c, chars.peek()) {
+                ('/', Some((_, '*'))) => {
+                    depth += 1;
+                    chars.next();
+                }
+                ('*', Some((_, '/'))) => {
+                    depth -= 1;
+                    chars.next();
+
+                    if depth == 0 {
+                        end = Some(i + 2);
+                        break;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        match end {
+            Some(end) => rest = comment[end..].trim_start(),
+            // An unterminated comment; leave the statement as it is and
+            // let the database report the syntax error.
+            None => break,
+        }
+    }
+
+    rest
+}
+
+fn sanitize_comment(comment: &str) -> Cow<'_, str> {
+    if comment.contains("*/") {
+        Cow::Owned(comment.replace("*/", "* /"))
+    } else {
+        Cow::Borrowed(comment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inject_comment_prepends_the_tag() {
+        assert_eq!(
+            "/* app=quaint */ SELECT 1",
+            inject_comment("SELECT 1", "app=quaint")
+        );
+    }
+
+    #[test]
+    fn inject_comment_replaces_an_existing_leading_comment() {
+        assert_eq!(
+            "/* request_id=def456 */ SELECT 1",
+            inject_comment("/* request_id=abc123 */ SELECT 1", "request_id=def456")
+        );
+    }
+
+    #[test]
+    fn inject_comment_strips_stacked_and_nested_comments() {
+        assert_eq!(
+            "/* tag */ SELECT 1",
+            inject_comment("  /* one */ /* two /* nested */ */  SELECT 1", "tag")
+        );
+    }
+
+    #[test]
+    fn inject_comment_keeps_comments_inside_the_statement() {
+        assert_eq!(
+            "/* tag */ SELECT 1 /* trailing */",
+            inject_comment("SELECT 1 /* trailing */", "tag")
+        );
+    }
+
+    #[test]
+    fn inject_comment_defuses_a_comment_terminator_in_the_tag() {
+        assert_eq!("/* boom * / */ SELECT 1", inject_comment("SELECT 1", "boom */"));
+    }
+
+    #[test]
+    fn inject_comment_leaves_an_unterminated_comment_alone() {
+        assert_eq!(
+            "/* tag */ /* oops SELECT 1",
+            inject_comment("/* oops SELECT 1", "tag")
+        );
+    }
+
+    #[test]
+    fn default_comment_yields_to_an_existing_tag() {
+        assert_eq!(
+            "/* request_id=abc123 */ SELECT 1",
+            with_default_comment("/* request_id=abc123 */ SELECT 1", Some("app=quaint"))
+        );
+
+        assert_eq!(
+            "/* app=quaint */ SELECT 1",
+            with_default_comment("SELECT 1", Some("app=quaint"))
+        );
+
+        assert_eq!("SELECT 1", with_default_comment("SELECT 1", None));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn tagged_queryable_runs_commented_statements() {
+        let conn = crate::connector::Sqlite::new_in_memory().unwrap();
+        let tagged = conn.with_tag("request_id=abc123");
+
+        let result = tagged.query_raw("SELECT 1", &[]).await.unwrap();
+        let row = result.into_single().unwrap();
+
+        assert_eq!(Some(1), row[0].as_integer());
+
+        let select = crate::ast::Select::default().value(2);
+        let result = tagged.query(select.into()).await.unwrap();
+        let row = result.into_single().unwrap();
+
+        assert_eq!(Some(2), row[0].as_integer());
+    }
+}
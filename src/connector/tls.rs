@@ -0,0 +1,382 @@
+//! Shared TLS configuration for the connectors. The [`TlsConfig`] builder is
+//! plain data; the `native_tls` machinery turning it into certificates and
+//! identities is only compiled for the connectors using that stack.
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+use crate::error::{Error, ErrorKind};
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+use native_tls::{Certificate, Identity};
+use std::fmt::Debug;
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+use std::fs;
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+impl From<native_tls::Error> for Error {
+    fn from(e: native_tls::Error) -> Error {
+        Error::from(&e)
+    }
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+impl From<&native_tls::Error> for Error {
+    fn from(e: &native_tls::Error) -> Error {
+        let kind = ErrorKind::TlsError {
+            message: format!("{e}"),
+        };
+
+        Error::builder(kind).build()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Hidden<T>(pub(crate) T);
+
+impl<T> Debug for Hidden<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<HIDDEN>")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SslAcceptMode {
+    Strict,
+    AcceptInvalidCerts,
+}
+
+/// Programmatic TLS configuration, carrying the certificate material in
+/// memory for setups where the secrets live in a secrets manager instead of
+/// on disk. Takes precedence over the file paths given in the connection
+/// string.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub(crate) root_cert_pem: Hidden<Option<Vec<u8>>>,
+    pub(crate) identity_pkcs12: Hidden<Option<Vec<u8>>>,
+    pub(crate) identity_password: Hidden<Option<String>>,
+    pub(crate) accept_mode: Option<SslAcceptMode>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            root_cert_pem: Hidden(None),
+            identity_pkcs12: Hidden(None),
+            identity_password: Hidden(None),
+            accept_mode: None,
+        }
+    }
+}
+
+impl TlsConfig {
+    /// An empty configuration, leaving the values from the connection string
+    /// in place.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The root certificate the server certificate is validated against, as
+    /// PEM bytes.
+    pub fn root_cert_pem(mut self, pem: Vec<u8>) -> Self {
+        self.root_cert_pem = Hidden(Some(pem));
+        self
+    }
+
+    /// The client identity as the bytes of a PKCS#12 archive.
+    pub fn identity_pkcs12(mut self, der: Vec<u8>) -> Self {
+        self.identity_pkcs12 = Hidden(Some(der));
+        self
+    }
+
+    /// The password of the PKCS#12 archive.
+    pub fn identity_password<T>(mut self, password: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.identity_password = Hidden(Some(password.into()));
+        self
+    }
+
+    /// Whether to validate the server certificate.
+    pub fn accept_mode(mut self, mode: SslAcceptMode) -> Self {
+        self.accept_mode = Some(mode);
+        self
+    }
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+#[derive(Debug, Clone)]
+pub struct SslParams {
+    pub(crate) certificate_file: Option<String>,
+    pub(crate) identity_file: Option<String>,
+    pub(crate) identity_password: Hidden<Option<String>>,
+    pub(crate) client_certificate_file: Option<String>,
+    pub(crate) client_key_file: Option<String>,
+    pub(crate) ssl_accept_mode: SslAcceptMode,
+    pub(crate) tls_config: Option<TlsConfig>,
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+#[derive(Debug)]
+pub(crate) struct SslAuth {
+    pub(crate) certificate: Hidden<Option<Certificate>>,
+    pub(crate) identity: Hidden<Option<Identity>>,
+    pub(crate) ssl_accept_mode: SslAcceptMode,
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+impl Default for SslAuth {
+    fn default() -> Self {
+        Self {
+            certificate: Hidden(None),
+            identity: Hidden(None),
+            ssl_accept_mode: SslAcceptMode::AcceptInvalidCerts,
+        }
+    }
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+impl SslAuth {
+    pub(crate) fn certificate(&mut self, certificate: Certificate) -> &mut Self {
+        self.certificate = Hidden(Some(certificate));
+        self
+    }
+
+    pub(crate) fn identity(&mut self, identity: Identity) -> &mut Self {
+        self.identity = Hidden(Some(identity));
+        self
+    }
+
+    pub(crate) fn accept_mode(&mut self, mode: SslAcceptMode) -> &mut Self {
+        self.ssl_accept_mode = mode;
+        self
+    }
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql"))]
+impl SslParams {
+    /// True when a programmatic [`TlsConfig`] carries certificate material in
+    /// memory. Drivers that only accept file paths cannot use such a
+    /// configuration.
+    pub(crate) fn has_in_memory_material(&self) -> bool {
+        self.tls_config
+            .as_ref()
+            .map(|config| config.root_cert_pem.0.is_some() || config.identity_pkcs12.0.is_some())
+            .unwrap_or(false)
+    }
+
+    pub(crate) async fn into_auth(self) -> crate::Result<SslAuth> {
+        let mut auth = SslAuth::default();
+        auth.accept_mode(self.ssl_accept_mode);
+
+        if let Some(config) = self.tls_config {
+            if let Some(mode) = config.accept_mode {
+                auth.accept_mode(mode);
+            }
+
+            if let Some(ref pem) = config.root_cert_pem.0 {
+                auth.certificate(Certificate::from_pem(pem)?);
+            }
+
+            if let Some(ref der) = config.identity_pkcs12.0 {
+                let password = config.identity_password.0.as_deref().unwrap_or("");
+
+                auth.identity(Identity::from_pkcs12(der, password)?);
+            }
+        }
+
+        // The programmatic configuration takes precedence, so the file paths
+        // from the connection string only fill the parts it left out.
+        if let (None, Some(ref cert_file)) = (&auth.certificate.0, &self.certificate_file) {
+            let cert = fs::read(cert_file).map_err(|err| {
+                Error::builder(ErrorKind::TlsError {
+                    message: format!("cert file not found ({err})"),
+                })
+                .build()
+            })?;
+
+            auth.certificate(Certificate::from_pem(&cert)?);
+        }
+
+        if let (None, Some(ref identity_file)) = (&auth.identity.0, &self.identity_file) {
+            let db = fs::read(identity_file).map_err(|err| {
+                Error::builder(ErrorKind::TlsError {
+                    message: format!("identity file not found ({err})"),
+                })
+                .build()
+            })?;
+            let password = self.identity_password.0.as_deref().unwrap_or("");
+            let identity = Identity::from_pkcs12(&db, password)?;
+
+            auth.identity(identity);
+        }
+
+        if let (None, Some(ref cert_file), Some(ref key_file)) =
+            (&auth.identity.0, &self.client_certificate_file, &self.client_key_file)
+        {
+            let cert = fs::read(cert_file).map_err(|err| {
+                Error::builder(ErrorKind::TlsError {
+                    message: format!("client cert file not found ({err})"),
+                })
+                .build()
+            })?;
+
+            let key = fs::read(key_file).map_err(|err| {
+                Error::builder(ErrorKind::TlsError {
+                    message: format!("client key file not found ({err})"),
+                })
+                .build()
+            })?;
+
+            auth.identity(Identity::from_pkcs8(&cert, &key)?);
+        }
+
+        Ok(auth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_config_debug_output_hides_the_secrets() {
+        let config = TlsConfig::new()
+            .root_cert_pem(b"s3cret root cert".to_vec())
+            .identity_pkcs12(b"s3cret archive".to_vec())
+            .identity_password("s3cret password")
+            .accept_mode(SslAcceptMode::Strict);
+
+        let printed = format!("{config:?}");
+
+        assert!(!printed.contains("s3cret"));
+        assert!(printed.contains("<HIDDEN>"));
+    }
+}
+
+/// An alternative TLS stack built on `rustls`, for targets where linking
+/// OpenSSL is not an option. Only used by the PostgreSQL connector.
+#[cfg(feature = "rustls")]
+pub(crate) mod rustls_backend {
+    use super::{SslAcceptMode, SslParams};
+    use crate::error::{Error, ErrorKind};
+    use rustls::{
+        client::{ServerCertVerified, ServerCertVerifier},
+        Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore,
+    };
+    use std::{fs, io::Cursor, sync::Arc, time::SystemTime};
+
+    fn tls_error(message: String) -> Error {
+        Error::builder(ErrorKind::TlsError { message }).build()
+    }
+
+    /// Builds a `rustls` client configuration from the TLS parameters of a
+    /// connection, honoring the programmatic [`TlsConfig`] the same way the
+    /// `native_tls` stack does.
+    ///
+    /// [`TlsConfig`]: super::TlsConfig
+    pub(crate) fn client_config(params: SslParams) -> crate::Result<ClientConfig> {
+        let mut roots = RootCertStore::empty();
+
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+
+        let root_cert_pem = match params.tls_config.as_ref().and_then(|config| config.root_cert_pem.0.clone()) {
+            Some(pem) => Some(pem),
+            None => match params.certificate_file {
+                Some(ref cert_file) => Some(
+                    fs::read(cert_file).map_err(|err| tls_error(format!("cert file not found ({err})")))?,
+                ),
+                None => None,
+            },
+        };
+
+        if let Some(pem) = root_cert_pem {
+            let certs = rustls_pemfile::certs(&mut Cursor::new(pem))
+                .map_err(|err| tls_error(format!("invalid root certificate ({err})")))?;
+
+            for cert in certs {
+                roots
+                    .add(&Certificate(cert))
+                    .map_err(|err| tls_error(format!("invalid root certificate ({err})")))?;
+            }
+        }
+
+        let pkcs12_identity = params.identity_file.is_some()
+            || params
+                .tls_config
+                .as_ref()
+                .map(|config| config.identity_pkcs12.0.is_some())
+                .unwrap_or(false);
+
+        if pkcs12_identity {
+            let message = "PKCS#12 client identities are not supported with the rustls backend. Use a PEM certificate and key pair instead.".to_string();
+
+            return Err(tls_error(message));
+        }
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+
+        let mut config = match (&params.client_certificate_file, &params.client_key_file) {
+            (Some(cert_file), Some(key_file)) => {
+                let cert = fs::read(cert_file)
+                    .map_err(|err| tls_error(format!("client cert file not found ({err})")))?;
+
+                let certs = rustls_pemfile::certs(&mut Cursor::new(cert))
+                    .map_err(|err| tls_error(format!("invalid client certificate ({err})")))?
+                    .into_iter()
+                    .map(Certificate)
+                    .collect();
+
+                let key = fs::read(key_file)
+                    .map_err(|err| tls_error(format!("client key file not found ({err})")))?;
+
+                let key = rustls_pemfile::pkcs8_private_keys(&mut Cursor::new(key))
+                    .map_err(|err| tls_error(format!("invalid client key ({err})")))?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| tls_error("no PKCS#8 private key found in the client key file".to_string()))?;
+
+                builder
+                    .with_client_auth_cert(certs, PrivateKey(key))
+                    .map_err(|err| tls_error(format!("invalid client identity ({err})")))?
+            }
+            _ => builder.with_no_client_auth(),
+        };
+
+        let accept_mode = params
+            .tls_config
+            .as_ref()
+            .and_then(|config| config.accept_mode)
+            .unwrap_or(params.ssl_accept_mode);
+
+        if accept_mode == SslAcceptMode::AcceptInvalidCerts {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(NoVerifier));
+        }
+
+        Ok(config)
+    }
+
+    struct NoVerifier;
+
+    impl ServerCertVerifier for NoVerifier {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &Certificate,
+            _intermediates: &[Certificate],
+            _server_name: &rustls::ServerName,
+            _scts: &mut dyn Iterator<Item = &[u8]>,
+            _ocsp_response: &[u8],
+            _now: SystemTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+}
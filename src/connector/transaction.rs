@@ -4,8 +4,11 @@ use crate::{
     error::{Error, ErrorKind},
 };
 use async_trait::async_trait;
-use metrics::{decrement_gauge, increment_gauge};
-use std::{fmt, str::FromStr};
+use metrics::{decrement_gauge, increment_counter, increment_gauge};
+use std::{
+    fmt, str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 extern crate metrics as metrics;
 
@@ -24,6 +27,9 @@ pub(crate) struct TransactionOptions {
 /// transaction object will panic.
 pub struct Transaction<'a> {
     pub(crate) inner: &'a dyn Queryable,
+    /// Whether the transaction was already committed or rolled back, keeping
+    /// the active gauge from being decremented twice.
+    finished: AtomicBool,
 }
 
 impl<'a> Transaction<'a> {
@@ -32,7 +38,10 @@ impl<'a> Transaction<'a> {
         begin_stmt: &str,
         tx_opts: TransactionOptions,
     ) -> crate::Result<Transaction<'a>> {
-        let this = Self { inner };
+        let this = Self {
+            inner,
+            finished: AtomicBool::new(false),
+        };
 
         if tx_opts.isolation_first {
             if let Some(isolation) = tx_opts.isolation_level {
@@ -50,22 +59,30 @@ impl<'a> Transaction<'a> {
 
         inner.server_reset_query(&this).await?;
 
-        increment_gauge!("prisma_client_queries_active", 1.0);
+        increment_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
         Ok(this)
     }
 
     /// Commit the changes to the database and consume the transaction.
     pub async fn commit(&self) -> crate::Result<()> {
-        decrement_gauge!("prisma_client_queries_active", 1.0);
+        if !self.finished.swap(true, Ordering::SeqCst) {
+            decrement_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
+        }
+
         self.inner.raw_cmd("COMMIT").await?;
+        increment_counter!(super::metrics::metric_name("quaint", "tx_committed_total"));
 
         Ok(())
     }
 
     /// Rolls back the changes to the database.
     pub async fn rollback(&self) -> crate::Result<()> {
-        decrement_gauge!("prisma_client_queries_active", 1.0);
+        if !self.finished.swap(true, Ordering::SeqCst) {
+            decrement_gauge!(super::metrics::metric_name("prisma", "client_queries_active"), 1.0);
+        }
+
         self.inner.raw_cmd("ROLLBACK").await?;
+        increment_counter!(super::metrics::metric_name("quaint", "tx_rolled_back_total"));
 
         Ok(())
     }
@@ -77,6 +94,10 @@ impl<'a> Queryable for Transaction<'a> {
         self.inner.query(q).await
     }
 
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        self.inner.render(q)
+    }
+
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
         self.inner.execute(q).await
     }
@@ -89,6 +110,10 @@ impl<'a> Queryable for Transaction<'a> {
         self.inner.query_raw_typed(sql, params).await
     }
 
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        self.inner.query_multi_raw(sql, params).await
+    }
+
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
         self.inner.execute_raw(sql, params).await
     }
@@ -105,10 +130,42 @@ impl<'a> Queryable for Transaction<'a> {
         self.inner.version().await
     }
 
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    async fn insert_returning(&self, insert: Insert<'_>, pk_column: &str) -> crate::Result<Value<'static>> {
+        self.inner.insert_returning(insert, pk_column).await
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        self.inner.table_exists(table, schema).await
+    }
+
+    async fn column_names(&self, table: &str) -> crate::Result<Vec<String>> {
+        self.inner.column_names(table).await
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        self.inner.column_info(table).await
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<ForeignKeyInfo>> {
+        self.inner.foreign_keys(table).await
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<IndexInfo>> {
+        self.inner.indexes(table).await
+    }
+
     fn is_healthy(&self) -> bool {
         self.inner.is_healthy()
     }
 
+    fn in_transaction(&self) -> bool {
+        true
+    }
+
     async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
         self.inner.set_tx_isolation_level(isolation_level).await
     }
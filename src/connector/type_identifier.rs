@@ -10,6 +10,13 @@ pub(crate) trait TypeIdentifier {
     fn is_text(&self) -> bool;
     fn is_bytes(&self) -> bool;
     fn is_bool(&self) -> bool;
+
+    /// A bit-string column. Only MySQL `bit(n)` columns with `n > 1` report
+    /// this; the other connectors carry the type information elsewhere.
+    fn is_bits(&self) -> bool {
+        false
+    }
+
     fn is_json(&self) -> bool;
     fn is_enum(&self) -> bool;
     fn is_null(&self) -> bool;
@@ -15,6 +15,10 @@ pub enum DatabaseConstraint {
 }
 
 impl DatabaseConstraint {
+#[cfg_attr(
+        not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+        allow(dead_code)
+    )]
     pub(crate) fn fields<I, S>(names: I) -> Self
     where
         I: IntoIterator<Item = S>,
@@ -86,6 +90,10 @@ pub(crate) struct ErrorBuilder {
 }
 
 impl ErrorBuilder {
+#[cfg_attr(
+        not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+        allow(dead_code)
+    )]
     pub(crate) fn set_original_code(&mut self, code: impl Into<String>) -> &mut Self {
         self.original_code = Some(code.into());
         self
@@ -133,6 +141,66 @@ impl Error {
     pub fn is_closed(&self) -> bool {
         matches!(self.kind, ErrorKind::ConnectionClosed)
     }
+
+    /// Whether retrying the operation unchanged can reasonably succeed.
+    ///
+    /// `true` for transient failures: write conflicts the database resolved
+    /// by aborting a transaction (deadlocks and serialization failures
+    /// surface as [`TransactionWriteConflict`]), connections the server
+    /// closed and connection, socket or pool check-out timeouts. All of
+    /// these depend on concurrent load and usually pass on another attempt.
+    ///
+    /// `false` for everything the server would report again on every
+    /// attempt with the same input, such as constraint violations, invalid
+    /// queries or failed authentication.
+    ///
+    /// [`TransactionWriteConflict`]: ErrorKind::TransactionWriteConflict
+    pub fn is_retriable(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::TransactionWriteConflict
+                | ErrorKind::ConnectionClosed
+                | ErrorKind::ConnectTimeout
+                | ErrorKind::SocketTimeout
+                | ErrorKind::PoolTimeout { .. }
+        )
+    }
+
+    /// A synonym for [`is_retriable`](Self::is_retriable).
+    pub fn is_transient(&self) -> bool {
+        self.is_retriable()
+    }
+
+    /// The SQLSTATE code of the database error, if available: five
+    /// alphanumeric characters, stable across database versions and
+    /// locales, making it a better discriminator than the human-readable
+    /// [`original_message`](Self::original_message).
+    ///
+    /// PostgreSQL and SQL Server report SQLSTATE codes directly. MySQL
+    /// reports its own numeric codes, which are translated to the
+    /// documented SQLSTATE equivalent here; codes without a meaningful
+    /// equivalent (MySQL maps many to the catch-all `HY000`) return
+    /// `None`.
+    pub fn sqlstate(&self) -> Option<&str> {
+        let code = self.original_code()?;
+
+        if code.len() == 5 && code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Some(code);
+        }
+
+        match code {
+            "1048" | "1062" | "1451" | "1452" => Some("23000"),
+            "1406" => Some("22001"),
+            "1264" => Some("22003"),
+            "1263" => Some("22004"),
+            "1044" | "1049" => Some("42000"),
+            "1045" => Some("28000"),
+            "1146" => Some("42S02"),
+            "1054" => Some("42S22"),
+            "1213" => Some("40001"),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -149,6 +217,12 @@ pub enum ErrorKind {
     #[error("Invalid input provided to query: {}", _0)]
     QueryInvalidInput(String),
 
+    #[error("The underlying database does not support the operation: {}", _0)]
+    UnsupportedOperation(String),
+
+    #[error("The operation is not valid in this state: {}", _0)]
+    InvalidOperation(String),
+
     #[error("Database does not exist: {}", db_name)]
     DatabaseDoesNotExist { db_name: Name },
 
@@ -212,11 +286,19 @@ pub enum ErrorKind {
     #[error("The server terminated the connection.")]
     ConnectionClosed,
 
+    #[error("The server does not fulfill the requested session attributes. {}", message)]
+    SessionAttributesMismatch { message: String },
+
     #[error(
-        "Timed out fetching a connection from the pool (connection limit: {}, in use: {}, pool timeout {})",
+        "Timed out fetching a connection from the pool (connection limit: {}, in use: {}, pool timeout {}). {}",
         max_open,
         in_use,
-        timeout
+        timeout,
+        if in_use >= max_open {
+            "The pool is saturated with busy connections; raise the connection limit or shorten how long the connections are held."
+        } else {
+            "The pool could not open new connections; check the availability of the database."
+        }
     )]
     PoolTimeout { max_open: u64, in_use: u64, timeout: u64 },
 
@@ -289,6 +371,10 @@ impl ErrorKind {
         }
     }
 
+#[cfg_attr(
+        not(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite")),
+        allow(dead_code)
+    )]
     pub(crate) fn invalid_isolation_level(isolation_level: &IsolationLevel) -> Self {
         Self::InvalidIsolationLevel(isolation_level.to_string())
     }
@@ -382,3 +468,90 @@ impl From<uuid::Error> for Error {
         Error::builder(ErrorKind::UUIDError(format!("{e}"))).build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_errors_are_retriable() {
+        let err = Error::builder(ErrorKind::TransactionWriteConflict).build();
+        assert!(err.is_retriable());
+        assert!(err.is_transient());
+
+        let err = Error::builder(ErrorKind::ConnectionClosed).build();
+        assert!(err.is_retriable());
+    }
+
+    #[test]
+    fn pool_timeout_reports_a_saturated_pool() {
+        let err = Error::builder(ErrorKind::PoolTimeout {
+            max_open: 5,
+            in_use: 5,
+            timeout: 10,
+        })
+        .build();
+
+        assert!(format!("{err}").contains("saturated with busy connections"), "{err}");
+    }
+
+    #[test]
+    fn pool_timeout_reports_failing_connections() {
+        let err = Error::builder(ErrorKind::PoolTimeout {
+            max_open: 5,
+            in_use: 2,
+            timeout: 10,
+        })
+        .build();
+
+        assert!(format!("{err}").contains("could not open new connections"), "{err}");
+    }
+
+    #[test]
+    fn sqlstate_passes_native_codes_through() {
+        let mut builder = Error::builder(ErrorKind::TransactionWriteConflict);
+        builder.set_original_code("40001");
+
+        assert_eq!(Some("40001"), builder.build().sqlstate());
+    }
+
+    #[test]
+    fn sqlstate_translates_mysql_codes() {
+        let mut builder = Error::builder(ErrorKind::UniqueConstraintViolation {
+            constraint: DatabaseConstraint::CannotParse,
+        });
+        builder.set_original_code("1062");
+
+        assert_eq!(Some("23000"), builder.build().sqlstate());
+    }
+
+    #[test]
+    fn sqlstate_is_none_without_an_equivalent() {
+        // `ER_NO_DEFAULT_FOR_FIELD` only maps to the catch-all `HY000`.
+        let mut builder = Error::builder(ErrorKind::NotFound);
+        builder.set_original_code("1364");
+
+        assert_eq!(None, builder.build().sqlstate());
+
+        let err = Error::builder(ErrorKind::NotFound).build();
+        assert_eq!(None, err.sqlstate());
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retriable() {
+        let err = Error::builder(ErrorKind::UniqueConstraintViolation {
+            constraint: DatabaseConstraint::CannotParse,
+        })
+        .build();
+        assert!(!err.is_retriable());
+
+        let err = Error::builder(ErrorKind::QueryInvalidInput("nope".into())).build();
+        assert!(!err.is_retriable());
+
+        let err = Error::builder(ErrorKind::AuthenticationFailed {
+            user: Name::available("postgres"),
+        })
+        .build();
+        assert!(!err.is_transient());
+    }
+}
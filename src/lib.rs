@@ -74,6 +74,11 @@
 //! AST is generic for all databases and the visitors generate correct SQL
 //! syntax for the database.
 //!
+//! The builder works without any database feature enabled: with
+//! `default-features = false` the crate compiles only the [ast](ast/index.html)
+//! and [visitor](visitor/index.html) modules, also on targets such as
+//! `wasm32-unknown-unknown` where the database drivers are not available.
+//!
 //! The visitor returns the query as a string and its parameters as a vector.
 //!
 //! ```
@@ -105,9 +110,6 @@
 //! ```
 
 
-#[cfg(not(any(feature = "sqlite", feature = "postgresql", feature = "mysql", feature = "mssql")))]
-compile_error!("one of 'sqlite', 'postgresql', 'mysql' or 'mssql' features must be enabled");
-
 #[macro_use]
 mod macros;
 
@@ -117,19 +119,30 @@ extern crate metrics;
 #[cfg(feature = "bigdecimal")]
 extern crate bigdecimal_ as bigdecimal;
 
+#[cfg(feature = "opentelemetry")]
+extern crate opentelemetry_ as opentelemetry;
+
 pub mod ast;
 pub mod connector;
 pub mod error;
+pub mod lint;
 #[cfg(feature = "pooled")]
 pub mod pooled;
 pub mod prelude;
 #[cfg(feature = "serde-support")]
 pub mod serde;
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
 pub mod single;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 #[cfg(test)]
 mod tests;
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+pub mod validate;
 pub mod visitor;
 
 pub use ast::Value;
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+pub use validate::validate_connection_string;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
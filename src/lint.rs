@@ -0,0 +1,644 @@
+//! Offline portability linting for queries.
+//!
+//! When migrating a query corpus from one database to another, a lot of the
+//! hazards are mechanical: backtick identifiers, `LIMIT x, y`, boolean
+//! literals, `||` meaning different things. The [`analyze`] function runs a
+//! rule set over a query and reports the hazards it can spot.
+//!
+//! Raw SQL is checked with a best-effort token-level scan: string literals
+//! and quoted identifiers are tracked so keywords inside them don't trigger,
+//! but no attempt is made to actually parse the SQL. AST queries are checked
+//! against the capabilities of the target database instead. The rule set is
+//! meant as a foundation to extend, not as a full SQL parser.
+
+use crate::ast::{Expression, ExpressionKind, Query, Value};
+
+/// The database dialect a query was written for or is moving to.
+///
+/// Unlike [`SqlFamily`](crate::prelude::SqlFamily), the variants are not tied
+/// to the enabled connector features: linting a corpus doesn't need a
+/// connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    Mysql,
+    Sqlite,
+    Mssql,
+}
+
+/// A portability hazard found in a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortabilityWarning {
+    /// A stable identifier of the rule that fired, e.g. `backtick-identifier`.
+    pub rule: &'static str,
+    /// A human-readable description of the hazard.
+    pub message: String,
+    /// The byte offset into the raw SQL where the hazard starts. `None` for
+    /// warnings coming from an AST query.
+    pub position: Option<usize>,
+}
+
+impl PortabilityWarning {
+    fn at(rule: &'static str, message: impl Into<String>, position: usize) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    fn ast(rule: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+/// The subject of an analysis: either an AST query or a raw SQL string.
+pub enum LintSubject<'a> {
+    Query(&'a Query<'a>),
+    Sql(&'a str),
+}
+
+impl<'a> From<&'a str> for LintSubject<'a> {
+    fn from(sql: &'a str) -> Self {
+        Self::Sql(sql)
+    }
+}
+
+impl<'a> From<&'a Query<'a>> for LintSubject<'a> {
+    fn from(query: &'a Query<'a>) -> Self {
+        Self::Query(query)
+    }
+}
+
+/// Analyze a query or a raw SQL string for hazards when moving it from the
+/// source to the target dialect.
+///
+/// ```rust
+/// use quaint::lint::{analyze, Dialect};
+///
+/// let warnings = analyze("SELECT `id` FROM users", Dialect::Mysql, Dialect::Postgres);
+///
+/// assert_eq!("backtick-identifier", warnings[0].rule);
+/// assert_eq!(Some(7), warnings[0].position);
+/// ```
+pub fn analyze<'a, S>(subject: S, source: Dialect, target: Dialect) -> Vec<PortabilityWarning>
+where
+    S: Into<LintSubject<'a>>,
+{
+    match subject.into() {
+        LintSubject::Sql(sql) => analyze_sql(sql, source, target),
+        LintSubject::Query(query) => analyze_query(query, source, target),
+    }
+}
+
+/// One token of interest from the scan: a byte offset and what sits there.
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    /// An identifier quoted with backticks.
+    BacktickQuoted(usize),
+    /// A string or identifier quoted with double quotes.
+    DoubleQuoted(usize),
+    /// An identifier quoted with square brackets.
+    BracketQuoted(usize),
+    /// A single-quoted string containing a backslash.
+    BackslashInString(usize),
+    /// A bare word, lowercased for keyword comparisons.
+    Word(usize, String),
+    /// A `?` positional parameter.
+    QuestionParam(usize),
+    /// A `$n` positional parameter.
+    DollarParam(usize),
+    /// An `@Pn` positional parameter.
+    AtParam(usize),
+    /// The `||` operator.
+    PipePipe(usize),
+}
+
+/// The best-effort scan. Walks the SQL byte by byte, consuming quoted
+/// sections whole, so rules never fire on the contents of a string literal.
+fn scan(sql: &str) -> Vec<Token> {
+    let bytes = sql.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => {
+                let start = i;
+                let mut backslash = false;
+
+                i += 1;
+
+                while i < bytes.len() {
+                    match bytes[i] {
+                        b'\\' => {
+                            backslash = true;
+                            i += 2;
+                        }
+                        b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 2,
+                        b'\'' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+
+                if backslash {
+                    tokens.push(Token::BackslashInString(start));
+                }
+            }
+            b'"' => {
+                tokens.push(Token::DoubleQuoted(i));
+                i += 1;
+
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+
+                i += 1;
+            }
+            b'`' => {
+                tokens.push(Token::BacktickQuoted(i));
+                i += 1;
+
+                while i < bytes.len() && bytes[i] != b'`' {
+                    i += 1;
+                }
+
+                i += 1;
+            }
+            b'[' => {
+                tokens.push(Token::BracketQuoted(i));
+                i += 1;
+
+                while i < bytes.len() && bytes[i] != b']' {
+                    i += 1;
+                }
+
+                i += 1;
+            }
+            b'?' => {
+                tokens.push(Token::QuestionParam(i));
+                i += 1;
+            }
+            b'$' if bytes.get(i + 1).map(u8::is_ascii_digit).unwrap_or(false) => {
+                tokens.push(Token::DollarParam(i));
+                i += 1;
+            }
+            b'@' if matches!(bytes.get(i + 1), Some(b'P' | b'p')) => {
+                tokens.push(Token::AtParam(i));
+                i += 2;
+            }
+            b'|' if bytes.get(i + 1) == Some(&b'|') => {
+                tokens.push(Token::PipePipe(i));
+                i += 2;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = i;
+
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+
+                tokens.push(Token::Word(start, sql[start..i].to_ascii_lowercase()));
+            }
+            _ => i += 1,
+        }
+    }
+
+    tokens
+}
+
+/// Keywords and common builtins excluded from the identifier case folding
+/// check. Not exhaustive — the rule is a heuristic.
+const KEYWORDS: &[&str] = &[
+    "select", "from", "where", "and", "or", "not", "in", "is", "null", "insert", "into", "values", "update", "set",
+    "delete", "join", "inner", "left", "right", "full", "outer", "on", "as", "order", "by", "group", "having",
+    "limit", "offset", "union", "all", "distinct", "case", "when", "then", "else", "end", "like", "between", "exists",
+    "asc", "desc", "true", "false", "count", "sum", "avg", "min", "max", "coalesce", "concat", "cast", "returning",
+];
+
+fn analyze_sql(sql: &str, source: Dialect, target: Dialect) -> Vec<PortabilityWarning> {
+    use Dialect::*;
+
+    let mut warnings = Vec::new();
+    let tokens = scan(sql);
+
+    for token in &tokens {
+        match token {
+            Token::BacktickQuoted(pos) if !matches!(target, Mysql | Sqlite) => {
+                warnings.push(PortabilityWarning::at(
+                    "backtick-identifier",
+                    format!("Backtick-quoted identifiers are not valid on {target:?}."),
+                    *pos,
+                ));
+            }
+            Token::DoubleQuoted(pos) if source == Mysql && target != Mysql => {
+                warnings.push(PortabilityWarning::at(
+                    "double-quoted-string",
+                    format!("MySQL reads double quotes as a string literal, {target:?} as an identifier."),
+                    *pos,
+                ));
+            }
+            Token::BracketQuoted(pos) if source == Mssql && target != Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "bracket-identifier",
+                    format!("Square bracket identifiers are not valid on {target:?}."),
+                    *pos,
+                ));
+            }
+            Token::BackslashInString(pos) if source == Mysql && target == Postgres => {
+                warnings.push(PortabilityWarning::at(
+                    "backslash-escape",
+                    "PostgreSQL does not interpret backslash escapes in standard string literals.",
+                    *pos,
+                ));
+            }
+            Token::PipePipe(pos) => {
+                if source == Mysql && target != Mysql {
+                    warnings.push(PortabilityWarning::at(
+                        "pipes-as-or",
+                        format!("MySQL reads || as logical OR by default, {target:?} as string concatenation."),
+                        *pos,
+                    ));
+                } else if source != Mysql && target == Mysql {
+                    warnings.push(PortabilityWarning::at(
+                        "pipes-as-concat",
+                        "|| concatenates here, but MySQL reads it as logical OR unless PIPES_AS_CONCAT is set.",
+                        *pos,
+                    ));
+                } else if source != Mssql && target == Mssql {
+                    warnings.push(PortabilityWarning::at(
+                        "pipes-as-concat",
+                        "SQL Server concatenates with + instead of ||.",
+                        *pos,
+                    ));
+                }
+            }
+            Token::QuestionParam(pos) if target == Postgres || target == Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "parameter-style",
+                    format!("? parameters have to be rewritten for {target:?}."),
+                    *pos,
+                ));
+            }
+            Token::DollarParam(pos) if target != Postgres => {
+                warnings.push(PortabilityWarning::at(
+                    "parameter-style",
+                    format!("$n parameters have to be rewritten for {target:?}."),
+                    *pos,
+                ));
+            }
+            Token::AtParam(pos) if source == Mssql && target != Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "parameter-style",
+                    format!("@Pn parameters have to be rewritten for {target:?}."),
+                    *pos,
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    for (i, token) in tokens.iter().enumerate() {
+        let (pos, word) = match token {
+            Token::Word(pos, word) => (*pos, word.as_str()),
+            _ => continue,
+        };
+
+        let next_word = tokens.get(i + 1).and_then(|t| match t {
+            Token::Word(_, word) => Some(word.as_str()),
+            _ => None,
+        });
+
+        match word {
+            "limit" if target == Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "limit-clause",
+                    "SQL Server has no LIMIT clause; use TOP or OFFSET/FETCH.",
+                    pos,
+                ));
+            }
+            "limit" if matches!(source, Mysql | Sqlite) && !matches!(target, Mysql | Sqlite) => {
+                // `LIMIT x, y` — only the comma form is a hazard.
+                let rest = &sql[pos..];
+
+                if rest
+                    .split_whitespace()
+                    .nth(1)
+                    .map(|arg| arg.ends_with(',') || rest.contains(','))
+                    .unwrap_or(false)
+                {
+                    warnings.push(PortabilityWarning::at(
+                        "limit-comma",
+                        format!("The LIMIT offset, count form is not valid on {target:?}."),
+                        pos,
+                    ));
+                }
+            }
+            "true" | "false" if target == Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "boolean-literal",
+                    "SQL Server has no boolean literals; use 1 and 0.",
+                    pos,
+                ));
+            }
+            "concat" if target == Sqlite => {
+                warnings.push(PortabilityWarning::at(
+                    "concat-function",
+                    "SQLite has no CONCAT function before 3.44; use ||.",
+                    pos,
+                ));
+            }
+            "auto_increment" if target != Mysql => {
+                warnings.push(PortabilityWarning::at(
+                    "auto-increment",
+                    format!("AUTO_INCREMENT is MySQL-only; {target:?} uses its own identity syntax."),
+                    pos,
+                ));
+            }
+            "ilike" if target != Postgres => {
+                warnings.push(PortabilityWarning::at(
+                    "ilike",
+                    format!("ILIKE is PostgreSQL-only and not valid on {target:?}."),
+                    pos,
+                ));
+            }
+            "ifnull" if !matches!(target, Mysql | Sqlite) => {
+                warnings.push(PortabilityWarning::at(
+                    "ifnull-function",
+                    "IFNULL is not portable; use COALESCE.",
+                    pos,
+                ));
+            }
+            "isnull" if source == Mssql && target != Mssql => {
+                warnings.push(PortabilityWarning::at(
+                    "isnull-function",
+                    "ISNULL is not portable; use COALESCE.",
+                    pos,
+                ));
+            }
+            "on" if source == Mysql && target != Mysql && next_word == Some("duplicate") => {
+                warnings.push(PortabilityWarning::at(
+                    "on-duplicate-key",
+                    format!("ON DUPLICATE KEY UPDATE is MySQL-only; {target:?} needs ON CONFLICT or MERGE."),
+                    pos,
+                ));
+            }
+            word if target == Postgres && source != Postgres && is_folded_identifier(word, &sql[pos..]) => {
+                warnings.push(PortabilityWarning::at(
+                    "identifier-case-folding",
+                    format!("PostgreSQL folds the unquoted identifier `{}` to lowercase unless it is quoted.", &sql[pos..pos + word.len()]),
+                    pos,
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    warnings
+}
+
+/// A bare mixed-case word that isn't a keyword gets folded to lowercase by
+/// PostgreSQL, silently changing which identifier it refers to.
+fn is_folded_identifier(lowercased: &str, original: &str) -> bool {
+    if KEYWORDS.contains(&lowercased) {
+        return false;
+    }
+
+    let original = &original[..lowercased.len()];
+
+    original.bytes().any(|b| b.is_ascii_uppercase()) && original.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+fn analyze_query(query: &Query<'_>, _source: Dialect, target: Dialect) -> Vec<PortabilityWarning> {
+    use Dialect::*;
+
+    let mut warnings = Vec::new();
+
+    if let Query::Select(select) = query {
+        if target == Sqlite && select.joins.iter().any(|join| join_data(join).lateral) {
+            warnings.push(PortabilityWarning::ast(
+                "lateral-join",
+                "Lateral joins are not supported on SQLite.",
+            ));
+        }
+
+        if target == Mssql && select.joins.iter().any(|join| join_data(join).lateral) {
+            for join in &select.joins {
+                if join_data(join).lateral && !matches!(join, crate::ast::Join::Inner(_) | crate::ast::Join::Left(_)) {
+                    warnings.push(PortabilityWarning::ast(
+                        "lateral-join",
+                        "SQL Server supports only inner and left lateral joins (CROSS/OUTER APPLY).",
+                    ));
+                }
+            }
+        }
+
+        if target != Postgres {
+            for column in &select.columns {
+                if contains_array_value(column) {
+                    warnings.push(PortabilityWarning::ast(
+                        "array-value",
+                        format!("Array values are PostgreSQL-only and not supported on {target:?}."),
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Query::Insert(insert) = query {
+        if insert.returning.is_some() && matches!(target, Mysql | Mssql) {
+            warnings.push(PortabilityWarning::ast(
+                "insert-returning",
+                format!("INSERT ... RETURNING is not supported on {target:?}."),
+            ));
+        }
+    }
+
+    warnings
+}
+
+fn join_data<'a, 'b>(join: &'b crate::ast::Join<'a>) -> &'b crate::ast::JoinData<'a> {
+    match join {
+        crate::ast::Join::Inner(data) => data,
+        crate::ast::Join::Left(data) => data,
+        crate::ast::Join::Right(data) => data,
+        crate::ast::Join::Full(data) => data,
+    }
+}
+
+fn contains_array_value(expression: &Expression<'_>) -> bool {
+    match &expression.kind {
+        ExpressionKind::Parameterized(Value::Array(_) | Value::TypedArray(..)) => true,
+        ExpressionKind::Row(row) => row.values.iter().any(contains_array_value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(warnings: &[PortabilityWarning]) -> Vec<&'static str> {
+        warnings.iter().map(|w| w.rule).collect()
+    }
+
+    #[test]
+    fn backtick_identifiers_flagged_for_postgres() {
+        let warnings = analyze("SELECT `id` FROM `users`", Dialect::Mysql, Dialect::Postgres);
+
+        assert_eq!(
+            vec!["backtick-identifier", "backtick-identifier"],
+            rules(&warnings)
+        );
+
+        assert_eq!(Some(7), warnings[0].position);
+        assert_eq!(Some(17), warnings[1].position);
+    }
+
+    #[test]
+    fn backticks_are_fine_for_sqlite() {
+        let warnings = analyze("SELECT `id` FROM `users`", Dialect::Mysql, Dialect::Sqlite);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn keywords_inside_string_literals_do_not_fire() {
+        let warnings = analyze(
+            "SELECT id FROM logs WHERE msg = 'LIMIT `1` TRUE ILIKE'",
+            Dialect::Mysql,
+            Dialect::Mssql,
+        );
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn limit_is_flagged_for_sql_server() {
+        let warnings = analyze("SELECT id FROM users LIMIT 10", Dialect::Mysql, Dialect::Mssql);
+        assert_eq!(vec!["limit-clause"], rules(&warnings));
+        assert_eq!(Some(21), warnings[0].position);
+    }
+
+    #[test]
+    fn limit_comma_form_is_flagged_for_postgres() {
+        let warnings = analyze("SELECT id FROM users LIMIT 10, 20", Dialect::Mysql, Dialect::Postgres);
+        assert_eq!(vec!["limit-comma"], rules(&warnings));
+
+        let warnings = analyze("SELECT id FROM users LIMIT 10", Dialect::Mysql, Dialect::Postgres);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn boolean_literals_flagged_for_sql_server() {
+        let warnings = analyze(
+            "SELECT id FROM users WHERE active = true",
+            Dialect::Postgres,
+            Dialect::Mssql,
+        );
+
+        assert_eq!(vec!["boolean-literal"], rules(&warnings));
+    }
+
+    #[test]
+    fn pipes_flagged_in_both_directions() {
+        let warnings = analyze("SELECT a || b FROM t", Dialect::Postgres, Dialect::Mysql);
+        assert_eq!(vec!["pipes-as-concat"], rules(&warnings));
+
+        let warnings = analyze("SELECT a || b FROM t", Dialect::Mysql, Dialect::Postgres);
+        assert_eq!(vec!["pipes-as-or"], rules(&warnings));
+
+        let warnings = analyze("SELECT a || b FROM t", Dialect::Postgres, Dialect::Sqlite);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn parameter_styles_are_checked() {
+        let warnings = analyze("SELECT id FROM users WHERE id = ?", Dialect::Mysql, Dialect::Postgres);
+        assert_eq!(vec!["parameter-style"], rules(&warnings));
+
+        let warnings = analyze("SELECT id FROM users WHERE id = $1", Dialect::Postgres, Dialect::Mysql);
+        assert_eq!(vec!["parameter-style"], rules(&warnings));
+
+        let warnings = analyze("SELECT id FROM users WHERE id = ?", Dialect::Mysql, Dialect::Sqlite);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn mysql_only_constructs_are_flagged() {
+        let warnings = analyze(
+            "INSERT INTO t (id) VALUES (1) ON DUPLICATE KEY UPDATE id = id",
+            Dialect::Mysql,
+            Dialect::Postgres,
+        );
+
+        assert!(rules(&warnings).contains(&"on-duplicate-key"), "{warnings:?}");
+
+        let warnings = analyze(
+            "CREATE TABLE t (id int AUTO_INCREMENT)",
+            Dialect::Mysql,
+            Dialect::Postgres,
+        );
+
+        assert!(rules(&warnings).contains(&"auto-increment"), "{warnings:?}");
+    }
+
+    #[test]
+    fn ilike_is_flagged_outside_postgres() {
+        let warnings = analyze(
+            "SELECT id FROM users WHERE name ILIKE 'a%'",
+            Dialect::Postgres,
+            Dialect::Mysql,
+        );
+
+        assert_eq!(vec!["ilike"], rules(&warnings));
+    }
+
+    #[test]
+    fn mixed_case_identifiers_flagged_for_postgres_folding() {
+        let warnings = analyze("SELECT userId FROM users", Dialect::Mysql, Dialect::Postgres);
+
+        assert_eq!(vec!["identifier-case-folding"], rules(&warnings));
+        assert!(warnings[0].message.contains("userId"), "{warnings:?}");
+    }
+
+    #[test]
+    fn backslash_escapes_flagged_from_mysql_to_postgres() {
+        let warnings = analyze(
+            r"SELECT id FROM t WHERE a = 'line\n'",
+            Dialect::Mysql,
+            Dialect::Postgres,
+        );
+
+        assert_eq!(vec!["backslash-escape"], rules(&warnings));
+    }
+
+    #[test]
+    fn ast_queries_check_capabilities() {
+        use crate::ast::*;
+
+        let inner = Select::from_table("posts");
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Query::from(Select::from_table("users").inner_join(join));
+
+        let warnings = analyze(&query, Dialect::Postgres, Dialect::Sqlite);
+        assert_eq!(vec!["lateral-join"], rules(&warnings));
+
+        let insert = Insert::single_into("users").value("id", 1);
+        let query = Query::from(insert.build().returning(["id"]));
+
+        let warnings = analyze(&query, Dialect::Postgres, Dialect::Mysql);
+        assert_eq!(vec!["insert-returning"], rules(&warnings));
+    }
+}
@@ -25,6 +25,9 @@
 //!
 //! - `connection_limit` defines the maximum number of connections opened to the
 //!   database.
+//! - `slow_threshold` defined in milliseconds (`slowThreshold` on SQL Server).
+//!   If set, any statement taking longer than the given value is logged with
+//!   `tracing::warn!` as a slow query. If set to zero, no slow query logging.
 //!
 //! ## SQLite
 //!
@@ -35,6 +38,9 @@
 //! - `socket_timeout` defined in seconds. Acts as the busy timeout in
 //!   SQLite. When set, queries that are waiting for a lock to be released
 //!   will return the `Timeout` error after the defined value.
+//! - `journal_mode` either `delete`, `wal`, `memory` or `off`. Sets the
+//!   journal mode with `PRAGMA journal_mode` during connection setup. `wal`
+//!   gives much better concurrent read performance.
 //!
 //! ## PostgreSQL
 //!
@@ -68,6 +74,10 @@
 //! - `statement_cache_size`, number of prepared statements kept cached.
 //!   Defaults to 500. If `pgbouncer` mode is enabled, caching is always off.
 //! - `options` Specifies command-line options to send to the server at connection start. [Read more](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-CONNECT-OPTIONS)
+//! - `set_<name>` sets the session variable `<name>` on every new connection,
+//!   avoiding the escaping the raw `options` parameter needs. For example
+//!   `set_statement_timeout=5000&set_app.tenant=acme`. Custom variables with
+//!   a dot in the name are supported.
 //!
 //! ## MySQL
 //!
@@ -90,6 +100,9 @@
 //!   If set to zero, no timeout.
 //! - `statement_cache_size`, number of prepared statements kept cached.
 //!   Defaults to 1000. Set to 0 to disable caching.
+//! - `mysql_flavour` either `mysql` (default) or `mariadb`. When set to
+//!   `mariadb`, queries use MariaDB-only features such as `INSERT ..
+//!   RETURNING`.
 //!
 //! ## Microsoft SQL Server
 //!
@@ -157,7 +170,15 @@ use crate::{
     error::{Error, ErrorKind},
 };
 use mobc_forked::Pool;
-use std::{sync::Arc, time::Duration};
+use std::{
+    backtrace::Backtrace,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 #[cfg(feature = "sqlite")]
 use std::convert::TryFrom;
@@ -169,6 +190,11 @@ pub struct Quaint {
     pub(crate) inner: Pool<QuaintManager>,
     connection_info: Arc<ConnectionInfo>,
     pool_timeout: Option<Duration>,
+    checkout_retries: u32,
+    checkout_retry_backoff: Duration,
+    leak_detector: Option<Arc<LeakDetector>>,
+    #[cfg(feature = "postgresql")]
+    statement_metadata: Option<Arc<crate::connector::StatementMetadataCache>>,
 }
 
 /// A `Builder` to construct an instance of a [`Quaint`] pool.
@@ -182,8 +208,20 @@ pub struct Builder {
     max_idle_lifetime: Option<Duration>,
     max_lifetime: Option<Duration>,
     health_check_interval: Option<Duration>,
+    health_check_query: Option<String>,
     test_on_check_out: bool,
     pool_timeout: Option<Duration>,
+    checkout_retries: u32,
+    checkout_retry_backoff: Duration,
+    slow_query_threshold: Option<Duration>,
+    metric_prefix: Option<String>,
+    connection_events: Option<crate::connector::ConnectionEvents>,
+    max_checkout_duration: Option<Duration>,
+    reclaim_leaked: bool,
+    #[cfg(feature = "postgresql")]
+    shared_statement_metadata: Option<usize>,
+    #[cfg(feature = "postgresql")]
+    warm_up_statements: Vec<String>,
 }
 
 impl Builder {
@@ -199,8 +237,20 @@ impl Builder {
             max_idle_lifetime: None,
             max_lifetime: None,
             health_check_interval: None,
+            health_check_query: None,
             test_on_check_out: false,
             pool_timeout: None,
+            checkout_retries: 0,
+            checkout_retry_backoff: Duration::from_millis(50),
+            slow_query_threshold: None,
+            metric_prefix: None,
+            connection_events: None,
+            max_checkout_duration: None,
+            reclaim_leaked: false,
+            #[cfg(feature = "postgresql")]
+            shared_statement_metadata: None,
+            #[cfg(feature = "postgresql")]
+            warm_up_statements: Vec::new(),
         })
     }
 
@@ -235,6 +285,30 @@ impl Builder {
         self.pool_timeout = Some(pool_timeout);
     }
 
+    /// How many times a failed connection acquisition is retried in
+    /// [`check_out`] before the error is returned, covering the brief spikes
+    /// where opening a new connection fails. The total time spent is still
+    /// bounded by [`pool_timeout`]; a checkout that timed out is not retried,
+    /// as its time budget is already spent.
+    ///
+    /// - Defaults to `0`, meaning a failed acquisition errors immediately.
+    ///
+    /// [`check_out`]: struct.Quaint.html#method.check_out
+    /// [`pool_timeout`]: #method.pool_timeout
+    pub fn checkout_retries(&mut self, checkout_retries: u32) {
+        self.checkout_retries = checkout_retries;
+    }
+
+    /// The time to wait between the acquisition attempts configured with
+    /// [`checkout_retries`].
+    ///
+    /// - Defaults to 50 milliseconds.
+    ///
+    /// [`checkout_retries`]: #method.checkout_retries
+    pub fn checkout_retry_backoff(&mut self, checkout_retry_backoff: Duration) {
+        self.checkout_retry_backoff = checkout_retry_backoff;
+    }
+
     /// A time how long a connection can be kept in the pool before
     /// replaced with a new one. The reconnect happens in the next
     /// [`check_out`].
@@ -293,11 +367,188 @@ impl Builder {
         self.health_check_interval = Some(health_check_interval);
     }
 
+    /// The query sent to the database when testing a connection's health on
+    /// checkout. Only used when [`test_on_check_out`] is set to `true`. Useful
+    /// when `SELECT 1` is not wanted or not allowed, e.g. behind a connection
+    /// pooler.
+    ///
+    /// - Defaults to `SELECT 1`.
+    ///
+    /// [`test_on_check_out`]: #method.test_on_check_out
+    pub fn health_check_query(&mut self, query: String) {
+        self.health_check_query = Some(query);
+    }
+
+    /// Attaches another SQLite database file under the given alias on every
+    /// connection the pool opens, making its tables available with a
+    /// database-qualified table such as `Table::from(("alias", "users"))`.
+    /// Can be called multiple times to attach several files. Does nothing on
+    /// the other databases.
+    #[cfg(feature = "sqlite")]
+    pub fn attach_database(&mut self, alias: String, path: String) {
+        self.manager.attach_database(alias, path);
+    }
+
+    /// A duration after which a statement is logged with `tracing::warn!` as
+    /// a slow query, including the SQL, the parameter count and the connector
+    /// name. Covers every statement sent through the pool, including the ones
+    /// Quaint itself sends when setting up a connection. An application can
+    /// take over the reporting with
+    /// [`set_slow_query_callback`].
+    ///
+    /// The setting is process-wide, the last started pool wins.
+    ///
+    /// - Defaults to not set, meaning no slow query reporting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slow_query_threshold` is zero.
+    ///
+    /// [`set_slow_query_callback`]: ../connector/fn.set_slow_query_callback.html
+    pub fn slow_query_threshold(&mut self, slow_query_threshold: Duration) {
+        assert_ne!(
+            slow_query_threshold,
+            Duration::from_secs(0),
+            "slow_query_threshold must be positive"
+        );
+
+        self.slow_query_threshold = Some(slow_query_threshold);
+    }
+
+    /// A prefix replacing the default `prisma_` and `quaint_` prefixes in
+    /// the names of the metrics recorded by Quaint, for consumers that want
+    /// the metrics under their own namespace.
+    ///
+    /// The setting is process-wide and can only be set once, the first
+    /// started pool wins.
+    ///
+    /// - Defaults to not set, meaning the default prefixes are kept.
+    pub fn metric_prefix(&mut self, metric_prefix: String) {
+        self.metric_prefix = Some(metric_prefix);
+    }
+
+    /// Callbacks triggered when a physical connection is opened, closed or
+    /// errors out, receiving the connector kind and a connection serial
+    /// number. See [`ConnectionEvents`] for the available hooks.
+    ///
+    /// The callbacks are process-wide and can only be registered once, the
+    /// first started pool wins. When connecting without a pool, the same
+    /// callbacks are registered with
+    /// [`set_connection_event_callbacks`](crate::connector::set_connection_event_callbacks).
+    ///
+    /// - Defaults to not set, meaning connection errors are logged with
+    ///   `tracing::error!`.
+    ///
+    /// [`ConnectionEvents`]: crate::connector::ConnectionEvents
+    pub fn connection_events(&mut self, events: crate::connector::ConnectionEvents) {
+        self.connection_events = Some(events);
+    }
+
+    /// A duration after which a checked out connection is considered leaked.
+    /// Outstanding checkouts older than the duration are reported once with
+    /// `tracing::warn!`, including the tag given to [`check_out_tagged`], the
+    /// checkout age and — when backtraces are enabled with `RUST_BACKTRACE` —
+    /// the backtrace captured at checkout. The check runs on every
+    /// [`check_out`] and when the connection finally returns to the pool.
+    ///
+    /// - Defaults to not set, meaning no leak detection. When not set, the
+    /// only overhead per checkout is a single `Option` check.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_checkout_duration` is zero.
+    ///
+    /// [`check_out`]: struct.Quaint.html#method.check_out
+    /// [`check_out_tagged`]: struct.Quaint.html#method.check_out_tagged
+    pub fn max_checkout_duration(&mut self, max_checkout_duration: Duration) {
+        assert_ne!(
+            max_checkout_duration,
+            Duration::from_secs(0),
+            "max_checkout_duration must be positive"
+        );
+
+        self.max_checkout_duration = Some(max_checkout_duration);
+    }
+
+    /// Discard a connection that was held longer than
+    /// [`max_checkout_duration`] when it finally returns to the pool, instead
+    /// of handing it out again. Does nothing unless `max_checkout_duration`
+    /// is set.
+    ///
+    /// - Defaults to `false`.
+    ///
+    /// [`max_checkout_duration`]: #method.max_checkout_duration
+    pub fn reclaim_leaked(&mut self, reclaim_leaked: bool) {
+        self.reclaim_leaked = reclaim_leaked;
+    }
+
+    /// Share prepared statement metadata between all the connections of the
+    /// pool. After a statement was prepared once on any connection, other
+    /// connections prepare it with the server-resolved parameter types
+    /// directly instead of inferring them from the values again. The
+    /// aggregate hit and miss counters are available with
+    /// [`statement_metadata_cache`]. The capacity bounds the number of
+    /// statements tracked. Only PostgreSQL consults the cache; the
+    /// per-connection statement cache is unaffected.
+    ///
+    /// - Defaults to not set, meaning every connection prepares on its own.
+    ///
+    /// [`statement_metadata_cache`]: struct.Quaint.html#method.statement_metadata_cache
+    #[cfg(feature = "postgresql")]
+    pub fn shared_statement_metadata(&mut self, capacity: usize) {
+        self.shared_statement_metadata = Some(capacity);
+    }
+
+    /// Statements to prepare on every connection the pool opens, before the
+    /// connection is handed out for the first time. Fills the statement
+    /// cache of a fresh connection up front instead of paying the
+    /// preparation cost on the first queries after a deploy. Only PostgreSQL
+    /// warms up connections.
+    ///
+    /// - Defaults to an empty list.
+    #[cfg(feature = "postgresql")]
+    pub fn warm_up_statements(&mut self, statements: Vec<String>) {
+        self.warm_up_statements = statements;
+    }
+
     /// Consume the builder and create a new instance of a pool.
     pub fn build(self) -> Quaint {
         let connection_info = Arc::new(self.connection_info);
         Self::log_start(&connection_info, self.connection_limit);
 
+        let mut manager = self.manager;
+
+        if let Some(query) = self.health_check_query {
+            manager.set_health_check_query(query);
+        }
+
+        if let Some(threshold) = self.slow_query_threshold {
+            crate::connector::metrics::set_slow_query_threshold(Some(threshold));
+        }
+
+        if let Some(prefix) = self.metric_prefix {
+            crate::connector::metrics::set_metric_prefix(prefix);
+        }
+
+        if let Some(events) = self.connection_events {
+            crate::connector::events::set_connection_event_callbacks(events);
+        }
+
+        #[cfg(feature = "postgresql")]
+        let statement_metadata = self
+            .shared_statement_metadata
+            .map(|capacity| Arc::new(crate::connector::StatementMetadataCache::new(capacity)));
+
+        #[cfg(feature = "postgresql")]
+        if let Some(cache) = &statement_metadata {
+            manager.share_statement_metadata(cache.clone());
+        }
+
+        #[cfg(feature = "postgresql")]
+        if !self.warm_up_statements.is_empty() {
+            manager.set_warm_up_statements(self.warm_up_statements);
+        }
+
         let inner = Pool::builder()
             .max_open(if let Some(file_path) = connection_info.file_path() {
                 if file_path == ":memory:" {
@@ -324,12 +575,21 @@ impl Builder {
             .get_timeout(None) // we handle timeouts here
             .health_check_interval(self.health_check_interval)
             .test_on_check_out(self.test_on_check_out)
-            .build(self.manager);
+            .build(manager);
+
+        let leak_detector = self
+            .max_checkout_duration
+            .map(|max| Arc::new(LeakDetector::new(max, self.reclaim_leaked)));
 
         Quaint {
             inner,
             connection_info,
             pool_timeout: self.pool_timeout,
+            checkout_retries: self.checkout_retries,
+            checkout_retry_backoff: self.checkout_retry_backoff,
+            leak_detector,
+            #[cfg(feature = "postgresql")]
+            statement_metadata,
         }
     }
 
@@ -357,10 +617,7 @@ impl Quaint {
             s if s.starts_with("file") || s.starts_with("sqlite") => {
                 let params = crate::connector::SqliteParams::try_from(s)?;
 
-                let manager = QuaintManager::Sqlite {
-                    url: s.to_string(),
-                    db_name: params.db_name,
-                };
+                let manager = QuaintManager::sqlite(s);
 
                 let mut builder = Builder::new(s, manager)?;
 
@@ -386,7 +643,7 @@ impl Quaint {
                 let max_connection_lifetime = url.max_connection_lifetime();
                 let max_idle_connection_lifetime = url.max_idle_connection_lifetime();
 
-                let manager = QuaintManager::Mysql { url };
+                let manager = QuaintManager::mysql(url);
                 let mut builder = Builder::new(s, manager)?;
 
                 if let Some(limit) = connection_limit {
@@ -415,7 +672,7 @@ impl Quaint {
                 let max_connection_lifetime = url.max_connection_lifetime();
                 let max_idle_connection_lifetime = url.max_idle_connection_lifetime();
 
-                let manager = QuaintManager::Postgres { url };
+                let manager = QuaintManager::postgres(url);
                 let mut builder = Builder::new(s, manager)?;
 
                 if let Some(limit) = connection_limit {
@@ -444,7 +701,7 @@ impl Quaint {
                 let max_connection_lifetime = url.max_connection_lifetime();
                 let max_idle_connection_lifetime = url.max_idle_connection_lifetime();
 
-                let manager = QuaintManager::Mssql { url };
+                let manager = QuaintManager::mssql(url);
                 let mut builder = Builder::new(s, manager)?;
 
                 if let Some(limit) = connection_limit {
@@ -476,30 +733,89 @@ impl Quaint {
 
     /// Reserve a connection from the pool.
     pub async fn check_out(&self) -> crate::Result<PooledConnection> {
-        let res = match self.pool_timeout {
-            Some(duration) => crate::connector::metrics::check_out(self.inner.get_timeout(duration)).await,
-            None => crate::connector::metrics::check_out(self.inner.get()).await,
-        };
+        self.check_out_inner(None).await
+    }
 
-        let inner = match res {
-            Ok(conn) => conn,
-            Err(mobc_forked::Error::PoolClosed) => return Err(Error::builder(ErrorKind::PoolClosed {}).build()),
-            Err(mobc_forked::Error::Timeout) => {
-                let state = self.inner.state().await;
-                // We can use unwrap here because a pool timeout has to be set to use a connection pool
-                let timeout_duration = self.pool_timeout.unwrap();
-                return Err(
-                    Error::builder(ErrorKind::pool_timeout(state.max_open, state.in_use, timeout_duration)).build(),
-                );
-            }
-            Err(mobc_forked::Error::Inner(e)) => return Err(e),
-            Err(e @ mobc_forked::Error::BadConn) => {
-                let error = Error::builder(ErrorKind::ConnectionError(Box::new(e))).build();
-                return Err(error);
+    /// Reserve a connection from the pool, annotating the checkout with a tag
+    /// included in leak reports. See [`Builder::max_checkout_duration`].
+    ///
+    /// [`Builder::max_checkout_duration`]: struct.Builder.html#method.max_checkout_duration
+    pub async fn check_out_tagged(&self, tag: &'static str) -> crate::Result<PooledConnection> {
+        self.check_out_inner(Some(tag)).await
+    }
+
+    async fn check_out_inner(&self, tag: Option<&'static str>) -> crate::Result<PooledConnection> {
+        if let Some(detector) = self.leak_detector.as_ref() {
+            detector.scan();
+        }
+
+        let deadline = self.pool_timeout.map(|timeout| Instant::now() + timeout);
+        let mut retries_left = self.checkout_retries;
+
+        let inner = loop {
+            // Each attempt only gets the time left until the deadline, so
+            // the retries never exceed the configured pool timeout in total.
+            let res = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    crate::connector::metrics::check_out(self.inner.get_timeout(remaining)).await
+                }
+                None => crate::connector::metrics::check_out(self.inner.get()).await,
+            };
+
+            match res {
+                Ok(conn) => break conn,
+                Err(mobc_forked::Error::PoolClosed) => return Err(Error::builder(ErrorKind::PoolClosed {}).build()),
+                Err(mobc_forked::Error::Timeout) => {
+                    // The whole time budget is spent waiting, so a retry
+                    // could only time out again.
+                    increment_counter!(crate::connector::metrics::metric_name(
+                        "quaint",
+                        "pool_checkout_timeouts_total"
+                    ));
+
+                    let state = self.inner.state().await;
+                    // We can use unwrap here because a pool timeout has to be set to use a connection pool
+                    let timeout_duration = self.pool_timeout.unwrap();
+                    return Err(
+                        Error::builder(ErrorKind::pool_timeout(state.max_open, state.in_use, timeout_duration)).build(),
+                    );
+                }
+                Err(e) => {
+                    let out_of_budget =
+                        deadline.is_some_and(|deadline| Instant::now() + self.checkout_retry_backoff >= deadline);
+
+                    if retries_left == 0 || out_of_budget {
+                        increment_counter!(crate::connector::metrics::metric_name(
+                            "quaint",
+                            "pool_checkout_errors_total"
+                        ));
+
+                        return Err(match e {
+                            mobc_forked::Error::Inner(e) => e,
+                            e => Error::builder(ErrorKind::ConnectionError(Box::new(e))).build(),
+                        });
+                    }
+
+                    retries_left -= 1;
+                    tokio::time::sleep(self.checkout_retry_backoff).await;
+                }
             }
         };
 
-        Ok(PooledConnection { inner })
+        let leak_guard = self.leak_detector.as_ref().map(|detector| detector.register(tag));
+
+        Ok(PooledConnection { inner, leak_guard })
+    }
+
+    /// The pool-level prepared statement metadata cache with its aggregate
+    /// hit and miss counters. `None` unless the pool was built with
+    /// [`shared_statement_metadata`].
+    ///
+    /// [`shared_statement_metadata`]: struct.Builder.html#method.shared_statement_metadata
+    #[cfg(feature = "postgresql")]
+    pub fn statement_metadata_cache(&self) -> Option<&crate::connector::StatementMetadataCache> {
+        self.statement_metadata.as_deref()
     }
 
     /// Info about the connection and underlying database.
@@ -507,3 +823,143 @@ impl Quaint {
         &self.connection_info
     }
 }
+
+/// Bookkeeping for leak detection. Holds the configuration and the registry
+/// of outstanding checkouts, shared between the pool and the guards carried
+/// by checked out connections.
+pub(crate) struct LeakDetector {
+    max_checkout_duration: Duration,
+    reclaim_leaked: bool,
+    next_id: AtomicU64,
+    outstanding: Mutex<HashMap<u64, Checkout>>,
+}
+
+struct Checkout {
+    checked_out_at: Instant,
+    tag: Option<&'static str>,
+    backtrace: Backtrace,
+    warned: bool,
+}
+
+impl LeakDetector {
+    fn new(max_checkout_duration: Duration, reclaim_leaked: bool) -> Self {
+        Self {
+            max_checkout_duration,
+            reclaim_leaked,
+            next_id: AtomicU64::new(0),
+            outstanding: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Registers a checkout, returning the guard carried by the connection
+    /// until it goes back to the pool.
+    fn register(self: &Arc<Self>, tag: Option<&'static str>) -> LeakGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let checked_out_at = Instant::now();
+
+        let checkout = Checkout {
+            checked_out_at,
+            tag,
+            backtrace: Backtrace::capture(),
+            warned: false,
+        };
+
+        self.outstanding.lock().unwrap().insert(id, checkout);
+
+        LeakGuard {
+            detector: self.clone(),
+            id,
+            tag,
+            checked_out_at,
+        }
+    }
+
+    /// Warns once for every outstanding checkout held longer than the
+    /// configured maximum.
+    fn scan(&self) {
+        let mut outstanding = self.outstanding.lock().unwrap();
+
+        for checkout in outstanding.values_mut() {
+            let age = checkout.checked_out_at.elapsed();
+
+            if age > self.max_checkout_duration && !checkout.warned {
+                checkout.warned = true;
+
+                tracing::warn!(
+                    message = "A connection has been checked out longer than the maximum checkout duration and may have leaked.",
+                    tag = checkout.tag.unwrap_or("<untagged>"),
+                    age_ms = age.as_millis() as u64,
+                    backtrace = %checkout.backtrace,
+                );
+            }
+        }
+    }
+}
+
+/// Carried by a [`PooledConnection`], deregistering the checkout when the
+/// connection goes back to the pool.
+pub(crate) struct LeakGuard {
+    detector: Arc<LeakDetector>,
+    id: u64,
+    tag: Option<&'static str>,
+    checked_out_at: Instant,
+}
+
+impl LeakGuard {
+    pub(crate) fn age(&self) -> Duration {
+        self.checked_out_at.elapsed()
+    }
+
+    pub(crate) fn max_checkout_duration(&self) -> Duration {
+        self.detector.max_checkout_duration
+    }
+
+    pub(crate) fn tag(&self) -> Option<&'static str> {
+        self.tag
+    }
+
+    pub(crate) fn reclaim(&self) -> bool {
+        self.detector.reclaim_leaked
+    }
+}
+
+impl Drop for LeakGuard {
+    fn drop(&mut self) {
+        self.detector.outstanding.lock().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leak_detector_tracks_outstanding_checkouts() {
+        let detector = Arc::new(LeakDetector::new(Duration::from_secs(10), false));
+
+        let first = detector.register(Some("job:billing"));
+        let second = detector.register(None);
+
+        assert_eq!(2, detector.outstanding.lock().unwrap().len());
+        assert_eq!(Some("job:billing"), first.tag());
+
+        drop(first);
+        drop(second);
+
+        assert!(detector.outstanding.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn leak_detector_scan_warns_only_once_per_checkout() {
+        let detector = Arc::new(LeakDetector::new(Duration::from_millis(1), false));
+        let guard = detector.register(Some("job:billing"));
+
+        std::thread::sleep(Duration::from_millis(5));
+
+        detector.scan();
+        assert!(detector.outstanding.lock().unwrap()[&guard.id].warned);
+
+        detector.scan();
+        assert!(detector.outstanding.lock().unwrap()[&guard.id].warned);
+    }
+}
@@ -11,11 +11,48 @@ use crate::{
 };
 use async_trait::async_trait;
 use mobc_forked::{Connection as MobcPooled, Manager};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 /// A connection from the pool. Implements
 /// [Queryable](connector/trait.Queryable.html).
 pub struct PooledConnection {
     pub(crate) inner: MobcPooled<QuaintManager>,
+    pub(crate) leak_guard: Option<super::LeakGuard>,
+}
+
+impl PooledConnection {
+    /// The elapsed time since the underlying connection was established.
+    pub fn age(&self) -> Duration {
+        self.inner.age()
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(guard) = &self.leak_guard else { return };
+
+        let age = guard.age();
+
+        if age > guard.max_checkout_duration() {
+            tracing::warn!(
+                message = "A connection was returned to the pool after exceeding the maximum checkout duration.",
+                tag = guard.tag().unwrap_or("<untagged>"),
+                age_ms = age.as_millis() as u64,
+            );
+
+            if guard.reclaim() {
+                // Marking the connection makes the pool discard it on
+                // check-in instead of handing it out again.
+                self.inner.mark_leaked();
+            }
+        }
+    }
 }
 
 impl TransactionCapable for PooledConnection {}
@@ -26,6 +63,10 @@ impl Queryable for PooledConnection {
         self.inner.query(q).await
     }
 
+    fn render<'b>(&self, q: ast::Query<'b>) -> crate::Result<(String, Vec<ast::Value<'b>>)> {
+        self.inner.render(q)
+    }
+
     async fn query_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<connector::ResultSet> {
         self.inner.query_raw(sql, params).await
     }
@@ -34,6 +75,10 @@ impl Queryable for PooledConnection {
         self.inner.query_raw_typed(sql, params).await
     }
 
+    async fn query_multi_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<Vec<connector::BatchResult>> {
+        self.inner.query_multi_raw(sql, params).await
+    }
+
     async fn execute(&self, q: ast::Query<'_>) -> crate::Result<u64> {
         self.inner.execute(q).await
     }
@@ -54,10 +99,42 @@ impl Queryable for PooledConnection {
         self.inner.version().await
     }
 
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    async fn insert_returning(&self, insert: ast::Insert<'_>, pk_column: &str) -> crate::Result<ast::Value<'static>> {
+        self.inner.insert_returning(insert, pk_column).await
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        self.inner.table_exists(table, schema).await
+    }
+
+    async fn column_names(&self, table: &str) -> crate::Result<Vec<String>> {
+        self.inner.column_names(table).await
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<crate::connector::ColumnInfo>> {
+        self.inner.column_info(table).await
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<crate::connector::ForeignKeyInfo>> {
+        self.inner.foreign_keys(table).await
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<crate::connector::IndexInfo>> {
+        self.inner.indexes(table).await
+    }
+
     fn is_healthy(&self) -> bool {
         self.inner.is_healthy()
     }
 
+    fn in_transaction(&self) -> bool {
+        self.inner.in_transaction()
+    }
+
     async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
         self.inner.server_reset_query(tx).await
     }
@@ -76,7 +153,18 @@ impl Queryable for PooledConnection {
 }
 
 #[doc(hidden)]
-pub enum QuaintManager {
+pub struct QuaintManager {
+    kind: ManagerKind,
+    health_check_query: Option<String>,
+    #[cfg(feature = "sqlite")]
+    attached_databases: Vec<(String, String)>,
+    #[cfg(feature = "postgresql")]
+    statement_metadata: Option<std::sync::Arc<crate::connector::StatementMetadataCache>>,
+    #[cfg(feature = "postgresql")]
+    warm_up_statements: Vec<String>,
+}
+
+enum ManagerKind {
     #[cfg(feature = "mysql")]
     Mysql { url: MysqlUrl },
 
@@ -84,60 +172,385 @@ pub enum QuaintManager {
     Postgres { url: PostgresUrl },
 
     #[cfg(feature = "sqlite")]
-    Sqlite { url: String, db_name: String },
+    Sqlite { url: String },
 
     #[cfg(feature = "mssql")]
     Mssql { url: MssqlUrl },
 }
 
+impl QuaintManager {
+    fn new(kind: ManagerKind) -> Self {
+        Self {
+            kind,
+            health_check_query: None,
+            #[cfg(feature = "sqlite")]
+            attached_databases: Vec::new(),
+            #[cfg(feature = "postgresql")]
+            statement_metadata: None,
+            #[cfg(feature = "postgresql")]
+            warm_up_statements: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "mysql")]
+    pub fn mysql(url: MysqlUrl) -> Self {
+        Self::new(ManagerKind::Mysql { url })
+    }
+
+    #[cfg(feature = "postgresql")]
+    pub fn postgres(url: PostgresUrl) -> Self {
+        Self::new(ManagerKind::Postgres { url })
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(url: impl Into<String>) -> Self {
+        Self::new(ManagerKind::Sqlite { url: url.into() })
+    }
+
+    #[cfg(feature = "mssql")]
+    pub fn mssql(url: MssqlUrl) -> Self {
+        Self::new(ManagerKind::Mssql { url })
+    }
+
+    pub(crate) fn set_health_check_query(&mut self, query: String) {
+        self.health_check_query = Some(query);
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn attach_database(&mut self, alias: String, path: String) {
+        self.attached_databases.push((alias, path));
+    }
+
+    #[cfg(feature = "postgresql")]
+    pub(crate) fn share_statement_metadata(&mut self, cache: std::sync::Arc<crate::connector::StatementMetadataCache>) {
+        self.statement_metadata = Some(cache);
+    }
+
+    #[cfg(feature = "postgresql")]
+    pub(crate) fn set_warm_up_statements(&mut self, statements: Vec<String>) {
+        self.warm_up_statements = statements;
+    }
+
+    fn health_check_query(&self) -> &str {
+        self.health_check_query.as_deref().unwrap_or("SELECT 1")
+    }
+}
+
+/// Server-side resources owned by a connection that outlive it if nobody
+/// cleans them up: prepared transaction gids, session-level advisory lock
+/// keys and temporary tables.
+#[derive(Debug, Default)]
+struct Resources {
+    prepared_transactions: Vec<String>,
+    advisory_locks: Vec<i64>,
+    temp_tables: Vec<String>,
+}
+
+impl Resources {
+    fn is_empty(&self) -> bool {
+        self.prepared_transactions.is_empty() && self.advisory_locks.is_empty() && self.temp_tables.is_empty()
+    }
+}
+
+/// A connection held by the pool. Stamps the underlying connection with its
+/// creation time, so the age of a connection can be inspected after check out
+/// and used for lifetime-based eviction.
+pub struct ManagedConnection {
+    inner: Option<Box<dyn Queryable>>,
+    resources: Mutex<Resources>,
+    created_at: Instant,
+    leaked: AtomicBool,
+}
+
+impl ManagedConnection {
+    fn new(inner: Box<dyn Queryable>) -> Self {
+        Self {
+            inner: Some(inner),
+            resources: Mutex::new(Resources::default()),
+            created_at: Instant::now(),
+            leaked: AtomicBool::new(false),
+        }
+    }
+
+    /// Flag the connection as having leaked from a checkout, making the pool
+    /// discard it on check-in instead of reusing it.
+    pub(crate) fn mark_leaked(&self) {
+        self.leaked.store(true, Ordering::Relaxed);
+    }
+
+    fn is_leaked(&self) -> bool {
+        self.leaked.load(Ordering::Relaxed)
+    }
+
+    fn conn(&self) -> &dyn Queryable {
+        self.inner.as_deref().expect("connection already taken for cleanup")
+    }
+
+    /// The elapsed time since the connection was established.
+    pub fn age(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
+    /// Register a prepared transaction gid owned by this connection. If the
+    /// connection is discarded before [`untrack_prepared_transaction`] is
+    /// called, the transaction is rolled back with `ROLLBACK PREPARED`.
+    ///
+    /// [`untrack_prepared_transaction`]: Self::untrack_prepared_transaction
+    pub fn track_prepared_transaction(&self, gid: impl Into<String>) {
+        self.resources.lock().unwrap().prepared_transactions.push(gid.into());
+    }
+
+    /// Remove a prepared transaction gid from the registry after it was
+    /// committed or rolled back.
+    pub fn untrack_prepared_transaction(&self, gid: &str) {
+        self.resources.lock().unwrap().prepared_transactions.retain(|g| g != gid);
+    }
+
+    /// Register a session-level advisory lock key held by this connection.
+    /// If the connection is discarded while the key is still registered, the
+    /// locks are released with `pg_advisory_unlock_all` before the connection
+    /// closes.
+    pub fn track_advisory_lock(&self, key: i64) {
+        self.resources.lock().unwrap().advisory_locks.push(key);
+    }
+
+    /// Remove an advisory lock key from the registry after it was unlocked.
+    pub fn untrack_advisory_lock(&self, key: i64) {
+        self.resources.lock().unwrap().advisory_locks.retain(|k| *k != key);
+    }
+
+    /// Register a temporary table created on this connection. If the
+    /// connection is discarded while tables are still registered, they are
+    /// dropped with `DISCARD TEMP` before the connection closes.
+    pub fn track_temp_table(&self, name: impl Into<String>) {
+        self.resources.lock().unwrap().temp_tables.push(name.into());
+    }
+
+    /// Remove a temporary table from the registry after it was dropped.
+    pub fn untrack_temp_table(&self, name: &str) {
+        self.resources.lock().unwrap().temp_tables.retain(|t| t != name);
+    }
+}
+
+impl Drop for ManagedConnection {
+    fn drop(&mut self) {
+        let resources = std::mem::take(&mut *self.resources.lock().unwrap());
+
+        if resources.is_empty() {
+            return;
+        }
+
+        let Some(conn) = self.inner.take() else { return };
+
+        // The pool discards connections from synchronous code, so the
+        // cleanup has to run as a detached task. Best-effort: with no
+        // runtime around, the resources are only reported.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(cleanup_orphaned_resources(conn, resources));
+            }
+            Err(_) => {
+                tracing::warn!(
+                    message = "Discarding a connection with orphaned server resources and no runtime to clean them up.",
+                    resources = ?resources,
+                );
+            }
+        }
+    }
+}
+
+/// Runs on the discarded connection before it closes, rolling back orphaned
+/// prepared transactions and releasing session-level state.
+async fn cleanup_orphaned_resources(conn: Box<dyn Queryable>, resources: Resources) {
+    for gid in &resources.prepared_transactions {
+        tracing::warn!(
+            message = "Rolling back an orphaned prepared transaction.",
+            gid = gid.as_str(),
+        );
+
+        let stmt = format!("ROLLBACK PREPARED '{}'", gid.replace('\'', "''"));
+
+        if let Err(e) = conn.raw_cmd(&stmt).await {
+            tracing::warn!(message = "Could not roll back an orphaned prepared transaction.", gid = gid.as_str(), error = %e);
+        }
+    }
+
+    if !resources.advisory_locks.is_empty() {
+        tracing::warn!(
+            message = "Releasing orphaned session-level advisory locks.",
+            keys = ?resources.advisory_locks,
+        );
+
+        if let Err(e) = conn.raw_cmd("SELECT pg_advisory_unlock_all()").await {
+            tracing::warn!(message = "Could not release orphaned advisory locks.", error = %e);
+        }
+    }
+
+    if !resources.temp_tables.is_empty() {
+        tracing::warn!(
+            message = "Dropping orphaned temporary tables.",
+            tables = ?resources.temp_tables,
+        );
+
+        if let Err(e) = conn.raw_cmd("DISCARD TEMP").await {
+            tracing::warn!(message = "Could not drop orphaned temporary tables.", error = %e);
+        }
+    }
+}
+
+#[async_trait]
+impl Queryable for ManagedConnection {
+    async fn query(&self, q: ast::Query<'_>) -> crate::Result<connector::ResultSet> {
+        self.conn().query(q).await
+    }
+
+    fn render<'b>(&self, q: ast::Query<'b>) -> crate::Result<(String, Vec<ast::Value<'b>>)> {
+        self.conn().render(q)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<connector::ResultSet> {
+        self.conn().query_raw(sql, params).await
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<connector::ResultSet> {
+        self.conn().query_raw_typed(sql, params).await
+    }
+
+    async fn query_multi_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<Vec<connector::BatchResult>> {
+        self.conn().query_multi_raw(sql, params).await
+    }
+
+    async fn execute(&self, q: ast::Query<'_>) -> crate::Result<u64> {
+        self.conn().execute(q).await
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<u64> {
+        self.conn().execute_raw(sql, params).await
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<u64> {
+        self.conn().execute_raw_typed(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.conn().raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.conn().version().await
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.conn().is_healthy()
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.conn().in_transaction()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
+        self.conn().server_reset_query(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.conn().begin_statement()
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
+        self.conn().set_tx_isolation_level(isolation_level).await
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        self.conn().requires_isolation_first()
+    }
+}
+
 #[async_trait]
 impl Manager for QuaintManager {
-    type Connection = Box<dyn Queryable>;
+    type Connection = ManagedConnection;
     type Error = Error;
 
     async fn connect(&self) -> crate::Result<Self::Connection> {
-        let conn = match self {
+        let conn = match &self.kind {
             #[cfg(feature = "sqlite")]
-            QuaintManager::Sqlite { url, .. } => {
+            ManagerKind::Sqlite { url } => {
                 use crate::connector::Sqlite;
 
                 let conn = Sqlite::new(url)?;
 
-                Ok(Box::new(conn) as Self::Connection)
+                // Attachments live on the connection, so every connection the
+                // pool opens has to re-attach the configured databases.
+                for (alias, path) in self.attached_databases.iter() {
+                    conn.attach_database(std::path::Path::new(path), alias).await?;
+                }
+
+                Ok(Box::new(conn) as Box<dyn Queryable>)
             }
 
             #[cfg(feature = "mysql")]
-            QuaintManager::Mysql { url } => {
+            ManagerKind::Mysql { url } => {
                 use crate::connector::Mysql;
-                Ok(Box::new(Mysql::new(url.clone()).await?) as Self::Connection)
+                Ok(Box::new(Mysql::new(url.clone()).await?) as Box<dyn Queryable>)
             }
 
             #[cfg(feature = "postgresql")]
-            QuaintManager::Postgres { url } => {
+            ManagerKind::Postgres { url } => {
                 use crate::connector::PostgreSql;
-                Ok(Box::new(PostgreSql::new(url.clone()).await?) as Self::Connection)
+
+                let mut conn = PostgreSql::new(url.clone()).await?;
+
+                if let Some(cache) = &self.statement_metadata {
+                    conn = conn.with_statement_metadata_cache(cache.clone());
+                }
+
+                if !self.warm_up_statements.is_empty() {
+                    conn.warm_up(&self.warm_up_statements).await?;
+                }
+
+                Ok(Box::new(conn) as Box<dyn Queryable>)
             }
 
             #[cfg(feature = "mssql")]
-            QuaintManager::Mssql { url } => {
+            ManagerKind::Mssql { url } => {
                 use crate::connector::Mssql;
-                Ok(Box::new(Mssql::new(url.clone()).await?) as Self::Connection)
+                Ok(Box::new(Mssql::new(url.clone()).await?) as Box<dyn Queryable>)
             }
         };
 
         conn.iter()
             .for_each(|_| tracing::debug!("Acquired database connection."));
 
-        conn
+        conn.map(ManagedConnection::new)
     }
 
     async fn check(&self, conn: Self::Connection) -> crate::Result<Self::Connection> {
-        conn.raw_cmd("SELECT 1").await?;
-        Ok(conn)
+        increment_counter!("prisma_pool_health_checks_total");
+
+        match conn.raw_cmd(self.health_check_query()).await {
+            Ok(()) => Ok(conn),
+            Err(e) => {
+                // The pool discards the connection when the check errors and
+                // replaces it within the same checkout.
+                increment_counter!("prisma_pool_health_check_evictions_total");
+                Err(e)
+            }
+        }
     }
 
     fn validate(&self, conn: &mut Self::Connection) -> bool {
-        conn.is_healthy()
+        // The pool library enforces `max_lifetime` on its own timer. Checking
+        // the age here as well guarantees a connection past its lifetime is
+        // discarded on the next checkout, even when the reaper hasn't run yet.
+        let lifetime_ok = match &self.kind {
+            #[cfg(feature = "postgresql")]
+            ManagerKind::Postgres { url } => url
+                .max_connection_lifetime()
+                .map(|limit| conn.age() < limit)
+                .unwrap_or(true),
+            _ => true,
+        };
+
+        lifetime_ok && !conn.is_leaked() && conn.is_healthy()
     }
 }
 
@@ -191,6 +604,192 @@ mod tests {
         assert_eq!(10, pool.capacity().await as usize);
     }
 
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn psql_pool_replaces_connections_past_max_lifetime() {
+        use std::time::Duration;
+
+        let conn_string = format!(
+            "{}?connection_limit=1&max_connection_lifetime=1",
+            std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.")
+        );
+
+        let pool = Quaint::builder(&conn_string).unwrap().build();
+
+        let conn = pool.check_out().await.unwrap();
+        drop(conn);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // The idle connection is now past its lifetime, so the next checkout
+        // must discard it and hand out a freshly established one.
+        let conn = pool.check_out().await.unwrap();
+        assert!(conn.age() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn psql_pool_health_checks_with_a_custom_query() {
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+
+        let mut builder = Quaint::builder(&conn_string).unwrap();
+        builder.health_check_query("SELECT 1 FROM pg_stat_activity LIMIT 1".into());
+        builder.test_on_check_out(true);
+
+        let pool = builder.build();
+
+        let conn = pool.check_out().await.unwrap();
+        drop(conn);
+
+        // The second checkout runs the configured probe against the idle
+        // connection.
+        pool.check_out().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn psql_pool_errors_out_when_the_health_check_query_fails() {
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+
+        let mut builder = Quaint::builder(&conn_string).unwrap();
+        builder.health_check_query("SELECT quaint_not_a_column".into());
+        builder.test_on_check_out(true);
+
+        let pool = builder.build();
+
+        let conn = pool.check_out().await.unwrap();
+        drop(conn);
+
+        // A probe failing on checkout evicts the connection. The replacement
+        // fails the same probe, so the checkout itself errors out.
+        assert!(pool.check_out().await.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn dropping_a_connection_rolls_back_orphaned_prepared_transactions() {
+        use super::QuaintManager;
+        use crate::{connector::Queryable, prelude::*, single::Quaint as SingleQuaint};
+        use mobc_forked::Manager;
+        use std::time::Duration;
+
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+
+        let url = crate::connector::PostgresUrl::new(url::Url::parse(&conn_string).unwrap()).unwrap();
+        let manager = QuaintManager::postgres(url);
+
+        let conn = manager.connect().await.unwrap();
+
+        conn.raw_cmd("BEGIN").await.unwrap();
+        conn.raw_cmd("PREPARE TRANSACTION 'quaint_cleanup_test'").await.unwrap();
+        conn.track_prepared_transaction("quaint_cleanup_test");
+
+        drop(conn);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let observer = SingleQuaint::new(&conn_string).await.unwrap();
+
+        let select = Select::from_table("pg_prepared_xacts").so_that("gid".equals("quaint_cleanup_test"));
+        let rows = observer.select(select).await.unwrap();
+
+        assert!(rows.is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn dropping_a_connection_releases_orphaned_advisory_locks() {
+        use super::QuaintManager;
+        use crate::connector::Queryable;
+        use mobc_forked::Manager;
+        use std::time::Duration;
+
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+
+        let url = crate::connector::PostgresUrl::new(url::Url::parse(&conn_string).unwrap()).unwrap();
+        let manager = QuaintManager::postgres(url);
+
+        let conn = manager.connect().await.unwrap();
+
+        conn.raw_cmd("SELECT pg_advisory_lock(4200)").await.unwrap();
+        conn.track_advisory_lock(4200);
+
+        drop(conn);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let conn = manager.connect().await.unwrap();
+
+        // The lock is free again, so taking it from another session succeeds
+        // immediately.
+        let rows = conn.query_raw("SELECT pg_try_advisory_lock(4200)", &[]).await.unwrap();
+        assert_eq!(Some(true), rows.into_single().unwrap()[0].as_bool());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn dropping_a_connection_discards_orphaned_temp_tables() {
+        use super::QuaintManager;
+        use crate::connector::Queryable;
+        use mobc_forked::Manager;
+        use std::time::Duration;
+
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+
+        let url = crate::connector::PostgresUrl::new(url::Url::parse(&conn_string).unwrap()).unwrap();
+        let manager = QuaintManager::postgres(url);
+
+        let conn = manager.connect().await.unwrap();
+
+        conn.raw_cmd("CREATE TEMPORARY TABLE quaint_cleanup_test (id int)")
+            .await
+            .unwrap();
+
+        conn.track_temp_table("quaint_cleanup_test");
+
+        // The cleanup running to completion before the connection closes is
+        // the interesting part; the drop alone proves nothing, since the
+        // server removes temporary tables with the session anyway.
+        drop(conn);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql")]
+    async fn psql_pool_shares_statement_metadata_between_connections() {
+        use crate::{ast::Value, connector::Queryable};
+
+        let conn_string = format!(
+            "{}?connection_limit=2",
+            std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.")
+        );
+
+        let mut builder = Quaint::builder(&conn_string).unwrap();
+        builder.shared_statement_metadata(100);
+        builder.warm_up_statements(vec!["SELECT 1".into()]);
+
+        let pool = builder.build();
+
+        let conn_a = pool.check_out().await.unwrap();
+        let conn_b = pool.check_out().await.unwrap();
+
+        conn_a
+            .query_raw("SELECT $1::int4 AS value", &[Value::int32(1)])
+            .await
+            .unwrap();
+
+        // The second connection finds the parameter types the first one
+        // resolved in the shared cache.
+        conn_b
+            .query_raw("SELECT $1::int4 AS value", &[Value::int32(1)])
+            .await
+            .unwrap();
+
+        let cache = pool.statement_metadata_cache().unwrap();
+
+        assert!(cache.hits() >= 1);
+        assert!(cache.misses() >= 1);
+        assert_eq!(Some(vec!["value".to_string()]), cache.columns("SELECT $1::int4 AS value"));
+    }
+
     #[tokio::test]
     #[cfg(feature = "mssql")]
     async fn mssql_default_connection_limit() {
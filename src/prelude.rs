@@ -1,6 +1,8 @@
 //! A "prelude" for users of the `quaint` crate.
 pub use crate::ast::*;
+#[cfg(any(feature = "mssql", feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+pub use crate::connector::ConnectionInfo;
 pub use crate::connector::{
-    ConnectionInfo, Queryable, ResultRow, ResultSet, SqlFamily, Transaction, TransactionCapable,
+    IsolationLevel, Queryable, ResultRow, ResultSet, SqlFamily, Transaction, TransactionCapable,
 };
 pub use crate::{col, val, values};
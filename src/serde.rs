@@ -112,12 +112,22 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             Value::Text(None) => visitor.visit_none(),
             Value::Bytes(Some(bytes)) => visitor.visit_bytes(bytes.as_ref()),
             Value::Bytes(None) => visitor.visit_none(),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(Some(geom)) => visitor.visit_bytes(&geom.0),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(None) => visitor.visit_none(),
+            Value::IpAddr(Some(ip)) => visitor.visit_string(ip.to_string()),
+            Value::IpAddr(None) => visitor.visit_none(),
+            Value::BitString(Some(bits)) => visitor.visit_string(bits.into_owned()),
+            Value::BitString(None) => visitor.visit_none(),
             Value::Enum(Some(s)) => visitor.visit_string(s.into_owned()),
             Value::Enum(None) => visitor.visit_none(),
             Value::Int32(Some(i)) => visitor.visit_i32(i),
             Value::Int32(None) => visitor.visit_none(),
             Value::Int64(Some(i)) => visitor.visit_i64(i),
             Value::Int64(None) => visitor.visit_none(),
+            Value::Int128(Some(i)) => visitor.visit_i128(i),
+            Value::Int128(None) => visitor.visit_none(),
             Value::Boolean(Some(b)) => visitor.visit_bool(b),
             Value::Boolean(None) => visitor.visit_none(),
             Value::Char(Some(c)) => visitor.visit_char(c),
@@ -154,10 +164,15 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             Value::Xml(None) => visitor.visit_none(),
 
             #[cfg(feature = "chrono")]
-            Value::DateTime(Some(dt)) => visitor.visit_string(dt.to_rfc3339()),
+            Value::DateTime(Some(dt)) => visitor.visit_string(format!("{dt}")),
             #[cfg(feature = "chrono")]
             Value::DateTime(None) => visitor.visit_none(),
 
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(Some(dt)) => visitor.visit_string(dt.to_rfc3339()),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(None) => visitor.visit_none(),
+
             #[cfg(feature = "chrono")]
             Value::Date(Some(d)) => visitor.visit_string(format!("{d}")),
             #[cfg(feature = "chrono")]
@@ -168,11 +183,11 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             #[cfg(feature = "chrono")]
             Value::Time(None) => visitor.visit_none(),
 
-            Value::Array(Some(values)) => {
+            Value::Array(Some(values)) | Value::TypedArray(Some(values), _) => {
                 let deserializer = serde::de::value::SeqDeserializer::new(values.into_iter());
                 visitor.visit_seq(deserializer)
             }
-            Value::Array(None) => visitor.visit_none(),
+            Value::Array(None) | Value::TypedArray(None, _) => visitor.visit_none(),
         }
     }
 
@@ -304,7 +319,7 @@ mod tests {
     fn deserialize_cat() {
         let row = make_row(vec![
             ("age", Value::numeric("18.800001".parse().unwrap())),
-            ("birthday", Value::datetime("2019-08-01T20:00:00Z".parse().unwrap())),
+            ("birthday", Value::datetime_utc("2019-08-01T20:00:00Z".parse().unwrap())),
             (
                 "human",
                 Value::json(serde_json::json!({
@@ -196,6 +196,10 @@ impl Queryable for Quaint {
         self.inner.query(q).await
     }
 
+    fn render<'b>(&self, q: ast::Query<'b>) -> crate::Result<(String, Vec<ast::Value<'b>>)> {
+        self.inner.render(q)
+    }
+
     async fn query_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<connector::ResultSet> {
         self.inner.query_raw(sql, params).await
     }
@@ -204,6 +208,10 @@ impl Queryable for Quaint {
         self.inner.query_raw_typed(sql, params).await
     }
 
+    async fn query_multi_raw(&self, sql: &str, params: &[ast::Value<'_>]) -> crate::Result<Vec<connector::BatchResult>> {
+        self.inner.query_multi_raw(sql, params).await
+    }
+
     async fn execute(&self, q: ast::Query<'_>) -> crate::Result<u64> {
         self.inner.execute(q).await
     }
@@ -224,10 +232,42 @@ impl Queryable for Quaint {
         self.inner.version().await
     }
 
+    async fn capabilities(&self) -> crate::Result<crate::visitor::Capabilities> {
+        self.inner.capabilities().await
+    }
+
+    async fn insert_returning(&self, insert: ast::Insert<'_>, pk_column: &str) -> crate::Result<ast::Value<'static>> {
+        self.inner.insert_returning(insert, pk_column).await
+    }
+
+    async fn table_exists(&self, table: &str, schema: Option<&str>) -> crate::Result<bool> {
+        self.inner.table_exists(table, schema).await
+    }
+
+    async fn column_names(&self, table: &str) -> crate::Result<Vec<String>> {
+        self.inner.column_names(table).await
+    }
+
+    async fn column_info(&self, table: &str) -> crate::Result<Vec<crate::connector::ColumnInfo>> {
+        self.inner.column_info(table).await
+    }
+
+    async fn foreign_keys(&self, table: &str) -> crate::Result<Vec<crate::connector::ForeignKeyInfo>> {
+        self.inner.foreign_keys(table).await
+    }
+
+    async fn indexes(&self, table: &str) -> crate::Result<Vec<crate::connector::IndexInfo>> {
+        self.inner.indexes(table).await
+    }
+
     fn is_healthy(&self) -> bool {
         self.inner.is_healthy()
     }
 
+    fn in_transaction(&self) -> bool {
+        self.inner.in_transaction()
+    }
+
     fn begin_statement(&self) -> &'static str {
         self.inner.begin_statement()
     }
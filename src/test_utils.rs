@@ -0,0 +1,324 @@
+//! Utilities for testing code built on quaint without a live database.
+//!
+//! Business logic using [`Queryable`] as its database abstraction can be
+//! unit tested against a [`MockQueryable`] programmed with the calls the
+//! test expects, removing the need for a running server. The module is
+//! behind the `test-utils` feature flag.
+
+use crate::ast::{Query, Value};
+use crate::connector::{BatchResult, IsolationLevel, Queryable, ResultSet, SqlFamily, TransactionCapable};
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// A [`Queryable`] answering from pre-programmed expectations instead of a
+/// database.
+///
+/// Expectations are registered up front with [`expect_query`] and
+/// [`expect_execute`] and consumed in any order as the code under test
+/// runs. A call nothing was registered for panics immediately; expectations
+/// never consumed panic when the mock is dropped, or earlier through
+/// [`assert_all_called`].
+///
+/// ```rust
+/// use quaint::{prelude::*, test_utils::MockQueryable};
+///
+/// # async fn function() {
+/// let mock = MockQueryable::new();
+///
+/// mock.expect_execute("DELETE FROM `users`", vec![Value::int32(1)], 1);
+///
+/// let query = Delete::from_table("users").so_that("id".equals(1));
+/// assert_eq!(1, mock.execute(query.into()).await.unwrap());
+///
+/// mock.assert_all_called();
+/// # }
+/// ```
+///
+/// [`expect_query`]: MockQueryable::expect_query
+/// [`expect_execute`]: MockQueryable::expect_execute
+/// [`assert_all_called`]: MockQueryable::assert_all_called
+pub struct MockQueryable {
+    family: SqlFamily,
+    expectations: Mutex<Vec<Expectation>>,
+}
+
+enum Expectation {
+    Query {
+        sql_pattern: String,
+        params: Vec<Value<'static>>,
+        result: ResultSet,
+    },
+    Execute {
+        sql_pattern: String,
+        params: Vec<Value<'static>>,
+        affected_rows: u64,
+    },
+}
+
+impl Expectation {
+    fn sql_pattern(&self) -> &str {
+        match self {
+            Self::Query { sql_pattern, .. } => sql_pattern,
+            Self::Execute { sql_pattern, .. } => sql_pattern,
+        }
+    }
+
+    fn matches(&self, sql: &str, params: &[Value<'_>]) -> bool {
+        match self {
+            Self::Query {
+                sql_pattern,
+                params: expected,
+                ..
+            } => sql.contains(sql_pattern.as_str()) && expected.as_slice() == params,
+            Self::Execute {
+                sql_pattern,
+                params: expected,
+                ..
+            } => sql.contains(sql_pattern.as_str()) && expected.as_slice() == params,
+        }
+    }
+}
+
+impl MockQueryable {
+    /// A mock rendering AST queries in the SQLite dialect.
+    pub fn new() -> Self {
+        Self::with_family(SqlFamily::Sqlite)
+    }
+
+    /// A mock rendering AST queries in the dialect of the given family.
+    pub fn with_family(family: SqlFamily) -> Self {
+        Self {
+            family,
+            expectations: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Expect one query returning rows. The call matches when the rendered
+    /// SQL contains `sql_pattern` and the parameters are equal, and answers
+    /// with the given result set.
+    pub fn expect_query(&self, sql_pattern: impl Into<String>, params: Vec<Value<'static>>, result: ResultSet) {
+        self.expectations.lock().unwrap().push(Expectation::Query {
+            sql_pattern: sql_pattern.into(),
+            params,
+            result,
+        });
+    }
+
+    /// Expect one write statement. The call matches when the rendered SQL
+    /// contains `sql_pattern` and the parameters are equal, and answers
+    /// with the given number of affected rows.
+    pub fn expect_execute(&self, sql_pattern: impl Into<String>, params: Vec<Value<'static>>, affected_rows: u64) {
+        self.expectations.lock().unwrap().push(Expectation::Execute {
+            sql_pattern: sql_pattern.into(),
+            params,
+            affected_rows,
+        });
+    }
+
+    /// Panics if any registered expectation was not consumed, listing the
+    /// leftovers. Also runs on drop, but calling it at the end of the test
+    /// points the panic to a better location.
+    pub fn assert_all_called(&self) {
+        let expectations = self.expectations.lock().unwrap();
+
+        if !expectations.is_empty() {
+            let leftovers: Vec<_> = expectations.iter().map(|e| e.sql_pattern()).collect();
+
+            panic!("MockQueryable dropped with uncalled expectations: {leftovers:?}");
+        }
+    }
+
+    fn take_matching(&self, sql: &str, params: &[Value<'_>], query: bool) -> Expectation {
+        let matching = {
+            let mut expectations = self.expectations.lock().unwrap();
+
+            let position = expectations.iter().position(|expectation| {
+                matches!(expectation, Expectation::Query { .. }) == query && expectation.matches(sql, params)
+            });
+
+            position.map(|position| expectations.remove(position))
+        };
+
+        match matching {
+            Some(expectation) => expectation,
+            None => panic!(
+                "MockQueryable got an unexpected {} call: {sql} with params {params:?}",
+                if query { "query" } else { "execute" }
+            ),
+        }
+    }
+}
+
+impl Default for MockQueryable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockQueryable {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            self.assert_all_called();
+        }
+    }
+}
+
+#[async_trait]
+impl Queryable for MockQueryable {
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let (sql, params) = self.render(q)?;
+        self.query_raw(&sql, &params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let (sql, params) = self.render(q)?;
+        self.execute_raw(&sql, &params).await
+    }
+
+    fn render<'b>(&self, q: Query<'b>) -> crate::Result<(String, Vec<Value<'b>>)> {
+        q.to_sql_for(self.family)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        match self.take_matching(sql, params, true) {
+            Expectation::Query { result, .. } => Ok(result),
+            Expectation::Execute { .. } => unreachable!(),
+        }
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.query_raw(sql, params).await
+    }
+
+    async fn query_multi_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<Vec<BatchResult>> {
+        let result = self.query_raw(sql, params).await?;
+
+        Ok(vec![BatchResult::ResultSet(result)])
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        match self.take_matching(sql, params, false) {
+            Expectation::Execute { affected_rows, .. } => Ok(affected_rows),
+            Expectation::Query { .. } => unreachable!(),
+        }
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.execute_raw(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.execute_raw(cmd, &[]).await?;
+
+        Ok(())
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> crate::Result<()> {
+        self.raw_cmd(&format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"))
+            .await
+    }
+
+    fn is_healthy(&self) -> bool {
+        true
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        false
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        "BEGIN"
+    }
+}
+
+impl TransactionCapable for MockQueryable {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+
+    #[tokio::test]
+    async fn expectations_answer_matching_calls() {
+        let mock = MockQueryable::new();
+
+        let names = vec!["id".to_string()];
+        let rows = vec![vec![Value::int64(1)]];
+        mock.expect_query("SELECT `users`.*", vec![Value::text("Alice")], ResultSet::new(names, rows));
+        mock.expect_execute("DELETE FROM `users`", vec![Value::int32(1)], 1);
+
+        let select = Select::from_table("users").so_that("name".equals("Alice"));
+        let result = mock.query(select.into()).await.unwrap();
+        assert_eq!(Some(1), result.into_single().unwrap()[0].as_i64());
+
+        let delete = Delete::from_table("users").so_that("id".equals(1));
+        assert_eq!(1, mock.execute(delete.into()).await.unwrap());
+
+        mock.assert_all_called();
+    }
+
+    #[tokio::test]
+    async fn expectations_match_out_of_order() {
+        let mock = MockQueryable::new();
+
+        mock.expect_execute("UPDATE", vec![], 2);
+        mock.expect_execute("DELETE", vec![], 1);
+
+        assert_eq!(1, mock.execute_raw("DELETE FROM users", &[]).await.unwrap());
+        assert_eq!(2, mock.execute_raw("UPDATE users SET active = false", &[]).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn transactions_consume_expectations() {
+        let mock = MockQueryable::new();
+
+        mock.expect_execute("BEGIN", vec![], 0);
+        mock.expect_execute("DELETE FROM users", vec![], 3);
+        mock.expect_execute("COMMIT", vec![], 0);
+
+        let tx = mock.start_transaction(None).await.unwrap();
+        assert_eq!(3, tx.execute_raw("DELETE FROM users", &[]).await.unwrap());
+        tx.commit().await.unwrap();
+
+        mock.assert_all_called();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "unexpected execute call")]
+    async fn unexpected_calls_panic() {
+        let mock = MockQueryable::new();
+
+        mock.execute_raw("DELETE FROM users", &[]).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "uncalled expectations")]
+    async fn uncalled_expectations_panic_on_drop() {
+        let mock = MockQueryable::new();
+
+        mock.expect_execute("DELETE FROM users", vec![], 1);
+
+        drop(mock);
+    }
+
+    #[tokio::test]
+    async fn params_must_match() {
+        let mock = MockQueryable::new();
+
+        mock.expect_query("SELECT", vec![Value::int64(1)], ResultSet::new(Vec::new(), Vec::new()));
+
+        let params = [Value::int64(2)];
+        let result = std::panic::AssertUnwindSafe(mock.query_raw("SELECT 1", &params));
+        let result = futures::FutureExt::catch_unwind(result).await;
+
+        assert!(result.is_err());
+
+        mock.expect_query("SELECT", vec![Value::int64(2)], ResultSet::new(Vec::new(), Vec::new()));
+        mock.query_raw("SELECT 1", &[Value::int64(2)]).await.unwrap();
+        mock.query_raw("SELECT 1", &[Value::int64(1)]).await.unwrap();
+    }
+}
@@ -4,3 +4,4 @@ mod query;
 pub mod test_api;
 mod types;
 mod upsert;
+mod url_parsing;
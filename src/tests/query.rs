@@ -127,6 +127,22 @@ async fn sqlite_serializable_tx(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[test_each_connector(tags("sqlite"))]
+async fn begin_transaction_with_a_custom_statement(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api.create_temp_table("id int, name varchar(255)").await?;
+
+    let tx = api.conn().begin_transaction_with_stmt("BEGIN IMMEDIATE").await?;
+
+    let insert = Insert::single_into(&table_name).value("id", 1).value("name", "Musti");
+    tx.insert(insert.into()).await?;
+    tx.commit().await?;
+
+    let res = api.conn().select(Select::from_table(&table_name)).await?;
+    assert_eq!(1, res.len());
+
+    Ok(())
+}
+
 // Only SQL Server supports snapshot.
 #[test_each_connector(tags("mssql"))]
 async fn mssql_snapshot_tx(api: &mut dyn TestApi) -> crate::Result<()> {
@@ -323,6 +339,121 @@ async fn where_equals(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[test_each_connector(tags("postgresql"))]
+async fn delete_using_removes_the_rows_matching_the_joined_table(api: &mut dyn TestApi) -> crate::Result<()> {
+    let users = api.create_temp_table("id int, name varchar(255)").await?;
+    let banned = api.create_temp_table("user_id int").await?;
+
+    let insert = Insert::multi_into(&users, vec!["id", "name"])
+        .values(vec![Value::integer(1), Value::text("Musti")])
+        .values(vec![Value::integer(2), Value::text("Naukio")]);
+
+    api.conn().insert(insert.into()).await?;
+
+    let insert = Insert::single_into(&banned).value("user_id", 1);
+    api.conn().insert(insert.into()).await?;
+
+    let condition = Column::from((users.as_str(), "id")).equals(Column::from((banned.as_str(), "user_id")));
+    let delete = Delete::from_table(users.as_str()).using(banned.as_str(), condition);
+
+    let changes = api.conn().execute(delete.into()).await?;
+    assert_eq!(1, changes);
+
+    let res = api.conn().select(Select::from_table(users.as_str())).await?;
+    assert_eq!(1, res.len());
+
+    let row = res.get(0).unwrap();
+    assert_eq!(Some("Naukio"), row["name"].as_str());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn delete_with_join_removes_the_rows_matching_the_joined_table(api: &mut dyn TestApi) -> crate::Result<()> {
+    let users = api.create_temp_table("id int, name varchar(255)").await?;
+    let banned = api.create_temp_table("user_id int").await?;
+
+    let insert = Insert::multi_into(&users, vec!["id", "name"])
+        .values(vec![Value::integer(1), Value::text("Musti")])
+        .values(vec![Value::integer(2), Value::text("Naukio")]);
+
+    api.conn().insert(insert.into()).await?;
+
+    let insert = Insert::single_into(&banned).value("user_id", 1);
+    api.conn().insert(insert.into()).await?;
+
+    let join = banned
+        .as_str()
+        .on(Column::from((banned.as_str(), "user_id")).equals(Column::from((users.as_str(), "id"))));
+
+    let delete = Delete::from_table(users.as_str()).inner_join(join);
+
+    let changes = api.conn().execute(delete.into()).await?;
+    assert_eq!(1, changes);
+
+    let res = api.conn().select(Select::from_table(users.as_str())).await?;
+    assert_eq!(1, res.len());
+
+    let row = res.get(0).unwrap();
+    assert_eq!(Some("Naukio"), row["name"].as_str());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgresql"))]
+async fn update_from_sets_the_value_of_the_joined_table(api: &mut dyn TestApi) -> crate::Result<()> {
+    let users = api.create_temp_table("id int, name varchar(255)").await?;
+    let profiles = api.create_temp_table("user_id int, name varchar(255)").await?;
+
+    let insert = Insert::single_into(&users).value("id", 1).value("name", "Musti");
+    api.conn().insert(insert.into()).await?;
+
+    let insert = Insert::single_into(&profiles).value("user_id", 1).value("name", "Naukio");
+    api.conn().insert(insert.into()).await?;
+
+    let condition = Column::from((users.as_str(), "id")).equals(Column::from((profiles.as_str(), "user_id")));
+
+    let update = Update::table(users.as_str())
+        .set("name", Column::from((profiles.as_str(), "name")))
+        .and_from(profiles.as_str(), condition);
+
+    let changes = api.conn().execute(update.into()).await?;
+    assert_eq!(1, changes);
+
+    let row = api.conn().select(Select::from_table(users.as_str())).await?.into_single()?;
+    assert_eq!(Some("Naukio"), row["name"].as_str());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn update_with_join_sets_the_value_of_the_joined_table(api: &mut dyn TestApi) -> crate::Result<()> {
+    let users = api.create_temp_table("id int, name varchar(255)").await?;
+    let profiles = api.create_temp_table("user_id int, name varchar(255)").await?;
+
+    let insert = Insert::single_into(&users).value("id", 1).value("name", "Musti");
+    api.conn().insert(insert.into()).await?;
+
+    let insert = Insert::single_into(&profiles).value("user_id", 1).value("name", "Naukio");
+    api.conn().insert(insert.into()).await?;
+
+    let join = profiles
+        .as_str()
+        .on(Column::from((profiles.as_str(), "user_id")).equals(Column::from((users.as_str(), "id"))));
+
+    let update = Update::table(users.as_str())
+        .inner_join(join)
+        .set((users.as_str(), "name"), Column::from((profiles.as_str(), "name")));
+
+    let changes = api.conn().execute(update.into()).await?;
+    assert_eq!(1, changes);
+
+    let row = api.conn().select(Select::from_table(users.as_str())).await?.into_single()?;
+    assert_eq!(Some("Naukio"), row["name"].as_str());
+
+    Ok(())
+}
+
 #[test_each_connector]
 async fn where_like(api: &mut dyn TestApi) -> crate::Result<()> {
     let table = api.create_temp_table("id int, name varchar(255)").await?;
@@ -1265,6 +1396,146 @@ async fn deletes(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[test_each_connector]
+async fn delete_with_limit_removes_only_that_many_rows(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api.create_temp_table("id int, name varchar(255)").await?;
+
+    let insert = Insert::multi_into(&table_name, vec!["id", "name"])
+        .values(vec![Value::int32(1), Value::text("Musti")])
+        .values(vec![Value::int32(2), Value::text("Naukio")])
+        .values(vec![Value::int32(3), Value::text("Belka")]);
+
+    api.conn().insert(insert.into()).await?;
+
+    let delete = Delete::from_table(&table_name).limit(2);
+    let changes = api.conn().execute(delete.into()).await?;
+
+    assert_eq!(2, changes);
+
+    let select = Select::from_table(&table_name);
+    let res = api.conn().select(select).await?;
+    assert_eq!(1, res.len());
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn insert_returning_reports_the_generated_key(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api
+        .create_temp_table(&format!("{}, name varchar(255)", api.autogen_id("id")))
+        .await?;
+
+    let insert = Insert::single_into(&table_name).value("name", "Musti");
+    let id = api.conn().insert_returning(insert.into(), "id").await?;
+
+    assert_eq!(Some(1), id.as_i64());
+
+    let insert = Insert::single_into(&table_name).value("name", "Naukio");
+    let id = api.conn().insert_returning(insert.into(), "id").await?;
+
+    assert_eq!(Some(2), id.as_i64());
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn insert_returning_errors_on_multi_row_inserts(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api
+        .create_temp_table(&format!("{}, name varchar(255)", api.autogen_id("id")))
+        .await?;
+
+    let insert = Insert::multi_into(&table_name, vec!["name"])
+        .values(vec![Value::text("Musti")])
+        .values(vec![Value::text("Naukio")]);
+
+    let err = api.conn().insert_returning(insert.into(), "id").await.unwrap_err();
+
+    assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn identity_insert_allows_explicit_keys(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api
+        .create_temp_table(&format!("{}, name varchar(255)", api.autogen_id("id")))
+        .await?;
+
+    let insert = Insert::single_into(&table_name).value("id", 42).value("name", "Musti");
+    api.conn().insert(Insert::from(insert).with_identity_insert()).await?;
+
+    let select = Select::from_table(&table_name).column("id").column("name");
+    let row = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(42), row["id"].as_integer());
+    assert_eq!(Some("Musti"), row["name"].as_str());
+
+    Ok(())
+}
+
+#[cfg(feature = "uuid")]
+#[test_each_connector(tags("postgresql"))]
+async fn insert_returning_a_uuid_key_on_postgresql(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table_name = api
+        .create_temp_table("id uuid primary key, name varchar(255)")
+        .await?;
+
+    let uuid = uuid::Uuid::new_v4();
+
+    let insert = Insert::single_into(&table_name)
+        .value("id", Value::uuid(uuid))
+        .value("name", "Musti");
+
+    let id = api.conn().insert_returning(insert.into(), "id").await?;
+
+    assert_eq!(Value::uuid(uuid), id);
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql_mariadb"))]
+async fn insert_returning_on_mariadb(api: &mut dyn TestApi) -> crate::Result<()> {
+    use crate::visitor::{Mysql, MysqlFlavour};
+
+    let table = api
+        .create_temp_table("id int auto_increment primary key, name varchar(255)")
+        .await?;
+
+    let insert = Insert::single_into(&table).value("name", "musti");
+    let query = Insert::from(insert).returning(["id", "name"]);
+
+    let (sql, params) = Mysql::build_with_flavour(query, MysqlFlavour::Mariadb)?;
+    let res = api.conn().query_raw(&sql, &params).await?;
+    let row = res.into_single()?;
+
+    assert_eq!(Some(1), row.get("id").and_then(|id| id.as_i64()));
+    assert_eq!(Some("musti"), row.get("name").and_then(|name| name.as_str()));
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql_mariadb"))]
+async fn delete_returning_on_mariadb(api: &mut dyn TestApi) -> crate::Result<()> {
+    use crate::visitor::{Mysql, MysqlFlavour};
+
+    let table = api
+        .create_temp_table("id int auto_increment primary key, name varchar(255)")
+        .await?;
+
+    let insert = Insert::single_into(&table).value("name", "naukio");
+    api.conn().insert(insert.into()).await?;
+
+    let delete = Delete::from_table(&table).so_that("id".equals(1)).returning(["name"]);
+
+    let (sql, params) = Mysql::build_with_flavour(delete, MysqlFlavour::Mariadb)?;
+    let res = api.conn().query_raw(&sql, &params).await?;
+    let row = res.into_single()?;
+
+    assert_eq!(Some("naukio"), row.get("name").and_then(|name| name.as_str()));
+
+    Ok(())
+}
+
 // TODO: Figure out why it doesn't work on MariaDB
 // Error { kind: QueryError(Server(ServerError { code: 1115, message: "Unknown character set: 'gb18030'", state: "42000" })), original_code: Some("1115"), original_message: Some("Unknown character set: 'gb18030'") }
 #[test_each_connector(tags("mysql"), ignore("mysql_mariadb"))]
@@ -1616,6 +1887,125 @@ async fn op_test_div_one_level(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[test_each_connector(tags("postgresql"))]
+async fn query_multi_raw_splits_the_batch(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int, name varchar(255)").await?;
+
+    let sql = format!(
+        "INSERT INTO {table} (id, name) VALUES (1, 'Musti'), (2, 'Naukio'); SELECT id, name FROM {table} ORDER BY id; DELETE FROM {table}",
+    );
+
+    let results = api.conn().query_multi_raw(&sql, &[]).await?;
+
+    assert_eq!(3, results.len());
+    assert_eq!(Some(2), results[0].as_count());
+
+    let rows = results[1].as_result_set().unwrap();
+
+    assert_eq!(2, rows.len());
+    // The simple query protocol returns every value as text.
+    let row = rows.first().unwrap();
+    assert_eq!(Some("Musti"), row[1].as_str());
+
+    assert_eq!(Some(2), results[2].as_count());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn query_multi_raw_returns_all_result_sets_of_a_procedure(api: &mut dyn TestApi) -> crate::Result<()> {
+    let proc = api.get_name();
+
+    api.conn()
+        .raw_cmd(&format!(
+            "CREATE PROCEDURE {proc}() BEGIN SELECT 1 one; SELECT 2 two, 3 three; END"
+        ))
+        .await?;
+
+    let results = api.conn().query_multi_raw(&format!("CALL {proc}()"), &[]).await?;
+
+    let sets: Vec<_> = results.iter().filter_map(|result| result.as_result_set()).collect();
+
+    assert_eq!(2, sets.len());
+    assert_eq!(&vec!["one".to_string()], sets[0].columns());
+    assert_eq!(&vec!["two".to_string(), "three".to_string()], sets[1].columns());
+
+    api.conn().raw_cmd(&format!("DROP PROCEDURE {proc}")).await?;
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgresql"))]
+async fn typed_array_binding(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api
+        .create_temp_table("id SERIAL PRIMARY KEY, ints int4[], tags text[]")
+        .await?;
+
+    let insert = Insert::single_into(&table)
+        .value("ints", Value::typed_array(Vec::<i32>::new(), ArrayType::Int32))
+        .value(
+            "tags",
+            Value::typed_array(vec![Value::text("a"), Value::Text(None)], ArrayType::Text),
+        );
+
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table).column("ints").column("tags");
+    let row = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(&Value::Array(Some(vec![]))), row.at(0));
+    assert_eq!(
+        Some(&Value::Array(Some(vec![Value::text("a"), Value::Text(None)]))),
+        row.at(1)
+    );
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgresql"))]
+#[cfg(all(feature = "json", feature = "uuid", feature = "bigdecimal", feature = "chrono"))]
+async fn typed_array_special_element_types(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api
+        .create_temp_table("id SERIAL PRIMARY KEY, docs jsonb[], uids uuid[], decs numeric[], stamps timestamptz[]")
+        .await?;
+
+    let uuid = uuid::Uuid::new_v4();
+    let stamp = chrono::Utc::now();
+    let dec: bigdecimal::BigDecimal = "3.14".parse().unwrap();
+
+    let insert = Insert::single_into(&table)
+        .value(
+            "docs",
+            Value::typed_array(vec![Value::json(serde_json::json!({"a": 1})), Value::Json(None)], ArrayType::Json),
+        )
+        .value("uids", Value::typed_array(vec![Value::uuid(uuid)], ArrayType::Uuid))
+        .value("decs", Value::typed_array(vec![Value::numeric(dec.clone())], ArrayType::Numeric))
+        .value("stamps", Value::typed_array(vec![Value::datetime_utc(stamp)], ArrayType::DateTime));
+
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table)
+        .column("docs")
+        .column("uids")
+        .column("decs")
+        .column("stamps");
+
+    let row = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(
+        Some(&Value::Array(Some(vec![
+            Value::json(serde_json::json!({"a": 1})),
+            Value::Json(None)
+        ]))),
+        row.at(0)
+    );
+    assert_eq!(Some(&Value::Array(Some(vec![Value::uuid(uuid)]))), row.at(1));
+    assert_eq!(Some(&Value::Array(Some(vec![Value::numeric(dec)]))), row.at(2));
+    assert_eq!(Some(&Value::Array(Some(vec![Value::datetime_utc(stamp)]))), row.at(3));
+
+    Ok(())
+}
+
 #[test_each_connector(tags("postgresql"))]
 async fn enum_values(api: &mut dyn TestApi) -> crate::Result<()> {
     let type_name = api.get_name();
@@ -3564,3 +3954,182 @@ async fn overflowing_int_errors_out(api: &mut dyn TestApi) -> crate::Result<()>
 
     Ok(())
 }
+
+#[test_each_connector(tags("postgresql", "sqlite", "mysql"))]
+async fn get_or_create_returns_existing_row(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int, nickname varchar(255) UNIQUE").await?;
+
+    let insert = Insert::single_into(&table).value("id", 1).value("nickname", "musti");
+    api.conn().execute(insert.into()).await?;
+
+    let (row, created) = api
+        .conn()
+        .get_or_create(
+            Table::from(&table),
+            &[(Column::from("nickname"), Value::text("musti"))],
+            &[(Column::from("id"), Value::int32(2))],
+        )
+        .await?;
+
+    assert!(!created);
+    assert_eq!(Some(1), row["id"].as_i32());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgresql", "sqlite", "mysql"))]
+async fn get_or_create_inserts_missing_row(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int, nickname varchar(255) UNIQUE").await?;
+
+    let (row, created) = api
+        .conn()
+        .get_or_create(
+            Table::from(&table),
+            &[(Column::from("nickname"), Value::text("naukio"))],
+            &[(Column::from("id"), Value::int32(2))],
+        )
+        .await?;
+
+    assert!(created);
+    assert_eq!(Some(2), row["id"].as_i32());
+    assert_eq!(Some("naukio"), row["nickname"].as_str());
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgresql"))]
+async fn get_or_create_races_create_exactly_once(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_table("id int, nickname varchar(255) UNIQUE").await?;
+
+    let mut tasks = Vec::new();
+
+    for _ in 0..20 {
+        let conn = api.create_additional_connection().await?;
+        let table = table.clone();
+
+        tasks.push(tokio::spawn(async move {
+            conn.get_or_create(
+                Table::from(table),
+                &[(Column::from("nickname"), Value::text("musti"))],
+                &[(Column::from("id"), Value::int32(1))],
+            )
+            .await
+        }));
+    }
+
+    let mut creations = 0;
+
+    for task in tasks {
+        let (row, created) = task.await.unwrap()?;
+
+        if created {
+            creations += 1;
+        }
+
+        assert_eq!(Some(1), row["id"].as_i32());
+        assert_eq!(Some("musti"), row["nickname"].as_str());
+    }
+
+    assert_eq!(1, creations);
+
+    api.delete_table(&table).await?;
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn exists_checks_for_matching_rows(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int, name varchar(255)").await?;
+
+    let insert = Insert::single_into(&table).value("id", 1).value("name", "musti");
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table).so_that("name".equals("musti"));
+    assert!(api.conn().exists(select).await?);
+
+    let select = Select::from_table(&table).so_that("name".equals("naukio"));
+    assert!(!api.conn().exists(select).await?);
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn exists_strips_the_ordering_of_the_inner_query(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int").await?;
+
+    let insert = Insert::multi_into(&table, ["id"]).values((1,)).values((2,));
+    api.conn().insert(insert.into()).await?;
+
+    // SQL Server rejects a derived table with an `ORDER BY` and no limit, so
+    // this only passes when the useless ordering is dropped.
+    let select = Select::from_table(&table).order_by("id".descend());
+    assert!(api.conn().exists(select).await?);
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn count_counts_matching_rows(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int, name varchar(255)").await?;
+
+    let insert = Insert::multi_into(&table, ["id", "name"])
+        .values((1, "musti"))
+        .values((2, "naukio"))
+        .values((3, "musti"));
+
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table).so_that("name".equals("musti"));
+    assert_eq!(2, api.conn().count(select).await?);
+
+    let select = Select::from_table(&table).so_that("name".equals("belka"));
+    assert_eq!(0, api.conn().count(select).await?);
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn count_honors_the_limit_of_the_inner_query(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_temp_table("id int").await?;
+
+    let insert = Insert::multi_into(&table, ["id"]).values((1,)).values((2,)).values((3,));
+    api.conn().insert(insert.into()).await?;
+
+    // A limit changes how many rows the inner query produces, so the
+    // ordering it depends on has to survive the wrapping.
+    let select = Select::from_table(&table).order_by("id".descend()).limit(2);
+    assert_eq!(2, api.conn().count(select).await?);
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn replace_into_claims_a_new_auto_increment_id(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api
+        .create_temp_table("id int auto_increment primary key, uniq int unique, val varchar(10)")
+        .await?;
+
+    let insert = Insert::single_into(&table).value("uniq", 1).value("val", "a");
+    api.conn().insert(insert.into()).await?;
+
+    let rows = api.conn().select(Select::from_table(&table).column("id")).await?;
+    let first_id = rows.get(0).and_then(|row| row["id"].as_integer()).unwrap();
+
+    // Deletes and re-inserts the conflicting row instead of updating it in
+    // place, claiming a new auto-increment id.
+    let replace = ReplaceInto::single_into(&table).value("uniq", 1).value("val", "b");
+    api.conn().execute(replace.into()).await?;
+
+    let rows = api
+        .conn()
+        .select(Select::from_table(&table).column("id").column("val"))
+        .await?;
+
+    assert_eq!(1, rows.len());
+
+    let row = rows.get(0).unwrap();
+    assert_eq!(Some("b"), row["val"].as_str());
+    assert!(row["id"].as_integer().unwrap() > first_id);
+
+    Ok(())
+}
@@ -7,7 +7,11 @@ pub(crate) async fn sqlite_test_api<'a>() -> crate::Result<Sqlite<'a>> {
     Sqlite::new().await
 }
 
-const CONN_STR: &str = "file:db/test.db";
+// A file in the temp directory instead of the `db/test.db` fixture, so test
+// runs do not modify the checked in file.
+fn conn_str() -> String {
+    format!("file:{}", std::env::temp_dir().join("quaint_test_api.db").display())
+}
 pub struct Sqlite<'a> {
     names: Generator<'a>,
     conn: Quaint,
@@ -16,7 +20,7 @@ pub struct Sqlite<'a> {
 impl<'a> Sqlite<'a> {
     pub async fn new() -> crate::Result<Sqlite<'a>> {
         let names = Generator::default();
-        let conn = Quaint::new(CONN_STR).await?;
+        let conn = Quaint::new(&conn_str()).await?;
 
         Ok(Self { names, conn })
     }
@@ -80,7 +84,7 @@ impl<'a> TestApi for Sqlite<'a> {
     }
 
     async fn create_additional_connection(&self) -> crate::Result<Quaint> {
-        Quaint::new(CONN_STR).await
+        Quaint::new(&conn_str()).await
     }
 
     fn unique_constraint(&mut self, column: &str) -> String {
@@ -2,6 +2,10 @@
 mod bigdecimal;
 
 use crate::tests::test_api::*;
+#[cfg(feature = "uuid")]
+use crate::{ast::*, connector::Queryable};
+#[cfg(feature = "uuid")]
+use std::str::FromStr;
 
 test_type!(nvarchar_limited(
     mssql,
@@ -144,23 +148,52 @@ test_type!(time(
 #[cfg(feature = "chrono")]
 test_type!(datetime2(mssql, "datetime2", Value::DateTime(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:00Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
 
 #[cfg(feature = "chrono")]
 test_type!(datetime(mssql, "datetime", Value::DateTime(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
 
 #[cfg(feature = "chrono")]
-test_type!(datetimeoffset(mssql, "datetimeoffset", Value::DateTime(None), {
+test_type!(datetimeoffset(mssql, "datetimeoffset", Value::DateTimeUtc(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime_utc(dt.with_timezone(&chrono::Utc))
 }));
 
 #[cfg(feature = "chrono")]
 test_type!(smalldatetime(mssql, "smalldatetime", Value::DateTime(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:00Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
+
+#[cfg(feature = "uuid")]
+test_type!(uuid(
+    mssql,
+    "uniqueidentifier",
+    Value::Uuid(None),
+    Value::uuid(uuid::Uuid::from_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap())
+));
+
+/// A round trip alone can't catch a byte order mistake, since swapping the
+/// bytes in both directions cancels out. Comparing against the server's own
+/// textual rendering proves the mixed-endian GUID wire format is handled
+/// right.
+#[cfg(feature = "uuid")]
+#[test_macros::test_each_connector(tags("mssql"))]
+async fn test_type_uuid_byte_order(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_type_table("uniqueidentifier").await?;
+    let uuid = uuid::Uuid::from_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap();
+
+    let insert = Insert::single_into(&table).value("value", Value::uuid(uuid));
+    api.conn().insert(insert.into()).await?;
+
+    let select = format!("SELECT CONVERT(varchar(36), value) AS value FROM {table}");
+    let res = api.conn().query_raw(&select, &[]).await?.into_single()?;
+
+    assert_eq!(Some("936DA01F-9ABD-4D9D-80C7-02AF85C822A8"), res["value"].as_str());
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::tests::test_api::*;
 
-#[cfg(feature = "bigdecimal")]
+#[cfg(any(feature = "bigdecimal", feature = "uuid"))]
 use std::str::FromStr;
 
 #[cfg(feature = "bigdecimal")]
@@ -103,6 +103,15 @@ test_type!(bigint(
     Value::int64(i64::MAX)
 ));
 
+test_type!(bigint_unsigned(
+    mysql,
+    "bigint unsigned",
+    Value::Int64(None),
+    Value::int64(0),
+    Value::int64(i64::MAX),
+    Value::int128(u64::MAX)
+));
+
 #[cfg(feature = "bigdecimal")]
 test_type!(decimal(
     mysql,
@@ -154,8 +163,15 @@ test_type!(bit1(
 test_type!(bit64(
     mysql,
     "bit(64)",
-    Value::Bytes(None),
-    Value::bytes(vec![0, 0, 0, 0, 0, 6, 107, 58])
+    (Value::Bytes(None), Value::BitString(None)),
+    (
+        Value::bytes(vec![0, 0, 0, 0, 0, 6, 107, 58]),
+        Value::bit_string("0000000000000000000000000000000000000000000001100110101100111010")
+    ),
+    (
+        Value::bit_string("0000000000000000000000000000000000000000000001100110101100111010"),
+        Value::bit_string("0000000000000000000000000000000000000000000001100110101100111010")
+    ),
 ));
 
 test_type!(char(mysql, "char(255)", Value::Text(None), Value::text("foobar")));
@@ -217,7 +233,7 @@ test_type!(json(
 #[cfg(feature = "chrono")]
 test_type!(date(mysql, "date", Value::Date(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-04-20T00:00:00Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
 
 #[cfg(feature = "chrono")]
@@ -231,11 +247,24 @@ test_type!(time(
 #[cfg(feature = "chrono")]
 test_type!(datetime(mysql, "datetime", Value::DateTime(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
 
 #[cfg(feature = "chrono")]
 test_type!(timestamp(mysql, "timestamp", {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
+
+// UUIDs have no native MySQL type. The canonical representation is the
+// hyphenated lowercase text form, reading back as text.
+#[cfg(feature = "uuid")]
+test_type!(uuid(
+    mysql,
+    "varchar(36)",
+    (Value::Uuid(None), Value::Text(None)),
+    (
+        Value::uuid(uuid::Uuid::from_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap()),
+        Value::text("936da01f-9abd-4d9d-80c7-02af85c822a8")
+    )
+));
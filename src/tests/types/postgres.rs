@@ -220,20 +220,29 @@ test_type!(text_array(
     Value::array(vec![Value::text("foobar"), Value::text("omgwtf"), Value::Text(None)])
 ));
 
-test_type!(bit(postgresql, "bit(4)", Value::Text(None), Value::text("1001")));
+test_type!(bit(
+    postgresql,
+    "bit(4)",
+    Value::BitString(None),
+    Value::bit_string("1001")
+));
 
 test_type!(bit_array(
     postgresql,
     "bit(4)[]",
     Value::Array(None),
-    Value::array(vec![Value::text("1001"), Value::text("0110"), Value::Text(None)])
+    Value::array(vec![
+        Value::bit_string("1001"),
+        Value::bit_string("0110"),
+        Value::BitString(None)
+    ])
 ));
 
 test_type!(varbit(
     postgresql,
     "varbit(20)",
-    Value::Text(None),
-    Value::text("001010101")
+    Value::BitString(None),
+    Value::bit_string("001010101")
 ));
 
 test_type!(varbit_array(
@@ -241,25 +250,53 @@ test_type!(varbit_array(
     "varbit(20)[]",
     Value::Array(None),
     Value::array(vec![
-        Value::text("001010101"),
-        Value::text("01101111"),
-        Value::Text(None)
+        Value::bit_string("001010101"),
+        Value::bit_string("01101111"),
+        Value::BitString(None)
     ])
 ));
 
-test_type!(inet(postgresql, "inet", Value::Text(None), Value::text("127.0.0.1")));
+test_type!(inet(
+    postgresql,
+    "inet",
+    Value::IpAddr(None),
+    Value::ip_addr("127.0.0.1".parse::<IpNet>()?),
+    Value::ip_addr("2001:db8::1/64".parse::<IpNet>()?)
+));
 
 test_type!(inet_array(
     postgresql,
     "inet[]",
     Value::Array(None),
     Value::array(vec![
-        Value::text("127.0.0.1"),
-        Value::text("192.168.1.1"),
-        Value::Text(None)
+        Value::ip_addr("127.0.0.1".parse::<IpNet>()?),
+        Value::ip_addr("192.168.1.1".parse::<IpNet>()?),
+        Value::IpAddr(None)
     ])
 ));
 
+test_type!(cidr(
+    postgresql,
+    "cidr",
+    Value::IpAddr(None),
+    Value::ip_addr("10.1.0.0/16".parse::<IpNet>()?),
+    Value::ip_addr("2001:db8::/32".parse::<IpNet>()?)
+));
+
+test_type!(macaddr(
+    postgresql,
+    "macaddr",
+    Value::Text(None),
+    Value::text("08:00:2b:01:02:03")
+));
+
+test_type!(macaddr8(
+    postgresql,
+    "macaddr8",
+    Value::Text(None),
+    Value::text("08:00:2b:01:02:03:04:05")
+));
+
 #[cfg(feature = "json")]
 test_type!(json(
     postgresql,
@@ -373,23 +410,20 @@ test_type!(time_array(
 #[cfg(feature = "chrono")]
 test_type!(timestamp(postgresql, "timestamp", Value::DateTime(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime(dt.naive_utc())
 }));
 
 #[cfg(feature = "chrono")]
 test_type!(timestamp_array(postgresql, "timestamp[]", Value::Array(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
 
-    Value::array(vec![
-        Value::datetime(dt.with_timezone(&chrono::Utc)),
-        Value::DateTime(None),
-    ])
+    Value::array(vec![Value::datetime(dt.naive_utc()), Value::DateTime(None)])
 }));
 
 #[cfg(feature = "chrono")]
-test_type!(timestamptz(postgresql, "timestamptz", Value::DateTime(None), {
+test_type!(timestamptz(postgresql, "timestamptz", Value::DateTimeUtc(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
-    Value::datetime(dt.with_timezone(&chrono::Utc))
+    Value::datetime_utc(dt.with_timezone(&chrono::Utc))
 }));
 
 #[cfg(feature = "chrono")]
@@ -397,8 +431,8 @@ test_type!(timestamptz_array(postgresql, "timestamptz[]", Value::Array(None), {
     let dt = chrono::DateTime::parse_from_rfc3339("2020-02-27T19:10:22Z").unwrap();
 
     Value::array(vec![
-        Value::datetime(dt.with_timezone(&chrono::Utc)),
-        Value::DateTime(None),
+        Value::datetime_utc(dt.with_timezone(&chrono::Utc)),
+        Value::DateTimeUtc(None),
     ])
 }));
 
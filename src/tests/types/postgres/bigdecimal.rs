@@ -8,6 +8,15 @@ test_type!(decimal(
     Value::numeric(BigDecimal::from_str("3.14")?)
 ));
 
+test_type!(decimal_int128(
+    postgresql,
+    "decimal(39, 0)",
+    (
+        Value::int128(u64::MAX),
+        Value::numeric(BigDecimal::from_str("18446744073709551615")?)
+    )
+));
+
 test_type!(decimal_10_2(
     postgresql,
     "decimal(10, 2)",
@@ -3,7 +3,7 @@ use crate::tests::test_api::sqlite_test_api;
 use crate::tests::test_api::TestApi;
 #[cfg(feature = "chrono")]
 use crate::{ast::*, connector::Queryable};
-#[cfg(feature = "bigdecimal")]
+#[cfg(any(feature = "bigdecimal", feature = "uuid"))]
 use std::str::FromStr;
 
 test_type!(integer(
@@ -89,7 +89,7 @@ test_type!(datetime(
     sqlite,
     "DATETIME",
     Value::DateTime(None),
-    Value::datetime(chrono::DateTime::from_str("2020-07-29T09:23:44.458Z").unwrap())
+    Value::datetime_utc(chrono::DateTime::from_str("2020-07-29T09:23:44.458Z").unwrap())
 ));
 
 #[cfg(feature = "chrono")]
@@ -108,7 +108,7 @@ async fn test_type_text_datetime_rfc3339(api: &mut dyn TestApi) -> crate::Result
     let select = Select::from_table(&table).column("value").order_by("id".descend());
     let res = api.conn().select(select).await?.into_single()?;
 
-    assert_eq!(Some(&Value::datetime(dt)), res.at(0));
+    assert_eq!(Some(&Value::datetime_utc(dt)), res.at(0));
 
     Ok(())
 }
@@ -131,7 +131,7 @@ async fn test_type_text_datetime_rfc2822(api: &mut dyn TestApi) -> crate::Result
     let select = Select::from_table(&table).column("value").order_by("id".descend());
     let res = api.conn().select(select).await?.into_single()?;
 
-    assert_eq!(Some(&Value::datetime(dt)), res.at(0));
+    assert_eq!(Some(&Value::datetime_utc(dt)), res.at(0));
 
     Ok(())
 }
@@ -151,8 +151,7 @@ async fn test_type_text_datetime_custom(api: &mut dyn TestApi) -> crate::Result<
     let select = Select::from_table(&table).column("value").order_by("id".descend());
     let res = api.conn().select(select).await?.into_single()?;
 
-    let naive = chrono::NaiveDateTime::parse_from_str("2020-04-20 16:20:00", "%Y-%m-%d %H:%M:%S").unwrap();
-    let expected = chrono::DateTime::from_utc(naive, chrono::Utc);
+    let expected = chrono::NaiveDateTime::parse_from_str("2020-04-20 16:20:00", "%Y-%m-%d %H:%M:%S").unwrap();
 
     assert_eq!(Some(&Value::datetime(expected)), res.at(0));
 
@@ -177,3 +176,16 @@ async fn test_get_int64_from_int32_field_fails(api: &mut dyn TestApi) -> crate::
 
     Ok(())
 }
+
+// UUIDs have no native SQLite type. The canonical representation is the
+// hyphenated lowercase text form, reading back as text.
+#[cfg(feature = "uuid")]
+test_type!(uuid(
+    sqlite,
+    "VARCHAR(36)",
+    (Value::Uuid(None), Value::Text(None)),
+    (
+        Value::uuid(uuid::Uuid::from_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap()),
+        Value::text("936da01f-9abd-4d9d-80c7-02af85c822a8")
+    )
+));
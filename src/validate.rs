@@ -0,0 +1,519 @@
+//! Strict validation of connection strings without connecting.
+//!
+//! The URL parsers of the connectors are lenient by design: unknown
+//! parameters are discarded with a trace log, so a shared connection string
+//! can carry parameters quaint does not know about. Tooling validating user
+//! input wants the opposite. [`validate_connection_string`] parses a
+//! connection string in a strict mode and reports unknown parameters, bad
+//! values and conflicting options as structured issues, without dialing the
+//! database. The constructors themselves stay lenient.
+
+use crate::connector::ConnectionInfo;
+
+/// A single problem found in a connection string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// The connection string parameter the issue is about, or the whole URL
+    /// when it does not parse at all.
+    pub parameter: String,
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// A suggested replacement when a close match or an obvious fix exists.
+    pub suggestion: Option<String>,
+}
+
+impl ValidationIssue {
+    fn new(parameter: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn suggesting(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+/// A connection string that survived strict validation.
+#[derive(Debug)]
+pub struct ValidatedConfig {
+    connection_info: ConnectionInfo,
+}
+
+impl ValidatedConfig {
+    /// The parsed form of the validated connection string.
+    pub fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
+}
+
+/// The value a parameter accepts, used for strict value checking.
+enum ParamKind {
+    /// A non-negative integer, e.g. a timeout in seconds.
+    Integer,
+    /// `true` or `false`.
+    Boolean,
+    /// One of a fixed set of keywords.
+    OneOf(&'static [&'static str]),
+    /// Free-form text, checked for presence only.
+    Text,
+}
+
+struct ParamSpec {
+    name: &'static str,
+    kind: ParamKind,
+}
+
+const fn integer(name: &'static str) -> ParamSpec {
+    ParamSpec {
+        name,
+        kind: ParamKind::Integer,
+    }
+}
+
+const fn boolean(name: &'static str) -> ParamSpec {
+    ParamSpec {
+        name,
+        kind: ParamKind::Boolean,
+    }
+}
+
+const fn one_of(name: &'static str, values: &'static [&'static str]) -> ParamSpec {
+    ParamSpec {
+        name,
+        kind: ParamKind::OneOf(values),
+    }
+}
+
+const fn text(name: &'static str) -> ParamSpec {
+    ParamSpec {
+        name,
+        kind: ParamKind::Text,
+    }
+}
+
+#[cfg(feature = "postgresql")]
+const POSTGRES_PARAMS: &[ParamSpec] = &[
+    boolean("pgbouncer"),
+    one_of("sslmode", &["disable", "prefer", "require"]),
+    text("sslcert"),
+    text("sslidentity"),
+    text("sslpassword"),
+    integer("statement_cache_size"),
+    one_of("sslaccept", &["strict", "accept_invalid_certs"]),
+    text("schema"),
+    integer("connection_limit"),
+    text("host"),
+    integer("socket_timeout"),
+    integer("connect_timeout"),
+    integer("pool_timeout"),
+    integer("max_connection_lifetime"),
+    integer("max_idle_connection_lifetime"),
+    integer("slow_threshold"),
+    text("application_name"),
+    one_of("channel_binding", &["disable", "prefer", "require"]),
+    text("options"),
+    one_of(
+        "target_session_attrs",
+        &["any", "read-write", "primary", "read-only", "standby"],
+    ),
+    boolean("assume_utc"),
+    text("query_comment"),
+];
+
+#[cfg(feature = "mysql")]
+const MYSQL_PARAMS: &[ParamSpec] = &[
+    integer("connection_limit"),
+    integer("statement_cache_size"),
+    text("sslcert"),
+    text("ssl_ca"),
+    text("ssl_cert"),
+    text("ssl_key"),
+    text("sslidentity"),
+    text("sslpassword"),
+    text("socket"),
+    integer("socket_timeout"),
+    boolean("prefer_socket"),
+    integer("connect_timeout"),
+    integer("pool_timeout"),
+    one_of("sslaccept", &["strict", "accept_invalid_certs"]),
+    one_of("ssl_accept_mode", &["strict", "accept_invalid_certs"]),
+    integer("max_connection_lifetime"),
+    integer("max_idle_connection_lifetime"),
+    integer("slow_threshold"),
+    one_of("mysql_flavour", &["mysql", "mariadb"]),
+    boolean("tinyint1_is_bool"),
+    boolean("assume_utc"),
+    text("time_zone"),
+    text("query_comment"),
+];
+
+#[cfg(feature = "sqlite")]
+const SQLITE_PARAMS: &[ParamSpec] = &[
+    integer("connection_limit"),
+    integer("socket_timeout"),
+    integer("max_connection_lifetime"),
+    integer("max_idle_connection_lifetime"),
+    integer("slow_threshold"),
+    one_of("journal_mode", &["delete", "wal", "memory", "off"]),
+    one_of("transaction_mode", &["deferred", "immediate", "exclusive"]),
+    boolean("assume_utc"),
+    text("query_comment"),
+];
+
+#[cfg(feature = "mssql")]
+const ISOLATION_LEVELS: &[&str] = &[
+    "READ UNCOMMITTED",
+    "READ COMMITTED",
+    "REPEATABLE READ",
+    "SNAPSHOT",
+    "SERIALIZABLE",
+];
+
+/// SQL Server parameters are matched case-insensitively against this list.
+#[cfg(feature = "mssql")]
+const MSSQL_PARAMS: &[ParamSpec] = &[
+    text("user"),
+    text("password"),
+    text("database"),
+    text("schema"),
+    integer("connectionlimit"),
+    integer("connection_limit"),
+    one_of("isolationlevel", ISOLATION_LEVELS),
+    one_of("isolation_level", ISOLATION_LEVELS),
+    integer("logintimeout"),
+    integer("login_timeout"),
+    integer("connecttimeout"),
+    integer("connect_timeout"),
+    integer("connectiontimeout"),
+    integer("connection_timeout"),
+    integer("pooltimeout"),
+    integer("pool_timeout"),
+    integer("sockettimeout"),
+    integer("socket_timeout"),
+    one_of("encrypt", &["true", "false", "DANGER_PLAINTEXT"]),
+    boolean("trustservercertificate"),
+    boolean("trust_server_certificate"),
+    text("trustservercertificateca"),
+    text("trust_server_certificate_ca"),
+    integer("max_connection_lifetime"),
+    integer("max_idle_connection_lifetime"),
+    integer("slowthreshold"),
+    integer("slow_threshold"),
+    text("querycomment"),
+    text("query_comment"),
+];
+
+/// Validate a connection string strictly, without connecting.
+///
+/// Returns the parsed configuration when the string is clean, or the full
+/// list of problems found. Unknown parameters come back with the closest
+/// known parameter as a suggestion, catching typos like `connec_timeout`.
+///
+/// ```rust
+/// # #[cfg(feature = "postgresql")] {
+/// use quaint::validate::validate_connection_string;
+///
+/// let issues = validate_connection_string("postgresql://localhost/db?connec_timeout=5").unwrap_err();
+///
+/// assert_eq!("connec_timeout", issues[0].parameter);
+/// assert_eq!(Some("connect_timeout".into()), issues[0].suggestion);
+/// # }
+/// ```
+pub fn validate_connection_string(url: &str) -> Result<ValidatedConfig, Vec<ValidationIssue>> {
+    let mut issues = validate_parameters(url);
+
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    match ConnectionInfo::from_url(url) {
+        Ok(connection_info) => Ok(ValidatedConfig { connection_info }),
+        Err(e) => {
+            issues.push(ValidationIssue::new("", e.to_string()));
+            Err(issues)
+        }
+    }
+}
+
+fn validate_parameters(url: &str) -> Vec<ValidationIssue> {
+    #[cfg(feature = "mssql")]
+    if url.starts_with("jdbc:sqlserver") || url.starts_with("sqlserver") {
+        return validate_mssql_parameters(url);
+    }
+
+    let parsed = match url::Url::parse(url) {
+        Ok(parsed) => parsed,
+        // Anything that is not a URL is left to the lenient parsers; SQLite
+        // accepts bare file paths here.
+        Err(_) => return Vec::new(),
+    };
+
+    let is_postgres = matches!(parsed.scheme(), "postgres" | "postgresql");
+
+    let specs = match parsed.scheme() {
+        #[cfg(feature = "postgresql")]
+        "postgres" | "postgresql" => POSTGRES_PARAMS,
+        #[cfg(feature = "mysql")]
+        "mysql" => MYSQL_PARAMS,
+        #[cfg(feature = "sqlite")]
+        "file" | "sqlite" => SQLITE_PARAMS,
+        _ => return Vec::new(),
+    };
+
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    let mut issues = Vec::new();
+
+    for (key, value) in &pairs {
+        issues.extend(check_parameter(specs, key, value));
+    }
+
+    // `pgbouncer=true` turns the statement cache off, so a configured cache
+    // size next to it never takes effect.
+    if is_postgres {
+        let pgbouncer = pairs.iter().any(|(k, v)| k == "pgbouncer" && v == "true");
+        let cache_size = pairs.iter().find(|(k, _)| k == "statement_cache_size");
+
+        if let (true, Some((_, size))) = (pgbouncer, cache_size) {
+            if size != "0" {
+                let issue = ValidationIssue::new(
+                    "statement_cache_size",
+                    "pgbouncer=true disables the statement cache, so this value never takes effect.",
+                )
+                .suggesting("statement_cache_size=0");
+
+                issues.push(issue);
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(feature = "mssql")]
+fn validate_mssql_parameters(url: &str) -> Vec<ValidationIssue> {
+    use connection_string::JdbcString;
+    use std::str::FromStr;
+
+    let with_prefix = if url.starts_with("jdbc") {
+        url.to_string()
+    } else {
+        format!("jdbc:{url}")
+    };
+
+    let conn = match JdbcString::from_str(&with_prefix) {
+        Ok(conn) => conn,
+        Err(e) => return vec![ValidationIssue::new("", e.to_string())],
+    };
+
+    let mut issues = Vec::new();
+
+    for (key, value) in conn.properties() {
+        issues.extend(check_parameter(MSSQL_PARAMS, &key.to_lowercase(), value));
+    }
+
+    issues
+}
+
+fn check_parameter(specs: &[ParamSpec], key: &str, value: &str) -> Option<ValidationIssue> {
+    let spec = match specs.iter().find(|spec| spec.name == key) {
+        Some(spec) => spec,
+        None => {
+            let issue = ValidationIssue::new(key, format!("`{key}` is not a known connection parameter."));
+
+            return match closest_parameter(specs, key) {
+                Some(closest) => Some(issue.suggesting(closest)),
+                None => Some(issue),
+            };
+        }
+    };
+
+    match spec.kind {
+        ParamKind::Integer if value.parse::<u64>().is_err() => Some(ValidationIssue::new(
+            key,
+            format!("`{value}` is not a valid value for `{key}`, expected a non-negative integer."),
+        )),
+        ParamKind::Boolean if value.parse::<bool>().is_err() => {
+            let issue = ValidationIssue::new(
+                key,
+                format!("`{value}` is not a valid value for `{key}`, expected `true` or `false`."),
+            );
+
+            Some(match value.to_lowercase().as_str() {
+                "1" | "yes" | "on" => issue.suggesting("true"),
+                "0" | "no" | "off" => issue.suggesting("false"),
+                _ => issue,
+            })
+        }
+        ParamKind::OneOf(values) if !values.iter().any(|v| v.eq_ignore_ascii_case(value)) => {
+            let issue = ValidationIssue::new(
+                key,
+                format!("`{value}` is not a valid value for `{key}`, expected one of {}.", values.join(", ")),
+            );
+
+            Some(issue)
+        }
+        _ => None,
+    }
+}
+
+/// The known parameter closest to a misspelled one, when close enough for
+/// the misspelling to be a plausible typo.
+fn closest_parameter(specs: &[ParamSpec], key: &str) -> Option<&'static str> {
+    specs
+        .iter()
+        .map(|spec| (spec.name, edit_distance(spec.name, key)))
+        .filter(|(name, distance)| *distance * 3 <= name.len().max(key.len()))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(name, _)| name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn a_clean_connection_string_validates() {
+        let config = validate_connection_string("postgresql://localhost:5432/db?connect_timeout=5").unwrap();
+
+        assert_eq!(Some("db"), config.connection_info().dbname());
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn a_misspelled_parameter_suggests_the_closest_one() {
+        let issues = validate_connection_string("postgresql://localhost/db?connec_timeout=5").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("connec_timeout", issues[0].parameter);
+        assert_eq!(Some("connect_timeout".to_string()), issues[0].suggestion);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn an_unrelated_parameter_gets_no_suggestion() {
+        let issues = validate_connection_string("postgresql://localhost/db?bananas=yes").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!(None, issues[0].suggestion);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn an_invalid_number_is_an_issue() {
+        let issues = validate_connection_string("postgresql://localhost/db?connect_timeout=soon").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("connect_timeout", issues[0].parameter);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn an_invalid_sslmode_is_an_issue() {
+        let issues = validate_connection_string("postgresql://localhost/db?sslmode=never").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("sslmode", issues[0].parameter);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn pgbouncer_with_a_statement_cache_is_a_conflict() {
+        let issues =
+            validate_connection_string("postgresql://localhost/db?pgbouncer=true&statement_cache_size=500")
+                .unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("statement_cache_size", issues[0].parameter);
+        assert_eq!(Some("statement_cache_size=0".to_string()), issues[0].suggestion);
+    }
+
+    #[test]
+    #[cfg(feature = "mysql")]
+    fn a_bad_boolean_suggests_the_spelling() {
+        let issues = validate_connection_string("mysql://localhost/db?tinyint1_is_bool=yes").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!(Some("true".to_string()), issues[0].suggestion);
+    }
+
+    #[test]
+    #[cfg(feature = "mysql")]
+    fn mysql_time_zone_options_are_known() {
+        validate_connection_string("mysql://localhost/db?assume_utc=true&time_zone=%2B00%3A00").unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn sqlite_journal_mode_is_checked() {
+        let issues = validate_connection_string("file:dev.db?journal_mode=rotating").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("journal_mode", issues[0].parameter);
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn sqlite_transaction_mode_is_checked() {
+        validate_connection_string("file:dev.db?transaction_mode=immediate").unwrap();
+
+        let issues = validate_connection_string("file:dev.db?transaction_mode=pessimistic").unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("transaction_mode", issues[0].parameter);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn query_comment_is_known() {
+        validate_connection_string("postgresql://localhost/db?query_comment=app%3Dbilling").unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "mssql")]
+    fn mssql_parameters_are_matched_case_insensitively() {
+        let issues =
+            validate_connection_string("sqlserver://localhost:1433;database=db;TrustServerCertificate=banana")
+                .unwrap_err();
+
+        assert_eq!(1, issues.len());
+        assert_eq!("trustservercertificate", issues[0].parameter);
+    }
+
+    #[test]
+    #[cfg(feature = "postgresql")]
+    fn multiple_issues_are_all_reported() {
+        let issues =
+            validate_connection_string("postgresql://localhost/db?connec_timeout=5&sslmode=never").unwrap_err();
+
+        assert_eq!(2, issues.len());
+    }
+}
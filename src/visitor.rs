@@ -6,29 +6,50 @@
 //! [ast](../ast/index.html) module.
 //!
 //! For prelude, all important imports are in `quaint::visitor::*`;
-#[cfg(feature = "mssql")]
 mod mssql;
-#[cfg(feature = "mysql")]
 mod mysql;
-#[cfg(feature = "postgresql")]
 mod postgres;
-#[cfg(feature = "sqlite")]
 mod sqlite;
 
-#[cfg(feature = "mssql")]
 pub use self::mssql::Mssql;
-#[cfg(feature = "mysql")]
-pub use self::mysql::Mysql;
-#[cfg(feature = "postgresql")]
-pub use self::postgres::Postgres;
-#[cfg(feature = "sqlite")]
+pub use self::mysql::{Mysql, MysqlFlavour};
+pub use self::postgres::{Postgres, PostgresFlavour};
 pub use self::sqlite::Sqlite;
 
-use crate::ast::*;
+use crate::{
+    ast::*,
+    error::{Error, ErrorKind},
+};
 use std::{borrow::Cow, fmt};
 
 pub type Result = crate::Result<()>;
 
+/// The feature set a database dialect supports, for downstream query builders
+/// that would otherwise hardcode the dialects. Every visitor exposes its
+/// baseline as an associated `CAPABILITIES` constant, assuming the oldest
+/// server version quaint supports; `Queryable::capabilities` refines the
+/// baseline with the version of the connected server, e.g. `RETURNING` on
+/// SQLite 3.35 or newer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `INSERT`/`UPDATE`/`DELETE` can return rows with a `RETURNING` clause.
+    pub returning: bool,
+    /// Row value comparisons, e.g. `(a, b) > (1, 2)`.
+    pub row_values: bool,
+    /// `LATERAL` subqueries in the `FROM` clause.
+    pub lateral: bool,
+    /// A native JSON document type with JSON functions.
+    pub json: bool,
+    /// A condition on the update branch of an upsert.
+    pub upsert_where: bool,
+    /// `SELECT DISTINCT ON (..)`.
+    pub distinct_on: bool,
+    /// The `UNION`, `INTERSECT` and `EXCEPT` set operations.
+    pub set_operations: bool,
+    /// Window functions, e.g. `ROW_NUMBER() OVER (..)`.
+    pub window_functions: bool,
+}
+
 /// A function travelling through the query AST, building the final query string
 /// and gathering parameters sent to the database together with the query.
 pub trait Visitor<'a> {
@@ -39,6 +60,15 @@ pub trait Visitor<'a> {
     /// Wildcard character to be used in `LIKE` queries.
     const C_WILDCARD: &'static str;
 
+    /// Whether the dialect renders a `TOP` limiter right after the `SELECT`
+    /// keyword. Without the syntax, a `TOP` folds into the limit of the
+    /// query.
+    const HAS_TOP: bool = false;
+
+    /// The dialect whose optimizer [`Hint`]s the visitor renders, if any.
+    /// Hints of the other dialects are dropped with a trace log.
+    const HINT_DIALECT: Option<HintDialect> = None;
+
     /// Convert the given `Query` to an SQL string and a vector of parameters.
     /// When certain parameters are replaced with the `C_PARAM` character in the
     /// query, the vector should contain the parameter value in the right position.
@@ -99,6 +129,28 @@ pub trait Visitor<'a> {
         Ok(())
     }
 
+    /// The column list and the `SELECT` providing the inserted rows of an
+    /// `INSERT INTO ... SELECT` statement.
+    fn visit_insert_from_select(&mut self, columns: Vec<Column<'a>>, query: SelectQuery<'a>) -> Result {
+        if !columns.is_empty() {
+            if let Some(count) = query.column_count() {
+                if count != columns.len() {
+                    let kind = ErrorKind::QueryInvalidInput(format!(
+                        "The INSERT lists {} columns, but the SELECT returns {count}.",
+                        columns.len()
+                    ));
+
+                    return Err(Error::builder(kind).build());
+                }
+            }
+
+            self.columns_to_bracket_list(columns)?;
+        }
+
+        self.write(" ")?;
+        self.visit_selection(query)
+    }
+
     /// When called, the visitor decided to not render the parameter into the query,
     /// replacing it with the `C_PARAM`, calling `add_parameter` with the replaced value.
     fn add_parameter(&mut self, value: Value<'a>);
@@ -118,34 +170,66 @@ pub trait Visitor<'a> {
     /// What to use to substitute a parameter in the query.
     fn visit_aggregate_to_string(&mut self, value: Expression<'a>) -> Result;
 
+    /// A visit to a `STRING_AGG`/`GROUP_CONCAT` aggregate, concatenating the
+    /// values of a group into one delimited string.
+    fn visit_string_agg(&mut self, string_agg: StringAgg<'a>) -> Result;
+
+    /// A visit to an `ARRAY_AGG` aggregate, collecting the values of a group
+    /// into an array.
+    fn visit_array_agg(&mut self, _array_agg: ArrayAgg<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("ARRAY_AGG is only supported on PostgreSQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// A visit to a `JSON_AGG` aggregate, collecting the values of a group
+    /// into a JSON array.
+    #[cfg(feature = "json")]
+    fn visit_json_agg(&mut self, _json_agg: JsonAgg<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("JSON_AGG is only supported on PostgreSQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// A visit to a `PERCENTILE_CONT` ordered-set aggregate.
+    fn visit_percentile_cont(&mut self, _percentile_cont: PercentileCont<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("PERCENTILE_CONT is only supported on PostgreSQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// A visit to a `PERCENTILE_DISC` ordered-set aggregate.
+    fn visit_percentile_disc(&mut self, _percentile_disc: PercentileDisc<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("PERCENTILE_DISC is only supported on PostgreSQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
     /// Visit a non-parameterized value.
     fn visit_raw_value(&mut self, value: Value<'a>) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract(&mut self, json_extract: JsonExtract<'a>) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_last_array_item(&mut self, extract: JsonExtractLastArrayElem<'a>) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_first_array_item(&mut self, extract: JsonExtractFirstArrayElem<'a>) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_array_contains(&mut self, left: Expression<'a>, right: Expression<'a>, not: bool) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_type_equals(&mut self, left: Expression<'a>, right: JsonType<'a>, not: bool) -> Result;
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_unquote(&mut self, json_unquote: JsonUnquote<'a>) -> Result;
 
-    #[cfg(any(feature = "postgresql", feature = "mysql"))]
     fn visit_text_search(&mut self, text_search: TextSearch<'a>) -> Result;
 
-    #[cfg(any(feature = "postgresql", feature = "mysql"))]
     fn visit_matches(&mut self, left: Expression<'a>, right: std::borrow::Cow<'a, str>, not: bool) -> Result;
 
-    #[cfg(any(feature = "postgresql", feature = "mysql"))]
     fn visit_text_search_relevance(&mut self, text_search_relevance: TextSearchRelevance<'a>) -> Result;
 
     /// A visit to a value we parameterize
@@ -154,32 +238,85 @@ pub trait Visitor<'a> {
         self.parameter_substitution()
     }
 
+    /// A raw SQL fragment written into the query as-is, with its `?`
+    /// placeholders replaced by the parameter substitution of the dialect and
+    /// the parameters spliced into the right positions. Placeholders inside
+    /// string literals, quoted identifiers or `--` comments are left alone.
+    fn visit_raw_fragment(&mut self, fragment: RawFragment<'a>) -> Result {
+        let RawFragment { sql, params } = fragment;
+        let placeholders = raw_fragment_placeholders(&sql);
+
+        if placeholders.len() != params.len() {
+            let kind = ErrorKind::QueryInvalidInput(format!(
+                "A raw fragment has {} placeholders, but {} parameters were given.",
+                placeholders.len(),
+                params.len(),
+            ));
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut start = 0;
+
+        for (offset, param) in placeholders.into_iter().zip(params) {
+            self.write(&sql[start..offset])?;
+            self.add_parameter(param);
+            self.parameter_substitution()?;
+
+            start = offset + 1;
+        }
+
+        self.write(&sql[start..])
+    }
+
     /// The join statements in the query
     fn visit_joins(&mut self, joins: Vec<Join<'a>>) -> Result {
         for j in joins {
-            match j {
-                Join::Inner(data) => {
-                    self.write(" INNER JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Left(data) => {
-                    self.write(" LEFT JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Right(data) => {
-                    self.write(" RIGHT JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Full(data) => {
-                    self.write(" FULL JOIN ")?;
-                    self.visit_join_data(data)?;
+            let (kind, data) = match j {
+                Join::Inner(data) => ("INNER", data),
+                Join::Left(data) => ("LEFT", data),
+                Join::Right(data) => ("RIGHT", data),
+                Join::Full(data) => ("FULL", data),
+            };
+
+            if data.lateral {
+                match (&data.table.typ, &data.table.alias) {
+                    (TableType::Query(_), Some(_)) => (),
+                    (TableType::Query(_), None) => {
+                        let kind = ErrorKind::QueryInvalidInput("A lateral join subquery must have an alias.".into());
+
+                        return Err(Error::builder(kind).build());
+                    }
+                    _ => {
+                        let kind =
+                            ErrorKind::QueryInvalidInput("A lateral join source must be a sub-select.".into());
+
+                        return Err(Error::builder(kind).build());
+                    }
                 }
+
+                self.visit_lateral_join(kind, data)?;
+            } else {
+                self.write(" ")?;
+                self.write(kind)?;
+                self.write(" JOIN ")?;
+                self.visit_join_data(data)?;
             }
         }
 
         Ok(())
     }
 
+    /// A join against a lateral subquery, able to refer to columns of the
+    /// preceding tables. The default renders the `LATERAL` keyword understood
+    /// by PostgreSQL and MySQL 8.0.14+.
+    fn visit_lateral_join(&mut self, kind: &str, data: JoinData<'a>) -> Result {
+        self.write(" ")?;
+        self.write(kind)?;
+        self.write(" JOIN LATERAL ")?;
+        self.visit_join_data(data)
+    }
+
     fn visit_join_data(&mut self, data: JoinData<'a>) -> Result {
         self.visit_table(data.table, true)?;
         self.write(" ON ")?;
@@ -187,7 +324,14 @@ pub trait Visitor<'a> {
     }
 
     /// A walk through a `SELECT` statement
-    fn visit_select(&mut self, select: Select<'a>) -> Result {
+    fn visit_select(&mut self, mut select: Select<'a>) -> Result {
+        let mut hints = hints_for_dialect(std::mem::take(&mut select.hints), Self::HINT_DIALECT);
+
+        if Self::HINT_DIALECT == Some(HintDialect::Postgres) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
         let number_of_ctes = select.ctes.len();
 
         if number_of_ctes > 0 {
@@ -206,10 +350,32 @@ pub trait Visitor<'a> {
 
         self.write("SELECT ")?;
 
+        if Self::HINT_DIALECT == Some(HintDialect::Mysql) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
         if select.distinct {
             self.write("DISTINCT ")?;
         }
 
+        // `TOP` goes between the `DISTINCT` and the column list on SQL
+        // Server; without the syntax the value folds into the limit of the
+        // query.
+        match select.top.take() {
+            Some(top) if Self::HAS_TOP => {
+                self.write("TOP (")?;
+                self.visit_parameterized(top)?;
+                self.write(") ")?;
+            }
+            Some(top) => {
+                if select.limit.is_none() {
+                    select.limit = Some(top);
+                }
+            }
+            None => (),
+        }
+
         if !select.tables.is_empty() {
             if select.columns.is_empty() {
                 for (i, table) in select.tables.iter().enumerate() {
@@ -218,7 +384,7 @@ pub trait Visitor<'a> {
                     }
 
                     match &table.typ {
-                        TableType::Query(_) | TableType::Values(_) => match table.alias {
+                        TableType::Query(_) | TableType::Values(_) | TableType::Fragment(_) => match table.alias {
                             Some(ref alias) => {
                                 self.surround_with(Self::C_BACKTICK_OPEN, Self::C_BACKTICK_CLOSE, |ref mut s| {
                                     s.write(alias)
@@ -276,6 +442,11 @@ pub trait Visitor<'a> {
                 self.visit_joins(select.joins)?;
             }
 
+            if let Some(expr) = select.as_of_system_time {
+                self.write(" ")?;
+                self.visit_as_of_system_time(expr)?;
+            }
+
             if let Some(conditions) = select.conditions {
                 self.write(" WHERE ")?;
                 self.visit_conditions(conditions)?;
@@ -293,13 +464,27 @@ pub trait Visitor<'a> {
                 self.visit_ordering(select.ordering)?;
             }
 
-            self.visit_limit_and_offset(select.limit, select.offset)?;
+            if select.offset_fetch {
+                self.visit_offset_fetch(select.limit, select.offset)?;
+            } else {
+                self.visit_limit_and_offset(select.limit, select.offset)?;
+            }
         } else if select.columns.is_empty() {
             self.write(" *")?;
         } else {
             self.visit_columns(select.columns)?;
         }
 
+        if let Some(outfile) = select.into_outfile {
+            self.write(" ")?;
+            self.visit_into_outfile(outfile)?;
+        }
+
+        if !hints.is_empty() {
+            self.write(" ")?;
+            self.visit_option_clause(hints)?;
+        }
+
         if let Some(comment) = select.comment {
             self.write(" ")?;
             self.visit_comment(comment)?;
@@ -308,9 +493,56 @@ pub trait Visitor<'a> {
         Ok(())
     }
 
+    /// A rendering of the SQL standard `OFFSET .. ROWS FETCH FIRST .. ROWS
+    /// ONLY` limiter.
+    fn visit_offset_fetch(&mut self, _limit: Option<Value<'a>>, _offset: Option<Value<'a>>) -> Result {
+        let kind =
+            ErrorKind::UnsupportedOperation("OFFSET .. FETCH is only supported on PostgreSQL and SQL Server.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// A rendering of the `AS OF SYSTEM TIME` clause of a time travel query.
+    fn visit_as_of_system_time(&mut self, _expr: Expression<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("AS OF SYSTEM TIME is only supported on CockroachDB.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
     /// A walk through an `UPDATE` statement
-    fn visit_update(&mut self, update: Update<'a>) -> Result {
+    fn visit_update(&mut self, mut update: Update<'a>) -> Result {
+        if !update.from_tables.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE ... FROM is only supported on PostgreSQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !update.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if update.limit.is_some() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE ... LIMIT is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut hints = hints_for_dialect(std::mem::take(&mut update.hints), Self::HINT_DIALECT);
+
+        if Self::HINT_DIALECT == Some(HintDialect::Postgres) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
         self.write("UPDATE ")?;
+
+        if Self::HINT_DIALECT == Some(HintDialect::Mysql) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
         self.visit_table(update.table, true)?;
 
         {
@@ -334,6 +566,11 @@ pub trait Visitor<'a> {
             self.visit_conditions(conditions)?;
         }
 
+        if !hints.is_empty() {
+            self.write(" ")?;
+            self.visit_option_clause(hints)?;
+        }
+
         if let Some(comment) = update.comment {
             self.write(" ")?;
             self.visit_comment(comment)?;
@@ -374,8 +611,41 @@ pub trait Visitor<'a> {
     }
 
     /// A walk through an `DELETE` statement
-    fn visit_delete(&mut self, delete: Delete<'a>) -> Result {
-        self.write("DELETE FROM ")?;
+    fn visit_delete(&mut self, mut delete: Delete<'a>) -> Result {
+        if !delete.using.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE ... USING is only supported on PostgreSQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !delete.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if delete.limit.is_some() || !delete.ordering.is_empty() {
+            let kind =
+                ErrorKind::UnsupportedOperation("DELETE with ORDER BY or LIMIT is not supported on this database.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        let mut hints = hints_for_dialect(std::mem::take(&mut delete.hints), Self::HINT_DIALECT);
+
+        if Self::HINT_DIALECT == Some(HintDialect::Postgres) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
+        self.write("DELETE ")?;
+
+        if Self::HINT_DIALECT == Some(HintDialect::Mysql) && !hints.is_empty() {
+            self.visit_hint_comment(std::mem::take(&mut hints))?;
+            self.write(" ")?;
+        }
+
+        self.write("FROM ")?;
         self.visit_table(delete.table, true)?;
 
         if let Some(conditions) = delete.conditions {
@@ -383,6 +653,11 @@ pub trait Visitor<'a> {
             self.visit_conditions(conditions)?;
         }
 
+        if !hints.is_empty() {
+            self.write(" ")?;
+            self.visit_option_clause(hints)?;
+        }
+
         if let Some(comment) = delete.comment {
             self.write(" ")?;
             self.visit_comment(comment)?;
@@ -418,6 +693,113 @@ pub trait Visitor<'a> {
         unimplemented!("Merges not supported for the underlying database.")
     }
 
+    /// Visit a `REPLACE INTO` statement. Only the MySQL visitor renders the
+    /// statement.
+    fn visit_replace_into(&mut self, _replace: ReplaceInto<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("REPLACE INTO is only supported on MySQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// Visit a `LOAD DATA INFILE` bulk load. Only the MySQL visitor renders
+    /// the statement.
+    fn visit_load_data_infile(&mut self, _load: LoadDataInfile<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("LOAD DATA INFILE is only supported on MySQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// Visit a `TABLESAMPLE` clause on a table. Only the PostgreSQL and SQL
+    /// Server visitors render the clause.
+    fn visit_table_sample(&mut self, _sample: TableSample) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("TABLESAMPLE is only supported on PostgreSQL and SQL Server.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// Visit a `SELECT ... INTO OUTFILE` export clause. Only the MySQL
+    /// visitor renders the clause.
+    fn visit_into_outfile(&mut self, _outfile: IntoOutfile<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("SELECT ... INTO OUTFILE is only supported on MySQL.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// Visit a `CREATE VIRTUAL TABLE .. USING fts5(..)` statement. Only the
+    /// SQLite visitor renders the statement.
+    fn visit_create_fts5_table(&mut self, _create: CreateFts5Table<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("FTS5 virtual tables are only supported on SQLite.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// Visit a full-text `MATCH` condition against an FTS5 virtual table.
+    /// Only the SQLite visitor renders the condition.
+    fn visit_fts_match(&mut self, _fts_match: FtsMatch<'a>) -> Result {
+        let kind = ErrorKind::UnsupportedOperation("FTS5 `MATCH` is only supported on SQLite.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
+    /// A visit to a `CASE WHEN .. THEN .. ELSE .. END` conditional expression
+    fn visit_case(&mut self, case: Case<'a>) -> Result {
+        self.write("CASE ")?;
+
+        if let Some(operand) = case.operand {
+            self.visit_expression(*operand)?;
+            self.write(" ")?;
+        }
+
+        for (when, then) in case.branches {
+            self.write("WHEN ")?;
+            self.visit_expression(when)?;
+            self.write(" THEN ")?;
+            self.visit_expression(then)?;
+            self.write(" ")?;
+        }
+
+        if let Some(else_branch) = case.else_branch {
+            self.write("ELSE ")?;
+            self.visit_expression(*else_branch)?;
+            self.write(" ")?;
+        }
+
+        self.write("END")
+    }
+
+    /// A visit to a `CAST` expression
+    fn visit_cast(&mut self, cast: Cast<'a>) -> Result {
+        self.write("CAST(")?;
+        self.visit_expression(*cast.expression)?;
+        self.write(" AS ")?;
+        self.visit_cast_type(cast.cast_type)?;
+        self.write(")")
+    }
+
+    /// The dialect's name for a `CAST` target type. The defaults follow the
+    /// standard SQL names.
+    fn visit_cast_type(&mut self, cast_type: CastType) -> Result {
+        let name = match cast_type {
+            CastType::Text => "TEXT",
+            CastType::Integer => "INTEGER",
+            CastType::BigInt => "BIGINT",
+            CastType::Float => "REAL",
+            CastType::Double => "DOUBLE PRECISION",
+            CastType::Boolean => "BOOLEAN",
+            CastType::Date => "DATE",
+            CastType::DateTime => "TIMESTAMP",
+            CastType::Json => "JSON",
+            CastType::Uuid => "UUID",
+            CastType::Unsigned => {
+                let kind = ErrorKind::UnsupportedOperation("CAST AS UNSIGNED is only supported on MySQL.".into());
+
+                return Err(Error::builder(kind).build());
+            }
+        };
+
+        self.write(name)
+    }
+
     /// A walk through a complete `Query` statement
     fn visit_query(&mut self, mut query: Query<'a>) -> Result {
         query = self.compatibility_modifications(query);
@@ -429,11 +811,18 @@ pub trait Visitor<'a> {
             Query::Delete(delete) => self.visit_delete(*delete),
             Query::Union(union) => self.visit_union(*union),
             Query::Merge(merge) => self.visit_merge(*merge),
+            Query::ReplaceInto(replace) => self.visit_replace_into(*replace),
+            Query::LoadDataInfile(load) => self.visit_load_data_infile(*load),
+            Query::CreateFts5Table(create) => self.visit_create_fts5_table(*create),
             Query::Raw(string) => self.write(string),
         }
     }
 
     fn visit_sub_selection(&mut self, query: SelectQuery<'a>) -> Result {
+        if let SelectQuery::Select(ref select) = query {
+            ensure_no_nested_into_outfile(select)?;
+        }
+
         self.visit_selection(query)
     }
 
@@ -531,8 +920,12 @@ pub trait Visitor<'a> {
             ExpressionKind::Value(value) => self.visit_expression(*value)?,
             ExpressionKind::ConditionTree(tree) => self.visit_conditions(tree)?,
             ExpressionKind::Compare(compare) => self.visit_compare(compare)?,
+            ExpressionKind::FtsMatch(fts_match) => self.visit_fts_match(fts_match)?,
+            ExpressionKind::Case(case) => self.visit_case(case)?,
+            ExpressionKind::Cast(cast) => self.visit_cast(cast)?,
             ExpressionKind::Parameterized(val) => self.visit_parameterized(val)?,
             ExpressionKind::RawValue(val) => self.visit_raw_value(val.0)?,
+            ExpressionKind::RawFragment(fragment) => self.visit_raw_fragment(fragment)?,
             ExpressionKind::Column(column) => self.visit_column(*column)?,
             ExpressionKind::Row(row) => self.visit_row(row)?,
             ExpressionKind::Selection(selection) => {
@@ -548,6 +941,7 @@ pub trait Visitor<'a> {
                 }
                 None => self.write("*")?,
             },
+            ExpressionKind::AsteriskExcept(except) => self.visit_columns(except.expand())?,
             ExpressionKind::Default => self.write("DEFAULT")?,
         }
 
@@ -587,8 +981,25 @@ pub trait Visitor<'a> {
                 Some(database) => self.delimited_identifiers(&[&*database, &*table_name])?,
                 None => self.delimited_identifiers(&[&*table_name])?,
             },
-            TableType::Values(values) => self.visit_values(values)?,
-            TableType::Query(select) => self.surround_with("(", ")", |ref mut s| s.visit_select(*select))?,
+            TableType::Values(values) => self.surround_with("(", ")", |ref mut s| {
+                s.write("VALUES ")?;
+
+                let len = values.len();
+                for (i, row) in values.into_iter().enumerate() {
+                    s.visit_row(row)?;
+
+                    if i < (len - 1) {
+                        s.write(",")?;
+                    }
+                }
+
+                Ok(())
+            })?,
+            TableType::Fragment(fragment) => self.visit_raw_fragment(fragment)?,
+            TableType::Query(select) => {
+                ensure_no_nested_into_outfile(&select)?;
+                self.surround_with("(", ")", |ref mut s| s.visit_select(*select))?
+            }
             TableType::JoinedTable(jt) => {
                 match table.database {
                     Some(database) => self.delimited_identifiers(&[&*database, &*jt.0])?,
@@ -603,7 +1014,37 @@ pub trait Visitor<'a> {
                 self.write(" AS ")?;
 
                 self.delimited_identifiers(&[&*alias])?;
+
+                if !table.alias_columns.is_empty() {
+                    let len = table.alias_columns.len();
+
+                    self.surround_with("(", ")", |ref mut s| {
+                        for (i, column) in table.alias_columns.iter().enumerate() {
+                            s.delimited_identifiers(&[&**column])?;
+
+                            if i < (len - 1) {
+                                s.write(",")?;
+                            }
+                        }
+
+                        Ok(())
+                    })?;
+                }
             };
+
+            if let Some(sample) = table.sample {
+                if !(0.0..=100.0).contains(&sample.fraction) {
+                    let kind = ErrorKind::QueryInvalidInput(format!(
+                        "TABLESAMPLE fraction must be a percentage between 0 and 100, got {}.",
+                        sample.fraction
+                    ));
+
+                    return Err(Error::builder(kind).build());
+                }
+
+                self.write(" ")?;
+                self.visit_table_sample(sample)?;
+            }
         }
 
         Ok(())
@@ -644,9 +1085,12 @@ pub trait Visitor<'a> {
         })
     }
 
-    /// A walk through the query conditions
+    /// A walk through the query conditions. Constant-true and constant-false
+    /// leaves, such as empty `IN` lists, are folded first so an `AND` holding
+    /// a condition that can never match renders as a plain `1=0` without the
+    /// parameters of the elided conditions.
     fn visit_conditions(&mut self, tree: ConditionTree<'a>) -> Result {
-        match tree {
+        match tree.simplify() {
             ConditionTree::And(expressions) => self.surround_with("(", ")", |ref mut s| {
                 let len = expressions.len();
 
@@ -707,18 +1151,48 @@ pub trait Visitor<'a> {
         self.visit_expression(right)
     }
 
-    fn visit_like(&mut self, left: Expression<'a>, right: Expression<'a>) -> Result {
+    fn visit_like(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> Result {
         self.visit_expression(left)?;
         self.write(" LIKE ")?;
         self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
 
         Ok(())
     }
 
-    fn visit_not_like(&mut self, left: Expression<'a>, right: Expression<'a>) -> Result {
+    fn visit_not_like(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> Result {
         self.visit_expression(left)?;
         self.write(" NOT LIKE ")?;
         self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
+
+        Ok(())
+    }
+
+    /// A case-insensitive `LIKE`. The default collations on MySQL, SQL
+    /// Server and SQLite already compare case-insensitively, so a plain
+    /// `LIKE` expresses it.
+    fn visit_ilike(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> Result {
+        self.visit_like(left, right, escape)
+    }
+
+    /// A case-insensitive `NOT LIKE`.
+    fn visit_not_ilike(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> Result {
+        self.visit_not_like(left, right, escape)
+    }
+
+    /// The `ESCAPE` clause of a `LIKE` comparison. The character is inlined
+    /// as a literal, since not every database accepts a parameter here.
+    fn visit_like_escape(&mut self, escape: Option<char>) -> Result {
+        if let Some(escape_char) = escape {
+            self.write(" ESCAPE ")?;
+
+            if escape_char == '\'' {
+                self.write("''''")?;
+            } else {
+                self.write(format!("'{escape_char}'"))?;
+            }
+        }
 
         Ok(())
     }
@@ -728,6 +1202,8 @@ pub trait Visitor<'a> {
         match compare {
             Compare::Equals(left, right) => self.visit_equals(*left, *right),
             Compare::NotEquals(left, right) => self.visit_not_equals(*left, *right),
+            Compare::IsDistinctFrom(left, right) => self.visit_is_distinct_from(*left, *right),
+            Compare::IsNotDistinctFrom(left, right) => self.visit_is_not_distinct_from(*left, *right),
             Compare::LessThan(left, right) => self.visit_less_than(*left, *right),
             Compare::LessThanOrEquals(left, right) => self.visit_less_than_or_equals(*left, *right),
             Compare::GreaterThan(left, right) => self.visit_greater_than(*left, *right),
@@ -876,8 +1352,10 @@ pub trait Visitor<'a> {
                     self.visit_expression(right)
                 }
             },
-            Compare::Like(left, right) => self.visit_like(*left, *right),
-            Compare::NotLike(left, right) => self.visit_not_like(*left, *right),
+            Compare::Like(left, right, escape) => self.visit_like(*left, *right, escape),
+            Compare::NotLike(left, right, escape) => self.visit_not_like(*left, *right, escape),
+            Compare::Ilike(left, right, escape) => self.visit_ilike(*left, *right, escape),
+            Compare::NotIlike(left, right, escape) => self.visit_not_ilike(*left, *right, escape),
             Compare::Null(column) => {
                 self.visit_expression(*column)?;
                 self.write(" IS NULL")
@@ -900,6 +1378,14 @@ pub trait Visitor<'a> {
                 self.write(" AND ")?;
                 self.visit_expression(*right)
             }
+            Compare::Exists(selection) => {
+                self.write("EXISTS ")?;
+                self.surround_with("(", ")", |ref mut s| s.visit_sub_selection(*selection))
+            }
+            Compare::NotExists(selection) => {
+                self.write("NOT EXISTS ")?;
+                self.surround_with("(", ")", |ref mut s| s.visit_sub_selection(*selection))
+            }
             Compare::Raw(left, comp, right) => {
                 self.visit_expression(*left)?;
                 self.write(" ")?;
@@ -907,23 +1393,19 @@ pub trait Visitor<'a> {
                 self.write(" ")?;
                 self.visit_expression(*right)
             }
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             Compare::JsonCompare(json_compare) => match json_compare {
                 JsonCompare::ArrayContains(left, right) => self.visit_json_array_contains(*left, *right, false),
                 JsonCompare::ArrayNotContains(left, right) => self.visit_json_array_contains(*left, *right, true),
                 JsonCompare::TypeEquals(left, json_type) => self.visit_json_type_equals(*left, json_type, false),
                 JsonCompare::TypeNotEquals(left, json_type) => self.visit_json_type_equals(*left, json_type, true),
             },
-            #[cfg(feature = "postgresql")]
             Compare::Matches(left, right) => self.visit_matches(*left, right, false),
-            #[cfg(feature = "postgresql")]
             Compare::NotMatches(left, right) => self.visit_matches(*left, right, true),
-            #[cfg(feature = "postgresql")]
             Compare::Any(left) => {
                 self.write("ANY")?;
                 self.surround_with("(", ")", |s| s.visit_expression(*left))
             }
-            #[cfg(feature = "postgresql")]
             Compare::All(left) => {
                 self.write("ALL")?;
                 self.surround_with("(", ")", |s| s.visit_expression(*left))
@@ -947,6 +1429,22 @@ pub trait Visitor<'a> {
         Ok(())
     }
 
+    fn visit_is_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> Result {
+        self.visit_expression(left)?;
+        self.write(" IS DISTINCT FROM ")?;
+        self.visit_expression(right)?;
+
+        Ok(())
+    }
+
+    fn visit_is_not_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> Result {
+        self.visit_expression(left)?;
+        self.write(" IS NOT DISTINCT FROM ")?;
+        self.visit_expression(right)?;
+
+        Ok(())
+    }
+
     /// A visit in the `GROUP BY` section of the query
     fn visit_grouping(&mut self, grouping: Grouping<'a>) -> Result {
         let len = grouping.0.len();
@@ -989,7 +1487,23 @@ pub trait Visitor<'a> {
             FunctionType::AggregateToString(agg) => {
                 self.visit_aggregate_to_string(agg.value.as_ref().clone())?;
             }
-            #[cfg(all(feature = "json", feature = "postgresql"))]
+            FunctionType::StringAgg(string_agg) => {
+                self.visit_string_agg(string_agg)?;
+            }
+            FunctionType::ArrayAgg(array_agg) => {
+                self.visit_array_agg(array_agg)?;
+            }
+            #[cfg(feature = "json")]
+            FunctionType::JsonAgg(json_agg) => {
+                self.visit_json_agg(json_agg)?;
+            }
+            FunctionType::PercentileCont(percentile_cont) => {
+                self.visit_percentile_cont(percentile_cont)?;
+            }
+            FunctionType::PercentileDisc(percentile_disc) => {
+                self.visit_percentile_disc(percentile_disc)?;
+            }
+            #[cfg(feature = "json")]
             FunctionType::RowToJson(row_to_json) => {
                 self.write("ROW_TO_JSON")?;
                 self.surround_with("(", ")", |ref mut s| s.visit_table(row_to_json.expr, false))?
@@ -1021,40 +1535,52 @@ pub trait Visitor<'a> {
                 self.write("COALESCE")?;
                 self.surround_with("(", ")", |s| s.visit_columns(coalesce.exprs))?;
             }
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            FunctionType::NullIf(nullif) => {
+                self.write("NULLIF")?;
+                self.surround_with("(", ")", |ref mut s| {
+                    s.visit_expression(*nullif.left)?;
+                    s.write(", ")?;
+                    s.visit_expression(*nullif.right)
+                })?;
+            }
+            FunctionType::Greatest(greatest) => {
+                self.write("GREATEST")?;
+                self.surround_with("(", ")", |s| s.visit_columns(greatest.exprs))?;
+            }
+            FunctionType::Least(least) => {
+                self.write("LEAST")?;
+                self.surround_with("(", ")", |s| s.visit_columns(least.exprs))?;
+            }
+            #[cfg(feature = "json")]
             FunctionType::JsonExtract(json_extract) => {
                 self.visit_json_extract(json_extract)?;
             }
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             FunctionType::JsonExtractFirstArrayElem(extract) => {
                 self.visit_json_extract_first_array_item(extract)?;
             }
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             FunctionType::JsonExtractLastArrayElem(extract) => {
                 self.visit_json_extract_last_array_item(extract)?;
             }
-            #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+            #[cfg(feature = "json")]
             FunctionType::JsonUnquote(unquote) => {
                 self.visit_json_unquote(unquote)?;
             }
-            #[cfg(any(feature = "postgresql", feature = "mysql"))]
             FunctionType::TextSearch(text_search) => {
                 self.visit_text_search(text_search)?;
             }
-            #[cfg(any(feature = "postgresql", feature = "mysql"))]
             FunctionType::TextSearchRelevance(text_search_relevance) => {
                 self.visit_text_search_relevance(text_search_relevance)?;
             }
-            #[cfg(feature = "mysql")]
             FunctionType::UuidToBin => {
                 self.write("uuid_to_bin(uuid())")?;
             }
-            #[cfg(feature = "mysql")]
             FunctionType::UuidToBinSwapped => {
                 self.write("uuid_to_bin(uuid(), 1)")?;
             }
-            #[cfg(feature = "mysql")]
             FunctionType::Uuid => self.write("uuid()")?,
+            FunctionType::Now => self.write("CURRENT_TIMESTAMP")?,
             FunctionType::Concat(concat) => {
                 self.visit_concat(concat)?;
             }
@@ -1130,10 +1656,92 @@ pub trait Visitor<'a> {
         self.write(" AS ")?;
 
         let selection = cte.selection;
+
+        if let SelectQuery::Select(ref select) = selection {
+            ensure_no_nested_into_outfile(select)?;
+        }
+
         self.surround_with("(", ")", |ref mut s| s.visit_selection(selection))
     }
 
     fn visit_comment(&mut self, comment: Cow<'a, str>) -> Result {
         self.surround_with("/* ", " */", |ref mut s| s.write(comment))
     }
+
+    /// A rendering of optimizer hint texts as a `/*+ ... */` comment, the
+    /// form MySQL optimizer hints and `pg_hint_plan` use.
+    fn visit_hint_comment(&mut self, hints: Vec<Cow<'a, str>>) -> Result {
+        self.write("/*+ ")?;
+
+        let len = hints.len();
+
+        for (i, hint) in hints.into_iter().enumerate() {
+            self.write(hint)?;
+
+            if i < len - 1 {
+                self.write(" ")?;
+            }
+        }
+
+        self.write(" */")
+    }
+
+    /// A rendering of the `OPTION (...)` clause carrying the query hints of
+    /// a SQL Server statement.
+    fn visit_option_clause(&mut self, hints: Vec<Cow<'a, str>>) -> Result {
+        self.write("OPTION (")?;
+
+        let len = hints.len();
+
+        for (i, hint) in hints.into_iter().enumerate() {
+            self.write(hint)?;
+
+            if i < len - 1 {
+                self.write(", ")?;
+            }
+        }
+
+        self.write(")")
+    }
+}
+
+/// `INTO OUTFILE` exports the outermost result set of a query; a nested
+/// `SELECT` cannot be redirected into a file.
+pub(crate) fn ensure_no_nested_into_outfile(select: &Select<'_>) -> Result {
+    if select.into_outfile.is_some() {
+        let kind = ErrorKind::QueryInvalidInput("INTO OUTFILE cannot be used in a subquery.".into());
+
+        return Err(Error::builder(kind).build());
+    }
+
+    Ok(())
+}
+
+/// Byte offsets of the `?` placeholders in a raw SQL fragment. Placeholders
+/// inside string literals, quoted identifiers or `--` comments are not
+/// bindable and get skipped.
+fn raw_fragment_placeholders(sql: &str) -> Vec<usize> {
+    let bytes = sql.as_bytes();
+    let mut offsets = Vec::new();
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (quote, bytes[i]) {
+            (None, b'?') => offsets.push(i),
+            (None, q @ (b'\'' | b'"' | b'`')) => quote = Some(q),
+            (None, b'[') => quote = Some(b']'),
+            (None, b'-') if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            (Some(q), c) if c == q => quote = None,
+            _ => (),
+        }
+
+        i += 1;
+    }
+
+    offsets
 }
@@ -1,14 +1,16 @@
 use super::Visitor;
-#[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+#[cfg(feature = "json")]
 use crate::prelude::{JsonExtract, JsonType, JsonUnquote};
 use crate::{
     ast::{
-        Column, Comparable, Expression, ExpressionKind, Insert, IntoRaw, Join, JoinData, Joinable, Merge, OnConflict,
-        Order, Ordering, Row, Table, TypeDataLength, TypeFamily, Values,
+        CastType, Column, Comparable, ConditionTree, Delete, Expression, ExpressionKind, HintDialect, Insert, IntoRaw,
+        Join, JoinData, Joinable,
+        Merge, OnConflict, Order, Ordering, Row, StringAgg, Table, TableSample, TypeDataLength, TypeFamily, Values,
     },
     error::{Error, ErrorKind},
     prelude::{Aliasable, Average, Query},
-    visitor, Value,
+    visitor::{self as visitor, Capabilities},
+    Value,
 };
 use std::{convert::TryFrom, fmt::Write, iter};
 
@@ -21,9 +23,69 @@ pub struct Mssql<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
     order_by_set: bool,
+    reuse_parameters: bool,
+    substitution_index: usize,
 }
 
 impl<'a> Mssql<'a> {
+    /// The features supported by every SQL Server version quaint supports.
+    /// `RETURNING` is false because the `OUTPUT` clause is an emulation with
+    /// differing semantics, not the same feature.
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        returning: false,
+        row_values: false,
+        lateral: false,
+        json: false,
+        upsert_where: false,
+        distinct_on: false,
+        set_operations: true,
+        window_functions: true,
+    };
+
+    /// Builds the query as [`build`] does, but reusing the placeholder of
+    /// the first occurrence when the same parameter value repeats within the
+    /// query. Equality is on the typed [`Value`], not on its rendering.
+    ///
+    /// Keeps the parameter list shorter when the same value filters several
+    /// joined subqueries, which matters with the 2100 parameter limit of SQL
+    /// Server.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mssql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users")
+    ///     .so_that("tenant_id".equals(7).and("manager_tenant_id".equals(7)));
+    ///
+    /// let (sql, params) = Mssql::build_with_reused_parameters(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT [users].* FROM [users] WHERE ([tenant_id] = @P1 AND [manager_tenant_id] = @P1)",
+    ///     sql
+    /// );
+    ///
+    /// assert_eq!(vec![Value::from(7)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`build`]: Visitor::build
+    pub fn build_with_reused_parameters<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<crate::ast::Query<'a>>,
+    {
+        let mut this = Mssql {
+            query: String::with_capacity(4096),
+            parameters: Vec::with_capacity(128),
+            order_by_set: false,
+            reuse_parameters: true,
+            substitution_index: 0,
+        };
+
+        Mssql::visit_query(&mut this, query.into())?;
+
+        Ok((this.query, this.parameters))
+    }
+
     // TODO: figure out that merge shit
     fn visit_returning(&mut self, columns: Vec<Column<'a>>) -> visitor::Result {
         let cols: Vec<_> = columns.into_iter().map(|c| c.table("Inserted")).collect();
@@ -180,9 +242,12 @@ impl<'a> Mssql<'a> {
 
 impl<'a> Visitor<'a> for Mssql<'a> {
     const C_BACKTICK_OPEN: &'static str = "[";
+    const HINT_DIALECT: Option<HintDialect> = Some(HintDialect::Mssql);
     const C_BACKTICK_CLOSE: &'static str = "]";
     const C_WILDCARD: &'static str = "%";
 
+    const HAS_TOP: bool = true;
+
     fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<crate::ast::Query<'a>>,
@@ -191,6 +256,8 @@ impl<'a> Visitor<'a> for Mssql<'a> {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
             order_by_set: false,
+            reuse_parameters: false,
+            substitution_index: 0,
         };
 
         Mssql::visit_query(&mut this, query.into())?;
@@ -204,7 +271,15 @@ impl<'a> Visitor<'a> for Mssql<'a> {
     }
 
     fn add_parameter(&mut self, value: Value<'a>) {
-        self.parameters.push(value)
+        if self.reuse_parameters {
+            if let Some(i) = self.parameters.iter().position(|p| p == &value) {
+                self.substitution_index = i + 1;
+                return;
+            }
+        }
+
+        self.parameters.push(value);
+        self.substitution_index = self.parameters.len();
     }
 
     /// A point to modify an incoming query to make it compatible with the
@@ -308,10 +383,60 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         Ok(())
     }
 
+    // SQL Server spells the double precision float `FLOAT` and the single
+    // precision one `REAL`, and has no JSON type, so JSON documents stay
+    // `NVARCHAR(MAX)`.
+    fn visit_cast_type(&mut self, cast_type: CastType) -> visitor::Result {
+        let name = match cast_type {
+            CastType::Text => "NVARCHAR(MAX)",
+            CastType::Integer => "INT",
+            CastType::BigInt => "BIGINT",
+            CastType::Float => "REAL",
+            CastType::Double => "FLOAT",
+            CastType::Boolean => "BIT",
+            CastType::Date => "DATE",
+            CastType::DateTime => "DATETIME2",
+            CastType::Json => "NVARCHAR(MAX)",
+            CastType::Uuid => "UNIQUEIDENTIFIER",
+            CastType::Unsigned => {
+                let kind = ErrorKind::UnsupportedOperation("CAST AS UNSIGNED is only supported on MySQL.".into());
+
+                return Err(Error::builder(kind).build());
+            }
+        };
+
+        self.write(name)
+    }
+
+    // SQL Server has no `IS DISTINCT FROM`, but `INTERSECT` compares `NULL`s
+    // as equal values, so an `EXISTS` over the single-value intersection of
+    // both sides expresses the same comparison. The emulation is
+    // self-parenthesising and composes inside larger condition trees.
+    fn visit_is_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.write("NOT EXISTS ")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.write("SELECT ")?;
+            s.visit_expression(left)?;
+            s.write(" INTERSECT SELECT ")?;
+            s.visit_expression(right)
+        })
+    }
+
+    fn visit_is_not_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.write("EXISTS ")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.write("SELECT ")?;
+            s.visit_expression(left)?;
+            s.write(" INTERSECT SELECT ")?;
+            s.visit_expression(right)
+        })
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Int32(i) => i.map(|i| self.write(i)),
             Value::Int64(i) => i.map(|i| self.write(i)),
+            Value::Int128(i) => i.map(|i| self.write(i)),
             Value::Float(d) => d.map(|f| match f {
                 f if f.is_nan() => self.write("'NaN'"),
                 f if f == f32::INFINITY => self.write("'Infinity'"),
@@ -327,9 +452,15 @@ impl<'a> Visitor<'a> for Mssql<'a> {
             Value::Text(t) => t.map(|t| self.write(format!("'{t}'"))),
             Value::Enum(e) => e.map(|e| self.write(e)),
             Value::Bytes(b) => b.map(|b| self.write(format!("0x{}", hex::encode(b)))),
+            Value::IpAddr(ip) => ip.map(|ip| self.write(format!("'{ip}'"))),
+            // SQL Server has no bit-string literal; a string is the
+            // closest thing for debugging output.
+            Value::BitString(bits) => bits.map(|bits| self.write(format!("'{bits}'"))),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(g) => g.map(|g| self.write(format!("0x{}", hex::encode(g.0)))),
             Value::Boolean(b) => b.map(|b| self.write(if b { 1 } else { 0 })),
             Value::Char(c) => c.map(|c| self.write(format!("'{c}'"))),
-            Value::Array(_) => {
+            Value::Array(_) | Value::TypedArray(..) => {
                 let msg = "Arrays are not supported in T-SQL.";
                 let kind = ErrorKind::conversion(msg);
 
@@ -349,6 +480,11 @@ impl<'a> Visitor<'a> for Mssql<'a> {
             }),
             #[cfg(feature = "chrono")]
             Value::DateTime(dt) => dt.map(|dt| {
+                let s = format!("CONVERT(datetime2, N'{dt}')");
+                self.write(s)
+            }),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.map(|dt| {
                 let s = format!("CONVERT(datetimeoffset, N'{}')", dt.to_rfc3339());
                 self.write(s)
             }),
@@ -413,7 +549,84 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         }
     }
 
+    // `OFFSET .. FETCH` is the only limiter syntax on SQL Server.
+    fn visit_offset_fetch(&mut self, limit: Option<Value<'a>>, offset: Option<Value<'a>>) -> visitor::Result {
+        self.visit_limit_and_offset(limit, offset)
+    }
+
+    fn visit_delete(&mut self, mut delete: Delete<'a>) -> visitor::Result {
+        let hints = crate::ast::hints_for_dialect(std::mem::take(&mut delete.hints), Self::HINT_DIALECT);
+
+        if !delete.using.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE ... USING is only supported on PostgreSQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !delete.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !delete.ordering.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE with ORDER BY is not supported on SQL Server.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("DELETE ")?;
+
+        if let Some(limit) = delete.limit {
+            self.write("TOP (")?;
+            self.visit_parameterized(limit)?;
+            self.write(") ")?;
+        }
+
+        self.write("FROM ")?;
+        self.visit_table(delete.table, true)?;
+
+        if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        if !hints.is_empty() {
+            self.write(" ")?;
+            self.visit_option_clause(hints)?;
+        }
+
+        if let Some(comment) = delete.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
+        // `SET IDENTITY_INSERT` permits explicit values for an identity
+        // column. Both the switch and its restoration render into the same
+        // batch, so the setting never leaks to later statements on the
+        // connection, even when the insert itself fails.
+        let identity_insert = match (insert.identity_insert, &insert.table) {
+            (true, Some(table)) => Some(table.clone()),
+            (true, None) => {
+                let kind = ErrorKind::QueryInvalidInput(
+                    "An identity insert needs a table to switch IDENTITY_INSERT on.".into(),
+                );
+
+                return Err(Error::builder(kind).build());
+            }
+            (false, _) => None,
+        };
+
+        if let Some(table) = identity_insert.clone() {
+            self.write("SET IDENTITY_INSERT ")?;
+            self.visit_table(table, false)?;
+            self.write(" ON; ")?;
+        }
+
         if let Some(returning) = insert.returning.as_ref().cloned() {
             self.create_generated_keys(returning)?;
             self.write(" ")?;
@@ -471,6 +684,35 @@ impl<'a> Visitor<'a> for Mssql<'a> {
                     }
                 }
             }
+            Expression {
+                kind: ExpressionKind::Selection(query),
+                ..
+            } => {
+                // The `OUTPUT` clause has to come before the source query, so
+                // the shared `INSERT INTO ... SELECT` helper cannot be used.
+                if !insert.columns.is_empty() {
+                    if let Some(count) = query.column_count() {
+                        if count != insert.columns.len() {
+                            let kind = ErrorKind::QueryInvalidInput(format!(
+                                "The INSERT lists {} columns, but the SELECT returns {count}.",
+                                insert.columns.len()
+                            ));
+
+                            return Err(Error::builder(kind).build());
+                        }
+                    }
+
+                    self.write(" ")?;
+                    self.visit_row(Row::from(insert.columns))?;
+                }
+
+                if let Some(ref returning) = insert.returning {
+                    self.visit_returning(returning.clone())?;
+                }
+
+                self.write(" ")?;
+                self.visit_selection(query)?;
+            }
             expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
         }
 
@@ -485,6 +727,12 @@ impl<'a> Visitor<'a> for Mssql<'a> {
             self.visit_comment(comment)?;
         }
 
+        if let Some(table) = identity_insert {
+            self.write("; SET IDENTITY_INSERT ")?;
+            self.visit_table(table, false)?;
+            self.write(" OFF")?;
+        }
+
         Ok(())
     }
 
@@ -530,9 +778,48 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         unimplemented!("Upsert not supported for the underlying database.")
     }
 
+    fn visit_lateral_join(&mut self, kind: &str, data: JoinData<'a>) -> visitor::Result {
+        match kind {
+            "INNER" => self.write(" CROSS APPLY ")?,
+            "LEFT" => self.write(" OUTER APPLY ")?,
+            _ => {
+                let kind = ErrorKind::QueryInvalidInput(format!(
+                    "SQL Server supports only inner and left lateral joins, not {kind}."
+                ));
+
+                return Err(Error::builder(kind).build());
+            }
+        }
+
+        // `APPLY` has no `ON` clause, the correlation lives inside the
+        // subquery.
+        match data.conditions {
+            ConditionTree::NoCondition => self.visit_table(data.table, true),
+            _ => {
+                let kind = ErrorKind::QueryInvalidInput(
+                    "SQL Server lateral joins take no join conditions, move them inside the subquery.".into(),
+                );
+
+                Err(Error::builder(kind).build())
+            }
+        }
+    }
+
+    /// SQL Server only samples pages, so both methods render as the plain
+    /// `TABLESAMPLE (n PERCENT)` form.
+    fn visit_table_sample(&mut self, sample: TableSample) -> visitor::Result {
+        self.write(format!("TABLESAMPLE ({} PERCENT)", sample.fraction))?;
+
+        if let Some(seed) = sample.seed {
+            self.write(format!(" REPEATABLE ({seed})"))?;
+        }
+
+        Ok(())
+    }
+
     fn parameter_substitution(&mut self) -> visitor::Result {
         self.write("@P")?;
-        self.write(self.parameters.len())
+        self.write(self.substitution_index)
     }
 
     fn visit_aggregate_to_string(&mut self, value: crate::ast::Expression<'a>) -> visitor::Result {
@@ -544,6 +831,39 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         })
     }
 
+    fn visit_string_agg(&mut self, string_agg: StringAgg<'a>) -> visitor::Result {
+        let StringAgg {
+            value,
+            separator,
+            distinct,
+            ordering,
+        } = string_agg;
+
+        // `STRING_AGG` has no distinct form on SQL Server.
+        if distinct {
+            let kind = ErrorKind::QueryInvalidInput("SQL Server does not support STRING_AGG(DISTINCT ..).".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("STRING_AGG")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.visit_expression(*value)?;
+            s.write(", ")?;
+            s.visit_parameterized(Value::text(separator))
+        })?;
+
+        if !ordering.is_empty() {
+            self.write(" WITHIN GROUP ")?;
+            self.surround_with("(", ")", |ref mut s| {
+                s.write("ORDER BY ")?;
+                s.visit_ordering(ordering)
+            })?;
+        }
+
+        Ok(())
+    }
+
     // MSSQL doesn't support tuples, we do AND/OR.
     fn visit_multiple_tuple_comparison(&mut self, left: Row<'a>, right: Values<'a>, negate: bool) -> visitor::Result {
         let row_len = left.len();
@@ -635,12 +955,12 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract(&mut self, _json_extract: JsonExtract<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on MSSQL")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_array_contains(
         &mut self,
         _left: Expression<'a>,
@@ -650,22 +970,20 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         unimplemented!("JSON filtering is not yet supported on MSSQL")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_type_equals(&mut self, _left: Expression<'a>, _json_type: JsonType, _not: bool) -> visitor::Result {
         unimplemented!("JSON_TYPE is not yet supported on MSSQL")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_unquote(&mut self, _json_unquote: JsonUnquote<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on MSSQL")
     }
 
-    #[cfg(feature = "postgresql")]
     fn visit_text_search(&mut self, _text_search: crate::prelude::TextSearch<'a>) -> visitor::Result {
         unimplemented!("Full-text search is not yet supported on MSSQL")
     }
 
-    #[cfg(feature = "postgresql")]
     fn visit_matches(
         &mut self,
         _left: Expression<'a>,
@@ -675,7 +993,6 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         unimplemented!("Full-text search is not yet supported on MSSQL")
     }
 
-    #[cfg(feature = "postgresql")]
     fn visit_text_search_relevance(
         &mut self,
         _text_search_relevance: crate::prelude::TextSearchRelevance<'a>,
@@ -683,7 +1000,7 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         unimplemented!("Full-text search is not yet supported on MSSQL")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_last_array_item(
         &mut self,
         _extract: crate::prelude::JsonExtractLastArrayElem<'a>,
@@ -691,7 +1008,7 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         unimplemented!("JSON filtering is not yet supported on MSSQL")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_first_array_item(
         &mut self,
         _extract: crate::prelude::JsonExtractFirstArrayElem<'a>,
@@ -704,6 +1021,7 @@ impl<'a> Visitor<'a> for Mssql<'a> {
 mod tests {
     use crate::{
         ast::*,
+        error::ErrorKind,
         val,
         visitor::{Mssql, Visitor},
     };
@@ -737,6 +1055,51 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_build_with_reused_parameters_deduplicates_equal_values() {
+        let query = Select::from_table("users").so_that(
+            "tenant_id"
+                .equals(7)
+                .and("manager_tenant_id".equals(7))
+                .and("name".equals("Musti")),
+        );
+
+        let (sql, params) = Mssql::build_with_reused_parameters(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE ([tenant_id] = @P1 AND [manager_tenant_id] = @P1 AND [name] = @P2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(7), Value::from("Musti")], params);
+    }
+
+    #[test]
+    fn test_build_with_reused_parameters_keeps_differently_typed_values_separate() {
+        let query = Select::from_table("users").so_that("id".equals(1).and("version".equals("1")));
+        let (sql, params) = Mssql::build_with_reused_parameters(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE ([id] = @P1 AND [version] = @P2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(1), Value::from("1")], params);
+    }
+
+    #[test]
+    fn test_build_keeps_repeated_parameters_by_default() {
+        let query = Select::from_table("users").so_that("tenant_id".equals(7).and("manager_tenant_id".equals(7)));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE ([tenant_id] = @P1 AND [manager_tenant_id] = @P2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(7), Value::from(7)], params);
+    }
+
     #[test]
     fn test_aliased_value() {
         let expected = expected_values("SELECT @P1 AS [test]", vec![1]);
@@ -891,6 +1254,17 @@ mod tests {
         assert_eq!(default_params(expected.1), params);
     }
 
+    #[test]
+    fn test_select_where_ilike_falls_back_to_like() {
+        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1", vec!["%meow%"]);
+
+        let query = Select::from_table("naukio").so_that("word".ilike("%meow%"));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
     #[test]
     fn test_select_where_begins_with() {
         let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1", vec!["%meow"]);
@@ -1192,6 +1566,52 @@ mod tests {
         assert_eq!(vec![Value::int64(10), Value::int64(9)], params);
     }
 
+    #[test]
+    fn test_offset_fetch_renders_the_native_limiter() {
+        let expected_sql = "SELECT [foo] FROM [bar] ORDER BY [id] OFFSET @P1 ROWS FETCH NEXT @P2 ROWS ONLY";
+        let query = Select::from_table("bar").column("foo").order_by("id").offset_fetch(10, 9);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10), Value::int64(9)], params);
+    }
+
+    #[test]
+    fn test_select_top() {
+        let expected_sql = "SELECT TOP (@P1) [foo] FROM [bar]";
+        let query = Select::from_table("bar").column("foo").top(10);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_delete_with_limit_renders_top() {
+        let expected_sql = "DELETE TOP (@P1) FROM [users] WHERE [active] = @P2";
+        let query = Delete::from_table("users").so_that("active".equals(false)).limit(10);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10), Value::boolean(false)], params);
+    }
+
+    #[test]
+    fn test_delete_with_order_by_is_unsupported() {
+        let query = Delete::from_table("users").order_by("id").limit(10);
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_with_limit_is_unsupported() {
+        let query = Update::table("users").set("checked", true).limit(10);
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
     #[test]
     fn test_raw_null() {
         let (sql, params) = Mssql::build(Select::default().value(Value::Text(None).raw())).unwrap();
@@ -1287,6 +1707,18 @@ mod tests {
         assert_eq!(vec![Value::from("lol"), Value::from("meow")], params);
     }
 
+    #[test]
+    fn test_insert_with_identity_insert_renders_the_switch() {
+        let insert = Insert::single_into("foo").value("id", 2).value("bar", "lol");
+        let (sql, params) = Mssql::build(Insert::from(insert).with_identity_insert()).unwrap();
+
+        assert_eq!(
+            "SET IDENTITY_INSERT [foo] ON; INSERT INTO [foo] ([id],[bar]) VALUES (@P1,@P2); SET IDENTITY_INSERT [foo] OFF",
+            sql
+        );
+        assert_eq!(vec![Value::from(2), Value::from("lol")], params);
+    }
+
     #[test]
     fn test_single_insert_default() {
         let insert = Insert::single_into("foo");
@@ -1297,7 +1729,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "mssql")]
     fn test_returning_insert() {
         let insert = Insert::single_into("foo").value("bar", "lol");
         let (sql, params) = Mssql::build(Insert::from(insert).returning(vec!["bar"])).unwrap();
@@ -1376,7 +1807,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "mssql")]
     fn test_single_insert_conflict_with_returning_clause() {
         let table = Table::from("foo").add_unique_index("bar");
 
@@ -1594,7 +2024,7 @@ mod tests {
             "
             MERGE INTO [foo]
             USING (SELECT @P1 AS [wtf], @P2 AS [lol]) AS [dual] ([wtf],[lol])
-            ON (([foo].[bar] = @P3 AND [dual].[wtf] = [foo].[wtf]) OR (1=0 AND [dual].[lol] = [foo].[lol]))
+            ON (([foo].[bar] = @P3 AND [dual].[wtf] = [foo].[wtf]))
             WHEN NOT MATCHED THEN
             INSERT ([wtf],[lol]) VALUES ([dual].[wtf],[dual].[lol]);
         "
@@ -1642,6 +2072,59 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
+    #[test]
+    fn test_hint_select() {
+        let expected_sql = "SELECT [users].* FROM [users] OPTION (RECOMPILE)";
+        let query = Select::from_table("users").hint(Hint::mssql("RECOMPILE"));
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hints_render_as_one_option_clause() {
+        let expected_sql = "SELECT [users].* FROM [users] WHERE [foo] = @P1 OPTION (RECOMPILE, MAXDOP 1)";
+        let query = Select::from_table("users")
+            .so_that("foo".equals(10))
+            .hint(Hint::mssql("RECOMPILE"))
+            .hint(Hint::mssql("MAXDOP 1"));
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_update() {
+        let expected_sql = "UPDATE [users] SET [foo] = @P1 OPTION (RECOMPILE)";
+        let query = Update::table("users").set("foo", 10).hint(Hint::mssql("RECOMPILE"));
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_delete() {
+        let expected_sql = "DELETE FROM [users] OPTION (RECOMPILE)";
+        let query = Delete::from_table("users").hint(Hint::mssql("RECOMPILE"));
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_of_another_dialect_is_dropped() {
+        let expected_sql = "SELECT [users].* FROM [users]";
+        let query = Select::from_table("users").hint(Hint::postgres("IndexScan(users idx)"));
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
     #[test]
     fn test_comment_insert() {
         let expected_sql = "INSERT INTO [users] DEFAULT VALUES /* trace_id='5bd66ef5095369c7b0d1f8f4bd33716a', parent_id='c532cb4098ac3dd2' */";
@@ -1806,4 +2289,199 @@ mod tests {
             sql
         );
     }
+
+    #[test]
+    fn test_lateral_join_renders_as_apply() {
+        let expected_sql = "SELECT [users].* FROM [users] CROSS APPLY (SELECT [posts].* FROM [posts] WHERE [posts].[user_id] = [users].[id] ORDER BY [posts].[id] OFFSET @P1 ROWS FETCH NEXT @P2 ROWS ONLY) AS [latest]";
+
+        let inner = Select::from_table("posts")
+            .so_that(("posts", "user_id").equals(Column::from(("users", "id"))))
+            .order_by(Column::from(("posts", "id")))
+            .limit(3);
+
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Select::from_table("users").inner_join(join);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int32(0), Value::int64(3)], params);
+    }
+
+    #[test]
+    fn test_left_lateral_join_renders_as_outer_apply() {
+        let inner = Select::from_table("posts").so_that(("posts", "user_id").equals(Column::from(("users", "id"))));
+
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Select::from_table("users").left_join(join);
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert!(sql.contains("OUTER APPLY"), "{sql}");
+    }
+
+    #[test]
+    fn test_lateral_join_with_conditions_is_an_error() {
+        let inner = Select::from_table("posts");
+
+        let join = Table::from(inner)
+            .alias("latest")
+            .on(("latest", "user_id").equals(Column::from(("users", "id"))))
+            .lateral();
+
+        let query = Select::from_table("users").inner_join(join);
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_raw_fragment_renumbers_placeholders() {
+        let fragment = Expression::raw("DIFFERENCE([name], ?)", vec!["musti"]);
+
+        let query = Select::from_table("users")
+            .so_that("age".less_than(10))
+            .and_where(fragment.equals(4));
+
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE ([age] < @P1 AND DIFFERENCE([name], @P2) = @P3)",
+            sql
+        );
+
+        assert_eq!(
+            vec![Value::from(10), Value::from("musti"), Value::from(4)],
+            params
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_in_table_position() {
+        let table = Table::raw("[users] TABLESAMPLE (1 PERCENT)");
+        let query = Select::from_table(table.alias("u")).column(("u", "id"));
+
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [u].[id] FROM [users] TABLESAMPLE (1 PERCENT) AS [u]", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_raw_fragment_with_a_wrong_parameter_count() {
+        let fragment = Expression::raw("[age] between ? and ?", vec![18]);
+        let query = Select::from_table("users").so_that(ConditionTree::single(fragment));
+
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_empty_in_short_circuits_an_and() {
+        let conditions = "foo".equals(1).and("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [musti].* FROM [musti] WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_empty_in_is_dropped_from_an_or() {
+        let conditions = "foo".equals(1).or("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [musti].* FROM [musti] WHERE ([foo] = @P1)", sql);
+        assert_eq!(vec![Value::from(1)], params);
+    }
+
+    #[test]
+    fn test_inverted_empty_not_in_never_matches() {
+        let conditions = "bar".not_in_selection(Vec::<i32>::new()).not();
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [musti].* FROM [musti] WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_table_sample() {
+        let table = Table::from("events").with_sample(TableSample::system(10.0).repeatable(42));
+        let query = Select::from_table(table);
+
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [events].* FROM [events] TABLESAMPLE (10 PERCENT) REPEATABLE (42)",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_string_agg() {
+        let query = Select::from_table("users")
+            .value(string_agg(Column::from("name")).separator(", ").order_by("name"))
+            .group_by("team");
+
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT STRING_AGG([name], @P1) WITHIN GROUP (ORDER BY [name]) FROM [users] GROUP BY [team]",
+            sql
+        );
+        assert_eq!(vec![Value::from(", ")], params);
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_distinct_from(Value::Text(None)));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE NOT EXISTS (SELECT [foo] INTERSECT SELECT @P1)",
+            sql
+        );
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_not_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_not_distinct_from(Value::text("bar")));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE EXISTS (SELECT [foo] INTERSECT SELECT @P1)",
+            sql
+        );
+        assert_eq!(vec![Value::text("bar")], params);
+    }
+
+    #[test]
+    fn test_is_distinct_from_with_nulls_on_both_sides() {
+        let query = Select::from_table("users").so_that(Expression::from(Value::Text(None)).is_distinct_from(Value::Text(None)));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE NOT EXISTS (SELECT @P1 INTERSECT SELECT @P2)",
+            sql
+        );
+        assert_eq!(vec![Value::Text(None), Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_string_agg_distinct_is_not_supported() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).distinct());
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_array_agg_is_unsupported() {
+        let query = Select::from_table("users").value(array_agg(Column::from("id")));
+        let err = Mssql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
 }
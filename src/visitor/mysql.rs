@@ -1,10 +1,21 @@
 use crate::{
     ast::*,
     error::{Error, ErrorKind},
-    visitor::{self, Visitor},
+    visitor::{self, Capabilities, Visitor},
 };
 use std::fmt::{self, Write};
 
+/// The server flavour spoken by the MySQL visitor. MariaDB supports a few
+/// statements Oracle MySQL does not, such as `INSERT .. RETURNING`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MysqlFlavour {
+    /// Oracle MySQL.
+    #[default]
+    Mysql,
+    /// MariaDB 10.5 or later.
+    Mariadb,
+}
+
 /// A visitor to generate queries for the MySQL database.
 ///
 /// The returned parameter values can be used directly with the mysql crate.
@@ -13,9 +24,168 @@ pub struct Mysql<'a> {
     parameters: Vec<Value<'a>>,
     /// The table a deleting or updating query is acting on.
     target_table: Option<Table<'a>>,
+    flavour: MysqlFlavour,
 }
 
+/// The file name rendered for an in-memory bulk load served through a local
+/// infile handler. The server sends the name back verbatim in the infile
+/// request, the value itself carries no meaning.
+const IN_MEMORY_INFILE: &str = "__quaint_in_memory__";
+
 impl<'a> Mysql<'a> {
+    /// The features supported by the oldest MySQL version quaint supports
+    /// (5.7). `RETURNING` on MariaDB, and `LATERAL` and window functions on
+    /// newer servers, are refined by the flavour or the server version.
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        returning: false,
+        row_values: true,
+        lateral: false,
+        json: true,
+        upsert_where: false,
+        distinct_on: false,
+        set_operations: true,
+        window_functions: false,
+    };
+
+    /// The features supported by the flavour rendered for, refining
+    /// [`CAPABILITIES`](Self::CAPABILITIES).
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            returning: self.flavour == MysqlFlavour::Mariadb,
+            ..Self::CAPABILITIES
+        }
+    }
+
+    /// Builds the query for the given server flavour. `build` renders for
+    /// Oracle MySQL.
+    pub fn build_with_flavour<Q>(query: Q, flavour: MysqlFlavour) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let query = query.into();
+        let mut mysql = Mysql {
+            query: String::with_capacity(4096),
+            parameters: Vec::with_capacity(128),
+            target_table: get_target_table(query.clone()),
+            flavour,
+        };
+
+        Mysql::visit_query(&mut mysql, query)?;
+
+        Ok((mysql.query, mysql.parameters))
+    }
+
+    /// The `RETURNING` clause on MariaDB. A no-op on Oracle MySQL, keeping
+    /// the rendered SQL unchanged.
+    fn visit_returning(&mut self, returning: Option<Vec<Column<'a>>>) -> visitor::Result {
+        if !self.capabilities().returning {
+            return Ok(());
+        }
+
+        if let Some(returning) = returning {
+            if !returning.is_empty() {
+                let values = returning.into_iter().map(|r| r.into()).collect();
+                self.write(" RETURNING ")?;
+                self.visit_columns(values)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A single-quoted string in a `LOAD DATA INFILE` statement, escaping
+    /// quotes, backslashes and the control characters used as separators.
+    fn write_infile_string(&mut self, s: &str) -> visitor::Result {
+        self.write("'")?;
+
+        for c in s.chars() {
+            match c {
+                '\n' => self.write("\\n")?,
+                '\r' => self.write("\\r")?,
+                '\t' => self.write("\\t")?,
+                '\\' => self.write("\\\\")?,
+                '\'' => self.write("\\'")?,
+                c => self.write(c)?,
+            }
+        }
+
+        self.write("'")
+    }
+
+    /// The `INTO table (columns) VALUES ...` part shared between `INSERT`
+    /// and `REPLACE INTO` statements.
+    fn visit_insert_body(
+        &mut self,
+        table: Option<Table<'a>>,
+        insert_columns: Vec<Column<'a>>,
+        values: Expression<'a>,
+    ) -> visitor::Result {
+        if let Some(table) = table {
+            self.write("INTO ")?;
+            self.visit_table(table, true)?;
+        }
+
+        match values {
+            Expression {
+                kind: ExpressionKind::Row(row),
+                ..
+            } => {
+                if row.values.is_empty() {
+                    self.write(" () VALUES ()")?;
+                } else {
+                    let columns = insert_columns.len();
+
+                    self.write(" (")?;
+                    for (i, c) in insert_columns.into_iter().enumerate() {
+                        self.visit_column(c.into_bare())?;
+
+                        if i < (columns - 1) {
+                            self.write(",")?;
+                        }
+                    }
+
+                    self.write(")")?;
+                    self.write(" VALUES ")?;
+                    self.visit_row(row)?;
+                }
+            }
+            Expression {
+                kind: ExpressionKind::Values(values),
+                ..
+            } => {
+                let columns = insert_columns.len();
+
+                self.write(" (")?;
+                for (i, c) in insert_columns.into_iter().enumerate() {
+                    self.visit_column(c.into_bare())?;
+
+                    if i < (columns - 1) {
+                        self.write(",")?;
+                    }
+                }
+                self.write(")")?;
+
+                self.write(" VALUES ")?;
+                let values_len = values.len();
+
+                for (i, row) in values.into_iter().enumerate() {
+                    self.visit_row(row)?;
+
+                    if i < (values_len - 1) {
+                        self.write(", ")?;
+                    }
+                }
+            }
+            Expression {
+                kind: ExpressionKind::Selection(query),
+                ..
+            } => self.visit_insert_from_select(insert_columns, query)?,
+            expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
+        }
+
+        Ok(())
+    }
+
     fn visit_regular_equality_comparison(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         self.visit_expression(left)?;
         self.write(" = ")?;
@@ -96,6 +266,7 @@ impl<'a> Mysql<'a> {
 
 impl<'a> Visitor<'a> for Mysql<'a> {
     const C_BACKTICK_OPEN: &'static str = "`";
+    const HINT_DIALECT: Option<HintDialect> = Some(HintDialect::Mysql);
     const C_BACKTICK_CLOSE: &'static str = "`";
     const C_WILDCARD: &'static str = "%";
 
@@ -103,16 +274,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
     where
         Q: Into<Query<'a>>,
     {
-        let query = query.into();
-        let mut mysql = Mysql {
-            query: String::with_capacity(4096),
-            parameters: Vec::with_capacity(128),
-            target_table: get_target_table(query.clone()),
-        };
-
-        Mysql::visit_query(&mut mysql, query)?;
-
-        Ok((mysql.query, mysql.parameters))
+        Self::build_with_flavour(query, MysqlFlavour::default())
     }
 
     fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
@@ -124,6 +286,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         let res = match value {
             Value::Int32(i) => i.map(|i| self.write(i)),
             Value::Int64(i) => i.map(|i| self.write(i)),
+            Value::Int128(i) => i.map(|i| self.write(i)),
             Value::Float(d) => d.map(|f| match f {
                 f if f.is_nan() => self.write("'NaN'"),
                 f if f == f32::INFINITY => self.write("'Infinity'"),
@@ -139,9 +302,13 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             Value::Text(t) => t.map(|t| self.write(format!("'{t}'"))),
             Value::Enum(e) => e.map(|e| self.write(e)),
             Value::Bytes(b) => b.map(|b| self.write(format!("x'{}'", hex::encode(b)))),
+            Value::IpAddr(ip) => ip.map(|ip| self.write(format!("'{ip}'"))),
+            Value::BitString(bits) => bits.map(|bits| self.write(format!("b'{bits}'"))),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(g) => g.map(|g| self.write(format!("x'{}'", hex::encode(g.0)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
             Value::Char(c) => c.map(|c| self.write(format!("'{c}'"))),
-            Value::Array(_) => {
+            Value::Array(_) | Value::TypedArray(..) => {
                 let msg = "Arrays are not supported in MySQL.";
                 let kind = ErrorKind::conversion(msg);
 
@@ -163,7 +330,9 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             #[cfg(feature = "uuid")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.hyphenated()))),
             #[cfg(feature = "chrono")]
-            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
+            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{dt}'"))),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
             #[cfg(feature = "chrono")]
             Value::Date(date) => date.map(|date| self.write(format!("'{date}'"))),
             #[cfg(feature = "chrono")]
@@ -183,76 +352,192 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             _ => self.write("INSERT ")?,
         };
 
-        if let Some(table) = insert.table {
-            self.write("INTO ")?;
-            self.visit_table(table, true)?;
+        self.visit_insert_body(insert.table, insert.columns, insert.values)?;
+        self.visit_returning(insert.returning)?;
+
+        if let Some(comment) = insert.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
         }
+        Ok(())
+    }
 
-        match insert.values {
-            Expression {
-                kind: ExpressionKind::Row(row),
-                ..
-            } => {
-                if row.values.is_empty() {
-                    self.write(" () VALUES ()")?;
-                } else {
-                    let columns = insert.columns.len();
+    fn visit_update(&mut self, mut update: Update<'a>) -> visitor::Result {
+        if !update.from_tables.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE ... FROM is only supported on PostgreSQL.".into());
 
-                    self.write(" (")?;
-                    for (i, c) in insert.columns.into_iter().enumerate() {
-                        self.visit_column(c.into_bare())?;
+            return Err(Error::builder(kind).build());
+        }
 
-                        if i < (columns - 1) {
-                            self.write(",")?;
-                        }
-                    }
+        if !update.joins.is_empty() && update.limit.is_some() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE with joined tables cannot use LIMIT.".into());
 
-                    self.write(")")?;
-                    self.write(" VALUES ")?;
-                    self.visit_row(row)?;
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("UPDATE ")?;
+
+        let hints = crate::ast::hints_for_dialect(std::mem::take(&mut update.hints), Self::HINT_DIALECT);
+
+        if !hints.is_empty() {
+            self.visit_hint_comment(hints)?;
+            self.write(" ")?;
+        }
+
+        self.visit_table(update.table, true)?;
+        self.visit_joins(update.joins)?;
+
+        {
+            self.write(" SET ")?;
+            let pairs = update.columns.into_iter().zip(update.values.into_iter());
+            let len = pairs.len();
+
+            for (i, (key, value)) in pairs.enumerate() {
+                self.visit_column(key)?;
+                self.write(" = ")?;
+                self.visit_expression(value)?;
+
+                if i < (len - 1) {
+                    self.write(", ")?;
                 }
             }
-            Expression {
-                kind: ExpressionKind::Values(values),
-                ..
-            } => {
-                let columns = insert.columns.len();
+        }
 
-                self.write(" (")?;
-                for (i, c) in insert.columns.into_iter().enumerate() {
-                    self.visit_column(c.into_bare())?;
+        if let Some(conditions) = update.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
 
-                    if i < (columns - 1) {
-                        self.write(",")?;
-                    }
-                }
-                self.write(")")?;
+        if let Some(limit) = update.limit {
+            self.write(" LIMIT ")?;
+            self.visit_parameterized(limit)?;
+        }
 
-                self.write(" VALUES ")?;
-                let values_len = values.len();
+        if let Some(comment) = update.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
+        }
 
-                for (i, row) in values.into_iter().enumerate() {
-                    self.visit_row(row)?;
+        Ok(())
+    }
 
-                    if i < (values_len - 1) {
-                        self.write(", ")?;
-                    }
-                }
+    fn visit_delete(&mut self, mut delete: Delete<'a>) -> visitor::Result {
+        if !delete.using.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE ... USING is only supported on PostgreSQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !delete.joins.is_empty() && (delete.limit.is_some() || !delete.ordering.is_empty()) {
+            let kind = ErrorKind::UnsupportedOperation(
+                "DELETE with joined tables cannot use ORDER BY or LIMIT.".into(),
+            );
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("DELETE ")?;
+
+        let hints = crate::ast::hints_for_dialect(std::mem::take(&mut delete.hints), Self::HINT_DIALECT);
+
+        if !hints.is_empty() {
+            self.visit_hint_comment(hints)?;
+            self.write(" ")?;
+        }
+
+        if delete.joins.is_empty() {
+            self.write("FROM ")?;
+            self.visit_table(delete.table, true)?;
+        } else {
+            // In the multi-table form the deleted table is named before
+            // `FROM`, by its alias when it has one.
+            match delete.table.alias.clone() {
+                Some(alias) => self.delimited_identifiers(&[&*alias])?,
+                None => self.visit_table(delete.table.clone(), false)?,
             }
-            expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
+
+            self.write(" FROM ")?;
+            self.visit_table(delete.table, true)?;
+            self.visit_joins(delete.joins)?;
         }
 
-        if let Some(comment) = insert.comment {
+        if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        if !delete.ordering.is_empty() {
+            self.write(" ORDER BY ")?;
+            self.visit_ordering(delete.ordering)?;
+        }
+
+        if let Some(limit) = delete.limit {
+            self.write(" LIMIT ")?;
+            self.visit_parameterized(limit)?;
+        }
+
+        self.visit_returning(delete.returning)?;
+
+        if let Some(comment) = delete.comment {
             self.write(" ")?;
             self.visit_comment(comment)?;
         }
+
         Ok(())
     }
 
+    fn visit_replace_into(&mut self, replace: ReplaceInto<'a>) -> visitor::Result {
+        self.write("REPLACE ")?;
+
+        self.visit_insert_body(replace.table, replace.columns, replace.values)
+    }
+
     fn visit_upsert(&mut self, _update: crate::ast::Update<'a>) -> visitor::Result {
         unimplemented!("Upsert not supported for the underlying database.")
     }
 
+    fn visit_load_data_infile(&mut self, load: LoadDataInfile<'a>) -> visitor::Result {
+        self.write("LOAD DATA LOCAL INFILE ")?;
+
+        match load.path {
+            Some(path) => self.write_infile_string(&path)?,
+            None => self.write_infile_string(IN_MEMORY_INFILE)?,
+        }
+
+        self.write(" INTO TABLE ")?;
+        self.visit_table(load.table, false)?;
+
+        self.write(" FIELDS TERMINATED BY ")?;
+        self.write_infile_string(&load.fields_terminated_by.to_string())?;
+
+        self.write(" LINES TERMINATED BY ")?;
+        self.write_infile_string(&load.lines_terminated_by)?;
+
+        if !load.columns.is_empty() {
+            self.columns_to_bracket_list(load.columns)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_into_outfile(&mut self, outfile: IntoOutfile<'a>) -> visitor::Result {
+        self.write("INTO OUTFILE ")?;
+        self.write_infile_string(&outfile.path)?;
+
+        self.write(" FIELDS TERMINATED BY ")?;
+        self.write_infile_string(&outfile.fields_terminated_by.to_string())?;
+
+        if let Some(enclosing) = outfile.optionally_enclosed_by {
+            self.write(" OPTIONALLY ENCLOSED BY ")?;
+            self.write_infile_string(&enclosing.to_string())?;
+        }
+
+        self.write(" LINES TERMINATED BY ")?;
+        self.write_infile_string(&outfile.lines_terminated_by)?;
+
+        Ok(())
+    }
+
     /// MySql will error if a `Update` or `Delete` query has a subselect
     /// that references a table that is being updated or deleted
     /// to get around that, we need to wrap the table in a tmp table name
@@ -264,6 +549,8 @@ impl<'a> Visitor<'a> for Mysql<'a> {
     fn visit_sub_selection(&mut self, query: SelectQuery<'a>) -> visitor::Result {
         match query {
             SelectQuery::Select(select) => {
+                crate::visitor::ensure_no_nested_into_outfile(&select)?;
+
                 if let Some(table) = &self.target_table {
                     if select.tables.contains(table) {
                         let tmp_name = "tmp_subselect_table";
@@ -319,6 +606,35 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         self.surround_with("(", ")", |ref mut s| s.visit_expression(value))
     }
 
+    fn visit_string_agg(&mut self, string_agg: StringAgg<'a>) -> visitor::Result {
+        let StringAgg {
+            value,
+            separator,
+            distinct,
+            ordering,
+        } = string_agg;
+
+        self.write("GROUP_CONCAT")?;
+        self.surround_with("(", ")", |ref mut s| {
+            if distinct {
+                s.write("DISTINCT ")?;
+            }
+
+            s.visit_expression(*value)?;
+
+            if !ordering.is_empty() {
+                s.write(" ORDER BY ")?;
+                s.visit_ordering(ordering)?;
+            }
+
+            // `SEPARATOR` only accepts a string literal, so the separator is
+            // inlined with the quoting characters escaped.
+            s.write(" SEPARATOR '")?;
+            s.write(separator.replace('\\', "\\\\").replace('\'', "\\'"))?;
+            s.write("'")
+        })
+    }
+
     fn visit_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         #[cfg(feature = "json")]
         {
@@ -351,6 +667,50 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         }
     }
 
+    // MySQL `CAST` only accepts its own list of type names: integers are
+    // `SIGNED`/`UNSIGNED`, strings are `CHAR` and the float types need MySQL
+    // 8.0.17 or later.
+    fn visit_cast_type(&mut self, cast_type: CastType) -> visitor::Result {
+        let name = match cast_type {
+            CastType::Text => "CHAR",
+            CastType::Integer => "SIGNED",
+            CastType::BigInt => "SIGNED",
+            CastType::Float => "FLOAT",
+            CastType::Double => "DOUBLE",
+            CastType::Boolean => "SIGNED",
+            CastType::Date => "DATE",
+            CastType::DateTime => "DATETIME",
+            CastType::Json => "JSON",
+            CastType::Unsigned => "UNSIGNED",
+            CastType::Uuid => {
+                let kind = ErrorKind::UnsupportedOperation("CAST AS UUID is not supported on MySQL.".into());
+
+                return Err(Error::builder(kind).build());
+            }
+        };
+
+        self.write(name)
+    }
+
+    // MySQL has no `IS DISTINCT FROM`, but the NULL-safe equality operator
+    // `<=>` expresses the same comparison.
+    fn visit_is_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.write("NOT ")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.visit_expression(left)?;
+            s.write(" <=> ")?;
+            s.visit_expression(right)
+        })
+    }
+
+    fn visit_is_not_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.visit_expression(left)?;
+        self.write(" <=> ")?;
+        self.visit_expression(right)?;
+
+        Ok(())
+    }
+
     fn visit_not_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         #[cfg(feature = "json")]
         {
@@ -383,7 +743,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         }
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract(&mut self, json_extract: JsonExtract<'a>) -> visitor::Result {
         if json_extract.extract_as_string {
             self.write("JSON_UNQUOTE(")?;
@@ -394,7 +754,6 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         self.write(", ")?;
 
         match json_extract.path.clone() {
-            #[cfg(feature = "postgresql")]
             JsonPath::Array(_) => panic!("JSON path array notation is not supported for MySQL"),
             JsonPath::String(path) => self.visit_parameterized(Value::text(path))?,
         }
@@ -408,7 +767,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_array_contains(&mut self, left: Expression<'a>, right: Expression<'a>, not: bool) -> visitor::Result {
         self.write("JSON_CONTAINS(")?;
         self.visit_expression(left)?;
@@ -423,7 +782,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_type_equals(&mut self, left: Expression<'a>, json_type: JsonType<'a>, not: bool) -> visitor::Result {
         self.write("(")?;
         self.write("JSON_TYPE")?;
@@ -492,6 +851,23 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
+    /// The escape character is inlined as a literal, and MySQL treats a
+    /// backslash in a string literal as its own escape character, so the
+    /// default `'\'` escape has to be doubled to stay a valid literal.
+    fn visit_like_escape(&mut self, escape: Option<char>) -> visitor::Result {
+        if let Some(escape_char) = escape {
+            self.write(" ESCAPE ")?;
+
+            match escape_char {
+                '\'' => self.write("''''")?,
+                '\\' => self.write(r"'\\'")?,
+                _ => self.write(format!("'{escape_char}'"))?,
+            }
+        }
+
+        Ok(())
+    }
+
     fn visit_text_search(&mut self, text_search: crate::prelude::TextSearch<'a>) -> visitor::Result {
         let len = text_search.exprs.len();
         self.surround_with("MATCH (", ")", |s| {
@@ -535,7 +911,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_last_array_item(&mut self, extract: JsonExtractLastArrayElem<'a>) -> visitor::Result {
         self.write("JSON_EXTRACT(")?;
         self.visit_expression(*extract.expr.clone())?;
@@ -548,7 +924,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_first_array_item(&mut self, extract: JsonExtractFirstArrayElem<'a>) -> visitor::Result {
         self.write("JSON_EXTRACT(")?;
         self.visit_expression(*extract.expr)?;
@@ -559,7 +935,7 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_unquote(&mut self, json_unquote: JsonUnquote<'a>) -> visitor::Result {
         self.write("JSON_UNQUOTE(")?;
         self.visit_expression(*json_unquote.expr)?;
@@ -664,17 +1040,246 @@ mod tests {
     }
 
     #[test]
-    fn test_multi_row_insert() {
-        let expected = expected_values("INSERT INTO `users` (`foo`) VALUES (?), (?)", vec![10, 11]);
-        let query = Insert::multi_into("users", vec!["foo"])
-            .values(vec![10])
-            .values(vec![11]);
+    fn test_single_row_insert_with_expression_values() {
+        let query = Insert::single_into("users").value("foo", 10).value("created_at", now());
+
         let (sql, params) = Mysql::build(query).unwrap();
 
-        assert_eq!(expected.0, sql);
+        assert_eq!("INSERT INTO `users` (`foo`,`created_at`) VALUES (?,CURRENT_TIMESTAMP)", sql);
+        assert_eq!(vec![Value::integer(10)], params);
+    }
+
+    #[test]
+    fn test_insert_with_identity_insert_is_a_no_op() {
+        let insert = Insert::single_into("users").value("id", 2);
+        let (sql, _) = Mysql::build(Insert::from(insert).with_identity_insert()).unwrap();
+
+        assert_eq!("INSERT INTO `users` (`id`) VALUES (?)", sql);
+    }
+
+    #[test]
+    fn test_insert_returning_is_rendered_on_mariadb() {
+        let insert = Insert::single_into("users").value("foo", 10);
+        let query = Insert::from(insert).returning(["id"]);
+
+        let (sql, params) = Mysql::build_with_flavour(query, MysqlFlavour::Mariadb).unwrap();
+
+        assert_eq!("INSERT INTO `users` (`foo`) VALUES (?) RETURNING `id`", sql);
+        assert_eq!(vec![Value::from(10)], params);
+    }
+
+    #[test]
+    fn test_insert_returning_is_ignored_on_mysql() {
+        let insert = Insert::single_into("users").value("foo", 10);
+        let query = Insert::from(insert).returning(["id"]);
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("INSERT INTO `users` (`foo`) VALUES (?)", sql);
+    }
+
+    #[test]
+    fn test_delete_returning_is_rendered_on_mariadb() {
+        let query = Delete::from_table("users").so_that("foo".equals(10)).returning(["id"]);
+
+        let (sql, params) = Mysql::build_with_flavour(query, MysqlFlavour::Mariadb).unwrap();
+
+        assert_eq!("DELETE FROM `users` WHERE `foo` = ? RETURNING `id`", sql);
+        assert_eq!(vec![Value::from(10)], params);
+    }
+
+    #[test]
+    fn test_delete_returning_is_ignored_on_mysql() {
+        let query = Delete::from_table("users").so_that("foo".equals(10)).returning(["id"]);
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("DELETE FROM `users` WHERE `foo` = ?", sql);
+    }
+
+    #[test]
+    fn test_multi_row_insert() {
+        let expected = expected_values("INSERT INTO `users` (`foo`) VALUES (?), (?)", vec![10, 11]);
+        let query = Insert::multi_into("users", vec!["foo"])
+            .values(vec![10])
+            .values(vec![11]);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_select_where_like_with_the_default_escape_character() {
+        let expected = expected_values(
+            "SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ? ESCAPE '\\\\'",
+            vec!["meow\\%%"],
+        );
+
+        let query = Select::from_table("naukio").so_that(Column::from("word").begins_with(like_escape("meow%")).escape('\\'));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
+    #[test]
+    fn test_where_exists_with_a_correlated_subquery() {
+        let expected_sql = "SELECT `users`.* FROM `users` WHERE EXISTS \
+                            (SELECT ? FROM `posts` WHERE `posts`.`user_id` = `users`.`id`)";
+
+        let sub_select = Select::from_table("posts")
+            .value(1)
+            .so_that(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+
+        let query = Select::from_table("users").so_that(exists(sub_select));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int32(1)], params);
+    }
+
+    #[test]
+    fn test_top_folds_into_the_limit() {
+        let expected_sql = "SELECT `foo` FROM `bar` LIMIT ?";
+        let query = Select::from_table("bar").column("foo").top(10);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_delete_with_order_by_and_limit() {
+        let expected_sql = "DELETE FROM `users` WHERE `active` = ? ORDER BY `id` LIMIT ?";
+
+        let query = Delete::from_table("users")
+            .so_that("active".equals(false))
+            .order_by("id")
+            .limit(10);
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(false), Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_delete_with_joined_tables_and_limit_is_unsupported() {
+        let join = "banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Delete::from_table("users").inner_join(join).limit(10);
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_with_joined_tables_and_limit_is_unsupported() {
+        let join = "posts".on(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Update::table("users").set("checked", true).inner_join(join).limit(10);
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_with_limit() {
+        let expected_sql = "UPDATE `users` SET `checked` = ? LIMIT ?";
+
+        let query = Update::table("users").set("checked", true).limit(10);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(true), Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_update_with_inner_join() {
+        let expected_sql = "UPDATE `users` INNER JOIN `profiles` ON `profiles`.`user_id` = `users`.`id` SET `users`.`name` = `profiles`.`name` WHERE `users`.`active` = ?";
+
+        let join = "profiles".on(Column::from(("profiles", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Update::table("users")
+            .inner_join(join)
+            .set(("users", "name"), Column::from(("profiles", "name")))
+            .so_that(Column::from(("users", "active")).equals(true));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(true)], params);
+    }
+
+    #[test]
+    fn test_update_from_is_unsupported() {
+        let condition = Column::from(("users", "id")).equals(Column::from(("profiles", "user_id")));
+        let query = Update::table("users").set("name", "Musti").and_from("profiles", condition);
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_with_inner_join() {
+        let expected_sql = "DELETE `users` FROM `users` INNER JOIN `banned` ON `banned`.`user_id` = `users`.`id` WHERE `users`.`active` = ?";
+
+        let join = "banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Delete::from_table("users")
+            .inner_join(join)
+            .so_that(Column::from(("users", "active")).equals(false));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(false)], params);
+    }
+
+    #[test]
+    fn test_delete_with_multiple_joins() {
+        let expected_sql = "DELETE `users` FROM `users` INNER JOIN `banned` ON `banned`.`user_id` = `users`.`id` LEFT JOIN `audits` ON `audits`.`user_id` = `users`.`id`";
+
+        let query = Delete::from_table("users")
+            .inner_join("banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id")))))
+            .left_join("audits".on(Column::from(("audits", "user_id")).equals(Column::from(("users", "id")))));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_delete_using_is_unsupported() {
+        let condition = Column::from(("users", "id")).equals(Column::from(("banned", "user_id")));
+        let query = Delete::from_table("users").using("banned", condition);
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_insert_from_select() {
+        let expected_sql = "INSERT INTO `users` (`name`,`age`) SELECT `name`, `age` FROM `candidates` WHERE `age` > ?";
+
+        let select = Select::from_table("candidates")
+            .column("name")
+            .column("age")
+            .so_that("age".greater_than(18));
+
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::from(18)], params);
+    }
+
+    #[test]
+    fn test_insert_from_select_with_a_mismatching_column_list_is_invalid() {
+        let select = Select::from_table("candidates").column("name");
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
     #[test]
     fn test_limit_and_offset_when_both_are_set() {
         let expected = expected_values("SELECT `users`.* FROM `users` LIMIT ? OFFSET ?", vec![10_i64, 2_i64]);
@@ -792,6 +1397,13 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_raw_bit_string() {
+        let (sql, params) = Mysql::build(Select::default().value(Value::bit_string("1010").raw())).unwrap();
+        assert_eq!("SELECT b'1010'", sql);
+        assert!(params.is_empty());
+    }
+
     #[test]
     fn test_raw_boolean() {
         let (sql, params) = Mysql::build(Select::default().value(true.raw())).unwrap();
@@ -845,6 +1457,56 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
+    #[test]
+    fn test_hint_select() {
+        let expected_sql = "SELECT /*+ MAX_EXECUTION_TIME(1000) */ `users`.* FROM `users`";
+        let query = Select::from_table("users").hint(Hint::mysql("MAX_EXECUTION_TIME(1000)"));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_update() {
+        let expected_sql = "UPDATE /*+ NO_ICP(users) */ `users` SET `foo` = ?";
+        let query = Update::table("users").set("foo", 10).hint(Hint::mysql("NO_ICP(users)"));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_delete() {
+        let expected_sql = "DELETE /*+ MAX_EXECUTION_TIME(1000) */ FROM `users`";
+        let query = Delete::from_table("users").hint(Hint::mysql("MAX_EXECUTION_TIME(1000)"));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_text_is_sanitized() {
+        let expected_sql = "SELECT /*+ boom * / DROP TABLE users; -- */ `users`.* FROM `users`";
+        let query = Select::from_table("users").hint(Hint::mysql("boom */ DROP TABLE users; --"));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_of_another_dialect_is_dropped() {
+        let expected_sql = "SELECT `users`.* FROM `users`";
+        let query = Select::from_table("users").hint(Hint::mssql("RECOMPILE"));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
     #[test]
     fn test_comment_insert() {
         let expected_sql = "INSERT INTO `users` () VALUES () /* trace_id='5bd66ef5095369c7b0d1f8f4bd33716a', parent_id='c532cb4098ac3dd2' */";
@@ -978,4 +1640,290 @@ mod tests {
             sql
         );
     }
+
+    #[test]
+    fn test_lateral_join() {
+        let expected_sql = "SELECT `users`.* FROM `users` LEFT JOIN LATERAL (SELECT `posts`.* FROM `posts` WHERE `posts`.`user_id` = `users`.`id` LIMIT ?) AS `latest` ON 1=1";
+
+        let inner = Select::from_table("posts")
+            .so_that(("posts", "user_id").equals(Column::from(("users", "id"))))
+            .limit(3);
+
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Select::from_table("users").left_join(join);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(3)], params);
+    }
+
+    #[test]
+    fn test_raw_fragment_splices_parameters_in_order() {
+        let fragment = Expression::raw("`name` sounds like ?", vec!["musti"]);
+
+        let query = Select::from_table("users")
+            .so_that("age".less_than(10))
+            .and_where(fragment.equals(true));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `users`.* FROM `users` WHERE (`age` < ? AND `name` sounds like ? = ?)",
+            sql
+        );
+
+        assert_eq!(
+            vec![Value::from(10), Value::from("musti"), Value::from(true)],
+            params
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_in_table_position() {
+        let table = Table::raw("`users` FORCE INDEX (`users_name_idx`)");
+        let query = Select::from_table(table.alias("u")).column(("u", "id"));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `u`.`id` FROM `users` FORCE INDEX (`users_name_idx`) AS `u`",
+            sql
+        );
+
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_raw_fragment_with_a_wrong_parameter_count() {
+        let fragment = Expression::raw("`age` between ? and ?", vec![18]);
+        let query = Select::from_table("users").so_that(ConditionTree::single(fragment));
+
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_load_data_infile_from_a_file() {
+        let load = LoadDataInfile::into_table(("db", "users"))
+            .path("/tmp/users.csv")
+            .columns(["id", "name"])
+            .fields_terminated_by(';')
+            .lines_terminated_by("\r\n");
+
+        let (sql, params) = Mysql::build(load).unwrap();
+
+        assert_eq!(
+            "LOAD DATA LOCAL INFILE '/tmp/users.csv' INTO TABLE `db`.`users` FIELDS TERMINATED BY ';' LINES TERMINATED BY '\\r\\n' (`id`,`name`)",
+            sql
+        );
+
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_load_data_infile_for_in_memory_data() {
+        let load = LoadDataInfile::into_table("users").columns(["id", "name"]);
+
+        let (sql, _) = Mysql::build(load).unwrap();
+
+        assert_eq!(
+            "LOAD DATA LOCAL INFILE '__quaint_in_memory__' INTO TABLE `users` FIELDS TERMINATED BY ',' LINES TERMINATED BY '\\n' (`id`,`name`)",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_select_into_outfile() {
+        let query = Select::from_table("users")
+            .column("name")
+            .into_outfile(IntoOutfile::new("/tmp/users.csv").optionally_enclosed_by('"'));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `name` FROM `users` INTO OUTFILE '/tmp/users.csv' FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '\"' LINES TERMINATED BY '\\n'",
+            sql
+        );
+
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_select_into_outfile_with_custom_separators() {
+        let query = Select::from_table("users").into_outfile(
+            IntoOutfile::new("/tmp/users.tsv")
+                .fields_terminated_by('\t')
+                .lines_terminated_by("\r\n"),
+        );
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `users`.* FROM `users` INTO OUTFILE '/tmp/users.tsv' FIELDS TERMINATED BY '\\t' LINES TERMINATED BY '\\r\\n'",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_into_outfile_is_not_allowed_in_a_subquery() {
+        let inner = Select::from_table("users").column("id").into_outfile("/tmp/ids.csv");
+        let query = Select::from_table("posts").so_that(Column::from("user_id").in_selection(inner));
+
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_table_sample_is_unsupported() {
+        let table = Table::from("events").with_sample(TableSample::system(10.0));
+        let query = Select::from_table(table);
+
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_string_agg() {
+        let query = Select::from_table("users")
+            .value(string_agg(Column::from("name")).distinct().separator(", ").order_by("name"))
+            .group_by("team");
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT GROUP_CONCAT(DISTINCT `name` ORDER BY `name` SEPARATOR ', ') FROM `users` GROUP BY `team`",
+            sql
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_cast() {
+        let query = Select::from_table("users")
+            .value(cast(Column::from("id"), CastType::Text))
+            .value(cast(Column::from("visits"), CastType::Unsigned));
+
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT CAST(`id` AS CHAR), CAST(`visits` AS UNSIGNED) FROM `users`",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_cast_uuid_is_unsupported() {
+        let query = Select::from_table("users").value(cast(Column::from("id"), CastType::Uuid));
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_greatest() {
+        // Unlike on PostgreSQL, any NULL argument makes `GREATEST` return
+        // NULL on MySQL. The rendering is the same, the semantics are not.
+        let exprs: Vec<Expression> = vec![Column::from("age").into(), Value::Int32(None).into()];
+        let query = Select::from_table("users").value(greatest(exprs));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT GREATEST(`age`, ?) FROM `users`", sql);
+        assert_eq!(vec![Value::Int32(None)], params);
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_distinct_from(Value::Text(None)));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (`foo` <=> ?)", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_not_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_not_distinct_from(Value::Text(None)));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` <=> ?", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_distinct_from_with_nulls_on_both_sides() {
+        let query = Select::from_table("users").so_that(Expression::from(Value::Text(None)).is_distinct_from(Value::Text(None)));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (? <=> ?)", sql);
+        assert_eq!(vec![Value::Text(None), Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_string_agg_escapes_the_separator() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).separator("', '"));
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT GROUP_CONCAT(`name` SEPARATOR '\\', \\'') FROM `users`", sql);
+    }
+
+    #[test]
+    fn test_array_agg_is_unsupported() {
+        let query = Select::from_table("users").value(array_agg(Column::from("id")));
+        let err = Mysql::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_single_row_replace_into() {
+        let expected = expected_values("REPLACE INTO `users` (`foo`) VALUES (?)", vec![10]);
+        let query = ReplaceInto::single_into("users").value("foo", 10);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_multi_row_replace_into() {
+        let expected = expected_values("REPLACE INTO `users` (`foo`) VALUES (?), (?)", vec![10, 11]);
+        let query = ReplaceInto::multi_into("users", ["foo"]).values(vec![10]).values(vec![11]);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_empty_in_short_circuits_an_and() {
+        let conditions = "foo".equals(1).and("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_empty_in_is_dropped_from_an_or() {
+        let conditions = "foo".equals(1).or("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE (`foo` = ?)", sql);
+        assert_eq!(vec![Value::from(1)], params);
+    }
+
+    #[test]
+    fn test_inverted_empty_not_in_never_matches() {
+        let conditions = "bar".not_in_selection(Vec::<i32>::new()).not();
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
 }
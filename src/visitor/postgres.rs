@@ -1,9 +1,22 @@
 use crate::{
     ast::*,
-    visitor::{self, Visitor},
+    error::{Error, ErrorKind},
+    visitor::{self, Capabilities, Visitor},
 };
 use std::fmt::{self, Write};
 
+/// The server flavour spoken by the PostgreSQL visitor. CockroachDB speaks
+/// the PostgreSQL wire protocol, but supports a few statements PostgreSQL
+/// does not, such as `AS OF SYSTEM TIME`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PostgresFlavour {
+    /// PostgreSQL.
+    #[default]
+    Postgres,
+    /// CockroachDB.
+    Cockroach,
+}
+
 /// A visitor to generate queries for the PostgreSQL database.
 ///
 /// The returned parameter values implement the `ToSql` trait from postgres and
@@ -11,20 +24,77 @@ use std::fmt::{self, Write};
 pub struct Postgres<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
+    reuse_parameters: bool,
+    substitution_index: usize,
+    flavour: PostgresFlavour,
 }
 
-impl<'a> Visitor<'a> for Postgres<'a> {
-    const C_BACKTICK_OPEN: &'static str = "\"";
-    const C_BACKTICK_CLOSE: &'static str = "\"";
-    const C_WILDCARD: &'static str = "%";
+impl<'a> Postgres<'a> {
+    /// The features supported by every PostgreSQL version quaint supports.
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        returning: true,
+        row_values: true,
+        lateral: true,
+        json: true,
+        upsert_where: true,
+        distinct_on: true,
+        set_operations: true,
+        window_functions: true,
+    };
+
+    /// Builds the query as [`build`] does, but reusing the placeholder of
+    /// the first occurrence when the same parameter value repeats within the
+    /// query. Equality is on the typed [`Value`], not on its rendering, so
+    /// e.g. the integer `1` and the text `'1'` stay separate parameters.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users")
+    ///     .so_that("tenant_id".equals(7).and("manager_tenant_id".equals(7)));
+    ///
+    /// let (sql, params) = Postgres::build_with_reused_parameters(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"users\".* FROM \"users\" WHERE (\"tenant_id\" = $1 AND \"manager_tenant_id\" = $1)",
+    ///     sql
+    /// );
+    ///
+    /// assert_eq!(vec![Value::from(7)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`build`]: Visitor::build
+    pub fn build_with_reused_parameters<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut postgres = Postgres {
+            query: String::with_capacity(4096),
+            parameters: Vec::with_capacity(128),
+            reuse_parameters: true,
+            substitution_index: 0,
+            flavour: PostgresFlavour::default(),
+        };
 
-    fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+        Postgres::visit_query(&mut postgres, query.into())?;
+
+        Ok((postgres.query, postgres.parameters))
+    }
+
+    /// Builds the query for the given server flavour. `build` renders for
+    /// PostgreSQL.
+    pub fn build_with_flavour<Q>(query: Q, flavour: PostgresFlavour) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<Query<'a>>,
     {
         let mut postgres = Postgres {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
+            reuse_parameters: false,
+            substitution_index: 0,
+            flavour,
         };
 
         Postgres::visit_query(&mut postgres, query.into())?;
@@ -32,18 +102,85 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok((postgres.query, postgres.parameters))
     }
 
+    fn visit_within_group(&mut self, name: &str, fraction: f64, order_by: Expression<'a>) -> visitor::Result {
+        if !(0.0..=1.0).contains(&fraction) {
+            let msg = format!("{name} fraction must be between 0.0 and 1.0, got {fraction}.");
+            let kind = ErrorKind::QueryInvalidInput(msg);
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write(name)?;
+        self.surround_with("(", ")", |ref mut s| s.write(fraction))?;
+        self.write(" WITHIN GROUP ")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.write("ORDER BY ")?;
+            s.visit_expression(order_by)
+        })
+    }
+}
+
+impl<'a> Visitor<'a> for Postgres<'a> {
+    const C_BACKTICK_OPEN: &'static str = "\"";
+    const HINT_DIALECT: Option<HintDialect> = Some(HintDialect::Postgres);
+    const C_BACKTICK_CLOSE: &'static str = "\"";
+    const C_WILDCARD: &'static str = "%";
+
+    fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Self::build_with_flavour(query, PostgresFlavour::default())
+    }
+
     fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
         write!(&mut self.query, "{s}")?;
         Ok(())
     }
 
     fn add_parameter(&mut self, value: Value<'a>) {
+        if self.reuse_parameters {
+            if let Some(i) = self.parameters.iter().position(|p| p == &value) {
+                self.substitution_index = i + 1;
+                return;
+            }
+        }
+
         self.parameters.push(value);
+        self.substitution_index = self.parameters.len();
+    }
+
+    fn visit_table_sample(&mut self, sample: TableSample) -> visitor::Result {
+        self.write("TABLESAMPLE ")?;
+
+        match sample.method {
+            SampleMethod::System => self.write("SYSTEM")?,
+            SampleMethod::Bernoulli => self.write("BERNOULLI")?,
+        }
+
+        self.write(format!(" ({})", sample.fraction))?;
+
+        if let Some(seed) = sample.seed {
+            self.write(format!(" REPEATABLE ({seed})"))?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_as_of_system_time(&mut self, expr: Expression<'a>) -> visitor::Result {
+        if self.flavour != PostgresFlavour::Cockroach {
+            let kind = ErrorKind::UnsupportedOperation("AS OF SYSTEM TIME is only supported on CockroachDB.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("AS OF SYSTEM TIME ")?;
+        self.visit_expression(expr)
     }
 
     fn parameter_substitution(&mut self) -> visitor::Result {
         self.write("$")?;
-        self.write(self.parameters.len())
+        self.write(self.substitution_index)
     }
 
     fn visit_limit_and_offset(&mut self, limit: Option<Value<'a>>, offset: Option<Value<'a>>) -> visitor::Result {
@@ -67,13 +204,34 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         }
     }
 
+    fn visit_offset_fetch(&mut self, limit: Option<Value<'a>>, offset: Option<Value<'a>>) -> visitor::Result {
+        if let Some(offset) = offset {
+            self.write(" OFFSET ")?;
+            self.visit_parameterized(offset)?;
+            self.write(" ROWS")?;
+        }
+
+        if let Some(limit) = limit {
+            self.write(" FETCH FIRST ")?;
+            self.visit_parameterized(limit)?;
+            self.write(" ROWS ONLY")?;
+        }
+
+        Ok(())
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Int32(i) => i.map(|i| self.write(i)),
             Value::Int64(i) => i.map(|i| self.write(i)),
+            Value::Int128(i) => i.map(|i| self.write(i)),
             Value::Text(t) => t.map(|t| self.write(format!("'{t}'"))),
             Value::Enum(e) => e.map(|e| self.write(e)),
             Value::Bytes(b) => b.map(|b| self.write(format!("E'{}'", hex::encode(b)))),
+            Value::IpAddr(ip) => ip.map(|ip| self.write(format!("'{ip}'"))),
+            Value::BitString(bits) => bits.map(|bits| self.write(format!("B'{bits}'"))),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(g) => g.map(|g| self.write(format!("'{}'", hex::encode(g.0)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
             Value::Xml(cow) => cow.map(|cow| self.write(format!("'{cow}'"))),
             Value::Char(c) => c.map(|c| self.write(format!("'{c}'"))),
@@ -89,7 +247,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
                 f if f == f64::NEG_INFINITY => self.write("'-Infinity"),
                 v => self.write(format!("{v:?}")),
             }),
-            Value::Array(ary) => ary.map(|ary| {
+            Value::Array(ary) | Value::TypedArray(ary, _) => ary.map(|ary| {
                 self.surround_with("'{", "}'", |ref mut s| {
                     let len = ary.len();
 
@@ -111,7 +269,9 @@ impl<'a> Visitor<'a> for Postgres<'a> {
             #[cfg(feature = "uuid")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.hyphenated()))),
             #[cfg(feature = "chrono")]
-            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
+            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{dt}'"))),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
             #[cfg(feature = "chrono")]
             Value::Date(date) => date.map(|date| self.write(format!("'{date}'"))),
             #[cfg(feature = "chrono")]
@@ -124,6 +284,149 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         }
     }
 
+    fn visit_update(&mut self, mut update: Update<'a>) -> visitor::Result {
+        let hints = crate::ast::hints_for_dialect(std::mem::take(&mut update.hints), Self::HINT_DIALECT);
+
+        if !hints.is_empty() {
+            self.visit_hint_comment(hints)?;
+            self.write(" ")?;
+        }
+
+        if !update.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if update.limit.is_some() {
+            let kind = ErrorKind::UnsupportedOperation("UPDATE ... LIMIT is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("UPDATE ")?;
+        self.visit_table(update.table, true)?;
+
+        {
+            self.write(" SET ")?;
+            let pairs = update.columns.into_iter().zip(update.values.into_iter());
+            let len = pairs.len();
+
+            for (i, (key, value)) in pairs.enumerate() {
+                self.visit_column(key)?;
+                self.write(" = ")?;
+                self.visit_expression(value)?;
+
+                if i < (len - 1) {
+                    self.write(", ")?;
+                }
+            }
+        }
+
+        if !update.from_tables.is_empty() {
+            self.write(" FROM ")?;
+
+            let len = update.from_tables.len();
+            for (i, table) in update.from_tables.into_iter().enumerate() {
+                self.visit_table(table, true)?;
+
+                if i < (len - 1) {
+                    self.write(", ")?;
+                }
+            }
+        }
+
+        if let Some(conditions) = update.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        if let Some(comment) = update.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, mut delete: Delete<'a>) -> visitor::Result {
+        let hints = crate::ast::hints_for_dialect(std::mem::take(&mut delete.hints), Self::HINT_DIALECT);
+
+        if !hints.is_empty() {
+            self.visit_hint_comment(hints)?;
+            self.write(" ")?;
+        }
+
+        if !delete.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if delete.limit.is_some() || !delete.ordering.is_empty() {
+            if !delete.using.is_empty() {
+                let kind = ErrorKind::UnsupportedOperation(
+                    "DELETE ... USING cannot be combined with ORDER BY or LIMIT.".into(),
+                );
+
+                return Err(Error::builder(kind).build());
+            }
+
+            // The native syntax has no limiter, so the batch is selected by
+            // the physical row identity.
+            let mut selection = Select::from_table(delete.table.clone()).column("ctid");
+
+            if let Some(conditions) = delete.conditions {
+                selection = selection.so_that(conditions);
+            }
+
+            selection.ordering = delete.ordering;
+            selection.limit = delete.limit;
+
+            self.write("DELETE FROM ")?;
+            self.visit_table(delete.table, true)?;
+            self.write(" WHERE ")?;
+            self.visit_column(Column::from("ctid"))?;
+            self.write(" IN ")?;
+            self.surround_with("(", ")", |ref mut s| s.visit_select(selection))?;
+
+            if let Some(comment) = delete.comment {
+                self.write(" ")?;
+                self.visit_comment(comment)?;
+            }
+
+            return Ok(());
+        }
+
+        self.write("DELETE FROM ")?;
+        self.visit_table(delete.table, true)?;
+
+        if !delete.using.is_empty() {
+            self.write(" USING ")?;
+
+            let len = delete.using.len();
+            for (i, table) in delete.using.into_iter().enumerate() {
+                self.visit_table(table, true)?;
+
+                if i < (len - 1) {
+                    self.write(", ")?;
+                }
+            }
+        }
+
+        if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        if let Some(comment) = delete.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
         self.write("INSERT ")?;
 
@@ -183,6 +486,10 @@ impl<'a> Visitor<'a> for Postgres<'a> {
                     }
                 }
             }
+            Expression {
+                kind: ExpressionKind::Selection(query),
+                ..
+            } => self.visit_insert_from_select(insert.columns, query)?,
             expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
         }
 
@@ -225,6 +532,93 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         self.write(")")
     }
 
+    fn visit_string_agg(&mut self, string_agg: StringAgg<'a>) -> visitor::Result {
+        let StringAgg {
+            value,
+            separator,
+            distinct,
+            ordering,
+        } = string_agg;
+
+        self.write("STRING_AGG")?;
+        self.surround_with("(", ")", |ref mut s| {
+            if distinct {
+                s.write("DISTINCT ")?;
+            }
+
+            s.visit_expression(*value)?;
+            s.write(", ")?;
+            s.visit_parameterized(Value::text(separator))?;
+
+            if !ordering.is_empty() {
+                s.write(" ORDER BY ")?;
+                s.visit_ordering(ordering)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn visit_array_agg(&mut self, array_agg: ArrayAgg<'a>) -> visitor::Result {
+        let ArrayAgg {
+            value,
+            distinct,
+            ordering,
+            filter,
+        } = array_agg;
+
+        self.write("ARRAY_AGG")?;
+        self.surround_with("(", ")", |ref mut s| {
+            if distinct {
+                s.write("DISTINCT ")?;
+            }
+
+            s.visit_expression(*value)?;
+
+            if !ordering.is_empty() {
+                s.write(" ORDER BY ")?;
+                s.visit_ordering(ordering)?;
+            }
+
+            Ok(())
+        })?;
+
+        if let Some(conditions) = filter {
+            self.write(" FILTER ")?;
+            self.surround_with("(", ")", |ref mut s| {
+                s.write("WHERE ")?;
+                s.visit_conditions(conditions)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    fn visit_json_agg(&mut self, json_agg: JsonAgg<'a>) -> visitor::Result {
+        let JsonAgg { value, ordering } = json_agg;
+
+        self.write("JSON_AGG")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.visit_expression(*value)?;
+
+            if !ordering.is_empty() {
+                s.write(" ORDER BY ")?;
+                s.visit_ordering(ordering)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn visit_percentile_cont(&mut self, percentile_cont: PercentileCont<'a>) -> visitor::Result {
+        self.visit_within_group("PERCENTILE_CONT", percentile_cont.fraction, *percentile_cont.order_by)
+    }
+
+    fn visit_percentile_disc(&mut self, percentile_disc: PercentileDisc<'a>) -> visitor::Result {
+        self.visit_within_group("PERCENTILE_DISC", percentile_disc.fraction, *percentile_disc.order_by)
+    }
+
     fn visit_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         // LHS must be cast to json/xml-text if the right is a json/xml-text value and vice versa.
         let right_cast = match left {
@@ -275,10 +669,9 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract(&mut self, json_extract: JsonExtract<'a>) -> visitor::Result {
         match json_extract.path {
-            #[cfg(feature = "mysql")]
             JsonPath::String(_) => panic!("JSON path string notation is not supported for Postgres"),
             JsonPath::Array(json_path) => {
                 self.write("(")?;
@@ -315,7 +708,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_unquote(&mut self, json_unquote: JsonUnquote<'a>) -> visitor::Result {
         self.write("(")?;
         self.visit_expression(*json_unquote.expr)?;
@@ -325,7 +718,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_array_contains(&mut self, left: Expression<'a>, right: Expression<'a>, not: bool) -> visitor::Result {
         if not {
             self.write("( NOT ")?;
@@ -342,7 +735,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_last_array_item(&mut self, extract: JsonExtractLastArrayElem<'a>) -> visitor::Result {
         self.write("(")?;
         self.visit_expression(*extract.expr)?;
@@ -352,7 +745,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_first_array_item(&mut self, extract: JsonExtractFirstArrayElem<'a>) -> visitor::Result {
         self.write("(")?;
         self.visit_expression(*extract.expr)?;
@@ -362,7 +755,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_type_equals(&mut self, left: Expression<'a>, json_type: JsonType<'a>, not: bool) -> visitor::Result {
         self.write("JSONB_TYPEOF")?;
         self.write("(")?;
@@ -446,7 +839,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
-    fn visit_like(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+    fn visit_like(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> visitor::Result {
         let need_cast = matches!(&left.kind, ExpressionKind::Column(_));
         self.visit_expression(left)?;
 
@@ -458,11 +851,12 @@ impl<'a> Visitor<'a> for Postgres<'a> {
 
         self.write(" LIKE ")?;
         self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
 
         Ok(())
     }
 
-    fn visit_not_like(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+    fn visit_not_like(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> visitor::Result {
         let need_cast = matches!(&left.kind, ExpressionKind::Column(_));
         self.visit_expression(left)?;
 
@@ -474,6 +868,37 @@ impl<'a> Visitor<'a> for Postgres<'a> {
 
         self.write(" NOT LIKE ")?;
         self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
+
+        Ok(())
+    }
+
+    fn visit_ilike(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> visitor::Result {
+        let need_cast = matches!(&left.kind, ExpressionKind::Column(_));
+        self.visit_expression(left)?;
+
+        if need_cast {
+            self.write("::text")?;
+        }
+
+        self.write(" ILIKE ")?;
+        self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
+
+        Ok(())
+    }
+
+    fn visit_not_ilike(&mut self, left: Expression<'a>, right: Expression<'a>, escape: Option<char>) -> visitor::Result {
+        let need_cast = matches!(&left.kind, ExpressionKind::Column(_));
+        self.visit_expression(left)?;
+
+        if need_cast {
+            self.write("::text")?;
+        }
+
+        self.write(" NOT ILIKE ")?;
+        self.visit_expression(right)?;
+        self.visit_like_escape(escape)?;
 
         Ok(())
     }
@@ -551,6 +976,27 @@ mod tests {
         assert_eq!(default_params(vec![]), params);
     }
 
+    #[test]
+    fn test_default_values_insert_with_returning() {
+        let query = Insert::default_values("users").returning(["id"]);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("INSERT INTO \"users\" DEFAULT VALUES RETURNING \"id\"", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_multi_row_insert_with_a_default_keyword_in_one_row() {
+        let query = Insert::multi_into("users", ["foo"])
+            .values(vec![default_value()])
+            .values(vec![Expression::from(10)]);
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("INSERT INTO \"users\" (\"foo\") VALUES (DEFAULT), ($1)", sql);
+        assert_eq!(vec![Value::from(10)], params);
+    }
+
     #[test]
     fn test_single_row_insert() {
         let expected = expected_values("INSERT INTO \"users\" (\"foo\") VALUES ($1)", vec![10]);
@@ -562,7 +1008,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "postgresql")]
     fn test_returning_insert() {
         let expected = expected_values(
             "INSERT INTO \"users\" (\"foo\") VALUES ($1) RETURNING \"foo\"",
@@ -576,7 +1021,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "postgresql")]
     fn test_insert_on_conflict_update() {
         let expected = expected_values(
             "INSERT INTO \"users\" (\"foo\") VALUES ($1) ON CONFLICT (\"foo\") DO UPDATE SET \"foo\" = $2 WHERE \"users\".\"foo\" = $3 RETURNING \"foo\"",
@@ -608,52 +1052,364 @@ mod tests {
     }
 
     #[test]
-    fn test_limit_and_offset_when_both_are_set() {
-        let expected = expected_values(
-            "SELECT \"users\".* FROM \"users\" LIMIT $1 OFFSET $2",
-            vec![10_i64, 2_i64],
-        );
-        let query = Select::from_table("users").limit(10).offset(2);
+    fn test_single_row_insert_with_expression_values() {
+        let query = Insert::single_into("users")
+            .value("name", "Musti")
+            .value("created_at", now())
+            .value("score", val!(10) * val!(2));
+
         let (sql, params) = Postgres::build(query).unwrap();
 
-        assert_eq!(expected.0, sql);
-        assert_eq!(expected.1, params);
+        assert_eq!(
+            "INSERT INTO \"users\" (\"name\",\"created_at\",\"score\") VALUES ($1,CURRENT_TIMESTAMP,($2 * $3))",
+            sql
+        );
+
+        assert_eq!(vec![Value::text("Musti"), Value::integer(10), Value::integer(2)], params);
     }
 
     #[test]
-    fn test_limit_and_offset_when_only_offset_is_set() {
-        let expected = expected_values("SELECT \"users\".* FROM \"users\" OFFSET $1", vec![10_i64]);
-        let query = Select::from_table("users").offset(10);
+    fn test_multi_row_insert_with_mixed_expressions_and_values() {
+        let query = Insert::multi_into("users", vec!["id", "created_at"])
+            .values_many(vec![(val!(1), now()), (val!(2) + val!(2), now())]);
+
         let (sql, params) = Postgres::build(query).unwrap();
 
-        assert_eq!(expected.0, sql);
-        assert_eq!(expected.1, params);
+        assert_eq!(
+            "INSERT INTO \"users\" (\"id\",\"created_at\") VALUES ($1,CURRENT_TIMESTAMP), (($2 + $3),CURRENT_TIMESTAMP)",
+            sql
+        );
+
+        assert_eq!(vec![Value::integer(1), Value::integer(2), Value::integer(2)], params);
     }
 
     #[test]
-    fn test_limit_and_offset_when_only_limit_is_set() {
-        let expected = expected_values("SELECT \"users\".* FROM \"users\" LIMIT $1", vec![10_i64]);
-        let query = Select::from_table("users").limit(10);
-        let (sql, params) = Postgres::build(query).unwrap();
+    fn test_insert_on_conflict_update_with_expression() {
+        let expected = expected_values(
+            "INSERT INTO \"users\" (\"foo\") VALUES ($1) ON CONFLICT (\"foo\") DO UPDATE SET \"foo\" = (\"users\".\"foo\" + $2) WHERE \"users\".\"foo\" = $3 RETURNING \"foo\"",
+            vec![10, 1, 2],
+        );
+
+        let update = Update::table("users")
+            .set("foo", col!("users", "foo") + val!(1))
+            .so_that(("users", "foo").equals(2));
+
+        let query: Insert = Insert::single_into("users").value("foo", 10).into();
+
+        let query = query.on_conflict(OnConflict::Update(update, Vec::from(["foo".into()])));
+
+        let (sql, params) = Postgres::build(query.returning(vec!["foo"])).unwrap();
 
         assert_eq!(expected.0, sql);
         assert_eq!(expected.1, params);
     }
 
     #[test]
-    fn test_distinct() {
-        let expected_sql = "SELECT DISTINCT \"bar\" FROM \"test\"";
-        let query = Select::from_table("test").column(Column::new("bar")).distinct();
+    fn test_update_from() {
+        let expected_sql = "UPDATE \"users\" SET \"name\" = \"profiles\".\"name\" FROM \"profiles\" WHERE \"users\".\"id\" = \"profiles\".\"user_id\"";
+
+        let query = Update::table("users")
+            .set("name", Column::from(("profiles", "name")))
+            .and_from(
+                "profiles",
+                Column::from(("users", "id")).equals(Column::from(("profiles", "user_id"))),
+            );
+
         let (sql, _) = Postgres::build(query).unwrap();
 
         assert_eq!(expected_sql, sql);
     }
 
     #[test]
-    fn test_distinct_with_subquery() {
-        let expected_sql = "SELECT DISTINCT (SELECT $1 FROM \"test2\"), \"bar\" FROM \"test\"";
-        let query = Select::from_table("test")
-            .value(Select::from_table("test2").value(val!(1)))
+    fn test_update_from_with_multiple_tables() {
+        let expected_sql = "UPDATE \"users\" SET \"name\" = \"profiles\".\"name\" FROM \"profiles\", \"accounts\" WHERE (\"users\".\"id\" = \"profiles\".\"user_id\" AND \"users\".\"id\" = \"accounts\".\"user_id\")";
+
+        let query = Update::table("users")
+            .set("name", Column::from(("profiles", "name")))
+            .and_from(
+                "profiles",
+                Column::from(("users", "id")).equals(Column::from(("profiles", "user_id"))),
+            )
+            .and_from(
+                "accounts",
+                Column::from(("users", "id")).equals(Column::from(("accounts", "user_id"))),
+            );
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_update_with_joined_tables_is_unsupported() {
+        let join = "profiles".on(Column::from(("profiles", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Update::table("users").inner_join(join).set("name", "Musti");
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_using() {
+        let expected_sql = "DELETE FROM \"users\" USING \"banned\", \"deleted\" WHERE (\"users\".\"id\" = \"banned\".\"user_id\" AND \"users\".\"id\" = \"deleted\".\"user_id\")";
+
+        let query = Delete::from_table("users")
+            .using(
+                "banned",
+                Column::from(("users", "id")).equals(Column::from(("banned", "user_id"))),
+            )
+            .using(
+                "deleted",
+                Column::from(("users", "id")).equals(Column::from(("deleted", "user_id"))),
+            );
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_delete_with_joined_tables_is_unsupported() {
+        let join = "banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Delete::from_table("users").inner_join(join);
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_with_limit_goes_through_the_ctid() {
+        let expected_sql = "DELETE FROM \"users\" WHERE \"ctid\" IN \
+                            (SELECT \"ctid\" FROM \"users\" WHERE \"active\" = $1 ORDER BY \"id\" LIMIT $2)";
+
+        let query = Delete::from_table("users")
+            .so_that("active".equals(false))
+            .order_by("id")
+            .limit(10);
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(false), Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_delete_using_with_limit_is_unsupported() {
+        let condition = Column::from(("users", "id")).equals(Column::from(("banned", "user_id")));
+        let query = Delete::from_table("users").using("banned", condition).limit(10);
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_with_limit_is_unsupported() {
+        let query = Update::table("users").set("checked", true).limit(10);
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_top_folds_into_the_limit() {
+        let expected_sql = "SELECT \"foo\" FROM \"bar\" LIMIT $1";
+        let query = Select::from_table("bar").column("foo").top(10);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_fetch_first() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" ORDER BY \"id\" FETCH FIRST $1 ROWS ONLY";
+        let query = Select::from_table("users").order_by("id").fetch_first(10);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_offset_fetch() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" ORDER BY \"id\" OFFSET $1 ROWS FETCH FIRST $2 ROWS ONLY";
+        let query = Select::from_table("users").order_by("id").offset_fetch(10, 5);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10), Value::int64(5)], params);
+    }
+
+    #[test]
+    fn test_where_exists_with_a_correlated_subquery() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" WHERE EXISTS \
+                            (SELECT $1 FROM \"posts\" WHERE \"posts\".\"user_id\" = \"users\".\"id\")";
+
+        let sub_select = Select::from_table("posts")
+            .value(1)
+            .so_that(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+
+        let query = Select::from_table("users").so_that(exists(sub_select));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int32(1)], params);
+    }
+
+    #[test]
+    fn test_where_not_exists_with_a_correlated_subquery() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" WHERE NOT EXISTS \
+                            (SELECT $1 FROM \"posts\" WHERE \"posts\".\"user_id\" = \"users\".\"id\")";
+
+        let sub_select = Select::from_table("posts")
+            .value(1)
+            .so_that(Column::from(("posts", "user_id")).equals(Column::from(("users", "id"))));
+
+        let query = Select::from_table("users").so_that(not_exists(sub_select));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int32(1)], params);
+    }
+
+    #[test]
+    fn test_as_of_system_time_renders_on_cockroach() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" AS OF SYSTEM TIME '-10s' WHERE \"name\" = $1";
+        let query = Select::from_table("users")
+            .as_of_system_time("-10s".raw())
+            .so_that("name".equals("Musti"));
+
+        let (sql, params) = Postgres::build_with_flavour(query, PostgresFlavour::Cockroach).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::text("Musti")], params);
+    }
+
+    #[test]
+    fn test_as_of_system_time_is_unsupported_on_postgres() {
+        let query = Select::from_table("users").as_of_system_time("-10s".raw());
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_build_with_reused_parameters_deduplicates_equal_values() {
+        let query = Select::from_table("users").so_that(
+            "tenant_id"
+                .equals(7)
+                .and("manager_tenant_id".equals(7))
+                .and("name".equals("Musti")),
+        );
+
+        let (sql, params) = Postgres::build_with_reused_parameters(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE (\"tenant_id\" = $1 AND \"manager_tenant_id\" = $1 AND \"name\" = $2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(7), Value::from("Musti")], params);
+    }
+
+    #[test]
+    fn test_build_with_reused_parameters_keeps_differently_typed_values_separate() {
+        let query = Select::from_table("users").so_that("id".equals(1).and("version".equals("1")));
+        let (sql, params) = Postgres::build_with_reused_parameters(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE (\"id\" = $1 AND \"version\" = $2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(1), Value::from("1")], params);
+    }
+
+    #[test]
+    fn test_build_keeps_repeated_parameters_by_default() {
+        let query = Select::from_table("users").so_that("tenant_id".equals(7).and("manager_tenant_id".equals(7)));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE (\"tenant_id\" = $1 AND \"manager_tenant_id\" = $2)",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(7), Value::from(7)], params);
+    }
+
+    #[test]
+    fn test_insert_from_select() {
+        let expected_sql = "INSERT INTO \"users\" (\"name\",\"age\") SELECT \"name\", \"age\" FROM \"candidates\" WHERE \"age\" > $1";
+
+        let select = Select::from_table("candidates")
+            .column("name")
+            .column("age")
+            .so_that("age".greater_than(18));
+
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::from(18)], params);
+    }
+
+    #[test]
+    fn test_insert_from_select_with_a_mismatching_column_list_is_invalid() {
+        let select = Select::from_table("candidates").column("name");
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_limit_and_offset_when_both_are_set() {
+        let expected = expected_values(
+            "SELECT \"users\".* FROM \"users\" LIMIT $1 OFFSET $2",
+            vec![10_i64, 2_i64],
+        );
+        let query = Select::from_table("users").limit(10).offset(2);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_limit_and_offset_when_only_offset_is_set() {
+        let expected = expected_values("SELECT \"users\".* FROM \"users\" OFFSET $1", vec![10_i64]);
+        let query = Select::from_table("users").offset(10);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_limit_and_offset_when_only_limit_is_set() {
+        let expected = expected_values("SELECT \"users\".* FROM \"users\" LIMIT $1", vec![10_i64]);
+        let query = Select::from_table("users").limit(10);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_distinct() {
+        let expected_sql = "SELECT DISTINCT \"bar\" FROM \"test\"";
+        let query = Select::from_table("test").column(Column::new("bar")).distinct();
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_distinct_with_subquery() {
+        let expected_sql = "SELECT DISTINCT (SELECT $1 FROM \"test2\"), \"bar\" FROM \"test\"";
+        let query = Select::from_table("test")
+            .value(Select::from_table("test2").value(val!(1)))
             .column(Column::new("bar"))
             .distinct();
 
@@ -675,6 +1431,46 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
+    #[test]
+    fn test_hint_select() {
+        let expected_sql = "/*+ IndexScan(users idx) */ SELECT \"users\".* FROM \"users\"";
+        let query = Select::from_table("users").hint(Hint::postgres("IndexScan(users idx)"));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_update() {
+        let expected_sql = "/*+ SeqScan(users) */ UPDATE \"users\" SET \"foo\" = $1";
+        let query = Update::table("users").set("foo", 10).hint(Hint::postgres("SeqScan(users)"));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_delete() {
+        let expected_sql = "/*+ SeqScan(users) */ DELETE FROM \"users\"";
+        let query = Delete::from_table("users").hint(Hint::postgres("SeqScan(users)"));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
+    #[test]
+    fn test_hint_of_another_dialect_is_dropped() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\"";
+        let query = Select::from_table("users").hint(Hint::mysql("MAX_EXECUTION_TIME(1000)"));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
     #[test]
     fn test_comment_select() {
         let expected_sql = "SELECT \"users\".* FROM \"users\" /* trace_id='5bd66ef5095369c7b0d1f8f4bd33716a', parent_id='c532cb4098ac3dd2' */";
@@ -846,6 +1642,35 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_qualified_asterisk_with_additional_columns() {
+        let users = Table::from("users");
+        let query = Select::from_table(users.clone())
+            .value(users.asterisk())
+            .column(Column::from(("posts", "id")).alias("post_id"))
+            .inner_join("posts".on(("posts", "user_id").equals(Column::from(("users", "id")))));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".*, \"posts\".\"id\" AS \"post_id\" FROM \"users\" INNER JOIN \"posts\" ON \"posts\".\"user_id\" = \"users\".\"id\"",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_asterisk_except_expands_to_column_list() {
+        let users = Table::from("users");
+        let selection = users
+            .clone()
+            .asterisk_except(["password_hash"], ["id", "name", "password_hash"]);
+
+        let query = Select::from_table(users).value(selection);
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".\"id\", \"users\".\"name\" FROM \"users\"", sql);
+    }
+
     #[test]
     fn test_raw_null() {
         let (sql, params) = Postgres::build(Select::default().value(Value::Text(None).raw())).unwrap();
@@ -881,6 +1706,13 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_raw_bit_string() {
+        let (sql, params) = Postgres::build(Select::default().value(Value::bit_string("1010").raw())).unwrap();
+        assert_eq!("SELECT B'1010'", sql);
+        assert!(params.is_empty());
+    }
+
     #[test]
     fn test_raw_boolean() {
         let (sql, params) = Postgres::build(Select::default().value(true.raw())).unwrap();
@@ -1020,6 +1852,64 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_ilike_cast_to_string() {
+        let expected = expected_values(
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text ILIKE $1"#,
+            vec!["%foo%"],
+        );
+
+        let query = Select::from_table("test").so_that(Column::from("jsonField").ilike("%foo%"));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_not_ilike_cast_to_string() {
+        let expected = expected_values(
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT ILIKE $1"#,
+            vec!["%foo%"],
+        );
+
+        let query = Select::from_table("test").so_that(Column::from("jsonField").not_ilike("%foo%"));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_like_with_an_escape_character() {
+        let expected = expected_values(
+            r#"SELECT "test".* FROM "test" WHERE "comment"::text LIKE $1 ESCAPE '\'"#,
+            vec!["%50\\%%"],
+        );
+
+        let query =
+            Select::from_table("test").so_that(Column::from("comment").contains(like_escape("50%")).escape('\\'));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_ilike_with_an_escape_character() {
+        let expected = expected_values(
+            r#"SELECT "test".* FROM "test" WHERE "comment"::text ILIKE $1 ESCAPE '!'"#,
+            vec!["%f!_oo%"],
+        );
+
+        let query = Select::from_table("test")
+            .so_that(Column::from("comment").ilike(format!("%{}%", like_escape_with("f_oo", '!'))).escape('!'));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
     #[test]
     fn test_default_insert() {
         let insert = Insert::single_into("foo")
@@ -1043,4 +1933,560 @@ mod tests {
 
         assert_eq!("SELECT \"User\".*, \"Toto\".* FROM \"User\" LEFT JOIN \"Post\" AS \"p\" ON \"p\".\"userId\" = \"User\".\"id\", \"Toto\"", sql);
     }
+
+    #[test]
+    fn test_lateral_join() {
+        let expected_sql = "SELECT \"users\".* FROM \"users\" INNER JOIN LATERAL (SELECT \"posts\".* FROM \"posts\" WHERE \"posts\".\"user_id\" = \"users\".\"id\" LIMIT $1) AS \"latest\" ON 1=1";
+
+        let inner = Select::from_table("posts")
+            .so_that(("posts", "user_id").equals(Column::from(("users", "id"))))
+            .limit(3);
+
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Select::from_table("users").inner_join(join);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(3)], params);
+    }
+
+    #[test]
+    fn test_lateral_join_parameter_ordering() {
+        let inner = Select::from_table("posts")
+            .so_that("status".equals("published"))
+            .limit(3);
+
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+
+        let query = Select::from_table("users")
+            .inner_join(join)
+            .so_that("name".equals("musti"));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        // The join renders before the outer conditions, so its parameters
+        // come first.
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" INNER JOIN LATERAL (SELECT \"posts\".* FROM \"posts\" WHERE \"status\" = $1 LIMIT $2) AS \"latest\" ON 1=1 WHERE \"name\" = $3",
+            sql
+        );
+
+        assert_eq!(
+            vec![Value::from("published"), Value::int64(3), Value::from("musti")],
+            params
+        );
+    }
+
+    #[test]
+    fn test_lateral_join_requires_an_alias() {
+        let inner = Select::from_table("posts").limit(3);
+        let join = JoinData::all_from(Table::from(inner)).lateral();
+        let query = Select::from_table("users").inner_join(join);
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_lateral_join_requires_a_subquery() {
+        let join = JoinData::all_from("posts").lateral();
+        let query = Select::from_table("users").inner_join(join);
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_raw_fragment_renumbers_placeholders() {
+        let fragment = Expression::raw("\"name\" % ?", vec!["musti"]);
+
+        let query = Select::from_table("users")
+            .so_that("age".less_than(10))
+            .and_where(fragment.equals(true))
+            .order_by(Expression::raw("levenshtein(\"name\", ?)", vec!["naukio"]).ascend());
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE (\"age\" < $1 AND \"name\" % $2 = $3) ORDER BY levenshtein(\"name\", $4) ASC",
+            sql
+        );
+
+        assert_eq!(
+            vec![
+                Value::from(10),
+                Value::from("musti"),
+                Value::from(true),
+                Value::from("naukio"),
+            ],
+            params
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_in_the_select_list() {
+        let fragment = Expression::raw("count(*) filter (where \"age\" > ?)", vec![18]).alias("adults");
+        let query = Select::from_table("users").value(fragment);
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT count(*) filter (where \"age\" > $1) AS \"adults\" FROM \"users\"",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(18)], params);
+    }
+
+    #[test]
+    fn test_raw_fragment_in_table_position() {
+        let table = Table::raw("\"users\" TABLESAMPLE SYSTEM (1)").alias("u");
+        let query = Select::from_table(table).column(("u", "id"));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"u\".\"id\" FROM \"users\" TABLESAMPLE SYSTEM (1) AS \"u\"",
+            sql
+        );
+
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_raw_fragment_skips_placeholders_in_string_literals() {
+        let fragment = Expression::raw("\"name\" like '%?%' and \"age\" > ?", vec![18]);
+        let query = Select::from_table("users").so_that(ConditionTree::single(fragment));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE \"name\" like '%?%' and \"age\" > $1",
+            sql
+        );
+
+        assert_eq!(vec![Value::from(18)], params);
+    }
+
+    #[test]
+    fn test_raw_fragment_with_a_wrong_parameter_count() {
+        let fragment = Expression::raw("\"age\" between ? and ?", vec![18]);
+        let query = Select::from_table("users").so_that(ConditionTree::single(fragment));
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_load_data_infile_is_unsupported() {
+        let load = LoadDataInfile::into_table("users").columns(["id", "name"]);
+
+        let err = Postgres::build(load).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_replace_into_is_unsupported() {
+        let query = ReplaceInto::single_into("users").value("foo", 10);
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_into_outfile_is_unsupported() {
+        let query = Select::from_table("users").into_outfile("/tmp/users.csv");
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_table_sample() {
+        let table = Table::from("events").with_sample(TableSample::system(10.0).repeatable(42));
+        let query = Select::from_table(table);
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"events\".* FROM \"events\" TABLESAMPLE SYSTEM (10) REPEATABLE (42)",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_table_sample_on_one_side_of_a_join() {
+        let events = Table::from("events").with_sample(TableSample::bernoulli(1.5));
+        let query = Select::from_table(events).inner_join("users".on(("users", "id").equals(Column::from(("events", "user_id")))));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"events\".* FROM \"events\" TABLESAMPLE BERNOULLI (1.5) INNER JOIN \"users\" ON \"users\".\"id\" = \"events\".\"user_id\"",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_table_sample_fraction_out_of_range() {
+        let table = Table::from("events").with_sample(TableSample::system(101.0));
+        let query = Select::from_table(table);
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_string_agg() {
+        let expected = expected_values(
+            "SELECT STRING_AGG(\"name\", $1 ORDER BY \"name\") FROM \"users\" GROUP BY \"team\"",
+            vec![", "],
+        );
+
+        let query = Select::from_table("users")
+            .value(string_agg(Column::from("name")).separator(", ").order_by("name"))
+            .group_by("team");
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_distinct_from(Value::Text(None)));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"foo\" IS DISTINCT FROM $1", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_not_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_not_distinct_from(Value::Text(None)));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"foo\" IS NOT DISTINCT FROM $1", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_nullif() {
+        let query = Select::from_table("users").value(nullif(Column::from("company"), Value::text("")));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT NULLIF(\"company\", $1) FROM \"users\"", sql);
+        assert_eq!(vec![Value::text("")], params);
+    }
+
+    #[test]
+    fn test_greatest_and_least() {
+        // PostgreSQL skips NULL arguments, so the NULL here does not poison
+        // the result, unlike on MySQL.
+        let exprs: Vec<Expression> = vec![Column::from("age").into(), Value::Int32(None).into()];
+        let query = Select::from_table("users").value(greatest(exprs));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT GREATEST(\"age\", $1) FROM \"users\"", sql);
+        assert_eq!(vec![Value::Int32(None)], params);
+
+        let exprs: Vec<Expression> = vec![Column::from("price").into(), Column::from("discounted_price").into()];
+        let query = Select::from_table("products").value(least(exprs));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT LEAST(\"price\", \"discounted_price\") FROM \"products\"", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_cast() {
+        let query = Select::from_table("users")
+            .value(cast(Column::from("id"), CastType::Text))
+            .so_that(cast(Column::from("external_id"), CastType::Uuid).equals(Column::from("uuid")));
+
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT CAST(\"id\" AS TEXT) FROM \"users\" WHERE CAST(\"external_id\" AS UUID) = \"uuid\"",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_cast_unsigned_is_unsupported() {
+        let query = Select::from_table("users").value(cast(Column::from("id"), CastType::Unsigned));
+        let res = Postgres::build(query);
+
+        assert!(matches!(
+            res.unwrap_err().kind(),
+            ErrorKind::UnsupportedOperation(_)
+        ));
+    }
+
+    #[test]
+    fn test_simple_case() {
+        let case = Case::simple(Column::from("role"))
+            .when(Value::text("admin"), Value::integer(1))
+            .when(Value::text("user"), Value::integer(2))
+            .otherwise(Value::integer(0));
+
+        let query = Select::from_table("users").value(case);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT CASE \"role\" WHEN $1 THEN $2 WHEN $3 THEN $4 ELSE $5 END FROM \"users\"",
+            sql
+        );
+
+        assert_eq!(
+            vec![
+                Value::text("admin"),
+                Value::integer(1),
+                Value::text("user"),
+                Value::integer(2),
+                Value::integer(0),
+            ],
+            params
+        );
+    }
+
+    #[test]
+    fn test_searched_case_without_else_evaluates_to_null() {
+        let case = Case::searched().when(Column::from("age").greater_than_or_equals(18), Value::text("adult"));
+
+        let query = Select::from_table("users").value(case);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT CASE WHEN \"age\" >= $1 THEN $2 END FROM \"users\"", sql);
+        assert_eq!(vec![Value::integer(18), Value::text("adult")], params);
+    }
+
+    #[test]
+    fn test_case_in_condition_and_ordering() {
+        let case = Case::searched()
+            .when(Column::from("deleted_at").is_null(), Value::integer(0))
+            .otherwise(Value::integer(1));
+
+        let query = Select::from_table("users")
+            .so_that(Expression::from(case.clone()).equals(0))
+            .order_by(Expression::from(case).ascend());
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE CASE WHEN \"deleted_at\" IS NULL THEN $1 ELSE $2 END = $3 ORDER BY CASE WHEN \"deleted_at\" IS NULL THEN $4 ELSE $5 END ASC",
+            sql
+        );
+
+        assert_eq!(
+            vec![
+                Value::integer(0),
+                Value::integer(1),
+                Value::integer(0),
+                Value::integer(0),
+                Value::integer(1),
+            ],
+            params
+        );
+    }
+
+    #[test]
+    fn test_is_distinct_from_with_nulls_on_both_sides() {
+        let query = Select::from_table("users").so_that(Expression::from(Value::Text(None)).is_distinct_from(Value::Text(None)));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".* FROM \"users\" WHERE $1 IS DISTINCT FROM $2", sql);
+        assert_eq!(vec![Value::Text(None), Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_string_agg_distinct() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).distinct());
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT STRING_AGG(DISTINCT \"name\", $1) FROM \"users\"", sql);
+        assert_eq!(vec![Value::from(",")], params);
+    }
+
+    #[test]
+    fn test_array_agg() {
+        let query = Select::from_table("users").value(array_agg(Column::from("id")).distinct().order_by(Column::from("id").descend()));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT ARRAY_AGG(DISTINCT \"id\" ORDER BY \"id\" DESC) FROM \"users\"", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_array_agg_with_a_filter() {
+        let query = Select::from_table("users")
+            .value(array_agg(Column::from("id")).order_by("id").filter("confirmed".equals(true)))
+            .group_by("team");
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT ARRAY_AGG(\"id\" ORDER BY \"id\") FILTER (WHERE \"confirmed\" = $1) FROM \"users\" GROUP BY \"team\"",
+            sql
+        );
+        assert_eq!(vec![Value::boolean(true)], params);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_agg_collects_related_rows_in_a_single_query() {
+        let query = Select::from_table("posts")
+            .column("author_id")
+            .value(json_agg(Column::from("title")).order_by("published_at"))
+            .group_by("author_id");
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"author_id\", JSON_AGG(\"title\" ORDER BY \"published_at\") FROM \"posts\" GROUP BY \"author_id\"",
+            sql
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_select_from_values_with_named_columns() {
+        let query = Select::from_values(values!((1, "Musti"), (2, "Naukio")), "vals", ["id", "name"])
+            .column(("vals", "id"))
+            .column(("vals", "name"));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"vals\".\"id\", \"vals\".\"name\" FROM (VALUES ($1,$2),($3,$4)) AS \"vals\"(\"id\",\"name\")",
+            sql
+        );
+
+        assert_eq!(
+            vec![
+                Value::integer(1),
+                Value::text("Musti"),
+                Value::integer(2),
+                Value::text("Naukio")
+            ],
+            params
+        );
+    }
+
+    #[test]
+    fn test_multi_row_insert_with_values_many() {
+        let query = Insert::multi_into("users", vec!["id", "name"]).values_many(vec![(1, "Musti"), (2, "Naukio")]);
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("INSERT INTO \"users\" (\"id\",\"name\") VALUES ($1,$2), ($3,$4)", sql);
+
+        assert_eq!(
+            vec![
+                Value::integer(1),
+                Value::text("Musti"),
+                Value::integer(2),
+                Value::text("Naukio")
+            ],
+            params
+        );
+    }
+
+    #[test]
+    fn test_percentile_cont() {
+        let query = Select::from_table("response_times").value(percentile_cont(0.5, Column::from("ms")));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY \"ms\") FROM \"response_times\"",
+            sql
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_percentile_disc() {
+        let query = Select::from_table("response_times")
+            .column("service")
+            .value(percentile_disc(0.9, Column::from("ms")))
+            .group_by("service");
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"service\", PERCENTILE_DISC(0.9) WITHIN GROUP (ORDER BY \"ms\") FROM \"response_times\" GROUP BY \"service\"",
+            sql
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_percentile_with_an_out_of_range_fraction_is_invalid() {
+        let query = Select::from_table("response_times").value(percentile_cont(1.5, Column::from("ms")));
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+
+        let query = Select::from_table("response_times").value(percentile_disc(-0.1, Column::from("ms")));
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_create_fts5_table_is_unsupported() {
+        let create = CreateFts5Table::new("docs").columns(["title", "body"]);
+
+        let err = Postgres::build(create).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_fts_match_is_unsupported() {
+        let query = Select::from_table("docs").so_that(ConditionTree::single(FtsMatch::new("docs", "hello")));
+
+        let err = Postgres::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_empty_in_short_circuits_an_and() {
+        let conditions = "foo".equals(1).and("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"musti\".* FROM \"musti\" WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_empty_in_is_dropped_from_an_or() {
+        let conditions = "foo".equals(1).or("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"musti\".* FROM \"musti\" WHERE (\"foo\" = $1)", sql);
+        assert_eq!(vec![Value::from(1)], params);
+    }
+
+    #[test]
+    fn test_inverted_empty_not_in_never_matches() {
+        let conditions = "bar".not_in_selection(Vec::<i32>::new()).not();
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!("SELECT \"musti\".* FROM \"musti\" WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
 }
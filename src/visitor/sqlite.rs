@@ -1,7 +1,7 @@
 use crate::{
     ast::*,
     error::{Error, ErrorKind},
-    visitor::{self, Visitor},
+    visitor::{self, Capabilities, Visitor},
 };
 
 use std::fmt::{self, Write};
@@ -16,6 +16,20 @@ pub struct Sqlite<'a> {
 }
 
 impl<'a> Sqlite<'a> {
+    /// The features supported by the oldest SQLite version quaint supports.
+    /// `RETURNING` needs SQLite 3.35 and is refined by the server version in
+    /// `Queryable::capabilities`.
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        returning: false,
+        row_values: true,
+        lateral: false,
+        json: true,
+        upsert_where: true,
+        distinct_on: false,
+        set_operations: true,
+        window_functions: true,
+    };
+
     fn visit_order_by(&mut self, direction: &str, value: Expression<'a>) -> visitor::Result {
         self.visit_expression(value)?;
         self.write(format!(" {direction}"))?;
@@ -76,9 +90,16 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         let res = match value {
             Value::Int32(i) => i.map(|i| self.write(i)),
             Value::Int64(i) => i.map(|i| self.write(i)),
+            Value::Int128(i) => i.map(|i| self.write(i)),
             Value::Text(t) => t.map(|t| self.write(format!("'{t}'"))),
             Value::Enum(e) => e.map(|e| self.write(e)),
             Value::Bytes(b) => b.map(|b| self.write(format!("x'{}'", hex::encode(b)))),
+            Value::IpAddr(ip) => ip.map(|ip| self.write(format!("'{ip}'"))),
+            // SQLite has no bit-string literal; rendered for debugging
+            // only, in the MySQL spelling.
+            Value::BitString(bits) => bits.map(|bits| self.write(format!("b'{bits}'"))),
+            #[cfg(feature = "postgis")]
+            Value::Geometry(g) => g.map(|g| self.write(format!("x'{}'", hex::encode(g.0)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
             Value::Char(c) => c.map(|c| self.write(format!("'{c}'"))),
             Value::Float(d) => d.map(|f| match f {
@@ -93,7 +114,7 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
                 f if f == f64::NEG_INFINITY => self.write("'-Infinity"),
                 v => self.write(format!("{v:?}")),
             }),
-            Value::Array(_) => {
+            Value::Array(_) | Value::TypedArray(..) => {
                 let msg = "Arrays are not supported in SQLite.";
                 let kind = ErrorKind::conversion(msg);
 
@@ -115,7 +136,9 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
             #[cfg(feature = "uuid")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.hyphenated()))),
             #[cfg(feature = "chrono")]
-            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
+            Value::DateTime(dt) => dt.map(|dt| self.write(format!("'{dt}'"))),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeUtc(dt) => dt.map(|dt| self.write(format!("'{}'", dt.to_rfc3339(),))),
             #[cfg(feature = "chrono")]
             Value::Date(date) => date.map(|date| self.write(format!("'{date}'"))),
             #[cfg(feature = "chrono")]
@@ -191,6 +214,10 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
                     }
                 }
             }
+            Expression {
+                kind: ExpressionKind::Selection(query),
+                ..
+            } => self.visit_insert_from_select(insert.columns, query)?,
             expr => self.visit_expression(expr)?,
         }
 
@@ -227,6 +254,85 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         Ok(())
     }
 
+    fn visit_delete(&mut self, mut delete: Delete<'a>) -> visitor::Result {
+        // SQLite has no optimizer hints; drop them with a trace log.
+        crate::ast::hints_for_dialect(std::mem::take(&mut delete.hints), Self::HINT_DIALECT);
+
+        if !delete.using.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE ... USING is only supported on PostgreSQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        if !delete.joins.is_empty() {
+            let kind = ErrorKind::UnsupportedOperation("DELETE with joined tables is only supported on MySQL.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("DELETE FROM ")?;
+        self.visit_table(delete.table.clone(), true)?;
+
+        if delete.limit.is_some() || !delete.ordering.is_empty() {
+            // The native `LIMIT` syntax of a delete is behind a compile-time
+            // flag most SQLite builds do not enable, so the batch is selected
+            // by the row identity instead.
+            let mut selection = Select::from_table(delete.table).column("rowid");
+
+            if let Some(conditions) = delete.conditions {
+                selection = selection.so_that(conditions);
+            }
+
+            selection.ordering = delete.ordering;
+            selection.limit = delete.limit;
+
+            self.write(" WHERE ")?;
+            self.visit_column(Column::from("rowid"))?;
+            self.write(" IN ")?;
+            self.surround_with("(", ")", |ref mut s| s.visit_select(selection))?;
+        } else if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        if let Some(comment) = delete.comment {
+            self.write(" ")?;
+            self.visit_comment(comment)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_create_fts5_table(&mut self, create: CreateFts5Table<'a>) -> visitor::Result {
+        self.write("CREATE VIRTUAL TABLE ")?;
+        self.surround_with_backticks(&create.name)?;
+        self.write(" USING fts5(")?;
+
+        let len = create.columns.len();
+
+        for (i, column) in create.columns.iter().enumerate() {
+            self.surround_with_backticks(column)?;
+
+            if i < (len - 1) {
+                self.write(", ")?;
+            }
+        }
+
+        if let Some(tokenizer) = create.tokenizer {
+            self.write(format!(", tokenize = '{}'", tokenizer.replace('\'', "''")))?;
+        }
+
+        self.write(")")
+    }
+
+    fn visit_fts_match(&mut self, fts_match: FtsMatch<'a>) -> visitor::Result {
+        self.visit_table(fts_match.table, false)?;
+        self.write(" MATCH ")?;
+
+        self.add_parameter(fts_match.query);
+        self.parameter_substitution()
+    }
+
     fn parameter_substitution(&mut self) -> visitor::Result {
         self.write("?")
     }
@@ -264,6 +370,89 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         self.surround_with("(", ")", |ref mut s| s.visit_expression(value))
     }
 
+    fn visit_string_agg(&mut self, string_agg: StringAgg<'a>) -> visitor::Result {
+        let StringAgg {
+            value,
+            separator,
+            distinct,
+            ordering,
+        } = string_agg;
+
+        // A distinct aggregate takes exactly one argument in SQLite, so the
+        // separator cannot be changed from the default.
+        if distinct && separator != "," {
+            let kind =
+                ErrorKind::QueryInvalidInput("SQLite does not support GROUP_CONCAT(DISTINCT ..) with a custom separator.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        // `ORDER BY` inside an aggregate only landed in SQLite 3.44, so an
+        // ordered aggregate cannot be rendered reliably.
+        if !ordering.is_empty() {
+            let kind = ErrorKind::QueryInvalidInput("SQLite does not support GROUP_CONCAT with ORDER BY.".into());
+
+            return Err(Error::builder(kind).build());
+        }
+
+        self.write("GROUP_CONCAT")?;
+        self.surround_with("(", ")", |ref mut s| {
+            if distinct {
+                s.write("DISTINCT ")?;
+                s.visit_expression(*value)?;
+            } else {
+                s.visit_expression(*value)?;
+                s.write(", ")?;
+                s.visit_parameterized(Value::text(separator))?;
+            }
+
+            Ok(())
+        })
+    }
+
+    // SQLite only has the `TEXT`, `INTEGER`, `REAL` and `NUMERIC` storage
+    // classes to cast to, so the richer types collapse onto those.
+    fn visit_cast_type(&mut self, cast_type: CastType) -> visitor::Result {
+        let name = match cast_type {
+            CastType::Text => "TEXT",
+            CastType::Integer => "INTEGER",
+            CastType::BigInt => "INTEGER",
+            CastType::Float => "REAL",
+            CastType::Double => "REAL",
+            CastType::Boolean => "INTEGER",
+            CastType::Date => "TEXT",
+            CastType::DateTime => "TEXT",
+            CastType::Json => "TEXT",
+            CastType::Uuid => "TEXT",
+            CastType::Unsigned => {
+                let kind = ErrorKind::UnsupportedOperation("CAST AS UNSIGNED is only supported on MySQL.".into());
+
+                return Err(Error::builder(kind).build());
+            }
+        };
+
+        self.write(name)
+    }
+
+    // SQLite has no `IS DISTINCT FROM`, but its `IS` operator compares
+    // `NULL`s as equal values.
+    fn visit_is_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.write("NOT ")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.visit_expression(left)?;
+            s.write(" IS ")?;
+            s.visit_expression(right)
+        })
+    }
+
+    fn visit_is_not_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.visit_expression(left)?;
+        self.write(" IS ")?;
+        self.visit_expression(right)?;
+
+        Ok(())
+    }
+
     fn visit_values(&mut self, values: Values<'a>) -> visitor::Result {
         self.surround_with("(VALUES ", ")", |ref mut s| {
             let len = values.len();
@@ -278,12 +467,12 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         })
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract(&mut self, _json_extract: JsonExtract<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on SQLite")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_array_contains(
         &mut self,
         _left: Expression<'a>,
@@ -293,17 +482,21 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         unimplemented!("JSON filtering is not yet supported on SQLite")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_type_equals(&mut self, _left: Expression<'a>, _json_type: JsonType, _not: bool) -> visitor::Result {
         unimplemented!("JSON_TYPE is not yet supported on SQLite")
     }
 
-    #[cfg(feature = "postgresql")]
+    fn visit_lateral_join(&mut self, _kind: &str, _data: JoinData<'a>) -> visitor::Result {
+        let kind = ErrorKind::QueryInvalidInput("Lateral joins are not supported on SQLite.".into());
+
+        Err(Error::builder(kind).build())
+    }
+
     fn visit_text_search(&mut self, _text_search: crate::prelude::TextSearch<'a>) -> visitor::Result {
         unimplemented!("Full-text search is not yet supported on SQLite")
     }
 
-    #[cfg(feature = "postgresql")]
     fn visit_matches(
         &mut self,
         _left: Expression<'a>,
@@ -313,22 +506,21 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         unimplemented!("Full-text search is not yet supported on SQLite")
     }
 
-    #[cfg(feature = "postgresql")]
     fn visit_text_search_relevance(&mut self, _text_search_relevance: TextSearchRelevance<'a>) -> visitor::Result {
         unimplemented!("Full-text search is not yet supported on SQLite")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_last_array_item(&mut self, _extract: JsonExtractLastArrayElem<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on SQLite")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_extract_first_array_item(&mut self, _extract: JsonExtractFirstArrayElem<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on SQLite")
     }
 
-    #[cfg(all(feature = "json", any(feature = "postgresql", feature = "mysql")))]
+    #[cfg(feature = "json")]
     fn visit_json_unquote(&mut self, _json_unquote: JsonUnquote<'a>) -> visitor::Result {
         unimplemented!("JSON filtering is not yet supported on SQLite")
     }
@@ -569,6 +761,45 @@ mod tests {
         assert_eq!(default_params(expected.1), params);
     }
 
+    #[test]
+    fn test_select_where_ilike_falls_back_to_like() {
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["%meow%"]);
+
+        let query = Select::from_table("naukio").so_that("word".ilike("%meow%"));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
+    #[test]
+    fn test_select_where_not_ilike_falls_back_to_not_like() {
+        let expected = expected_values(
+            "SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ?",
+            vec!["%meow%"],
+        );
+
+        let query = Select::from_table("naukio").so_that("word".not_ilike("%meow%"));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
+    #[test]
+    fn test_select_where_like_with_an_escape_character() {
+        let expected = expected_values(
+            "SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ? ESCAPE '\\'",
+            vec!["meow\\%%"],
+        );
+
+        let query = Select::from_table("naukio").so_that(Column::from("word").begins_with(like_escape("meow%")).escape('\\'));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
     #[test]
     fn test_select_where_begins_with() {
         let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["%meow"]);
@@ -796,6 +1027,18 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
+    #[test]
+    fn test_hints_are_dropped() {
+        let expected_sql = "SELECT `users`.* FROM `users`";
+        let query = Select::from_table("users")
+            .hint(Hint::mysql("MAX_EXECUTION_TIME(1000)"))
+            .hint(Hint::mssql("RECOMPILE"));
+
+        let (sql, _) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+    }
+
     #[test]
     fn test_comment_insert() {
         let expected_sql = "INSERT INTO `users` DEFAULT VALUES; /* trace_id='5bd66ef5095369c7b0d1f8f4bd33716a', parent_id='c532cb4098ac3dd2' */";
@@ -808,7 +1051,109 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
-    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_top_folds_into_the_limit() {
+        let expected_sql = "SELECT `foo` FROM `bar` LIMIT ?";
+        let query = Select::from_table("bar").column("foo").top(10);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_offset_fetch_is_unsupported() {
+        let query = Select::from_table("users").fetch_first(10);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_from_is_unsupported() {
+        let condition = Column::from(("users", "id")).equals(Column::from(("profiles", "user_id")));
+        let query = Update::table("users").set("name", "Musti").and_from("profiles", condition);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_update_with_joined_tables_is_unsupported() {
+        let join = "profiles".on(Column::from(("profiles", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Update::table("users").inner_join(join).set("name", "Musti");
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_using_is_unsupported() {
+        let condition = Column::from(("users", "id")).equals(Column::from(("banned", "user_id")));
+        let query = Delete::from_table("users").using("banned", condition);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_with_joined_tables_is_unsupported() {
+        let join = "banned".on(Column::from(("banned", "user_id")).equals(Column::from(("users", "id"))));
+        let query = Delete::from_table("users").inner_join(join);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_delete_with_limit_goes_through_the_rowid() {
+        let expected_sql = "DELETE FROM `users` WHERE `rowid` IN \
+                            (SELECT `rowid` FROM `users` WHERE `active` = ? ORDER BY `id` LIMIT ?)";
+
+        let query = Delete::from_table("users")
+            .so_that("active".equals(false))
+            .order_by("id")
+            .limit(10);
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::boolean(false), Value::int64(10)], params);
+    }
+
+    #[test]
+    fn test_update_with_limit_is_unsupported() {
+        let query = Update::table("users").set("checked", true).limit(10);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_insert_from_select() {
+        let expected_sql = "INSERT INTO `users` (`name`,`age`) SELECT `name`, `age` FROM `candidates` WHERE `age` > ?";
+
+        let select = Select::from_table("candidates")
+            .column("name")
+            .column("age")
+            .so_that("age".greater_than(18));
+
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::from(18)], params);
+    }
+
+    #[test]
+    fn test_insert_from_select_with_a_mismatching_column_list_is_invalid() {
+        let select = Select::from_table("candidates").column("name");
+        let query = Insert::from_select("users", vec!["name", "age"], select);
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
     fn sqlite_harness() -> ::rusqlite::Connection {
         let conn = ::rusqlite::Connection::open_in_memory().unwrap();
 
@@ -828,7 +1173,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "sqlite")]
     fn bind_test_1() {
         let conn = sqlite_harness();
 
@@ -984,7 +1328,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "sqlite")]
     fn test_insert_on_conflict_update() {
         let expected = expected_values(
             "INSERT INTO \"users\" (\"foo\") VALUES ($1) ON CONFLICT (\"foo\") DO UPDATE SET \"foo\" = $2 WHERE \"users\".\"foo\" = $3 RETURNING \"foo\"",
@@ -1002,4 +1345,213 @@ mod tests {
         assert_eq!(expected.0, sql);
         assert_eq!(expected.1, params);
     }
+
+    #[test]
+    fn test_lateral_join_is_unsupported() {
+        let inner = Select::from_table("posts");
+        let join = JoinData::all_from(Table::from(inner).alias("latest")).lateral();
+        let query = Select::from_table("users").inner_join(join);
+
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_raw_fragment_splices_parameters_in_order() {
+        let fragment = Expression::raw("`name` glob ?", vec!["musti*"]);
+
+        let query = Select::from_table("users")
+            .so_that("age".less_than(10))
+            .and_where(fragment.equals(true));
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `users`.* FROM `users` WHERE (`age` < ? AND `name` glob ? = ?)",
+            sql
+        );
+
+        assert_eq!(
+            vec![Value::from(10), Value::from("musti*"), Value::from(true)],
+            params
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_in_table_position() {
+        let table = Table::raw("`users` INDEXED BY `users_name_idx`");
+        let query = Select::from_table(table.alias("u")).column(("u", "id"));
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `u`.`id` FROM `users` INDEXED BY `users_name_idx` AS `u`", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_raw_fragment_with_a_wrong_parameter_count() {
+        let fragment = Expression::raw("`age` between ? and ?", vec![18]);
+        let query = Select::from_table("users").so_that(ConditionTree::single(fragment));
+
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_empty_in_short_circuits_an_and() {
+        let conditions = "foo".equals(1).and("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_empty_in_is_dropped_from_an_or() {
+        let conditions = "foo".equals(1).or("bar".in_selection(Vec::<i32>::new()));
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE (`foo` = ?)", sql);
+        assert_eq!(vec![Value::from(1)], params);
+    }
+
+    #[test]
+    fn test_inverted_empty_not_in_never_matches() {
+        let conditions = "bar".not_in_selection(Vec::<i32>::new()).not();
+        let query = Select::from_table("musti").so_that(conditions);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `musti`.* FROM `musti` WHERE 1=0", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_create_fts5_table() {
+        let create = CreateFts5Table::new("docs").columns(["title", "body"]);
+        let (sql, params) = Sqlite::build(create).unwrap();
+
+        assert_eq!("CREATE VIRTUAL TABLE `docs` USING fts5(`title`, `body`)", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_create_fts5_table_with_a_tokenizer() {
+        let create = CreateFts5Table::new("docs").columns(["body"]).tokenizer("porter");
+        let (sql, _) = Sqlite::build(create).unwrap();
+
+        assert_eq!("CREATE VIRTUAL TABLE `docs` USING fts5(`body`, tokenize = 'porter')", sql);
+    }
+
+    #[test]
+    fn test_fts_match_binds_the_query_string() {
+        let query = Select::from_table("docs")
+            .value(asterisk())
+            .so_that(ConditionTree::single(FtsMatch::new("docs", "hello world")));
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT * FROM `docs` WHERE `docs` MATCH ?", sql);
+        assert_eq!(vec![Value::from("hello world")], params);
+    }
+
+    #[test]
+    fn test_string_agg() {
+        let query = Select::from_table("users")
+            .value(string_agg(Column::from("name")).separator(", "))
+            .group_by("team");
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT GROUP_CONCAT(`name`, ?) FROM `users` GROUP BY `team`", sql);
+        assert_eq!(vec![Value::from(", ")], params);
+    }
+
+    #[test]
+    fn test_string_agg_with_an_ordering_is_invalid() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).order_by("name"));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_distinct_from(Value::Text(None)));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (`foo` IS ?)", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_not_distinct_from() {
+        let query = Select::from_table("users").so_that("foo".is_not_distinct_from(Value::Text(None)));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` IS ?", sql);
+        assert_eq!(vec![Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_is_distinct_from_with_nulls_on_both_sides() {
+        let query = Select::from_table("users").so_that(Expression::from(Value::Text(None)).is_distinct_from(Value::Text(None)));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (? IS ?)", sql);
+        assert_eq!(vec![Value::Text(None), Value::Text(None)], params);
+    }
+
+    #[test]
+    fn test_string_agg_distinct_keeps_the_default_separator() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).distinct());
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT GROUP_CONCAT(DISTINCT `name`) FROM `users`", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_string_agg_distinct_with_a_custom_separator_is_invalid() {
+        let query = Select::from_table("users").value(string_agg(Column::from("name")).distinct().separator("; "));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(_)));
+    }
+
+    #[test]
+    fn test_array_agg_is_unsupported() {
+        let query = Select::from_table("users").value(array_agg(Column::from("id")));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_agg_is_unsupported() {
+        let query = Select::from_table("users").value(json_agg(Column::from("id")));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_percentile_cont_is_unsupported() {
+        let query = Select::from_table("response_times").value(percentile_cont(0.5, Column::from("ms")));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_percentile_disc_is_unsupported() {
+        let query = Select::from_table("response_times").value(percentile_disc(0.9, Column::from("ms")));
+        let err = Sqlite::build(query).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedOperation(_)));
+    }
 }